@@ -0,0 +1,76 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use dynamic_domain::Domain;
+
+/// Union of `members` intervals of width 5, starting every 10 values
+fn spaced(members: i32) -> Domain<i32> {
+    Domain::Union((0..members).map(|i| Domain::between(i * 10, i * 10 + 4)).collect())
+}
+
+fn bench_contains(c: &mut Criterion) {
+    let (interval, union) = (Domain::between(-1_000, 1_000), spaced(8));
+
+    let mut group = c.benchmark_group("contains");
+    group.bench_function("interval", |b| b.iter(|| black_box(&interval).contains(black_box(500))));
+    group.bench_function("union of 8", |b| b.iter(|| black_box(&union).contains(black_box(72))));
+    group.finish();
+}
+
+fn bench_gt_chain(c: &mut Criterion) {
+    c.bench_function("gt chain of 16", |b| {
+        b.iter(|| (0..16).fold(Domain::new(), |domain, i| domain.gt(black_box(i * 3))))
+    });
+}
+
+fn bench_small_intersect(c: &mut Criterion) {
+    let (a, b) = (spaced(4), Domain::Union(vec![Domain::between(2, 12), Domain::between(25, 40)]));
+    c.bench_function("intersect small unions", |bench| bench.iter(|| black_box(&a).intersect(black_box(&b))));
+}
+
+fn bench_repr(c: &mut Criterion) {
+    let mut group = c.benchmark_group("repr");
+    for members in [1, 8, 1_000].iter() {
+        let domain = if *members == 1 { Domain::between(-1_000, 1_000) } else { spaced(*members) };
+        group.bench_with_input(BenchmarkId::new("to_string", members), &domain, |b, domain| {
+            b.iter(|| black_box(domain).to_string())
+        });
+        // One buffer for every call, as a logger formatting lines would
+        let mut out = String::with_capacity(16 * 1_024);
+        group.bench_with_input(BenchmarkId::new("repr_into", members), &domain, |b, domain| {
+            b.iter(|| {
+                out.clear();
+                black_box(domain).repr_into(&mut out);
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_iterate(c: &mut Criterion) {
+    let domain = Domain::between(0, 999_999);
+    c.bench_function("iterate 1M values", |b| b.iter(|| black_box(&domain).iter().map(i64::from).sum::<i64>()));
+}
+
+fn bench_ingest(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ingest");
+    group.sample_size(10);
+    // Points two apart never merge, the worst case for uniting one by one
+    for points in [10_000, 100_000, 1_000_000].iter() {
+        group.bench_with_input(BenchmarkId::new("union_assign_deferred", points), points, |b, points| {
+            b.iter(|| {
+                let mut domain = Domain::None;
+                for i in 0..*points {
+                    domain.union_assign_deferred(Domain::between(i * 2, i * 2));
+                }
+                domain.flush();
+                domain
+            })
+        });
+    }
+    group.bench_function(BenchmarkId::new("union", 10_000), |b| {
+        b.iter(|| (0..10_000).fold(Domain::None, |domain, i| domain.union(&Domain::between(i * 2, i * 2)).into_inner()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_contains, bench_gt_chain, bench_small_intersect, bench_repr, bench_iterate, bench_ingest);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dynamic_domain::{Domain, DomainIndex};
+
+/// 20 000 disjoint intervals spread over a wide range
+fn wide_domain() -> Domain<i32> {
+    Domain::Union(
+        (0..20_000)
+            .map(|i| Domain::between(i * 100, i * 100 + 40))
+            .collect()
+    )
+}
+
+fn queries() -> Vec<i32> {
+    let mut state: u32 = 0x2545_F491;
+    (0..10_000)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state % 2_000_000) as i32
+        })
+        .collect()
+}
+
+fn bench_contains(c: &mut Criterion) {
+    let domain = wide_domain();
+    let index = DomainIndex::build(&domain);
+    let queries = queries();
+    let mut out = vec![false; queries.len()];
+
+    let mut group = c.benchmark_group("contains");
+    group.bench_function("domain", |b| {
+        b.iter(|| queries.iter().filter(|q| domain.contains(black_box(**q))).count())
+    });
+    group.bench_function("index", |b| {
+        b.iter(|| queries.iter().filter(|q| index.contains(black_box(**q))).count())
+    });
+    group.bench_function("index_many", |b| {
+        b.iter(|| index.contains_many(black_box(&queries), &mut out))
+    });
+    group.finish();
+}
+
+fn bench_bulk(c: &mut Criterion) {
+    let domain = wide_domain();
+    let queries = queries();
+    let mut sorted = queries.clone();
+    sorted.sort_unstable();
+
+    let mut group = c.benchmark_group("bulk");
+    group.bench_function("loop", |b| {
+        b.iter(|| queries.iter().partition::<Vec<i32>, _>(|q| domain.contains(black_box(**q))))
+    });
+    group.bench_function("partition", |b| {
+        b.iter(|| domain.partition_contained(black_box(&queries)))
+    });
+    group.bench_function("partition_sorted", |b| {
+        b.iter(|| domain.partition_contained(black_box(&sorted)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_contains, bench_bulk);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use dynamic_domain::Domain;
+
+/// Union of `members` intervals of width 5, starting every 10 values
+/// from `offset`
+fn interleaved(members: i32, offset: i32) -> Domain<i32> {
+    Domain::Union(
+        (0..members)
+            .map(|i| Domain::between(i * 10 + offset, i * 10 + offset + 4))
+            .collect(),
+    )
+}
+
+fn bench_intersect(c: &mut Criterion) {
+    let mut group = c.benchmark_group("intersect");
+    // Two unions of the same size shifted against each other, every
+    // member overlapping one member of the other union, so the time
+    // should double with the size
+    for members in [12_500, 25_000, 50_000].iter() {
+        let (a, b) = (interleaved(*members, 0), interleaved(*members, 3));
+        group.bench_with_input(BenchmarkId::new("intersect", members), &(&a, &b), |bench, (a, b)| {
+            bench.iter(|| black_box(a).intersect(black_box(b)))
+        });
+        group.bench_with_input(BenchmarkId::new("union", members), &(&a, &b), |bench, (a, b)| {
+            bench.iter(|| black_box(a).union(black_box(b)))
+        });
+        group.bench_with_input(BenchmarkId::new("difference", members), &(&a, &b), |bench, (a, b)| {
+            bench.iter(|| black_box(a).difference(black_box(b)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_intersect);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dynamic_domain::Domain;
+
+/// 100 domains of 100 intervals each built by `interval(domain, index)`
+fn domains(interval: impl Fn(i32, i32) -> Domain<i32>) -> Vec<Domain<i32>> {
+    (0..100)
+        .map(|i| Domain::Union((0..100).map(|j| interval(i, j)).collect()))
+        .collect()
+}
+
+fn bench_many(c: &mut Criterion) {
+    // Overlapping intervals shifted against each other, the intersection
+    // keeps 100 intervals
+    let shifted = domains(|i, j| Domain::between(j * 1_000 + i, j * 1_000 + 900 + i));
+    // Disjoint intervals interleaved with each other, the union grows
+    // to 10000 intervals
+    let interleaved = domains(|i, j| Domain::between(j * 10_000 + i * 100, j * 10_000 + i * 100 + 50));
+
+    let mut group = c.benchmark_group("many");
+    group.bench_function("intersect_fold", |b| {
+        b.iter(|| {
+            black_box(&shifted)
+                .iter()
+                .fold(Domain::new(), |all, domain| all.intersect(domain).into_inner())
+        })
+    });
+    group.bench_function("intersect_many", |b| {
+        b.iter(|| Domain::intersect_many(black_box(&shifted)))
+    });
+    group.bench_function("union_fold", |b| {
+        b.iter(|| {
+            black_box(&interleaved)
+                .iter()
+                .fold(Domain::None, |all, domain| all.union(domain).into_inner())
+        })
+    });
+    group.bench_function("union_many", |b| {
+        b.iter(|| Domain::union_many(black_box(&interleaved)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_many);
+criterion_main!(benches);
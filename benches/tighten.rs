@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dynamic_domain::{Constraint, Domain, Value};
+
+/// 500 mixed constraints: creeping bounds and scattered removed values
+fn constraints() -> Vec<Constraint> {
+    (0..500)
+        .map(|i| match i % 4 {
+            0 => Constraint::Gt(Value::Included(i)),
+            1 => Constraint::Lt(Value::Secluded(1_000_000 - i)),
+            2 => Constraint::Ne(i * 997 % 1_000_000),
+            _ => Constraint::OutsideOf(Domain::between(i * 1_000, i * 1_000 + 10)),
+        })
+        .collect()
+}
+
+fn bench_tighten(c: &mut Criterion) {
+    let constraints = constraints();
+    let domain = Domain::new();
+
+    let mut group = c.benchmark_group("tighten");
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            constraints
+                .iter()
+                .fold(domain.clone(), |domain, constraint| constraint.apply(&domain))
+        })
+    });
+    group.bench_function("tighten_all", |b| {
+        b.iter(|| black_box(&domain).tighten_all(black_box(&constraints)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_tighten);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+fn main() {
+    #[cfg(feature = "ffi")]
+    ffi::generate_header();
+}
+
+#[cfg(feature = "ffi")]
+mod ffi {
+    use std::env;
+    use std::path::PathBuf;
+
+    /// Writes `dynamic_domain.h` into OUT_DIR, the copy in `include/`
+    /// is checked against it by `tests/ffi.rs`
+    pub fn generate_header() {
+        let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+        let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+        let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(crate_dir.join("src").join("ffi.rs"))
+            .generate()
+            .expect("unable to generate the C header")
+            .write_to_file(out_dir.join("dynamic_domain.h"));
+
+        println!("cargo:rustc-env=DYNAMIC_DOMAIN_HEADER_DIR={}", out_dir.display());
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+    }
+}
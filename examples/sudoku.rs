@@ -0,0 +1,142 @@
+//! Sudoku as a small constraint solver over `Domain<i32>`: 81 variables
+//! starting at [1;9], a fixed value removed from all its peers with
+//! `remove_value`, first-fail branching on the smallest `cardinality` and
+//! backtracking by restoring a snapshot of the store
+//!
+//! Run with `cargo run --example sudoku`
+
+use dynamic_domain::Domain;
+
+/// The puzzle from the Wikipedia article on Sudoku, `.` is an empty cell
+const PUZZLE: &str = "\
+    53..7....\
+    6..195...\
+    .98....6.\
+    8...6...3\
+    4..8.3..1\
+    7...2...6\
+    .6....28.\
+    ...419..5\
+    ....8..79";
+
+const SOLUTION: &str = "\
+    534678912\
+    672195348\
+    198342567\
+    859761423\
+    426853791\
+    713924856\
+    961537284\
+    287419635\
+    345286179";
+
+/// Cells sharing a row, a column or a box with `cell`
+fn peers(cell: usize) -> impl Iterator<Item = usize> {
+    let (row, col) = (cell / 9, cell % 9);
+    let (top, left) = (row / 3 * 3, col / 3 * 3);
+    (0..81).filter(move |&other| {
+        let (r, c) = (other / 9, other % 9);
+        other != cell && (r == row || c == col || (r / 3 * 3 == top && c / 3 * 3 == left))
+    })
+}
+
+/// Domains of all 81 cells, cloned as a whole for a snapshot
+#[derive(Clone)]
+struct Store {
+    cells: Vec<Domain<i32>>,
+}
+
+impl Store {
+    fn new() -> Self {
+        Store { cells: vec![Domain::between(1, 9); 81] }
+    }
+
+    /// Fixes `cell` to `value` and propagates until nothing changes,
+    /// `false` once some cell has no value left
+    fn assign(&mut self, cell: usize, value: i32) -> bool {
+        self.cells[cell] = Domain::between(value, value);
+        let mut fixed = vec![(cell, value)];
+
+        while let Some((cell, value)) = fixed.pop() {
+            for peer in peers(cell) {
+                if !self.cells[peer].contains(value) {
+                    continue;
+                }
+                self.cells[peer] = self.cells[peer].remove_value(value).into_inner();
+                match self.cells[peer].cardinality() {
+                    Some(0) => return false,
+                    Some(1) => fixed.push((peer, self.cells[peer].nth(0).unwrap())),
+                    _ => (),
+                }
+            }
+        }
+        true
+    }
+
+    /// Undecided cell with the fewest values left, the first-fail choice
+    fn branching_cell(&self) -> Option<usize> {
+        (0..81)
+            .filter(|&cell| self.cells[cell].cardinality() != Some(1))
+            .min_by_key(|&cell| self.cells[cell].cardinality())
+    }
+
+    fn search(self) -> Option<Store> {
+        let cell = match self.branching_cell() {
+            Some(cell) => cell,
+            None => return Some(self),
+        };
+
+        for value in self.cells[cell].iter() {
+            let mut snapshot = self.clone();
+            if snapshot.assign(cell, value) {
+                if let Some(solved) = snapshot.search() {
+                    return Some(solved);
+                }
+            }
+        }
+        None
+    }
+
+    fn render(&self) -> String {
+        self.cells
+            .iter()
+            .map(|domain| domain.nth(0).map_or('.', |value| (b'0' + value as u8) as char))
+            .collect()
+    }
+}
+
+fn solve(puzzle: &str) -> Option<String> {
+    let mut store = Store::new();
+    for (cell, c) in puzzle.chars().enumerate() {
+        if let Some(value) = c.to_digit(10) {
+            if !store.assign(cell, value as i32) {
+                return None;
+            }
+        }
+    }
+    store.search().map(|solved| solved.render())
+}
+
+fn main() {
+    let solution = solve(PUZZLE).expect("the puzzle has a solution");
+    for row in solution.as_bytes().chunks(9) {
+        println!("{}", String::from_utf8_lossy(row));
+    }
+    assert_eq!(solution, SOLUTION);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{solve, PUZZLE, SOLUTION};
+
+    #[test]
+    fn test_known_solution() {
+        assert_eq!(solve(PUZZLE).as_deref(), Some(SOLUTION));
+    }
+
+    #[test]
+    fn test_contradiction() {
+        let broken = format!("55{}", &PUZZLE[2..]);
+        assert_eq!(solve(&broken), None);
+    }
+}
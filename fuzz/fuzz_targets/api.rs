@@ -0,0 +1,10 @@
+#![no_main]
+
+use dynamic_domain::fuzzing::{run, Session};
+use libfuzzer_sys::fuzz_target;
+
+// Any sequence of public calls returns, errors are fine but panics are not.
+// Every call does bounded work, run with `-timeout` to catch hangs
+fuzz_target!(|session: Session| {
+    run(&session);
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use dynamic_domain::Domain;
+use libfuzzer_sys::fuzz_target;
+
+// The parser must return structured errors, never panic
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Err(error) = input.parse::<Domain<i32>>() {
+            assert!(input.is_char_boundary(error.position()));
+        }
+    }
+});
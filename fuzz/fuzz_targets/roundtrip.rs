@@ -0,0 +1,11 @@
+#![no_main]
+
+use dynamic_domain::Domain;
+use libfuzzer_sys::fuzz_target;
+
+// Parsing the representation gives back the same set of values
+fuzz_target!(|domain: Domain<i32>| {
+    let repr = domain.clone().repr();
+    let parsed: Domain<i32> = repr.parse().expect("repr must parse");
+    assert!(parsed == domain, "{}", repr);
+});
@@ -0,0 +1,99 @@
+#ifndef DYNAMIC_DOMAIN_H
+#define DYNAMIC_DOMAIN_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Result of every fallible call
+ */
+typedef enum DdStatus {
+  DD_STATUS_OK = 0,
+  /**
+   * A required pointer argument was NULL
+   */
+  DD_STATUS_NULL_POINTER = 1,
+  /**
+   * The output buffer can not hold the result, see the length out parameter
+   */
+  DD_STATUS_BUFFER_TOO_SMALL = 2,
+  /**
+   * The call panicked internally, output parameters are unchanged
+   */
+  DD_STATUS_PANIC = 3,
+} DdStatus;
+
+/**
+ * Opaque domain handle
+ */
+typedef struct DdDomain DdDomain;
+
+/**
+ * Creates the full domain `(-∞;∞)`. Returns NULL on failure
+ */
+struct DdDomain *dd_domain_new(void);
+
+/**
+ * Releases a handle, NULL is ignored
+ *
+ * # Safety
+ * `domain` must be NULL or a handle which was not freed yet
+ */
+void dd_domain_free(struct DdDomain *domain);
+
+/**
+ * Restricts the domain to values greater than `value`
+ * (or equal when `inclusive`)
+ *
+ * # Safety
+ * `domain` must be NULL or a live handle
+ */
+enum DdStatus dd_domain_gt(struct DdDomain *domain, int32_t value, bool inclusive);
+
+/**
+ * Restricts the domain to values less than `value`
+ * (or equal when `inclusive`)
+ *
+ * # Safety
+ * `domain` must be NULL or a live handle
+ */
+enum DdStatus dd_domain_lt(struct DdDomain *domain, int32_t value, bool inclusive);
+
+/**
+ * Writes whether `value` is admitted by the domain into `out`
+ *
+ * # Safety
+ * `domain` must be NULL or a live handle, `out` NULL or writable
+ */
+enum DdStatus dd_domain_contains(const struct DdDomain *domain, int32_t value, bool *out);
+
+/**
+ * Writes the NUL-terminated UTF-8 representation into `buf`.
+ * `out_len` always receives the representation length in bytes
+ * without the terminator; when `buf_len` is not larger than that,
+ * nothing is written and `DD_STATUS_BUFFER_TOO_SMALL` is returned.
+ * `buf` may be NULL when `buf_len` is 0 to query the length
+ *
+ * # Safety
+ * `domain` must be NULL or a live handle, `buf` must be valid for
+ * `buf_len` bytes and `out_len` NULL or writable
+ */
+enum DdStatus dd_domain_repr(const struct DdDomain *domain,
+                             char *buf,
+                             size_t buf_len,
+                             size_t *out_len);
+
+/**
+ * Stores a new handle holding the intersection of `a` and `b` into `out`
+ *
+ * # Safety
+ * `a` and `b` must be NULL or live handles, `out` NULL or writable
+ */
+enum DdStatus dd_domain_intersect(const struct DdDomain *a,
+                                  const struct DdDomain *b,
+                                  struct DdDomain **out);
+
+#endif  /* DYNAMIC_DOMAIN_H */
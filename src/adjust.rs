@@ -0,0 +1,153 @@
+use crate::util;
+use crate::Domain;
+
+/// Smallest change of a single border admitting a value, the answer of
+/// `Domain::min_adjustment_for`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Adjustment {
+    /// The value is admitted as it is
+    AlreadyContained,
+    /// The value is above every admitted value, the ending border of the
+    /// last member moves up to it
+    RaiseUpperTo(i32),
+    /// The value is below every admitted value, the starting border of
+    /// the first member moves down to it
+    LowerLowerTo(i32),
+    /// The value is in a gap between two members, one of them grows
+    /// into it by admitting every value from `from` through `to`
+    FillGap {
+        /// First value to admit
+        from: i32,
+        /// Last value to admit
+        to: i32,
+    },
+}
+
+impl Domain<i32> {
+
+    /// Smallest change of a single border, counted in the values it adds,
+    /// which makes `value` admitted. Within a gap the nearer member grows
+    /// into it, on a tie the member below, like raising an upper bound.
+    /// `None` for an empty domain, which has no border to move
+    /// ```
+    /// use dynamic_domain::{Adjustment, Domain};
+    /// let limits = Domain::Union(vec![Domain::between(0, 10), Domain::between(20, 30)]);
+    /// assert_eq!(limits.min_adjustment_for(33), Some(Adjustment::RaiseUpperTo(33)));
+    /// assert_eq!(limits.min_adjustment_for(-1), Some(Adjustment::LowerLowerTo(-1)));
+    /// assert_eq!(limits.min_adjustment_for(18), Some(Adjustment::FillGap { from: 18, to: 19 }));
+    /// assert_eq!(limits.min_adjustment_for(15), Some(Adjustment::FillGap { from: 11, to: 15 }));
+    /// assert_eq!(limits.min_adjustment_for(5), Some(Adjustment::AlreadyContained));
+    /// assert_eq!(Domain::None.min_adjustment_for(5), None);
+    /// ```
+    pub fn min_adjustment_for(&self, value: i32) -> Option<Adjustment> {
+        let runs = util::i32_runs(self);
+        let v = value as i64;
+        // Runs starting at or before `value`, the last one is below or around it
+        let at = runs.partition_point(|(lo, _)| *lo <= v);
+
+        let below = at.checked_sub(1).map(|i| runs[i]);
+        let above = runs.get(at).copied();
+        Some(match (below, above) {
+            (None, None) => return None,
+            (Some((_, hi)), _) if v <= hi => Adjustment::AlreadyContained,
+            (Some(_), None) => Adjustment::RaiseUpperTo(value),
+            (None, Some(_)) => Adjustment::LowerLowerTo(value),
+            (Some((_, hi)), Some((lo, _))) => {
+                if v - hi <= lo - v {
+                    Adjustment::FillGap { from: (hi + 1) as i32, to: value }
+                } else {
+                    Adjustment::FillGap { from: value, to: (lo - 1) as i32 }
+                }
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::flat_domain;
+    use crate::util;
+    use crate::{Adjustment, Domain, Value};
+
+    /// Values the adjustment adds to the domain
+    fn added(domain: &Domain<i32>, adjustment: Adjustment) -> Domain<i32> {
+        let runs = util::i32_runs(domain);
+        match adjustment {
+            Adjustment::AlreadyContained => Domain::None,
+            Adjustment::RaiseUpperTo(v) => Domain::between(runs[runs.len() - 1].1 as i32 + 1, v),
+            Adjustment::LowerLowerTo(v) => Domain::between(v, runs[0].0 as i32 - 1),
+            Adjustment::FillGap { from, to } => Domain::between(from, to),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_adjustment_is_smallest(domain in flat_domain(), value in prop_oneof![-35..35, Just(i32::MIN), Just(i32::MAX)]) {
+            let adjustment = domain.min_adjustment_for(value);
+            prop_assert_eq!(adjustment.is_none(), domain.is_empty());
+            let adjustment = match adjustment {
+                Some(adjustment) => adjustment,
+                None => return Ok(()),
+            };
+            prop_assert_eq!(adjustment == Adjustment::AlreadyContained, domain.contains(value));
+
+            // Adds only missing values next to a member, so no member appears
+            let added = added(&domain, adjustment);
+            prop_assert!(added.intersect(&domain).is_empty());
+            let adjusted = domain.union(&added);
+            prop_assert!(adjusted.contains(value));
+            prop_assert!(adjusted.members().len() <= domain.normalize().members().len());
+
+            // As many values as the distance to the nearest admitted one
+            let v = value as i64;
+            let distance = util::i32_runs(&domain)
+                .iter()
+                .map(|(lo, hi)| if v < *lo { lo - v } else if v > *hi { v - hi } else { 0 })
+                .min()
+                .unwrap();
+            prop_assert_eq!(added.cardinality(), Some(distance as u64));
+        }
+    }
+
+    #[test]
+    fn test_just_outside() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Secluded(0), Value::Secluded(10)),
+            Domain::between(20, 29),
+        ]);
+        assert_eq!(domain.min_adjustment_for(0), Some(Adjustment::LowerLowerTo(0)));
+        assert_eq!(domain.min_adjustment_for(1), Some(Adjustment::AlreadyContained));
+        assert_eq!(domain.min_adjustment_for(9), Some(Adjustment::AlreadyContained));
+        assert_eq!(domain.min_adjustment_for(10), Some(Adjustment::FillGap { from: 10, to: 10 }));
+        assert_eq!(domain.min_adjustment_for(19), Some(Adjustment::FillGap { from: 19, to: 19 }));
+        assert_eq!(domain.min_adjustment_for(30), Some(Adjustment::RaiseUpperTo(30)));
+        assert_eq!(domain.min_adjustment_for(-100), Some(Adjustment::LowerLowerTo(-100)));
+    }
+
+    #[test]
+    fn test_gap_ties_raise() {
+        // Gap of 10..=18, 14 is 5 values from either member
+        let domain = Domain::Union(vec![Domain::between(0, 9), Domain::between(19, 29)]);
+        assert_eq!(domain.min_adjustment_for(14), Some(Adjustment::FillGap { from: 10, to: 14 }));
+        assert_eq!(domain.min_adjustment_for(15), Some(Adjustment::FillGap { from: 15, to: 18 }));
+        assert_eq!(domain.min_adjustment_for(13), Some(Adjustment::FillGap { from: 10, to: 13 }));
+    }
+
+    #[test]
+    fn test_limits() {
+        let domain = Domain::Union(vec![Domain::at_most(i32::MIN), Domain::at_least(i32::MAX)]);
+        assert_eq!(domain.min_adjustment_for(i32::MIN + 1), Some(Adjustment::FillGap { from: i32::MIN + 1, to: i32::MIN + 1 }));
+        // 0 is one value nearer to i32::MAX than to i32::MIN
+        assert_eq!(domain.min_adjustment_for(-1), Some(Adjustment::FillGap { from: i32::MIN + 1, to: -1 }));
+        assert_eq!(domain.min_adjustment_for(0), Some(Adjustment::FillGap { from: 0, to: i32::MAX - 1 }));
+        assert_eq!(Domain::between(0, 0).min_adjustment_for(i32::MAX), Some(Adjustment::RaiseUpperTo(i32::MAX)));
+        assert_eq!(Domain::between(0, 0).min_adjustment_for(i32::MIN), Some(Adjustment::LowerLowerTo(i32::MIN)));
+        assert_eq!(Domain::new().min_adjustment_for(i32::MIN), Some(Adjustment::AlreadyContained));
+
+        // Admits no i32, so it is empty
+        assert_eq!(Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite).min_adjustment_for(0), None);
+        assert_eq!(Domain::between(5, 1).min_adjustment_for(3), None);
+    }
+}
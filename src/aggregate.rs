@@ -0,0 +1,530 @@
+use std::convert::TryFrom;
+
+use crate::error::AggregateError;
+use crate::util;
+use crate::{Domain, DomainIter, Value};
+
+/// Pieces admitted by any of `domains` in ascending order, each with the
+/// number of domains admitting it. `None` if one of them is unbounded
+fn coverage(domains: &[Domain<i32>]) -> Option<Vec<(i64, i64, u128)>> {
+    // A run adds one domain at its first value and drops it past its last
+    let mut events = Vec::new();
+    for domain in domains {
+        let members = util::normalized(domain);
+        if util::is_unbounded(&members) {
+            return None;
+        }
+        for (lo, hi) in util::runs_of(&members) {
+            events.push((lo, true));
+            events.push((hi + 1, false));
+        }
+    }
+    events.sort_unstable();
+
+    let (mut pieces, mut depth, mut start) = (Vec::new(), 0u128, i64::MIN);
+    for (point, starts) in events {
+        if depth > 0 && start < point {
+            pieces.push((start, point - 1, depth));
+        }
+        if starts {
+            depth += 1;
+        } else {
+            depth -= 1;
+        }
+        start = point;
+    }
+    Some(pieces)
+}
+
+impl Domain<i32> {
+
+    /// Number of admitted values, `None` if the domain is unbounded
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Secluded(5), Value::Included(10));
+    /// assert_eq!(domain.cardinality(), Some(5));
+    /// assert_eq!(Domain::new().cardinality(), None);
+    /// ```
+    pub fn cardinality(&self) -> Option<u64> {
+        let members = util::normalized(self);
+        if util::is_unbounded(&members) {
+            return None;
+        }
+
+        Some(
+            util::runs_of(&members)
+                .iter()
+                .map(|(lo, hi)| (hi - lo + 1) as u64)
+                .sum()
+        )
+    }
+
+    /// Number of admitted values below `value`, infinite borders
+    /// count from the `i32` limits
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(10, 14), Domain::between(0, 4)]);
+    /// assert_eq!(domain.rank(0), 0);
+    /// assert_eq!(domain.rank(12), 7);
+    /// assert_eq!(Domain::new().rank(i32::MIN + 3), 3);
+    /// ```
+    pub fn rank(&self, value: i32) -> u64 {
+        let value = value as i64;
+        util::i32_runs(self)
+            .iter()
+            .filter(|(lo, _)| *lo < value)
+            .map(|(lo, hi)| ((*hi).min(value - 1) - lo + 1) as u64)
+            .sum()
+    }
+
+    /// Admitted value at position `n` in ascending order, counting from 0
+    /// and from the `i32` limit for an infinite starting border
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(10, 14), Domain::between(0, 4)]);
+    /// assert_eq!(domain.nth(0), Some(0));
+    /// assert_eq!(domain.nth(6), Some(11));
+    /// assert_eq!(domain.nth(10), None);
+    /// ```
+    pub fn nth(&self, n: u64) -> Option<i32> {
+        util::nth_of_runs(util::i32_runs(self).into_iter(), n)
+    }
+
+    /// Splits a bounded domain into consecutive pieces of `n` values each in
+    /// ascending order, the last one holding what is left. A piece goes on
+    /// past a gap only to reach `n` values and then holds just the admitted
+    /// values on both sides, never the hull. The pieces are disjoint and
+    /// unite into the domain. `None` for an unbounded domain and for `n == 0`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(0, 2), Domain::between(10, 13)]);
+    /// let chunks: Vec<String> = domain.chunks(3).unwrap().into_iter().map(Domain::repr).collect();
+    /// assert_eq!(chunks, vec!["[0;2]", "[10;12]", "[13;13]"]);
+    /// let chunks: Vec<String> = domain.chunks(5).unwrap().into_iter().map(Domain::repr).collect();
+    /// assert_eq!(chunks, vec!["[0;2]⋃[10;11]", "[12;13]"]);
+    /// ```
+    pub fn chunks(&self, n: u64) -> Option<Vec<Domain<i32>>> {
+        let members = util::normalized(self);
+        if n == 0 || util::is_unbounded(&members) {
+            return None;
+        }
+
+        let mut chunks = Vec::new();
+        let mut chunk = Vec::new();
+        let mut left = n;
+
+        for (mut lo, hi) in util::runs_of(&members) {
+            while lo <= hi {
+                let last = hi.min(lo + (left - 1).min(u32::MAX as u64) as i64);
+                chunk.push((Value::Included(lo as i32), Value::Included(last as i32)));
+                left -= (last - lo + 1) as u64;
+                lo = last + 1;
+                if left == 0 {
+                    chunks.push(util::from_members(std::mem::take(&mut chunk)));
+                    left = n;
+                }
+            }
+        }
+        if !chunk.is_empty() {
+            chunks.push(util::from_members(chunk));
+        }
+        Some(chunks)
+    }
+
+    /// Splits the hull of a bounded domain into `buckets` intervals of
+    /// equal width, the last one absorbing the remainder, and pairs each
+    /// with the part of the domain inside it and its cardinality.
+    /// When the hull is narrower than `buckets`, the leading buckets
+    /// are empty, all `buckets` of them are still built. Errors for
+    /// unbounded and empty domains and for 0 buckets
+    /// ```
+    /// use dynamic_domain::{AggregateError, Domain};
+    /// let domain = Domain::Union(vec![Domain::between(0, 3), Domain::between(8, 9)]);
+    /// let histogram = domain.histogram(2).unwrap();
+    /// assert_eq!(histogram[0].0.clone().repr(), "[0;3]");
+    /// assert_eq!(histogram[1].1, 2);
+    /// assert_eq!(domain.histogram(0), Err(AggregateError::ZeroBuckets));
+    /// ```
+    pub fn histogram(&self, buckets: usize) -> Result<Vec<(Domain<i32>, u64)>, AggregateError> {
+        if buckets == 0 {
+            return Err(AggregateError::ZeroBuckets);
+        }
+        let buckets = i64::try_from(buckets).map_err(|_| AggregateError::TooManyBuckets)?;
+
+        let members = util::normalized(self);
+        if util::is_unbounded(&members) {
+            return Err(AggregateError::Unbounded);
+        }
+
+        let runs = util::runs_of(&members);
+        let (lo, hi) = match (runs.first(), runs.last()) {
+            (Some(first), Some(last)) => (first.0, last.1),
+            _ => return Err(AggregateError::Empty),
+        };
+        let width = (hi - lo + 1) / buckets;
+
+        let histogram = (0..buckets)
+            .map(|i| {
+                let start = lo + i * width;
+                let end = if i == buckets - 1 { hi } else { start + width - 1 };
+
+                if start > end {
+                    return (Domain::None, 0);
+                }
+
+                let part = self.intersect(&Domain::between(start as i32, end as i32)).into_inner();
+                let count = part.cardinality().unwrap_or(0);
+                (part, count)
+            })
+            .collect();
+
+        Ok(histogram)
+    }
+
+    /// Sum of all admitted values, computed per interval in closed form.
+    /// `None` if the domain is unbounded
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(1, 10), Domain::between(5, 12)]);
+    /// assert_eq!(domain.sum(), Some(78));
+    /// assert_eq!(Domain::at_least(0).sum(), None);
+    /// ```
+    pub fn sum(&self) -> Option<i64> {
+        let members = util::normalized(self);
+        if util::is_unbounded(&members) {
+            return None;
+        }
+
+        let total: i128 = util::runs_of(&members)
+            .iter()
+            .map(|(lo, hi)| (hi - lo + 1) as i128 * (lo + hi) as i128 / 2)
+            .sum();
+
+        i64::try_from(total).ok()
+    }
+
+    /// Value at the fraction `q` of all values admitted by `domains`, a
+    /// value admitted by several of them counted once for each: the value
+    /// at position `⌊q·n⌋` of the `n` counted values in ascending order,
+    /// the last one for `q = 1`. The borders of all domains are swept once
+    /// and every piece between them is counted with the number of domains
+    /// covering it, so huge domains cost no more than small ones. `None` if
+    /// any domain is unbounded, if no value is admitted and for `q` out of
+    /// `[0;1]`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// // 1 through 4 once and 3 through 4 twice: 1 2 3 3 4 4
+    /// let domains = vec![Domain::between(1, 4), Domain::between(3, 4)];
+    /// assert_eq!(Domain::quantile_across(&domains, 0.0), Some(1));
+    /// assert_eq!(Domain::quantile_across(&domains, 0.5), Some(3));
+    /// assert_eq!(Domain::quantile_across(&domains, 1.0), Some(4));
+    /// assert_eq!(Domain::quantile_across(&[Domain::at_least(0)], 0.5), None);
+    /// ```
+    pub fn quantile_across(domains: &[Domain<i32>], q: f64) -> Option<i32> {
+        if !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        let pieces = coverage(domains)?;
+        let total: u128 = pieces.iter().map(|(lo, hi, depth)| (hi - lo + 1) as u128 * depth).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut position = ((q * total as f64) as u128).min(total - 1);
+        for (lo, hi, depth) in pieces {
+            let counted = (hi - lo + 1) as u128 * depth;
+            if position < counted {
+                return Some((lo + (position / depth) as i64) as i32);
+            }
+            position -= counted;
+        }
+        None
+    }
+
+    /// Admitted value with the smallest key, the smallest value on a tie.
+    /// Every value is visited, so domains admitting more than `cap`
+    /// values are refused up front
+    /// ```
+    /// use dynamic_domain::{AggregateError, Domain};
+    /// let domain = Domain::between(-5, 5);
+    /// assert_eq!(domain.min_by_key(|v| ((v - 2) as i64).pow(2), 100), Ok(2));
+    /// assert_eq!(domain.min_by_key(|v| v as i64, 10), Err(AggregateError::TooLarge(10)));
+    /// ```
+    pub fn min_by_key(&self, f: impl Fn(i32) -> i64, cap: u64) -> Result<i32, AggregateError> {
+        self.capped_iter(cap)?
+            .min_by_key(|v| f(*v))
+            .ok_or(AggregateError::Empty)
+    }
+
+    /// Admitted value with the largest key, the largest value on a tie.
+    /// Every value is visited, so domains admitting more than `cap`
+    /// values are refused up front
+    /// ```
+    /// use dynamic_domain::{AggregateError, Domain};
+    /// let domain = Domain::between(-5, 5);
+    /// assert_eq!(domain.max_by_key(|v| (v as i64).abs(), 100), Ok(5));
+    /// assert_eq!(Domain::at_least(0).max_by_key(|v| v as i64, 100), Err(AggregateError::Unbounded));
+    /// ```
+    pub fn max_by_key(&self, f: impl Fn(i32) -> i64, cap: u64) -> Result<i32, AggregateError> {
+        self.capped_iter(cap)?
+            .max_by_key(|v| f(*v))
+            .ok_or(AggregateError::Empty)
+    }
+
+    fn capped_iter(&self, cap: u64) -> Result<DomainIter, AggregateError> {
+        let members = util::normalized(self);
+        if util::is_unbounded(&members) {
+            return Err(AggregateError::Unbounded);
+        }
+
+        let runs = util::runs_of(&members);
+        let cardinality: u64 = runs.iter().map(|(lo, hi)| (hi - lo + 1) as u64).sum();
+        if cardinality > cap {
+            return Err(AggregateError::TooLarge(cap));
+        }
+        Ok(DomainIter::new(runs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::error::AggregateError;
+    use crate::{Domain, Value};
+
+    /// Bounded domains of small values, so brute force can list them
+    fn bounded() -> impl Strategy<Value = Domain<i32>> {
+        let border = prop_oneof![(-20..20).prop_map(Value::Included), (-20..20).prop_map(Value::Secluded)];
+        union_of(border, 0..4)
+    }
+
+    proptest! {
+        #[test]
+        fn test_quantile_matches_brute_force(domains in prop::collection::vec(bounded(), 0..5), q in 0.0..=1.0f64) {
+            let mut values: Vec<i32> = domains.iter().flat_map(|domain| domain.iter()).collect();
+            values.sort_unstable();
+            let expected = if values.is_empty() {
+                None
+            } else {
+                Some(values[((q * values.len() as f64) as usize).min(values.len() - 1)])
+            };
+            prop_assert_eq!(Domain::quantile_across(&domains, q), expected);
+        }
+    }
+
+    #[test]
+    fn test_quantile_edges() {
+        let full = vec![Domain::between(i32::MIN, i32::MAX)];
+        assert_eq!(Domain::quantile_across(&full, 0.0), Some(i32::MIN));
+        assert_eq!(Domain::quantile_across(&full, 0.5), Some(0));
+        assert_eq!(Domain::quantile_across(&full, 1.0), Some(i32::MAX));
+
+        // i32::MIN twice and i32::MAX once
+        let limits = vec![Domain::between(i32::MIN, i32::MIN), Domain::between(i32::MIN, i32::MIN), Domain::between(i32::MAX, i32::MAX)];
+        assert_eq!(Domain::quantile_across(&limits, 0.66), Some(i32::MIN));
+        assert_eq!(Domain::quantile_across(&limits, 0.67), Some(i32::MAX));
+
+        // 2^35 counted values
+        let copies = vec![Domain::between(i32::MIN, i32::MAX); 8];
+        assert_eq!(Domain::quantile_across(&copies, 0.75), Some(i32::MAX / 2 + 1));
+
+        assert_eq!(Domain::quantile_across(&[], 0.5), None);
+        assert_eq!(Domain::quantile_across(&[Domain::None, Domain::between(3, 1)], 0.5), None);
+        assert_eq!(Domain::quantile_across(&[Domain::between(0, 9)], -0.1), None);
+        assert_eq!(Domain::quantile_across(&[Domain::between(0, 9)], 1.5), None);
+        assert_eq!(Domain::quantile_across(&[Domain::between(0, 9)], f64::NAN), None);
+        // Unbounded anywhere, even next to bounded ones
+        assert_eq!(Domain::quantile_across(&[Domain::between(0, 9), Domain::new()], 0.5), None);
+    }
+
+    fn chunks(domain: &Domain<i32>, n: u64) -> Vec<String> {
+        domain.chunks(n).unwrap().into_iter().map(Domain::repr).collect()
+    }
+
+    #[test]
+    fn test_chunks_divide_evenly() {
+        let domain = Domain::Domain(Value::Secluded(0), Value::Included(9));
+        assert_eq!(chunks(&domain, 3), vec!["[1;3]", "[4;6]", "[7;9]"]);
+        assert_eq!(chunks(&domain, 9), vec!["[1;9]"]);
+        assert_eq!(chunks(&domain, 1).len(), 9);
+    }
+
+    #[test]
+    fn test_chunks_across_gaps() {
+        let domain = Domain::Union(vec![
+            Domain::between(20, 20),
+            Domain::between(0, 3),
+            Domain::Domain(Value::Secluded(9), Value::Secluded(12)),
+        ]);
+        assert_eq!(chunks(&domain, 3), vec!["[0;2]", "[3;3]⋃[10;11]", "[20;20]"]);
+        assert_eq!(chunks(&domain, 4), vec!["[0;3]", "[10;11]⋃[20;20]"]);
+        assert_eq!(chunks(&domain, 100), vec!["[0;3]⋃[10;11]⋃[20;20]"]);
+
+        for n in 1..9 {
+            let pieces = domain.chunks(n).unwrap();
+            assert!(pieces.iter().all(|piece| piece.cardinality().unwrap() <= n));
+            assert!(pieces[..pieces.len() - 1].iter().all(|piece| piece.cardinality() == Some(n)));
+            assert_eq!(Domain::union_of(pieces.clone()), domain);
+            let values: Vec<i32> = pieces.iter().flat_map(|piece| piece.iter()).collect();
+            assert_eq!(values, domain.iter().collect::<Vec<i32>>());
+        }
+    }
+
+    #[test]
+    fn test_chunks_edges() {
+        assert_eq!(Domain::between(1, 5).chunks(0), None);
+        assert_eq!(Domain::at_least(0).chunks(10), None);
+        assert_eq!(Domain::None.chunks(10), Some(Vec::new()));
+
+        let full = Domain::between(i32::MIN, i32::MAX);
+        assert_eq!(full.chunks(u64::MAX), Some(vec![full.clone()]));
+        let halves = full.chunks(1 << 31).unwrap();
+        assert_eq!(halves, vec![Domain::between(i32::MIN, -1), Domain::between(0, i32::MAX)]);
+        assert_eq!(chunks(&Domain::between(i32::MAX - 2, i32::MAX), 2), vec!["[2147483645;2147483646]", "[2147483647;2147483647]"]);
+    }
+
+    #[test]
+    fn test_cardinality() {
+        let domain = Domain::Union(vec![Domain::between(1, 5), Domain::between(3, 8), Domain::None]);
+        assert_eq!(domain.cardinality(), Some(8));
+        assert_eq!(Domain::None.cardinality(), Some(0));
+        assert_eq!(Domain::between(i32::MIN, i32::MAX).cardinality(), Some(1 << 32));
+        assert_eq!(Domain::at_least(0).cardinality(), None);
+    }
+
+    #[test]
+    fn test_rank_and_nth() {
+        let domain = Domain::Union(vec![
+            Domain::between(5, 9),
+            Domain::Domain(Value::Secluded(0), Value::Secluded(3)),
+            Domain::between(7, 12),
+        ]);
+        let ranks: Vec<u64> = [0, 1, 2, 3, 5, 6, 12, 13, 100].iter().map(|v| domain.rank(*v)).collect();
+        assert_eq!(ranks, vec![0, 0, 1, 2, 2, 3, 9, 10, 10]);
+        let values: Vec<Option<i32>> = (0..11).map(|n| domain.nth(n)).collect();
+        assert_eq!(values[..3], [Some(1), Some(2), Some(5)]);
+        assert_eq!(values[9..], [Some(12), None]);
+        assert_eq!(Domain::None.nth(0), None);
+        assert_eq!(Domain::None.rank(i32::MAX), 0);
+    }
+
+    #[test]
+    fn test_rank_and_nth_at_limits() {
+        let full = Domain::new();
+        assert_eq!(full.rank(i32::MIN), 0);
+        assert_eq!(full.rank(i32::MAX), u32::MAX as u64);
+        assert_eq!(full.nth(u32::MAX as u64), Some(i32::MAX));
+        assert_eq!(full.nth(1 << 32), None);
+        assert_eq!(full.nth(u64::MAX), None);
+
+        let beyond = Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite);
+        assert_eq!(beyond.rank(i32::MAX), 0);
+        assert_eq!(beyond.nth(0), None);
+    }
+
+    #[test]
+    fn test_histogram_remainder() {
+        let histogram = Domain::between(0, 9).histogram(3).unwrap();
+        let reprs: Vec<(String, u64)> = histogram
+            .into_iter()
+            .map(|(domain, count)| (domain.repr(), count))
+            .collect();
+        assert_eq!(reprs, vec![
+            ("[0;2]".to_string(), 3),
+            ("[3;5]".to_string(), 3),
+            ("[6;9]".to_string(), 4),
+        ]);
+    }
+
+    #[test]
+    fn test_histogram_gaps() {
+        let domain = Domain::Union(vec![
+            Domain::between(0, 1),
+            Domain::Domain(Value::Secluded(4), Value::Secluded(7)),
+            Domain::between(11, 11),
+        ]);
+        let histogram = domain.histogram(4).unwrap();
+        let counts: Vec<u64> = histogram.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, vec![2, 1, 1, 1]);
+        assert_eq!(histogram[1].0.clone().repr(), "(4;5]");
+        assert_eq!(histogram[2].0.clone().repr(), "[6;7)");
+        assert_eq!(counts.iter().sum::<u64>(), domain.cardinality().unwrap());
+    }
+
+    #[test]
+    fn test_histogram_edge_cases() {
+        let histogram = Domain::between(5, 6).histogram(4).unwrap();
+        let counts: Vec<u64> = histogram.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, vec![0, 0, 0, 2]);
+
+        assert_eq!(Domain::None.histogram(2), Err(AggregateError::Empty));
+        assert_eq!(Domain::at_most(3).histogram(2), Err(AggregateError::Unbounded));
+
+        let full = Domain::between(i32::MIN, i32::MAX).histogram(2).unwrap();
+        assert_eq!(full[0].0.clone().repr(), "[-2147483648;-1]");
+        assert_eq!(full[1].1, 1 << 31);
+    }
+
+    #[test]
+    fn test_histogram_bucket_count() {
+        assert_eq!(Domain::between(0, 1).histogram(0), Err(AggregateError::ZeroBuckets));
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!(Domain::between(0, 1).histogram(usize::MAX), Err(AggregateError::TooManyBuckets));
+        }
+    }
+
+    #[test]
+    fn test_sum() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Secluded(0), Value::Secluded(4)),
+            Domain::between(-3, -1),
+        ]);
+        assert_eq!(domain.sum(), Some(0));
+        assert_eq!(domain.sum(), Some(domain.iter().map(|v| v as i64).sum()));
+        assert_eq!(Domain::None.sum(), Some(0));
+        assert_eq!(Domain::at_most(0).sum(), None);
+    }
+
+    #[test]
+    fn test_sum_limits() {
+        let full = Domain::between(i32::MIN, i32::MAX);
+        assert_eq!(full.sum(), Some(i32::MIN as i64));
+
+        let positive = Domain::between(0, i32::MAX);
+        let n = i32::MAX as i64;
+        assert_eq!(positive.sum(), Some(n * (n + 1) / 2));
+    }
+
+    #[test]
+    fn test_by_key() {
+        let domain = Domain::Union(vec![Domain::between(-10, -6), Domain::between(6, 10)]);
+        assert_eq!(domain.min_by_key(|v| (v as i64).abs(), 10), Ok(-6));
+        assert_eq!(domain.max_by_key(|v| (v as i64).abs(), 10), Ok(10));
+        assert_eq!(domain.min_by_key(|_| 0, 10), Ok(-10));
+        assert_eq!(Domain::None.min_by_key(|v| v as i64, 10), Err(AggregateError::Empty));
+        assert_eq!(Domain::at_least(0).max_by_key(|v| v as i64, 10), Err(AggregateError::Unbounded));
+    }
+
+    #[test]
+    fn test_by_key_cap() {
+        let domain = Domain::between(1, 10);
+        assert_eq!(domain.max_by_key(|v| v as i64, 10), Ok(10));
+        assert_eq!(domain.max_by_key(|v| v as i64, 9), Err(AggregateError::TooLarge(9)));
+
+        let calls = std::cell::Cell::new(0);
+        let full = Domain::between(i32::MIN, i32::MAX);
+        let result = full.min_by_key(|v| { calls.set(calls.get() + 1); v as i64 }, 1_000);
+        assert_eq!(result, Err(AggregateError::TooLarge(1_000)));
+        assert_eq!(calls.get(), 0);
+    }
+
+    #[test]
+    fn test_empty_beyond_limits() {
+        let beyond = Domain::new().gt(Value::Secluded(i32::MAX));
+        assert_eq!(beyond.cardinality(), Some(0));
+        assert_eq!(beyond.sum(), Some(0));
+        assert_eq!(beyond.min_by_key(|v| v as i64, 10), Err(AggregateError::Empty));
+    }
+}
@@ -0,0 +1,314 @@
+//! The set operations must agree with each other: every law is checked
+//! by brute force against a bitset model over a small window of values
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+use proptest::prelude::*;
+
+use crate::{Domain, Value};
+
+/// Smallest value of the window the laws are checked on
+const LO: i64 = -1000;
+
+/// Largest value of the window the laws are checked on
+const HI: i64 = 1000;
+
+/// Membership of every value of the window, bit `x - LO` is `x`
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Bitset {
+    words: Vec<u64>,
+}
+
+impl Bitset {
+    const LEN: usize = (HI - LO + 1) as usize;
+
+    fn empty() -> Self {
+        Bitset { words: vec![0; Self::LEN.div_ceil(64)] }
+    }
+
+    fn full() -> Self {
+        Self::empty().not()
+    }
+
+    fn from_fn(f: impl Fn(i64) -> bool) -> Self {
+        let mut set = Self::empty();
+        for x in LO..=HI {
+            if f(x) {
+                set.insert(x);
+            }
+        }
+        set
+    }
+
+    /// Reference membership of the domain, evaluated straight from the
+    /// borders without any of the crate helpers
+    fn of(domain: &Domain<i32>) -> Self {
+        Self::from_fn(|x| admits(domain, x))
+    }
+
+    fn index(x: i64) -> (usize, u64) {
+        let bit = (x - LO) as usize;
+        (bit / 64, 1 << (bit % 64))
+    }
+
+    fn insert(&mut self, x: i64) {
+        let (word, mask) = Self::index(x);
+        self.words[word] |= mask;
+    }
+
+    fn contains(&self, x: i64) -> bool {
+        let (word, mask) = Self::index(x);
+        self.words[word] & mask != 0
+    }
+
+    fn zip(&self, other: &Bitset, f: impl Fn(u64, u64) -> u64) -> Self {
+        let words = self.words.iter().zip(&other.words).map(|(a, b)| f(*a, *b)).collect();
+        Bitset { words }.trimmed()
+    }
+
+    fn and(&self, other: &Bitset) -> Self {
+        self.zip(other, |a, b| a & b)
+    }
+
+    fn or(&self, other: &Bitset) -> Self {
+        self.zip(other, |a, b| a | b)
+    }
+
+    fn not(&self) -> Self {
+        Bitset { words: self.words.iter().map(|word| !word).collect() }.trimmed()
+    }
+
+    /// Clears the padding bits past the window
+    fn trimmed(mut self) -> Self {
+        let used = Self::LEN % 64;
+        if used != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << used) - 1;
+            }
+        }
+        self
+    }
+
+    fn count(&self) -> u32 {
+        self.words.iter().map(|word| word.count_ones()).sum()
+    }
+}
+
+fn above(value: &Value<i32>, x: i64) -> bool {
+    match value {
+        Value::Included(i) => x >= *i as i64,
+        Value::Secluded(i) => x > *i as i64,
+        Value::Infinite => true,
+    }
+}
+
+fn below(value: &Value<i32>, x: i64) -> bool {
+    match value {
+        Value::Included(i) => x <= *i as i64,
+        Value::Secluded(i) => x < *i as i64,
+        Value::Infinite => true,
+    }
+}
+
+fn admits(domain: &Domain<i32>, x: i64) -> bool {
+    match domain {
+        Domain::Union(domains) => domains.iter().any(|domain| admits(domain, x)),
+        Domain::Domain(l, r) => above(l, x) && below(r, x),
+        Domain::None => false,
+    }
+}
+
+/// Points reaching a little past the window so the edges get crossed
+fn point() -> impl Strategy<Value = i32> {
+    prop_oneof![
+        8 => -1100..1100,
+        1 => Just(i32::MIN),
+        1 => Just(i32::MAX),
+    ]
+}
+
+fn finite() -> impl Strategy<Value = Value<i32>> {
+    prop_oneof![
+        point().prop_map(Value::Included),
+        point().prop_map(Value::Secluded),
+    ]
+}
+
+fn value() -> impl Strategy<Value = Value<i32>> {
+    prop_oneof![
+        1 => Just(Value::Infinite),
+        4 => finite(),
+    ]
+}
+
+/// Domains including reversed borders, empty members and nested unions
+fn domain() -> impl Strategy<Value = Domain<i32>> {
+    let leaf = prop_oneof![
+        1 => Just(Domain::None),
+        8 => (value(), value()).prop_map(|(l, r)| Domain::Domain(l, r)),
+    ];
+    leaf.prop_recursive(2, 16, 6, |inner| {
+        prop::collection::vec(inner, 0..6).prop_map(Domain::Union)
+    })
+}
+
+/// Included, secluded and infinite borders at the points of `point`,
+/// shared by the property tests of the other modules
+pub(crate) fn border_at<T: Clone + Debug>(point: impl Strategy<Value = T> + Clone) -> impl Strategy<Value = Value<T>> + Clone {
+    prop_oneof![
+        point.clone().prop_map(Value::Included),
+        point.prop_map(Value::Secluded),
+        Just(Value::Infinite),
+    ]
+}
+
+/// `border_at` points within `-reach..reach`, or at the `i32` limits
+/// one time in five
+pub(crate) fn border(reach: i32) -> impl Strategy<Value = Value<i32>> + Clone {
+    border_at(prop_oneof![8 => -reach..reach, 1 => Just(i32::MIN), 1 => Just(i32::MAX)])
+}
+
+/// Flat members between two borders of `border`, reversed and empty
+/// ones included
+pub(crate) fn members<T: Clone + Debug>(
+    border: impl Strategy<Value = Value<T>> + Clone,
+    count: Range<usize>,
+) -> impl Strategy<Value = Vec<Domain<T>>> {
+    prop::collection::vec((border.clone(), border), count)
+        .prop_map(|members| members.into_iter().map(|(l, r)| Domain::Domain(l, r)).collect())
+}
+
+/// The union of `members`
+pub(crate) fn union_of<T: Clone + Debug>(
+    border: impl Strategy<Value = Value<T>> + Clone,
+    count: Range<usize>,
+) -> impl Strategy<Value = Domain<T>> {
+    members(border, count).prop_map(Domain::Union)
+}
+
+/// Unions of up to four members with borders near 0 or at the limits,
+/// the usual input of a property test
+pub(crate) fn flat_domain() -> impl Strategy<Value = Domain<i32>> {
+    union_of(border(30), 0..5)
+}
+
+/// Asserts the domain admits exactly the values of `expected` on the window
+fn assert_model(domain: &Domain<i32>, expected: &Bitset) -> Result<(), TestCaseError> {
+    let actual = Bitset::of(domain);
+    prop_assert_eq!(&actual, expected, "{}", domain.clone().repr());
+    for x in LO..=HI {
+        prop_assert_eq!(domain.contains(x as i32), expected.contains(x), "{} at {}", domain.clone().repr(), x);
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn test_contains_matches_model(a in domain()) {
+        assert_model(&a, &Bitset::of(&a))?;
+    }
+
+    #[test]
+    fn test_intersect(a in domain(), b in domain()) {
+        let expected = Bitset::of(&a).and(&Bitset::of(&b));
+        assert_model(&a.intersect(&b), &expected)?;
+        assert_model(&b.intersect(&a), &expected)?;
+    }
+
+    #[test]
+    fn test_union(a in domain(), b in domain()) {
+        let expected = Bitset::of(&a).or(&Bitset::of(&b));
+        assert_model(&a.union(&b), &expected)?;
+        assert_model(&b.union(&a), &expected)?;
+    }
+
+    #[test]
+    fn test_complement(a in domain()) {
+        let model = Bitset::of(&a);
+        assert_model(&a.complement(), &model.not())?;
+        assert_model(&a.complement().complement(), &model)?;
+        prop_assert_eq!(a.complement().complement(), a.normalize());
+    }
+
+    #[test]
+    fn test_difference(a in domain(), b in domain()) {
+        let expected = Bitset::of(&a).and(&Bitset::of(&b).not());
+        let difference = a.difference(&b);
+        assert_model(&difference, &expected)?;
+        prop_assert_eq!(difference, a.intersect(&b.complement()));
+    }
+
+    #[test]
+    fn test_normalize(a in domain()) {
+        let normalized = a.normalize();
+        assert_model(&normalized, &Bitset::of(&a))?;
+        prop_assert_eq!(normalized.normalize().repr(), normalized.clone().repr());
+        if let Domain::Union(members) = &*normalized {
+            prop_assert!(members.len() > 1);
+            prop_assert!(members.iter().all(|member| matches!(member, Domain::Domain(..))));
+        }
+    }
+
+    #[test]
+    fn test_de_morgan(a in domain(), b in domain()) {
+        prop_assert_eq!(
+            a.union(&b).complement(),
+            a.complement().intersect(&b.complement())
+        );
+        prop_assert_eq!(
+            a.intersect(&b).complement(),
+            a.complement().union(&b.complement())
+        );
+    }
+
+    #[test]
+    fn test_distributive(a in domain(), b in domain(), c in domain()) {
+        prop_assert_eq!(
+            a.intersect(&b.union(&c)),
+            a.intersect(&b).union(&a.intersect(&c))
+        );
+        prop_assert_eq!(
+            a.union(&b.intersect(&c)),
+            a.union(&b).intersect(&a.union(&c))
+        );
+    }
+
+    #[test]
+    fn test_identities(a in domain()) {
+        let model = Bitset::of(&a);
+        assert_model(&a.intersect(&Domain::new()), &model)?;
+        assert_model(&a.union(&Domain::None), &model)?;
+        assert_model(&a.intersect(&Domain::None), &Bitset::empty())?;
+        assert_model(&a.union(&a.complement()), &Bitset::full())?;
+        assert_model(&a.difference(&a), &Bitset::empty())?;
+    }
+
+    #[test]
+    fn test_gt_lt(a in domain(), v in finite()) {
+        let model = Bitset::of(&a);
+        assert_model(&a.gt(v.clone()), &model.and(&Bitset::from_fn(|x| above(&v, x))))?;
+        assert_model(&a.lt(v.clone()), &model.and(&Bitset::from_fn(|x| below(&v, x))))?;
+    }
+
+    #[test]
+    fn test_remove_value(a in domain(), v in -1100..1100i32) {
+        let expected = Bitset::of(&a).and(&Bitset::from_fn(|x| x != v as i64));
+        assert_model(&a.remove_value(v), &expected)?;
+    }
+
+    #[test]
+    fn test_repr_parse(a in domain()) {
+        let parsed: Domain<i32> = a.clone().repr().parse().unwrap();
+        assert_model(&parsed, &Bitset::of(&a))?;
+    }
+}
+
+#[test]
+fn test_bitset() {
+    assert_eq!(Bitset::empty().count(), 0);
+    assert_eq!(Bitset::full().count(), Bitset::LEN as u32);
+    assert!(Bitset::full().contains(LO) && Bitset::full().contains(HI));
+    assert_eq!(Bitset::of(&Domain::between(-1, 1)).count(), 3);
+    assert_eq!(Bitset::of(&Domain::at_least(990)).not().count(), 1990);
+}
@@ -0,0 +1,654 @@
+use crate::util::{self, first_admitted, last_admitted, Member, NEG_INF, POS_INF};
+use crate::{Domain, DomainError, Value};
+
+/// What the arithmetic does with a border moved past the `i32` range
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Refuses the operation with `DomainError::Overflow`
+    Error,
+    /// Clamps the border to `i32::MIN` or `i32::MAX`, kept included
+    Saturate,
+    /// Turns the border into `Value::Infinite`. A member left entirely
+    /// past the range has no `i32` to keep and is dropped
+    Unbounded,
+}
+
+/// How `div_round` rounds a quotient which is not an integer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Towards -∞ at both borders
+    Floor,
+    /// Towards +∞ at both borders
+    Ceil,
+    /// To the closest integer, halves away from zero
+    Nearest,
+    /// Starting borders up and ending ones down: every value of the
+    /// result times `k` is admitted, a safe under-approximation
+    Inward,
+    /// Starting borders down and ending ones up: every value the
+    /// quotient lies next to is in the result, a safe over-approximation
+    Outward,
+}
+
+/// Normalized members as closed `i64` pairs, infinite borders kept
+/// as `NEG_INF`/`POS_INF` and finite ones clipped to the `i32` range.
+/// Members admitting no `i32` are dropped, infinite ones included
+fn closed(domain: &Domain<i32>) -> Vec<(i64, i64)> {
+    util::normalized(domain)
+        .iter()
+        .filter_map(|(l, r)| {
+            let first = first_admitted(l).max(i32::MIN as i64);
+            let last = last_admitted(r).min(i32::MAX as i64);
+            if first > last {
+                return None;
+            }
+            Some((
+                if *l == Value::Infinite { NEG_INF } else { first },
+                if *r == Value::Infinite { POS_INF } else { last },
+            ))
+        })
+        .collect()
+}
+
+/// Closed member for the pair, infinite sentinels become
+/// `Value::Infinite` and finite values saturate to `i32`
+fn member_of((first, last): (i64, i64)) -> Member {
+    let border = |n: i64| match n {
+        NEG_INF | POS_INF => Value::Infinite,
+        n => Value::Included(n.max(i32::MIN as i64).min(i32::MAX as i64) as i32),
+    };
+    (border(first), border(last))
+}
+
+fn is_infinite(n: i64) -> bool {
+    n == NEG_INF || n == POS_INF
+}
+
+/// Truncating division over the extended integers, `y` is never 0.
+/// A finite dividend over an infinite divisor is 0, an infinite
+/// dividend stays infinite with the sign of the quotient
+fn extended_div(x: i64, y: i64) -> i64 {
+    if is_infinite(x) {
+        if (x > 0) == (y > 0) { POS_INF } else { NEG_INF }
+    } else if is_infinite(y) {
+        0
+    } else {
+        x / y
+    }
+}
+
+/// Sum over the extended integers, an infinite term stays infinite.
+/// Both terms are starting borders or both are ending ones, so the
+/// infinities never cancel out
+fn extended_add(x: i64, y: i64) -> i64 {
+    if is_infinite(x) {
+        x
+    } else if is_infinite(y) {
+        y
+    } else {
+        x + y
+    }
+}
+
+/// Product over the extended integers, anything times 0 is 0
+fn extended_mul(x: i64, y: i64) -> i64 {
+    if x == 0 || y == 0 {
+        0
+    } else if is_infinite(x) || is_infinite(y) {
+        if (x > 0) == (y > 0) { POS_INF } else { NEG_INF }
+    } else {
+        x * y
+    }
+}
+
+/// Closed members of an operation's result back into a domain,
+/// applying `policy` to the finite borders past the `i32` range
+fn with_policy(
+    members: Vec<(i64, i64)>,
+    policy: OverflowPolicy,
+    overflow: impl Fn() -> DomainError,
+) -> Result<Domain<i32>, DomainError> {
+    let (min, max) = (i32::MIN as i64, i32::MAX as i64);
+    let in_range = |n: i64| is_infinite(n) || (min..=max).contains(&n);
+
+    let mut result = Vec::with_capacity(members.len());
+    for (first, last) in members {
+        if in_range(first) && in_range(last) {
+            result.push(member_of((first, last)));
+            continue;
+        }
+        match policy {
+            OverflowPolicy::Error => return Err(overflow()),
+            OverflowPolicy::Saturate => result.push(member_of((first, last))),
+            OverflowPolicy::Unbounded => {
+                if first > max || last < min {
+                    continue;
+                }
+                let first = if first < min { NEG_INF } else { first };
+                let last = if last > max { POS_INF } else { last };
+                result.push(member_of((first, last)));
+            },
+        }
+    }
+
+    Ok(util::from_members(util::normalize_members(result)))
+}
+
+impl Domain<i32> {
+
+    /// Image of `x + delta` for every admitted `x`, refusing to go past
+    /// the `i32` range. Same as `shift_with` and `OverflowPolicy::Error`
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Secluded(0), Value::Included(10));
+    /// assert_eq!(domain.shift(5).unwrap().repr(), "[6;15]");
+    /// assert!(Domain::at_most(i32::MAX).shift(1).is_err());
+    /// ```
+    pub fn shift(&self, delta: i32) -> Result<Self, DomainError> {
+        self.shift_with(delta, OverflowPolicy::Error)
+    }
+
+    /// Image of `x + delta` for every admitted `x`, infinite borders stay
+    /// infinite and the finite ones past the `i32` range follow `policy`
+    /// ```
+    /// use dynamic_domain::{Domain, OverflowPolicy};
+    /// let domain = Domain::between(0, i32::MAX);
+    /// assert_eq!(domain.shift_with(1, OverflowPolicy::Saturate).unwrap().repr(), "[1;2147483647]");
+    /// assert_eq!(domain.shift_with(1, OverflowPolicy::Unbounded).unwrap().repr(), "[1;∞)");
+    /// ```
+    pub fn shift_with(&self, delta: i32, policy: OverflowPolicy) -> Result<Self, DomainError> {
+        let members = closed(self)
+            .into_iter()
+            .map(|(a, b)| (extended_add(a, delta as i64), extended_add(b, delta as i64)))
+            .collect();
+        with_policy(members, policy, || DomainError::overflow("shift", self, delta))
+    }
+
+    /// Image of `x + y` for every admitted `x` and `y`, refusing to go past
+    /// the `i32` range. Same as `add_with` and `OverflowPolicy::Error`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::between(0, 10).add(&Domain::between(-1, 1)).unwrap();
+    /// assert_eq!(domain.repr(), "[-1;11]");
+    /// ```
+    pub fn add(&self, other: &Domain<i32>) -> Result<Self, DomainError> {
+        self.add_with(other, OverflowPolicy::Error)
+    }
+
+    /// Image of `x + y` for every admitted `x` and `y`, member by member.
+    /// Finite borders past the `i32` range follow `policy`
+    /// ```
+    /// use dynamic_domain::{Domain, OverflowPolicy};
+    /// let gaps = Domain::Union(vec![Domain::between(0, 1), Domain::between(10, 11)]);
+    /// let domain = gaps.add_with(&Domain::between(0, 2), OverflowPolicy::Error).unwrap();
+    /// assert_eq!(domain.repr(), "[0;3]⋃[10;13]");
+    /// ```
+    pub fn add_with(&self, other: &Domain<i32>, policy: OverflowPolicy) -> Result<Self, DomainError> {
+        let terms = closed(other);
+        let members = closed(self)
+            .into_iter()
+            .flat_map(|(a, b)| terms.iter().map(move |&(c, d)| (extended_add(a, c), extended_add(b, d))))
+            .collect();
+        with_policy(members, policy, || DomainError::overflow("add", self, other.clone().repr()))
+    }
+
+    /// Bounds of `x * k` for every admitted `x`, refusing to go past
+    /// the `i32` range. Same as `scale_with` and `OverflowPolicy::Error`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::between(1, 3).scale(-2).unwrap().repr(), "[-6;-2]");
+    /// ```
+    pub fn scale(&self, k: i32) -> Result<Self, DomainError> {
+        self.scale_with(k, OverflowPolicy::Error)
+    }
+
+    /// Bounds of `x * k` for every admitted `x`: every member becomes the
+    /// closed hull of its image, so `[1;3] * 2` is `[2;6]` and not only the
+    /// even values. Finite borders past the `i32` range follow `policy`
+    /// ```
+    /// use dynamic_domain::{Domain, OverflowPolicy};
+    /// let domain = Domain::at_least(1).scale_with(-3, OverflowPolicy::Error).unwrap();
+    /// assert_eq!(domain.repr(), "(-∞;-3]");
+    /// assert_eq!(Domain::new().scale_with(0, OverflowPolicy::Error).unwrap().repr(), "[0;0]");
+    /// ```
+    pub fn scale_with(&self, k: i32, policy: OverflowPolicy) -> Result<Self, DomainError> {
+        self.mul_with(&Domain::between(k, k), policy)
+            .map_err(|_| DomainError::overflow("scale", self, k))
+    }
+
+    /// Bounds of `x * y` for every admitted `x` and `y`, refusing to go past
+    /// the `i32` range. Same as `mul_with` and `OverflowPolicy::Error`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::between(-2, 3).mul(&Domain::between(4, 5)).unwrap();
+    /// assert_eq!(domain.repr(), "[-10;15]");
+    /// ```
+    pub fn mul(&self, other: &Domain<i32>) -> Result<Self, DomainError> {
+        self.mul_with(other, OverflowPolicy::Error)
+    }
+
+    /// Bounds of `x * y` for every admitted `x` and `y`: every pair of
+    /// members gives the closed hull of its image, from the products of
+    /// their borders. Finite borders past the `i32` range follow `policy`
+    /// ```
+    /// use dynamic_domain::{Domain, OverflowPolicy};
+    /// let domain = Domain::at_least(2).mul_with(&Domain::between(-1, 1), OverflowPolicy::Error).unwrap();
+    /// assert_eq!(domain.repr(), "(-∞;∞)");
+    /// ```
+    pub fn mul_with(&self, other: &Domain<i32>, policy: OverflowPolicy) -> Result<Self, DomainError> {
+        let factors = closed(other);
+        let mut members = Vec::new();
+        for (a, b) in closed(self) {
+            for &(c, d) in &factors {
+                let corners = [
+                    extended_mul(a, c),
+                    extended_mul(a, d),
+                    extended_mul(b, c),
+                    extended_mul(b, d),
+                ];
+                let first = *corners.iter().min().unwrap();
+                let last = *corners.iter().max().unwrap();
+                members.push((first, last));
+            }
+        }
+        with_policy(members, policy, || DomainError::overflow("mul", self, other.clone().repr()))
+    }
+
+    /// Image of `x / k` for every admitted `x`, with Rust's integer
+    /// division which truncates towards zero: `[-7;7] / 2` is `[-3;3]`.
+    /// The result is closed and dividing by 0 gives `Domain::None`.
+    /// `i32::MIN / -1` is past `i32::MAX`, so it is left out like in
+    /// `i32::checked_div`
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Secluded(-8), Value::Included(9));
+    /// assert_eq!(domain.div_const(2).repr(), "[-3;4]");
+    /// assert_eq!(domain.div_const(-2).repr(), "[-4;3]");
+    /// assert_eq!(Domain::at_least(5).div_const(-5).repr(), "(-∞;-1]");
+    /// assert_eq!(Domain::between(i32::MIN, i32::MIN).div_const(-1), Domain::None);
+    /// ```
+    pub fn div_const(&self, k: i32) -> Self {
+        self.div(&Domain::between(k, k))
+    }
+
+    /// Image of `x / y` for every admitted `x` and every admitted
+    /// non-zero `y`, truncating like `div_const`. The divisor is split
+    /// into its positive and negative parts, so a divisor of exactly
+    /// {0} gives `Domain::None`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let total = Domain::between(100, 200);
+    /// let count = Domain::between(-2, 4);
+    /// assert_eq!(total.div(&count).repr(), "[-200;-50]⋃[25;200]");
+    /// ```
+    pub fn div(&self, other: &Domain<i32>) -> Self {
+        let divisors: Vec<(i64, i64)> = closed(
+            &other.intersect(&Domain::at_most(-1))
+                .union(&other.intersect(&Domain::at_least(1)))
+        );
+
+        let mut members = Vec::new();
+        for (a, b) in closed(self) {
+            for &(c, d) in &divisors {
+                let corners = [
+                    extended_div(a, c),
+                    extended_div(a, d),
+                    extended_div(b, c),
+                    extended_div(b, d),
+                ];
+                let first = *corners.iter().min().unwrap();
+                let last = *corners.iter().max().unwrap();
+                // Only i32::MIN / -1 goes past the range, by one quotient
+                // which no admitted `x` gives as an i32
+                let max = i32::MAX as i64;
+                if first > max && !is_infinite(first) {
+                    continue;
+                }
+                let last = if is_infinite(last) { last } else { last.min(max) };
+                members.push(member_of((first, last)));
+            }
+        }
+
+        util::from_members(util::normalize_members(members))
+    }
+
+    /// Converts to a unit `k` times coarser, like milliseconds to seconds.
+    /// Open borders are first moved to the admitted integers, then each
+    /// border of every member is divided by `k` and rounded by `mode`,
+    /// infinite borders stay infinite. Floor, Ceil and Nearest give the
+    /// exact image of `x / k` under that rounding. Inward keeps only the
+    /// quotients which are whole, a member without a multiple of `k`
+    /// is dropped, and Outward grows each member to the integers
+    /// surrounding its quotients. The members are merged after division
+    /// and the result is closed. Errors with `DomainError::NotPositive`
+    /// unless `k` is positive
+    /// ```
+    /// use dynamic_domain::{Domain, DomainError, Rounding, Value};
+    /// let ms = Domain::Domain(Value::Included(1500), Value::Secluded(4200));
+    /// assert_eq!(ms.div_round(1000, Rounding::Floor).unwrap().repr(), "[1;4]");
+    /// assert_eq!(ms.div_round(1000, Rounding::Nearest).unwrap().repr(), "[2;4]");
+    /// assert_eq!(ms.div_round(1000, Rounding::Inward).unwrap().repr(), "[2;4]");
+    /// assert_eq!(ms.div_round(1000, Rounding::Outward).unwrap().repr(), "[1;5]");
+    /// assert_eq!(ms.div_round(0, Rounding::Floor), Err(DomainError::NotPositive));
+    /// ```
+    pub fn div_round(&self, k: i32, mode: Rounding) -> Result<Self, DomainError> {
+        if k <= 0 {
+            return Err(DomainError::NotPositive);
+        }
+        let k = k as i64;
+
+        let floor = |x: i64| x.div_euclid(k);
+        let ceil = |x: i64| -(-x).div_euclid(k);
+        let nearest = |x: i64| {
+            let half_up = (2 * x.abs() + k).div_euclid(2 * k);
+            if x < 0 { -half_up } else { half_up }
+        };
+        let round = |x: i64, starting: bool| match (mode, starting) {
+            _ if is_infinite(x) => x,
+            (Rounding::Floor, _) => floor(x),
+            (Rounding::Ceil, _) => ceil(x),
+            (Rounding::Nearest, _) => nearest(x),
+            (Rounding::Inward, true) | (Rounding::Outward, false) => ceil(x),
+            (Rounding::Inward, false) | (Rounding::Outward, true) => floor(x),
+        };
+
+        let members = closed(self)
+            .into_iter()
+            .map(|(a, b)| (round(a, true), round(b, false)))
+            .filter(|(first, last)| first <= last)
+            .map(member_of)
+            .collect();
+        Ok(util::from_members(util::normalize_members(members)))
+    }
+
+    /// Image of `x.rem_euclid(m)` for every admitted `x`, a part of
+    /// `[0;m-1]`. A member spanning at least `m` values or an unbounded
+    /// one covers every residue, a shorter one which wraps around the
+    /// modulus gives two pieces. Errors with `DomainError::NotPositive`
+    /// unless `m` is positive, like `div_round`
+    /// ```
+    /// use dynamic_domain::{Domain, DomainError};
+    /// assert_eq!(Domain::between(12, 14).rem_euclid_const(10).unwrap().repr(), "[2;4]");
+    /// assert_eq!(Domain::between(8, 11).rem_euclid_const(10).unwrap().repr(), "[0;1]⋃[8;9]");
+    /// assert_eq!(Domain::at_least(0).rem_euclid_const(10).unwrap().repr(), "[0;9]");
+    /// assert_eq!(Domain::between(12, 14).rem_euclid_const(0), Err(DomainError::NotPositive));
+    /// assert_eq!(Domain::between(12, 14).rem_euclid_const(-10), Err(DomainError::NotPositive));
+    /// ```
+    pub fn rem_euclid_const(&self, m: i32) -> Result<Self, DomainError> {
+        if m <= 0 {
+            return Err(DomainError::NotPositive);
+        }
+        let modulus = m as i64;
+
+        let mut members = Vec::new();
+        for (a, b) in closed(self) {
+            if is_infinite(a) || is_infinite(b) || b - a + 1 >= modulus {
+                members.push(member_of((0, modulus - 1)));
+                continue;
+            }
+
+            let (ra, rb) = (a.rem_euclid(modulus), b.rem_euclid(modulus));
+            if ra <= rb {
+                members.push(member_of((ra, rb)));
+            } else {
+                members.push(member_of((ra, modulus - 1)));
+                members.push(member_of((0, rb)));
+            }
+        }
+
+        Ok(util::from_members(util::normalize_members(members)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Domain, DomainError, OverflowPolicy, Rounding, Value};
+
+    const POLICIES: [OverflowPolicy; 3] = [OverflowPolicy::Error, OverflowPolicy::Saturate, OverflowPolicy::Unbounded];
+
+    /// Results under every policy, errors written as `"error"`
+    fn matrix(f: impl Fn(OverflowPolicy) -> Result<Domain<i32>, DomainError>) -> [String; 3] {
+        POLICIES.map(|policy| f(policy).map_or("error".to_string(), |domain| domain.repr()))
+    }
+
+    #[test]
+    fn test_policies_in_range() {
+        let domain = Domain::Union(vec![Domain::between(-5, -1), Domain::Domain(Value::Secluded(10), Value::Infinite)]);
+        for policy in POLICIES {
+            assert_eq!(domain.shift_with(3, policy).unwrap().repr(), "[-2;2]⋃[14;∞)");
+            assert_eq!(domain.add_with(&Domain::between(0, 1), policy).unwrap().repr(), "[-5;0]⋃[11;∞)");
+            assert_eq!(domain.scale_with(-2, policy).unwrap().repr(), "(-∞;-22]⋃[2;10]");
+            assert_eq!(domain.mul_with(&Domain::between(1, 2), policy).unwrap().repr(), "[-10;-1]⋃[11;∞)");
+            assert_eq!(Domain::None.shift_with(i32::MAX, policy), Ok(Domain::None));
+        }
+    }
+
+    #[test]
+    fn test_shift_matrix() {
+        let top = Domain::between(i32::MAX - 1, i32::MAX);
+        assert_eq!(
+            matrix(|policy| top.shift_with(1, policy)),
+            ["error", "[2147483647;2147483647]", "[2147483647;∞)"]
+        );
+        let bottom = Domain::Domain(Value::Infinite, Value::Included(i32::MIN + 1));
+        assert_eq!(
+            matrix(|policy| bottom.shift_with(-3, policy)),
+            ["error", "(-∞;-2147483648]", "∅"]
+        );
+        let past = Domain::between(i32::MAX - 1, i32::MAX);
+        assert_eq!(
+            matrix(|policy| past.shift_with(5, policy)),
+            ["error", "[2147483647;2147483647]", "∅"]
+        );
+        assert_eq!(Domain::new().shift(i32::MAX), Ok(Domain::new()));
+        assert_eq!(
+            top.shift(1).unwrap_err(),
+            DomainError::Overflow { operation: "shift", operands: "[2147483646;2147483647] and 1".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_add_matrix() {
+        let domain = Domain::between(0, i32::MAX);
+        let other = Domain::between(-1, 1);
+        assert_eq!(
+            matrix(|policy| domain.add_with(&other, policy)),
+            ["error", "[-1;2147483647]", "[-1;∞)"]
+        );
+        let low = Domain::between(i32::MIN, i32::MIN + 1);
+        assert_eq!(
+            matrix(|policy| low.add_with(&low, policy)),
+            ["error", "[-2147483648;-2147483648]", "∅"]
+        );
+        assert_eq!(low.add(&Domain::at_most(0)).unwrap().repr(), "(-∞;-2147483647]");
+        assert!(matches!(domain.add(&other), Err(DomainError::Overflow { operation: "add", .. })));
+    }
+
+    #[test]
+    fn test_scale_matrix() {
+        let domain = Domain::between(-3, 3);
+        assert_eq!(
+            matrix(|policy| domain.scale_with(i32::MAX, policy)),
+            ["error", "[-2147483648;2147483647]", "(-∞;∞)"]
+        );
+        let min = Domain::between(i32::MIN, i32::MIN);
+        assert_eq!(
+            matrix(|policy| min.scale_with(-1, policy)),
+            ["error", "[2147483647;2147483647]", "∅"]
+        );
+        assert_eq!(Domain::at_least(1).scale(0).unwrap().repr(), "[0;0]");
+        assert_eq!(
+            min.scale(-1).unwrap_err().to_string(),
+            "scale of [-2147483648;-2147483648] and -1 overflows i32"
+        );
+    }
+
+    #[test]
+    fn test_mul_matrix() {
+        let domain = Domain::between(-70_000, 70_000);
+        assert_eq!(
+            matrix(|policy| domain.mul_with(&domain, policy)),
+            ["error", "[-2147483648;2147483647]", "(-∞;∞)"]
+        );
+        let positive = Domain::between(50_000, 60_000);
+        assert_eq!(
+            matrix(|policy| positive.mul_with(&positive, policy)),
+            ["error", "[2147483647;2147483647]", "∅"]
+        );
+        assert_eq!(Domain::at_least(0).mul(&Domain::at_most(0)).unwrap().repr(), "(-∞;0]");
+        assert_eq!(Domain::new().mul(&Domain::between(0, 0)).unwrap().repr(), "[0;0]");
+        assert_eq!(Domain::at_least(1).mul(&Domain::None), Ok(Domain::None));
+    }
+
+    #[test]
+    fn test_div_const_rounding() {
+        assert_eq!(Domain::between(-7, 7).div_const(2).repr(), "[-3;3]");
+        assert_eq!(Domain::between(-7, -1).div_const(3).repr(), "[-2;0]");
+        assert_eq!(Domain::between(1, 7).div_const(-3).repr(), "[-2;0]");
+        assert_eq!(Domain::Domain(Value::Secluded(0), Value::Secluded(4)).div_const(4).repr(), "[0;0]");
+    }
+
+    #[test]
+    fn test_div_const_edges() {
+        assert_eq!(Domain::new().div_const(3).repr(), "(-∞;∞)");
+        assert_eq!(Domain::at_most(-10).div_const(-5).repr(), "[2;∞)");
+        assert_eq!(Domain::between(1, 5).div_const(0), Domain::None);
+        assert_eq!(Domain::None.div_const(3), Domain::None);
+        // i32::MIN / -1 overflows: left out alone, clipped off a longer member
+        assert_eq!(Domain::between(i32::MIN, 0).div_const(-1).repr(), "[0;2147483647]");
+        assert_eq!(Domain::between(i32::MIN, i32::MIN).div_const(-1), Domain::None);
+        assert_eq!(Domain::between(i32::MIN, i32::MIN).div(&Domain::between(-1, -1)), Domain::None);
+        assert_eq!(Domain::between(i32::MIN, i32::MIN + 1).div_const(-1).repr(), "[2147483647;2147483647]");
+        assert_eq!(Domain::between(i32::MIN, i32::MIN).div(&Domain::between(-2, -1)).repr(), "[1073741824;2147483647]");
+        assert_eq!(Domain::between(i32::MIN, i32::MIN).div_const(1).repr(), "[-2147483648;-2147483648]");
+
+        let union = Domain::Union(vec![Domain::between(0, 9), Domain::between(30, 39)]);
+        assert_eq!(union.div_const(10).repr(), "[0;0]⋃[3;3]");
+    }
+
+    #[test]
+    fn test_per_unit() {
+        let total = Domain::between(100, 200);
+        let count = Domain::between(3, 5);
+        assert_eq!(total.div(&count).repr(), "[20;66]");
+    }
+
+    #[test]
+    fn test_div_by_domain() {
+        let x = Domain::between(-10, 20);
+        assert_eq!(x.div(&Domain::between(2, 5)).repr(), "[-5;10]");
+        assert_eq!(x.div(&Domain::between(-5, -2)).repr(), "[-10;5]");
+        assert_eq!(x.div(&Domain::between(0, 0)), Domain::None);
+        assert_eq!(x.div(&Domain::between(0, 2)).repr(), "[-10;20]");
+        assert_eq!(x.div(&Domain::None), Domain::None);
+    }
+
+    #[test]
+    fn test_div_infinite() {
+        let x = Domain::Domain(Value::Infinite, Value::Included(-5));
+        assert_eq!(x.div(&Domain::at_least(1)).repr(), "(-∞;0]");
+        assert_eq!(Domain::at_least(0).div(&Domain::at_least(1)).repr(), "[0;∞)");
+        assert_eq!(Domain::between(-3, 3).div(&Domain::new()).repr(), "[-3;3]");
+        assert_eq!(Domain::between(10, 10).div(&Domain::at_least(20)).repr(), "[0;0]");
+    }
+
+    #[test]
+    fn test_rem_euclid_within_period() {
+        assert_eq!(Domain::between(20, 29).rem_euclid_const(10).unwrap().repr(), "[0;9]");
+        assert_eq!(Domain::between(21, 29).rem_euclid_const(10).unwrap().repr(), "[1;9]");
+        assert_eq!(Domain::between(-3, -1).rem_euclid_const(10).unwrap().repr(), "[7;9]");
+        assert_eq!(Domain::Domain(Value::Secluded(4), Value::Secluded(6)).rem_euclid_const(4).unwrap().repr(), "[1;1]");
+        assert_eq!(Domain::between(5, 1000).rem_euclid_const(1).unwrap().repr(), "[0;0]");
+    }
+
+    #[test]
+    fn test_rem_euclid_wraps() {
+        assert_eq!(Domain::between(-2, 3).rem_euclid_const(10).unwrap().repr(), "[0;3]⋃[8;9]");
+        assert_eq!(Domain::between(19, 20).rem_euclid_const(10).unwrap().repr(), "[0;0]⋃[9;9]");
+        assert_eq!(Domain::between(15, 23).rem_euclid_const(10).unwrap().repr(), "[0;3]⋃[5;9]");
+        assert_eq!(Domain::between(15, 24).rem_euclid_const(10).unwrap().repr(), "[0;9]");
+        assert_eq!(Domain::between(i32::MAX - 1, i32::MAX).rem_euclid_const(i32::MAX).unwrap().repr(), "[0;0]⋃[2147483646;2147483646]");
+    }
+
+    #[test]
+    fn test_rem_euclid_unions_and_unbounded() {
+        let shards = Domain::Union(vec![Domain::between(1, 2), Domain::between(7, 9)]);
+        assert_eq!(shards.rem_euclid_const(8).unwrap().repr(), "[0;2]⋃[7;7]");
+        assert_eq!(Domain::at_most(-100).rem_euclid_const(3).unwrap().repr(), "[0;2]");
+        assert_eq!(Domain::None.rem_euclid_const(3).unwrap(), Domain::None);
+        assert_eq!(Domain::new().gt(Value::Secluded(i32::MAX)).rem_euclid_const(10).unwrap(), Domain::None);
+        assert_eq!(Domain::new().lt(Value::Secluded(i32::MIN)).div_const(2), Domain::None);
+    }
+
+    #[test]
+    fn test_rem_euclid_not_positive() {
+        // Refused whatever the domain, an empty one included
+        for m in [0, -1, -10, i32::MIN].iter().copied() {
+            assert_eq!(Domain::between(1, 2).rem_euclid_const(m), Err(DomainError::NotPositive));
+            assert_eq!(Domain::new().rem_euclid_const(m), Err(DomainError::NotPositive));
+            assert_eq!(Domain::None.rem_euclid_const(m), Err(DomainError::NotPositive));
+        }
+        assert_eq!(Domain::None.rem_euclid_const(1), Ok(Domain::None));
+        assert_eq!(Domain::new().rem_euclid_const(i32::MAX).unwrap().repr(), "[0;2147483646]");
+    }
+
+    #[test]
+    fn test_div_round_brute_force() {
+        let mut borders = vec![Value::Infinite];
+        for i in -9..=9 {
+            borders.push(Value::Included(i));
+            borders.push(Value::Secluded(i));
+        }
+        let floor = |x: i32, k: i32| x.div_euclid(k);
+        let ceil = |x: i32, k: i32| -(-x).div_euclid(k);
+        let nearest = |x: i32, k: i32| (x as f64 / k as f64).round() as i32;
+
+        for l in &borders {
+            for r in &borders {
+                let domain = Domain::Union(vec![
+                    Domain::Domain(l.clone(), r.clone()),
+                    Domain::between(20, 21),
+                ]);
+                let xs: Vec<i32> = (-40..=40).filter(|x| domain.contains(*x)).collect();
+                for k in 1..=4 {
+                    let image = |f: &dyn Fn(i32, i32) -> i32, y: i32| xs.iter().any(|x| f(*x, k) == y);
+                    let result = |mode| domain.div_round(k, mode).unwrap();
+                    let (f, c, n) = (result(Rounding::Floor), result(Rounding::Ceil), result(Rounding::Nearest));
+                    let (inward, outward) = (result(Rounding::Inward), result(Rounding::Outward));
+
+                    for y in -5..=9 {
+                        let case = format!("{} / {} at {}", domain.clone().repr(), k, y);
+                        assert_eq!(f.contains(y), image(&floor, y), "floor {}", case);
+                        assert_eq!(c.contains(y), image(&ceil, y), "ceil {}", case);
+                        assert_eq!(n.contains(y), image(&nearest, y), "nearest {}", case);
+                        assert_eq!(inward.contains(y), domain.contains(y * k), "inward {}", case);
+                        assert_eq!(outward.contains(y), image(&floor, y) || image(&ceil, y), "outward {}", case);
+                        if inward.contains(y) {
+                            assert!(f.contains(y) && c.contains(y) && n.contains(y), "{}", case);
+                        }
+                        if f.contains(y) || c.contains(y) || n.contains(y) {
+                            assert!(outward.contains(y), "{}", case);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_div_round_edges() {
+        let full = Domain::between(i32::MIN, i32::MAX);
+        assert_eq!(full.div_round(1, Rounding::Outward).unwrap(), full);
+        assert_eq!(full.div_round(i32::MAX, Rounding::Ceil).unwrap().repr(), "[-1;1]");
+        assert_eq!(full.div_round(i32::MAX, Rounding::Inward).unwrap().repr(), "[-1;1]");
+        assert_eq!(Domain::between(i32::MIN, i32::MIN).div_round(2, Rounding::Nearest).unwrap().repr(), "[-1073741824;-1073741824]");
+        assert_eq!(Domain::at_least(i32::MAX).div_round(2, Rounding::Floor).unwrap().repr(), "[1073741823;∞)");
+        assert_eq!(Domain::new().div_round(7, Rounding::Inward).unwrap(), Domain::new());
+        assert_eq!(Domain::between(1, 999).div_round(1000, Rounding::Inward).unwrap(), Domain::None);
+        assert_eq!(Domain::None.div_round(3, Rounding::Outward), Ok(Domain::None));
+        assert_eq!(Domain::new().div_round(-1, Rounding::Floor), Err(DomainError::NotPositive));
+        assert_eq!(Domain::new().div_round(i32::MIN, Rounding::Ceil), Err(DomainError::NotPositive));
+        let merged = Domain::Union(vec![Domain::between(0, 9), Domain::between(11, 19)]);
+        assert_eq!(merged.div_round(10, Rounding::Floor).unwrap().repr(), "[0;1]");
+    }
+}
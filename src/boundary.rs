@@ -0,0 +1,196 @@
+use std::cmp::Ordering;
+
+use crate::util::{self, NEG_INF, POS_INF};
+use crate::{Domain, Value};
+
+/// Which border of a member a boundary event is, emitted by
+/// `Domain::boundary_events`
+///
+/// Kinds on the same point are ordered by where they sit on the line:
+/// an exclusive close just before the point, an inclusive open on it, an
+/// inclusive close on it too but after the open, an exclusive open just
+/// after it. Sorting events of several domains by `(point, kind)` keeps
+/// every value covered exactly while its members are open
+/// ```
+/// use dynamic_domain::BoundaryKind;
+/// let mut kinds = vec![
+///     BoundaryKind::Open { inclusive: false },
+///     BoundaryKind::Close { inclusive: true },
+///     BoundaryKind::Open { inclusive: true },
+///     BoundaryKind::Close { inclusive: false },
+/// ];
+/// kinds.sort();
+/// assert_eq!(kinds, vec![
+///     BoundaryKind::Close { inclusive: false },
+///     BoundaryKind::Open { inclusive: true },
+///     BoundaryKind::Close { inclusive: true },
+///     BoundaryKind::Open { inclusive: false },
+/// ]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BoundaryKind {
+    /// Starting border, `inclusive` when it admits its point
+    Open { inclusive: bool },
+    /// Ending border, `inclusive` when it admits its point
+    Close { inclusive: bool },
+}
+
+impl BoundaryKind {
+    fn rank(&self) -> u8 {
+        match self {
+            BoundaryKind::Close { inclusive: false } => 0,
+            BoundaryKind::Open { inclusive: true } => 1,
+            BoundaryKind::Close { inclusive: true } => 2,
+            BoundaryKind::Open { inclusive: false } => 3,
+        }
+    }
+}
+
+impl PartialOrd for BoundaryKind {
+    fn partial_cmp(&self, other: &BoundaryKind) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BoundaryKind {
+    fn cmp(&self, other: &BoundaryKind) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+/// Point and inclusivity of a border, infinite ones sit on the sentinels
+fn event(value: &Value<i32>, infinity: i64) -> (i64, bool) {
+    match value {
+        Value::Included(i) => (*i as i64, true),
+        Value::Secluded(i) => (*i as i64, false),
+        Value::Infinite => (infinity, false),
+    }
+}
+
+impl Domain<i32> {
+
+    /// Borders of the normalized members as an ascending stream: an open
+    /// and a close event per member, ordered by point and then by
+    /// `BoundaryKind`. Points are the written ones, not moved to the
+    /// admitted values, and `i64` so moving them by one can not overflow.
+    /// An infinite starting border opens at `i64::MIN` and an infinite
+    /// ending one closes at `i64::MAX`, both exclusive
+    /// ```
+    /// use dynamic_domain::{BoundaryKind, Domain, Value};
+    /// let domain = Domain::Union(vec![
+    ///     Domain::Domain(Value::Secluded(5), Value::Infinite),
+    ///     Domain::Domain(Value::Included(1), Value::Secluded(5)),
+    /// ]);
+    /// let events: Vec<_> = domain.boundary_events().collect();
+    /// assert_eq!(events, vec![
+    ///     (1, BoundaryKind::Open { inclusive: true }),
+    ///     (5, BoundaryKind::Close { inclusive: false }),
+    ///     (5, BoundaryKind::Open { inclusive: false }),
+    ///     (i64::MAX, BoundaryKind::Close { inclusive: false }),
+    /// ]);
+    /// ```
+    pub fn boundary_events(&self) -> impl Iterator<Item = (i64, BoundaryKind)> {
+        util::normalized(self).into_iter().flat_map(|(l, r)| {
+            let (start, opens) = event(&l, NEG_INF);
+            let (end, closes) = event(&r, POS_INF);
+            vec![
+                (start, BoundaryKind::Open { inclusive: opens }),
+                (end, BoundaryKind::Close { inclusive: closes }),
+            ]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::{BoundaryKind, Domain, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            1 => Just(Value::Infinite),
+            3 => (-20..20).prop_map(Value::Included),
+            3 => (-20..20).prop_map(Value::Secluded),
+            1 => Just(Value::Included(i32::MIN)),
+            1 => Just(Value::Included(i32::MAX)),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..6)
+    }
+
+    /// Depth of the value after sweeping the sorted events up to it
+    fn covered(events: &[(i64, BoundaryKind)], x: i64) -> usize {
+        let mut depth: usize = 0;
+        for (point, kind) in events {
+            let reached = match kind {
+                BoundaryKind::Open { inclusive: true } | BoundaryKind::Close { inclusive: false } => *point <= x,
+                BoundaryKind::Open { inclusive: false } | BoundaryKind::Close { inclusive: true } => *point < x,
+            };
+            if !reached {
+                break;
+            }
+            match kind {
+                BoundaryKind::Open { .. } => depth += 1,
+                BoundaryKind::Close { .. } => depth -= 1,
+            }
+        }
+        depth
+    }
+
+    proptest! {
+        #[test]
+        fn test_events_sorted_and_sweepable(a in domain(), b in domain()) {
+            let events: Vec<_> = a.boundary_events().collect();
+            prop_assert!(events.windows(2).all(|pair| pair[0] <= pair[1]));
+            prop_assert_eq!(events.len() % 2, 0);
+
+            let mut merged: Vec<_> = a.boundary_events().chain(b.boundary_events()).collect();
+            merged.sort();
+            for x in -25..25 {
+                prop_assert_eq!(covered(&events, x) == 1, a.contains(x as i32));
+                let depth = covered(&merged, x);
+                prop_assert_eq!(depth, a.contains(x as i32) as usize + b.contains(x as i32) as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn test_coincident_events() {
+        let point = Domain::between(5, 5);
+        assert_eq!(
+            point.boundary_events().collect::<Vec<_>>(),
+            vec![(5, BoundaryKind::Open { inclusive: true }), (5, BoundaryKind::Close { inclusive: true })]
+        );
+
+        let mut events: Vec<_> = Domain::Domain(Value::Secluded(5), Value::Included(9))
+            .boundary_events()
+            .chain(Domain::between(0, 5).boundary_events())
+            .collect();
+        events.sort();
+        assert_eq!(events, vec![
+            (0, BoundaryKind::Open { inclusive: true }),
+            (5, BoundaryKind::Close { inclusive: true }),
+            (5, BoundaryKind::Open { inclusive: false }),
+            (9, BoundaryKind::Close { inclusive: true }),
+        ]);
+    }
+
+    #[test]
+    fn test_limits() {
+        let events: Vec<_> = Domain::new().boundary_events().collect();
+        assert_eq!(events, vec![
+            (i64::MIN, BoundaryKind::Open { inclusive: false }),
+            (i64::MAX, BoundaryKind::Close { inclusive: false }),
+        ]);
+
+        let edge = Domain::Domain(Value::Secluded(i32::MAX - 1), Value::Included(i32::MAX));
+        let (point, _) = edge.boundary_events().next().unwrap();
+        assert_eq!(point + 1, i32::MAX as i64);
+        assert_eq!(Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite).boundary_events().count(), 0);
+        assert_eq!(Domain::None.boundary_events().count(), 0);
+    }
+}
@@ -0,0 +1,342 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::util::{self, first_admitted, last_admitted, Member};
+use crate::{Domain, NormalizedDomain, TooComplex};
+
+/// What `normalize_with_budget` does with a domain of more members than
+/// its budget
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetPolicy {
+    /// Refuses with `TooComplex`
+    Error,
+    /// Fills the gaps missing the fewest values until the budget is met,
+    /// an over-approximation: every exact value stays admitted
+    Outward,
+    /// Drops the members admitting the fewest values until the budget is
+    /// met, an under-approximation: no value is added
+    Inward,
+}
+
+/// What `normalize_with_budget` did to meet the budget
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetAction {
+    /// The normalized domain fit, or `BudgetPolicy::Outward` kept its
+    /// only member, the result is exact
+    WithinBudget,
+    /// `BudgetPolicy::Outward` filled this many gaps
+    MergedGaps(usize),
+    /// `BudgetPolicy::Inward` dropped this many members
+    DroppedMembers(usize),
+}
+
+/// Which way `Domain::approximate` gives up exactness
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApproxDirection {
+    /// Fills the gaps missing the fewest values, a superset of the domain
+    Outward,
+    /// Drops the members admitting the fewest values, a subset of it
+    Inward,
+}
+
+/// Indices of the `count` smallest `keys`, ties going to the lower index
+fn smallest(keys: impl Iterator<Item = i64>, count: usize) -> Vec<bool> {
+    let mut queue: BinaryHeap<Reverse<(i64, usize)>> = keys.enumerate().map(|(i, key)| Reverse((key, i))).collect();
+    let mut picked = vec![false; queue.len()];
+    for _ in 0..count {
+        match queue.pop() {
+            Some(Reverse((_, i))) => picked[i] = true,
+            None => break,
+        }
+    }
+    picked
+}
+
+/// Joins the members across the `merges` smallest gaps, with the number
+/// of values the gaps held
+fn merge_gaps(members: Vec<Member>, merges: usize) -> (Vec<Member>, u64) {
+    let gaps: Vec<i64> = members.windows(2).map(|pair| first_admitted(&pair[1].0) - last_admitted(&pair[0].1) - 1).collect();
+    let filled = smallest(gaps.iter().copied(), merges);
+    let added = gaps.iter().zip(&filled).filter(|(_, filled)| **filled).map(|(gap, _)| *gap as u64).sum();
+
+    let mut merged: Vec<Member> = Vec::with_capacity(members.len() - merges);
+    for (i, (l, r)) in members.into_iter().enumerate() {
+        match merged.last_mut() {
+            Some((_, last)) if filled[i - 1] => *last = r,
+            _ => merged.push((l, r)),
+        }
+    }
+    (merged, added)
+}
+
+/// Drops the `drops` members admitting the fewest values, with the
+/// number of values they admitted
+fn drop_members(members: Vec<Member>, drops: usize) -> (Vec<Member>, u64) {
+    let sizes: Vec<i64> = members
+        .iter()
+        .map(|(l, r)| {
+            let (first, last) = util::clipped_run(l, r);
+            last - first + 1
+        })
+        .collect();
+    let dropped = smallest(sizes.iter().copied(), drops);
+    let removed = sizes.iter().zip(&dropped).filter(|(_, dropped)| **dropped).map(|(size, _)| *size as u64).sum();
+    let kept = members.into_iter().zip(dropped).filter(|(_, dropped)| !dropped).map(|(member, _)| member).collect();
+    (kept, removed)
+}
+
+impl Domain<i32> {
+
+    /// `normalize` keeping at most `max_members` members, so a domain
+    /// riddled with holes can not slow every later operation down. Past
+    /// the budget `policy` decides: `Error` refuses with `TooComplex`,
+    /// `Outward` fills the smallest gaps, which admits more values than
+    /// the exact domain, and `Inward` drops the smallest members, which
+    /// admits fewer. `Outward` always keeps a member of a non-empty
+    /// domain, even for a budget of 0, so it merges down to one member
+    /// there and returns a domain of one member as it is, with
+    /// `BudgetAction::WithinBudget`. Returns the result with the action
+    /// taken, `BudgetAction::WithinBudget` for an exact one
+    /// ```
+    /// use dynamic_domain::{BudgetAction, BudgetPolicy, Domain};
+    /// let domain = Domain::Union(vec![Domain::between(0, 9), Domain::between(11, 12), Domain::between(20, 29)]);
+    /// let (outward, action) = domain.normalize_with_budget(2, BudgetPolicy::Outward).unwrap();
+    /// assert_eq!((outward.repr(), action), ("[0;12]⋃[20;29]".to_string(), BudgetAction::MergedGaps(1)));
+    /// let (inward, action) = domain.normalize_with_budget(2, BudgetPolicy::Inward).unwrap();
+    /// assert_eq!((inward.repr(), action), ("[0;9]⋃[20;29]".to_string(), BudgetAction::DroppedMembers(1)));
+    /// assert!(domain.normalize_with_budget(2, BudgetPolicy::Error).is_err());
+    /// let (exact, action) = domain.normalize_with_budget(3, BudgetPolicy::Error).unwrap();
+    /// assert_eq!((exact, action), (domain.normalize(), BudgetAction::WithinBudget));
+    /// let (single, action) = Domain::between(0, 9).normalize_with_budget(0, BudgetPolicy::Outward).unwrap();
+    /// assert_eq!((single.repr(), action), ("[0;9]".to_string(), BudgetAction::WithinBudget));
+    /// ```
+    pub fn normalize_with_budget(
+        &self,
+        max_members: usize,
+        policy: BudgetPolicy,
+    ) -> Result<(NormalizedDomain<i32>, BudgetAction), TooComplex> {
+        let members = util::normalized(self);
+        let budget = match policy {
+            BudgetPolicy::Outward => max_members.max(1),
+            _ => max_members,
+        };
+        if members.len() <= budget {
+            return Ok((NormalizedDomain::new_unchecked(util::from_members(members)), BudgetAction::WithinBudget));
+        }
+
+        let (members, action) = match policy {
+            BudgetPolicy::Error => return Err(TooComplex::new(max_members)),
+            BudgetPolicy::Outward => {
+                let merges = members.len() - budget;
+                (merge_gaps(members, merges).0, BudgetAction::MergedGaps(merges))
+            },
+            BudgetPolicy::Inward => {
+                let drops = members.len() - max_members;
+                (drop_members(members, drops).0, BudgetAction::DroppedMembers(drops))
+            },
+        };
+        Ok((NormalizedDomain::new_unchecked(util::from_members(members)), action))
+    }
+
+    /// Closest domain of at most `k` members in `direction`, with the
+    /// number of values it adds or removes: `Outward` fills the smallest
+    /// gaps of the normalized domain and `Inward` drops its smallest
+    /// members, as `normalize_with_budget` does past its budget. `Outward`
+    /// keeps a member of a non-empty domain even for `k` of 0. Infinite
+    /// borders count from the `i32` limits
+    /// ```
+    /// use dynamic_domain::{ApproxDirection, Domain};
+    /// let domain = Domain::Union(vec![Domain::between(0, 9), Domain::between(12, 12), Domain::between(20, 29)]);
+    /// let (outward, added) = domain.approximate(2, ApproxDirection::Outward);
+    /// assert_eq!((outward.repr(), added), ("[0;12]⋃[20;29]".to_string(), 2));
+    /// let (inward, removed) = domain.approximate(2, ApproxDirection::Inward);
+    /// assert_eq!((inward.repr(), removed), ("[0;9]⋃[20;29]".to_string(), 1));
+    /// assert_eq!(domain.approximate(3, ApproxDirection::Inward).1, 0);
+    /// ```
+    pub fn approximate(&self, k: usize, direction: ApproxDirection) -> (Domain<i32>, u64) {
+        let members = util::normalized(self);
+        let (members, error) = match direction {
+            ApproxDirection::Outward => {
+                let merges = members.len().saturating_sub(k.max(1));
+                merge_gaps(members, merges)
+            },
+            ApproxDirection::Inward => {
+                let drops = members.len().saturating_sub(k);
+                drop_members(members, drops)
+            },
+        };
+        (util::from_members(members), error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::{border, union_of};
+    use crate::util;
+    use crate::{ApproxDirection, BudgetAction, BudgetPolicy, Domain};
+
+    proptest! {
+        #[test]
+        fn test_approximation_direction(domain in union_of(border(40), 0..12), max_members in 0..6usize) {
+            let exact = domain.normalize();
+            let count = exact.members().len();
+
+            let (outward, action) = domain.normalize_with_budget(max_members, BudgetPolicy::Outward).unwrap();
+            prop_assert!(exact.difference(&outward).is_empty(), "{:?} misses exact values", outward);
+            prop_assert!(outward.members().len() <= max_members.max(1));
+            if count > max_members.max(1) {
+                prop_assert_eq!(action, BudgetAction::MergedGaps(count - max_members.max(1)));
+                prop_assert_eq!(outward.members().len(), max_members.max(1));
+                let hull = |domain: &Domain<i32>| {
+                    let runs = util::i32_runs(domain);
+                    (runs[0].0, runs[runs.len() - 1].1)
+                };
+                prop_assert_eq!(hull(&outward), hull(&exact));
+            } else {
+                prop_assert_eq!((&outward, action), (&exact, BudgetAction::WithinBudget));
+            }
+
+            let (inward, action) = domain.normalize_with_budget(max_members, BudgetPolicy::Inward).unwrap();
+            prop_assert!(inward.difference(&exact).is_empty(), "{:?} adds values", inward);
+            prop_assert!(inward.members().len() <= max_members);
+            if count > max_members {
+                prop_assert_eq!(action, BudgetAction::DroppedMembers(count - max_members));
+                prop_assert!(inward.members().iter().all(|member| exact.members().contains(member)));
+            } else {
+                prop_assert_eq!((&inward, action), (&exact, BudgetAction::WithinBudget));
+            }
+
+            let result = domain.normalize_with_budget(max_members, BudgetPolicy::Error);
+            prop_assert_eq!(result.is_ok(), count <= max_members);
+            if let Err(error) = result {
+                prop_assert_eq!(error.max_output_members(), max_members);
+            }
+        }
+    }
+
+    /// Values admitted, infinite borders counting from the `i32` limits
+    fn count(domain: &Domain<i32>) -> u64 {
+        util::i32_runs(domain).iter().map(|(lo, hi)| (hi - lo + 1) as u64).sum()
+    }
+
+    proptest! {
+        #[test]
+        fn test_approximate_error(domain in union_of(border(40), 0..12), k in 0..6usize) {
+            let exact = domain.normalize();
+
+            let (outward, added) = domain.approximate(k, ApproxDirection::Outward);
+            prop_assert!(exact.difference(&outward).is_empty(), "{:?} misses exact values", outward);
+            prop_assert_eq!(count(&outward) - count(&exact), added);
+            let (budget, _) = domain.normalize_with_budget(k, BudgetPolicy::Outward).unwrap();
+            prop_assert_eq!(&outward, &budget.into_inner());
+
+            let (inward, removed) = domain.approximate(k, ApproxDirection::Inward);
+            prop_assert!(inward.difference(&exact).is_empty(), "{:?} adds values", inward);
+            prop_assert_eq!(count(&exact) - count(&inward), removed);
+            let (budget, _) = domain.normalize_with_budget(k, BudgetPolicy::Inward).unwrap();
+            prop_assert_eq!(&inward, &budget.into_inner());
+        }
+    }
+
+    #[test]
+    fn test_approximate_counts() {
+        // Gaps of 4, 1, 3 and 1 missing values, members of 1, 1, 1, 1 and 8
+        let domain = Domain::Union(vec![
+            Domain::between(0, 0),
+            Domain::between(5, 5),
+            Domain::between(7, 7),
+            Domain::between(11, 11),
+            Domain::between(13, 20),
+        ]);
+        let outward = |k| {
+            let (approximation, added) = domain.approximate(k, ApproxDirection::Outward);
+            (approximation.repr(), added)
+        };
+        assert_eq!(outward(5), ("[0;0]⋃[5;5]⋃[7;7]⋃[11;11]⋃[13;20]".to_string(), 0));
+        assert_eq!(outward(3), ("[0;0]⋃[5;7]⋃[11;20]".to_string(), 2));
+        assert_eq!(outward(1), ("[0;20]".to_string(), 9));
+        assert_eq!(outward(0), ("[0;20]".to_string(), 9));
+
+        let inward = |k| {
+            let (approximation, removed) = domain.approximate(k, ApproxDirection::Inward);
+            (approximation.repr(), removed)
+        };
+        assert_eq!(inward(9), ("[0;0]⋃[5;5]⋃[7;7]⋃[11;11]⋃[13;20]".to_string(), 0));
+        assert_eq!(inward(2), ("[11;11]⋃[13;20]".to_string(), 3));
+        assert_eq!(inward(0), ("∅".to_string(), 12));
+        assert_eq!(Domain::None.approximate(0, ApproxDirection::Outward).1, 0);
+    }
+
+    #[test]
+    fn test_approximate_limits() {
+        let ends = Domain::Union(vec![Domain::at_most(i32::MIN), Domain::between(i32::MAX, i32::MAX)]);
+        let (outward, added) = ends.approximate(1, ApproxDirection::Outward);
+        assert_eq!((outward.repr(), added), ("(-∞;2147483647]".to_string(), (1 << 32) - 2));
+        let (inward, removed) = ends.approximate(1, ApproxDirection::Inward);
+        assert_eq!((inward.repr(), removed), ("[2147483647;2147483647]".to_string(), 1));
+
+        let halves = Domain::Union(vec![Domain::at_most(-1), Domain::at_least(1)]);
+        assert_eq!(halves.approximate(0, ApproxDirection::Inward).1, (1 << 32) - 1);
+        assert_eq!(halves.approximate(0, ApproxDirection::Outward), (Domain::new(), 1));
+    }
+
+    #[test]
+    fn test_nearest_gaps_first() {
+        // Gaps of 4, 1, 3 and 1 missing values
+        let domain = Domain::Union(vec![
+            Domain::between(0, 0),
+            Domain::between(5, 5),
+            Domain::between(7, 7),
+            Domain::between(11, 11),
+            Domain::between(13, 20),
+        ]);
+        let repr = |budget, policy| domain.normalize_with_budget(budget, policy).unwrap().0.repr();
+        assert_eq!(repr(4, BudgetPolicy::Outward), "[0;0]⋃[5;7]⋃[11;11]⋃[13;20]");
+        assert_eq!(repr(3, BudgetPolicy::Outward), "[0;0]⋃[5;7]⋃[11;20]");
+        assert_eq!(repr(2, BudgetPolicy::Outward), "[0;0]⋃[5;20]");
+        assert_eq!(repr(0, BudgetPolicy::Outward), "[0;20]");
+        let action = |domain: &Domain<i32>, budget| domain.normalize_with_budget(budget, BudgetPolicy::Outward).unwrap().1;
+        assert_eq!(action(&domain, 0), BudgetAction::MergedGaps(4));
+        assert_eq!(action(&domain, 1), BudgetAction::MergedGaps(4));
+
+        // A single member is kept exact, nothing was merged
+        for single in [Domain::between(3, 9), Domain::new(), Domain::at_least(i32::MAX)].iter() {
+            let (outward, action) = single.normalize_with_budget(0, BudgetPolicy::Outward).unwrap();
+            assert_eq!((outward.into_inner(), action), (single.clone(), BudgetAction::WithinBudget));
+        }
+        assert_eq!(action(&Domain::None, 0), BudgetAction::WithinBudget);
+
+        // Members of one value each, ties drop the lower ones first
+        assert_eq!(repr(2, BudgetPolicy::Inward), "[11;11]⋃[13;20]");
+        assert_eq!(repr(1, BudgetPolicy::Inward), "[13;20]");
+        assert_eq!(repr(0, BudgetPolicy::Inward), "∅");
+    }
+
+    #[test]
+    fn test_unbounded_members() {
+        let domain = Domain::Union(vec![Domain::at_most(-10), Domain::between(0, 0), Domain::at_least(i32::MAX)]);
+        let (inward, _) = domain.normalize_with_budget(1, BudgetPolicy::Inward).unwrap();
+        assert_eq!(inward.repr(), "(-∞;-10]");
+        let (outward, _) = domain.normalize_with_budget(1, BudgetPolicy::Outward).unwrap();
+        assert_eq!(outward.repr(), "(-∞;∞)");
+    }
+
+    #[test]
+    fn test_many_holes() {
+        let holes = Domain::Union((0..100_000).map(|i| Domain::between(i * 10 + 5, i * 10 + 5)).collect());
+        let riddled = Domain::between(0, 1_000_000).difference(&holes);
+        assert_eq!(riddled.members().len(), 100_001);
+
+        let (outward, action) = riddled.normalize_with_budget(1_000, BudgetPolicy::Outward).unwrap();
+        assert_eq!((outward.members().len(), action), (1_000, BudgetAction::MergedGaps(99_001)));
+        assert!(riddled.difference(&outward).is_empty());
+
+        let (inward, action) = riddled.normalize_with_budget(1_000, BudgetPolicy::Inward).unwrap();
+        assert_eq!((inward.members().len(), action), (1_000, BudgetAction::DroppedMembers(99_001)));
+        // The two outer members of 5 values go first, then the lowest ones of 9
+        assert_eq!(util::i32_runs(&inward).last(), Some(&(999_986, 999_994)));
+        assert!(inward.difference(&riddled).is_empty());
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{BuildConflict, Constraint, Domain, Value};
+
+/// Records constraints with optional labels and evaluates them only on
+/// `build`, so a conflict can name the constraints which caused it
+/// ```
+/// use dynamic_domain::DomainBuilder;
+/// let domain = DomainBuilder::new().ge(1).lt(10).ne(5).build().unwrap();
+/// assert_eq!(domain.repr(), "[1;5)⋃(5;10)");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DomainBuilder {
+    constraints: Vec<(Constraint, Option<String>)>,
+}
+
+impl DomainBuilder {
+    /// Builder without constraints, building the full domain
+    /// ```
+    /// use dynamic_domain::{Domain, DomainBuilder};
+    /// assert_eq!(DomainBuilder::new().build().unwrap(), Domain::new());
+    /// ```
+    pub fn new() -> Self {
+        DomainBuilder { constraints: Vec::new() }
+    }
+
+    fn with(mut self, constraint: Constraint) -> Self {
+        self.constraints.push((constraint, None));
+        self
+    }
+
+    /// Labels the last recorded constraint, does nothing before the first one
+    /// ```
+    /// use dynamic_domain::DomainBuilder;
+    /// let conflict = DomainBuilder::new().gt(5).labeled("min").lt(3).labeled("max").build().unwrap_err();
+    /// assert_eq!(conflict.label(), Some("max"));
+    /// ```
+    pub fn labeled(mut self, label: impl Into<String>) -> Self {
+        if let Some((_, last)) = self.constraints.last_mut() {
+            *last = Some(label.into());
+        }
+        self
+    }
+
+    /// Keeps the values greater than `value`
+    /// ```
+    /// use dynamic_domain::DomainBuilder;
+    /// assert_eq!(DomainBuilder::new().gt(5).build().unwrap().repr(), "(5;∞)");
+    /// ```
+    pub fn gt(self, value: i32) -> Self {
+        self.with(Constraint::Gt(Value::Secluded(value)))
+    }
+
+    /// Keeps the values greater than or equal to `value`
+    /// ```
+    /// use dynamic_domain::DomainBuilder;
+    /// assert_eq!(DomainBuilder::new().ge(5).build().unwrap().repr(), "[5;∞)");
+    /// ```
+    pub fn ge(self, value: i32) -> Self {
+        self.with(Constraint::Gt(Value::Included(value)))
+    }
+
+    /// Keeps the values less than `value`
+    /// ```
+    /// use dynamic_domain::DomainBuilder;
+    /// assert_eq!(DomainBuilder::new().lt(5).build().unwrap().repr(), "(-∞;5)");
+    /// ```
+    pub fn lt(self, value: i32) -> Self {
+        self.with(Constraint::Lt(Value::Secluded(value)))
+    }
+
+    /// Keeps the values less than or equal to `value`
+    /// ```
+    /// use dynamic_domain::DomainBuilder;
+    /// assert_eq!(DomainBuilder::new().le(5).build().unwrap().repr(), "(-∞;5]");
+    /// ```
+    pub fn le(self, value: i32) -> Self {
+        self.with(Constraint::Lt(Value::Included(value)))
+    }
+
+    /// Removes `value`
+    /// ```
+    /// use dynamic_domain::DomainBuilder;
+    /// assert_eq!(DomainBuilder::new().ge(0).le(2).ne(1).build().unwrap().repr(), "[0;1)⋃(1;2]");
+    /// ```
+    pub fn ne(self, value: i32) -> Self {
+        self.with(Constraint::Ne(value))
+    }
+
+    /// Keeps the values admitted by `domain`
+    /// ```
+    /// use dynamic_domain::{Domain, DomainBuilder};
+    /// let domain = DomainBuilder::new().inside(Domain::between(0, 9)).build().unwrap();
+    /// assert_eq!(domain.repr(), "[0;9]");
+    /// ```
+    pub fn inside(self, domain: Domain<i32>) -> Self {
+        self.with(Constraint::InsideOf(domain))
+    }
+
+    /// Removes the values admitted by `domain`
+    /// ```
+    /// use dynamic_domain::{Domain, DomainBuilder};
+    /// let domain = DomainBuilder::new().ge(0).outside(Domain::between(3, 9)).build().unwrap();
+    /// assert_eq!(domain.repr(), "[0;3)⋃(9;∞)");
+    /// ```
+    pub fn outside(self, domain: Domain<i32>) -> Self {
+        self.with(Constraint::OutsideOf(domain))
+    }
+
+    /// Recorded constraints with their labels, in recording order
+    /// ```
+    /// use dynamic_domain::{Constraint, DomainBuilder};
+    /// let builder = DomainBuilder::new().ne(3).labeled("reserved");
+    /// assert_eq!(builder.constraints()[0], (Constraint::Ne(3), Some("reserved".to_string())));
+    /// ```
+    pub fn constraints(&self) -> &[(Constraint, Option<String>)] {
+        &self.constraints
+    }
+
+    /// Applies the constraints in recording order to the full domain.
+    /// The result does not depend on the order, only the reported conflict
+    /// does: it names the first constraint leaving no value, the domain
+    /// just before it and a minimal set of constraints leaving no value
+    /// together, each of them needed for the conflict
+    /// ```
+    /// use dynamic_domain::{Domain, DomainBuilder};
+    /// let conflict = DomainBuilder::new()
+    ///     .ge(0).labeled("non-negative")
+    ///     .ne(1)
+    ///     .inside(Domain::between(-10, -1)).labeled("legacy range")
+    ///     .build()
+    ///     .unwrap_err();
+    /// assert_eq!(conflict.index(), 2);
+    /// assert_eq!(conflict.before().clone().repr(), "[0;1)⋃(1;∞)");
+    /// assert_eq!(conflict.conflicting(), &[(0, Some("non-negative".to_string())), (2, Some("legacy range".to_string()))]);
+    /// ```
+    pub fn build(&self) -> Result<Domain<i32>, BuildConflict> {
+        let mut domain = Domain::new();
+        for (index, (constraint, label)) in self.constraints.iter().enumerate() {
+            let next = constraint.apply(&domain);
+            if next.is_empty() {
+                let conflicting = self
+                    .minimal_conflict(index)
+                    .into_iter()
+                    .map(|i| (i, self.constraints[i].1.clone()))
+                    .collect();
+                return Err(BuildConflict::new(index, label.clone(), domain, conflicting));
+            }
+            domain = next;
+        }
+        Ok(domain)
+    }
+
+    /// Drops every constraint up to `last` whose removal still leaves no
+    /// value, the remaining ones all take part in the conflict
+    fn minimal_conflict(&self, last: usize) -> Vec<usize> {
+        let empty = |indices: &[usize]| {
+            indices
+                .iter()
+                .fold(Domain::new(), |domain, i| self.constraints[*i].0.apply(&domain))
+                .is_empty()
+        };
+
+        let mut kept: Vec<usize> = (0..=last).collect();
+        let mut i = 0;
+        while i < kept.len() {
+            let mut without = kept.clone();
+            without.remove(i);
+            if empty(&without) {
+                kept = without;
+            } else {
+                i += 1;
+            }
+        }
+        kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{Domain, DomainBuilder};
+
+    #[derive(Clone, Debug)]
+    enum Call {
+        Gt(i32),
+        Ge(i32),
+        Lt(i32),
+        Le(i32),
+        Ne(i32),
+        Inside(i32, i32),
+        Outside(i32, i32),
+    }
+
+    fn call() -> impl Strategy<Value = Call> {
+        let point = -20..20;
+        prop_oneof![
+            point.clone().prop_map(Call::Gt),
+            point.clone().prop_map(Call::Ge),
+            point.clone().prop_map(Call::Lt),
+            point.clone().prop_map(Call::Le),
+            point.clone().prop_map(Call::Ne),
+            (point.clone(), point.clone()).prop_map(|(lo, hi)| Call::Inside(lo, hi)),
+            (point.clone(), point).prop_map(|(lo, hi)| Call::Outside(lo, hi)),
+        ]
+    }
+
+    fn record(calls: &[Call]) -> DomainBuilder {
+        calls.iter().enumerate().fold(DomainBuilder::new(), |builder, (i, call)| {
+            let builder = match call {
+                Call::Gt(n) => builder.gt(*n),
+                Call::Ge(n) => builder.ge(*n),
+                Call::Lt(n) => builder.lt(*n),
+                Call::Le(n) => builder.le(*n),
+                Call::Ne(n) => builder.ne(*n),
+                Call::Inside(lo, hi) => builder.inside(Domain::between(*lo, *hi)),
+                Call::Outside(lo, hi) => builder.outside(Domain::between(*lo, *hi)),
+            };
+            builder.labeled(format!("#{}", i))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_order_independent(calls in prop::collection::vec(call(), 0..8), seed: u64) {
+            let mut shuffled = calls.clone();
+            let mut state = seed;
+            for i in (1..shuffled.len()).rev() {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                shuffled.swap(i, (state >> 33) as usize % (i + 1));
+            }
+
+            match (record(&calls).build(), record(&shuffled).build()) {
+                (Ok(a), Ok(b)) => prop_assert_eq!(a, b),
+                (Err(_), Err(_)) => (),
+                (a, b) => prop_assert!(false, "{:?} vs {:?}", a, b),
+            }
+        }
+
+        #[test]
+        fn test_conflict_minimal(calls in prop::collection::vec(call(), 0..8)) {
+            let builder = record(&calls);
+            if let Err(conflict) = builder.build() {
+                prop_assert!(conflict.before().clone().normalize() != Domain::None);
+                let subset: Vec<usize> = conflict.conflicting().iter().map(|(i, _)| *i).collect();
+                prop_assert_eq!(subset.last(), Some(&conflict.index()));
+
+                let only = |indices: &[usize]| {
+                    let calls: Vec<Call> = indices.iter().map(|i| calls[*i].clone()).collect();
+                    record(&calls).build().is_err()
+                };
+                prop_assert!(only(&subset));
+                for skip in 0..subset.len() {
+                    let mut fewer = subset.clone();
+                    fewer.remove(skip);
+                    prop_assert!(!only(&fewer));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_conflict_labels() {
+        let conflict = DomainBuilder::new()
+            .le(100).labeled("max")
+            .ge(10).labeled("min")
+            .ne(50)
+            .lt(5)
+            .build()
+            .unwrap_err();
+        assert_eq!(conflict.index(), 3);
+        assert_eq!(conflict.label(), None);
+        assert_eq!(conflict.before().clone().repr(), "[10;50)⋃(50;100]");
+        assert_eq!(conflict.conflicting(), &[(1, Some("min".to_string())), (3, None)]);
+        assert_eq!(
+            conflict.to_string(),
+            "constraint 3 leaves no value in [10;50)⋃(50;100], conflicting constraints: 1 (min), 3"
+        );
+    }
+
+    #[test]
+    fn test_edges() {
+        let point = DomainBuilder::new().ge(i32::MAX).build().unwrap();
+        assert_eq!(point, Domain::between(i32::MAX, i32::MAX));
+        let conflict = DomainBuilder::new().gt(i32::MAX).labeled("past").build().unwrap_err();
+        assert_eq!((conflict.index(), conflict.label()), (0, Some("past")));
+        assert_eq!(conflict.before(), &Domain::new());
+
+        let builder = DomainBuilder::new().labeled("ignored").ne(i32::MIN);
+        assert_eq!(builder.constraints()[0].1, None);
+        assert_eq!(builder.build().unwrap().repr(), "(-2147483648;∞)");
+    }
+}
@@ -0,0 +1,168 @@
+use crate::util;
+use crate::Domain;
+
+/// Membership of many values against the runs of one domain. The position
+/// of the last match is kept and the next value is searched for by
+/// galloping from it, so ascending values walk the runs once and values
+/// in any order cost a logarithmic search each
+struct Cursor {
+    runs: Vec<(i64, i64)>,
+    position: usize,
+}
+
+impl Cursor {
+    fn new(domain: &Domain<i32>) -> Self {
+        Cursor { runs: util::i32_runs(domain), position: 0 }
+    }
+
+    /// Index of the first run not ending before `value`
+    fn seek(&mut self, value: i64) -> usize {
+        let runs = &self.runs;
+        let ends_before = |i: usize| runs[i].1 < value;
+
+        let (mut lo, mut hi) = if self.position < runs.len() && ends_before(self.position) {
+            let mut step = 1;
+            let mut lo = self.position + 1;
+            while lo + step <= runs.len() && ends_before(lo + step - 1) {
+                lo += step;
+                step *= 2;
+            }
+            (lo, (lo + step).min(runs.len()))
+        } else if self.position == 0 || ends_before(self.position - 1) {
+            return self.position;
+        } else {
+            (0, self.position)
+        };
+
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if ends_before(mid) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        self.position = lo;
+        lo
+    }
+
+    fn contains(&mut self, value: i32) -> bool {
+        let value = value as i64;
+        let run = self.seek(value);
+        self.runs.get(run).is_some_and(|(first, _)| *first <= value)
+    }
+}
+
+impl Domain<i32> {
+
+    /// Checks whether every value is admitted, `true` for no values.
+    /// The domain is normalized once for the whole slice, ascending
+    /// values are checked in a single pass over its runs
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(0, 9), Domain::between(20, 29)]);
+    /// assert!(domain.contains_all(&[1, 5, 25]));
+    /// assert!(!domain.contains_all(&[25, 15]));
+    /// assert!(Domain::None.contains_all(&[]));
+    /// ```
+    pub fn contains_all(&self, values: &[i32]) -> bool {
+        let mut cursor = Cursor::new(self);
+        values.iter().all(|value| cursor.contains(*value))
+    }
+
+    /// Checks whether some value is admitted, `false` for no values.
+    /// Same single pass as `contains_all`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(0, 9), Domain::between(20, 29)]);
+    /// assert!(domain.contains_any(&[15, 25]));
+    /// assert!(!domain.contains_any(&[-1, 10, 30]));
+    /// assert!(!Domain::new().contains_any(&[]));
+    /// ```
+    pub fn contains_any(&self, values: &[i32]) -> bool {
+        let mut cursor = Cursor::new(self);
+        values.iter().any(|value| cursor.contains(*value))
+    }
+
+    /// Splits the values into the admitted ones and the others, both in
+    /// their order in `values`. Same single pass as `contains_all`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::between(10, 19);
+    /// let (valid, invalid) = domain.partition_contained(&[12, 3, 19, 20, 10]);
+    /// assert_eq!(valid, vec![12, 19, 10]);
+    /// assert_eq!(invalid, vec![3, 20]);
+    /// ```
+    pub fn partition_contained(&self, values: &[i32]) -> (Vec<i32>, Vec<i32>) {
+        let mut cursor = Cursor::new(self);
+        values.iter().partition(|value| cursor.contains(**value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::{Domain, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            1 => Just(Value::Infinite),
+            3 => (-50..50).prop_map(Value::Included),
+            3 => (-50..50).prop_map(Value::Secluded),
+            1 => Just(Value::Included(i32::MIN)),
+            1 => Just(Value::Included(i32::MAX)),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..12)
+    }
+
+    fn values() -> impl Strategy<Value = Vec<i32>> {
+        prop::collection::vec(
+            prop_oneof![4 => -60..60, 1 => Just(i32::MIN), 1 => Just(i32::MAX)],
+            0..40,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_contains(domain in domain(), values in values(), sort: bool) {
+            let mut values = values;
+            if sort {
+                values.sort_unstable();
+            }
+
+            prop_assert_eq!(domain.contains_all(&values), values.iter().all(|v| domain.contains(*v)));
+            prop_assert_eq!(domain.contains_any(&values), values.iter().any(|v| domain.contains(*v)));
+
+            let (admitted, others) = domain.partition_contained(&values);
+            let expected: (Vec<i32>, Vec<i32>) = values.iter().partition(|v| domain.contains(**v));
+            prop_assert_eq!((admitted, others), expected);
+        }
+    }
+
+    #[test]
+    fn test_many_runs() {
+        let domain = Domain::Union((0..1000).map(|i| Domain::between(i * 10, i * 10 + 4)).collect());
+        let ascending: Vec<i32> = (-5..10_005).collect();
+        let (admitted, others) = domain.partition_contained(&ascending);
+        assert_eq!((admitted.len(), others.len()), (5000, 5010));
+
+        let descending: Vec<i32> = ascending.iter().rev().copied().collect();
+        let (admitted, _) = domain.partition_contained(&descending);
+        assert_eq!(admitted.first(), Some(&9994));
+        assert!(domain.contains_all(&[9994, 0, 5004, 4, 9990]));
+    }
+
+    #[test]
+    fn test_limits() {
+        let edges = [i32::MIN, i32::MAX, i32::MIN, 0];
+        assert!(Domain::new().contains_all(&edges));
+        assert!(!Domain::None.contains_any(&edges));
+        let domain = Domain::Domain(Value::Secluded(i32::MIN), Value::Secluded(i32::MAX));
+        assert_eq!(domain.partition_contained(&edges), (vec![0], vec![i32::MIN, i32::MAX, i32::MIN]));
+    }
+}
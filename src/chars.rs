@@ -0,0 +1,334 @@
+use std::convert::TryFrom;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::util;
+use crate::{Domain, Value};
+
+/// Number of code points skipped by the surrogate gap
+const SURROGATES: u32 = 0x800;
+
+/// Ordinal of `char::MAX`
+const MAX_ORDINAL: i32 = (0x10FFFF - SURROGATES) as i32;
+
+/// Position of `c` among the scalar values, so consecutive
+/// scalar values get consecutive ordinals across the surrogate gap
+fn ordinal(c: char) -> i32 {
+    let code = c as u32;
+    if code < 0xD800 { code as i32 } else { (code - SURROGATES) as i32 }
+}
+
+fn from_ordinal(ordinal: i32) -> char {
+    let code = ordinal as u32;
+    let code = if code < 0xD800 { code } else { code + SURROGATES };
+    char::from_u32(code).unwrap()
+}
+
+/// The domain over ordinals, limited to valid scalar values
+fn to_ordinals(domain: &Domain<char>) -> Domain<i32> {
+    let border = |value: &Value<char>| match value {
+        Value::Included(c) => Value::Included(ordinal(*c)),
+        Value::Secluded(c) => Value::Secluded(ordinal(*c)),
+        Value::Infinite => Value::Infinite,
+    };
+
+    let mut members = Vec::new();
+    collect_ordinals(domain, &border, &mut members);
+    util::from_members(members).intersect(&Domain::between(0, MAX_ORDINAL)).into_inner()
+}
+
+fn collect_ordinals(
+    domain: &Domain<char>,
+    border: &impl Fn(&Value<char>) -> Value<i32>,
+    members: &mut Vec<util::Member>,
+) {
+    match domain {
+        Domain::Union(domains) => {
+            for domain in domains {
+                collect_ordinals(domain, border, members);
+            }
+        },
+        Domain::Domain(l, r) => members.push((border(l), border(r))),
+        Domain::None => (),
+    }
+}
+
+/// Closed char members for the runs of an ordinal domain
+fn from_ordinals(domain: &Domain<i32>) -> Domain<char> {
+    let mut members: Vec<Domain<char>> = util::i32_runs(domain)
+        .into_iter()
+        .map(|(lo, hi)| Domain::Domain(
+            Value::Included(from_ordinal(lo as i32)),
+            Value::Included(from_ordinal(hi as i32)),
+        ))
+        .collect();
+
+    match members.len() {
+        0 => Domain::None,
+        1 => members.remove(0),
+        _ => Domain::Union(members),
+    }
+}
+
+/// Writes `c` as a class member, only printable ASCII is written as is
+fn push_escaped(class: &mut String, c: char) {
+    match c {
+        ']' | '[' | '^' | '-' | '\\' => {
+            class.push('\\');
+            class.push(c);
+        },
+        ' '..='~' => class.push(c),
+        _ => class.extend(c.escape_unicode()),
+    }
+}
+
+struct ClassParser<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> ClassParser<'a> {
+    fn position(&mut self) -> usize {
+        match self.chars.peek() {
+            Some((position, _)) => *position,
+            None => self.input.len(),
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn error(&mut self) -> ParseError {
+        let position = self.position();
+        match self.peek() {
+            Some(c) => ParseError::new(position, ParseErrorKind::UnexpectedChar(c)),
+            None => ParseError::new(position, ParseErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        if self.peek() == Some(expected) {
+            self.chars.next();
+            return Ok(());
+        }
+        Err(self.error())
+    }
+
+    /// Single member character, escapes resolved
+    fn atom(&mut self) -> Result<char, ParseError> {
+        match self.peek() {
+            Some('\\') => {
+                let start = self.position();
+                self.chars.next();
+                match self.chars.next() {
+                    Some((_, 'n')) => Ok('\n'),
+                    Some((_, 't')) => Ok('\t'),
+                    Some((_, 'r')) => Ok('\r'),
+                    Some((_, 'u')) => self.unicode_escape(start),
+                    Some((_, c)) if c.is_ascii_punctuation() => Ok(c),
+                    Some(_) => Err(ParseError::new(start, ParseErrorKind::InvalidEscape)),
+                    None => Err(self.error()),
+                }
+            },
+            Some(']') | None => Err(self.error()),
+            Some(c) => {
+                self.chars.next();
+                Ok(c)
+            },
+        }
+    }
+
+    /// Rest of `\u{...}` holding one to six hex digits of a scalar value
+    fn unicode_escape(&mut self, start: usize) -> Result<char, ParseError> {
+        self.expect('{')?;
+        let mut digits = String::new();
+        while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+            digits.push(self.chars.next().unwrap().1);
+        }
+        self.expect('}')?;
+
+        if digits.is_empty() || digits.len() > 6 {
+            return Err(ParseError::new(start, ParseErrorKind::InvalidEscape));
+        }
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(|code| char::try_from(code).ok())
+            .ok_or_else(|| ParseError::new(start, ParseErrorKind::InvalidEscape))
+    }
+
+    fn class(&mut self) -> Result<Domain<i32>, ParseError> {
+        self.expect('[')?;
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.chars.next();
+        }
+
+        let mut members = Vec::new();
+        while self.peek() != Some(']') {
+            let start = self.position();
+            let lo = self.atom()?;
+            let mut hi = lo;
+
+            if self.peek() == Some('-') {
+                self.chars.next();
+                // A trailing `-` is literal, as in most regex flavours
+                if self.peek() == Some(']') {
+                    members.push((Value::Included(ordinal('-')), Value::Included(ordinal('-'))));
+                } else {
+                    hi = self.atom()?;
+                    if hi < lo {
+                        return Err(ParseError::new(start, ParseErrorKind::InvalidRange));
+                    }
+                }
+            }
+            members.push((Value::Included(ordinal(lo)), Value::Included(ordinal(hi))));
+        }
+        self.expect(']')?;
+
+        if self.peek().is_some() {
+            return Err(self.error());
+        }
+
+        let domain = util::from_members(util::normalize_members(members));
+        Ok(if negated {
+            domain.complement().intersect(&Domain::between(0, MAX_ORDINAL)).into_inner()
+        } else {
+            domain
+        })
+    }
+}
+
+impl Domain<char> {
+
+    /// Regex character class admitting the same characters, built from the
+    /// normalized runs. `]`, `[`, `^`, `-` and `\` are escaped with a
+    /// backslash and everything but printable ASCII is written as `\u{...}`.
+    /// The empty domain becomes the class matching nothing, `[^\u{0}-\u{10ffff}]`
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Union(vec![
+    ///     Domain::Domain(Value::Included('k'), Value::Included('z')),
+    ///     Domain::Domain(Value::Included('a'), Value::Included('f')),
+    ///     Domain::Domain(Value::Included('0'), Value::Included('9')),
+    /// ]);
+    /// assert_eq!(domain.to_char_class(), "[0-9a-fk-z]");
+    /// ```
+    pub fn to_char_class(&self) -> String {
+        let runs = util::i32_runs(&to_ordinals(self));
+        if runs.is_empty() {
+            return "[^\\u{0}-\\u{10ffff}]".to_string();
+        }
+
+        let mut class = String::from("[");
+        for (lo, hi) in runs {
+            let (lo, hi) = (from_ordinal(lo as i32), from_ordinal(hi as i32));
+            push_escaped(&mut class, lo);
+            if hi != lo {
+                if ordinal(hi) - ordinal(lo) > 1 {
+                    class.push('-');
+                }
+                push_escaped(&mut class, hi);
+            }
+        }
+        class.push(']');
+        class
+    }
+
+    /// Parses a simple regex character class: single characters, ranges
+    /// `a-z`, backslash escapes of punctuation, `\n`, `\t`, `\r` and `\u{...}`.
+    /// A leading `^` complements the class over all scalar values and a `-`
+    /// right before the closing bracket is literal
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::<char>::from_char_class("[^a-z]").unwrap();
+    /// assert!(domain.contains('A'));
+    /// assert!(!domain.contains('q'));
+    /// ```
+    pub fn from_char_class(class: &str) -> Result<Domain<char>, ParseError> {
+        let mut parser = ClassParser { input: class, chars: class.char_indices().peekable() };
+        parser.class().map(|domain| from_ordinals(&domain))
+    }
+}
+
+/// Domains are equal when they admit the same characters
+impl PartialEq for Domain<char> {
+    fn eq(&self, other: &Self) -> bool {
+        to_ordinals(self) == to_ordinals(other)
+    }
+}
+
+impl Eq for Domain<char> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ParseErrorKind;
+    use crate::{Domain, Value};
+
+    fn chars(lo: char, hi: char) -> Domain<char> {
+        Domain::Domain(Value::Included(lo), Value::Included(hi))
+    }
+
+    fn round_trip(class: &str) -> String {
+        Domain::<char>::from_char_class(class).unwrap().to_char_class()
+    }
+
+    #[test]
+    fn test_escaping() {
+        let domain = Domain::Union(vec![chars(']', ']'), chars('-', '-'), chars('a', 'a')]);
+        assert_eq!(domain.to_char_class(), "[\\-\\]a]");
+        assert_eq!(round_trip("[\\]\\-a]"), "[\\-\\]a]");
+        assert_eq!(chars('[', '^').to_char_class(), "[\\[-\\^]");
+        assert_eq!(chars('\u{0}', '\u{1f}').to_char_class(), "[\\u{0}-\\u{1f}]");
+        assert_eq!(chars('é', 'é').to_char_class(), "[\\u{e9}]");
+        assert_eq!(round_trip("[a-]"), "[\\-a]");
+        assert_eq!(round_trip("[ab]"), "[ab]");
+    }
+
+    #[test]
+    fn test_complemented() {
+        let domain = Domain::<char>::from_char_class("[^\\u{1}-\\u{10fffe}]").unwrap();
+        assert_eq!(domain.to_char_class(), "[\\u{0}\\u{10ffff}]");
+
+        let not_digit = Domain::<char>::from_char_class("[^0-9]").unwrap();
+        assert_eq!(not_digit.to_char_class(), "[\\u{0}-/:-\\u{10ffff}]");
+        assert_eq!(Domain::<char>::from_char_class("[^\\u{0}-\\u{10ffff}]").unwrap(), Domain::None);
+        assert_eq!(round_trip("[^]"), "[\\u{0}-\\u{10ffff}]");
+        assert_eq!(round_trip("[]"), "[^\\u{0}-\\u{10ffff}]");
+    }
+
+    #[test]
+    fn test_astral_ranges() {
+        let emoji = Domain::<char>::from_char_class("[\\u{1f600}-\\u{1f64f}x]").unwrap();
+        assert!(emoji.contains('😀'));
+        assert!(emoji.contains('x'));
+        assert_eq!(emoji.to_char_class(), "[x\\u{1f600}-\\u{1f64f}]");
+
+        let raw = Domain::<char>::from_char_class("[😀-🙏]").unwrap();
+        assert_eq!(raw, Domain::<char>::from_char_class("[\\u{1f600}-\\u{1f64f}]").unwrap());
+        assert_eq!(round_trip("[\\u{10000}-\\u{10ffff}]"), "[\\u{10000}-\\u{10ffff}]");
+    }
+
+    #[test]
+    fn test_surrogate_gap() {
+        // U+D7FF and U+E000 are consecutive scalar values
+        let domain = Domain::Domain(Value::Secluded('\u{d7fe}'), Value::Secluded('\u{e001}'));
+        assert_eq!(domain.to_char_class(), "[\\u{d7ff}\\u{e000}]");
+        let everything: Domain<char> = Domain::Domain(Value::Infinite, Value::Infinite);
+        assert_eq!(everything.to_char_class(), "[\\u{0}-\\u{10ffff}]");
+        assert!(Domain::<char>::from_char_class("[\\u{d800}]").is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        let kind = |class: &str| Domain::<char>::from_char_class(class).unwrap_err().kind().clone();
+        assert_eq!(kind("[z-a]"), ParseErrorKind::InvalidRange);
+        assert_eq!(kind("[\\q]"), ParseErrorKind::InvalidEscape);
+        assert_eq!(kind("[\\u{110000}]"), ParseErrorKind::InvalidEscape);
+        assert_eq!(kind("[a"), ParseErrorKind::UnexpectedEnd);
+        assert_eq!(kind("a]"), ParseErrorKind::UnexpectedChar('a'));
+        assert_eq!(kind("[a]b"), ParseErrorKind::UnexpectedChar('b'));
+        assert_eq!(Domain::<char>::from_char_class("[ab\\q]").unwrap_err().position(), 3);
+    }
+}
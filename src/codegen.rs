@@ -0,0 +1,155 @@
+//! Source code listing every value of a small domain, for validation
+//! code generated into other code bases. Each language is an arm of
+//! `CodegenLang` with its keywords, identifier rule and formatter
+
+use crate::error::CodegenError;
+use crate::Domain;
+
+/// Values `Domain::codegen_values` lists at most
+const DEFAULT_LIMIT: u64 = 1024;
+
+const TYPESCRIPT_KEYWORDS: &[&str] = &[
+    "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete", "do", "else",
+    "enum", "export", "extends", "false", "finally", "for", "function", "if", "implements", "import", "in",
+    "instanceof", "interface", "let", "new", "null", "package", "private", "protected", "public", "return", "static",
+    "super", "switch", "this", "throw", "true", "try", "typeof", "var", "void", "while", "with", "yield",
+];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "_", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "Self", "static", "struct",
+    "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+    "yield",
+];
+
+/// Target language of `Domain::codegen_values`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CodegenLang {
+    /// `export const NAME = [1,2,3] as const;`
+    TypeScript,
+    /// `pub const NAME: &[i32] = &[1, 2, 3];`
+    Rust,
+}
+
+impl CodegenLang {
+    /// Whether `name` can be the name of the constant as is
+    fn accepts(self, name: &str) -> bool {
+        let (keywords, dollar) = match self {
+            CodegenLang::TypeScript => (TYPESCRIPT_KEYWORDS, true),
+            CodegenLang::Rust => (RUST_KEYWORDS, false),
+        };
+        let word = |c: char| c == '_' || (dollar && c == '$');
+        let mut chars = name.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic() || word(c))
+            && chars.all(|c| c.is_ascii_alphanumeric() || word(c))
+            && !keywords.contains(&name)
+    }
+
+    /// The declaration of the constant `name` holding `values`
+    fn render(self, name: &str, values: &[i32]) -> String {
+        let join = |separator: &str| values.iter().map(i32::to_string).collect::<Vec<_>>().join(separator);
+        match self {
+            CodegenLang::TypeScript => format!("export const {} = [{}] as const;", name, join(",")),
+            CodegenLang::Rust => format!("pub const {}: &[i32] = &[{}];", name, join(", ")),
+        }
+    }
+}
+
+impl Domain<i32> {
+
+    /// Declaration of a constant `name` in `lang` listing the admitted
+    /// values in ascending order, each once. Refuses names which are not
+    /// identifiers of `lang` or are its keywords, domains with an
+    /// infinite border and domains of more than 1024 values, see
+    /// `codegen_values_limited` for another limit
+    /// ```
+    /// use dynamic_domain::{CodegenLang, Domain};
+    /// let domain = Domain::Union(vec![Domain::between(3, 5), Domain::between(1, 3)]);
+    /// assert_eq!(domain.codegen_values(CodegenLang::TypeScript, "LEVELS").unwrap(), "export const LEVELS = [1,2,3,4,5] as const;");
+    /// assert_eq!(domain.codegen_values(CodegenLang::Rust, "LEVELS").unwrap(), "pub const LEVELS: &[i32] = &[1, 2, 3, 4, 5];");
+    /// assert!(Domain::at_least(0).codegen_values(CodegenLang::Rust, "LEVELS").is_err());
+    /// ```
+    pub fn codegen_values(&self, lang: CodegenLang, name: &str) -> Result<String, CodegenError> {
+        self.codegen_values_limited(lang, name, DEFAULT_LIMIT)
+    }
+
+    /// `codegen_values` refusing domains of more than `max_values` values
+    /// with `CodegenError::TooLarge`
+    /// ```
+    /// use dynamic_domain::{CodegenError, CodegenLang, Domain};
+    /// let domain = Domain::between(-1, 1);
+    /// assert_eq!(domain.codegen_values_limited(CodegenLang::Rust, "SIGNS", 3).unwrap(), "pub const SIGNS: &[i32] = &[-1, 0, 1];");
+    /// assert_eq!(domain.codegen_values_limited(CodegenLang::Rust, "SIGNS", 2), Err(CodegenError::TooLarge(2)));
+    /// ```
+    pub fn codegen_values_limited(&self, lang: CodegenLang, name: &str, max_values: u64) -> Result<String, CodegenError> {
+        if !lang.accepts(name) {
+            return Err(CodegenError::InvalidName(name.to_string()));
+        }
+        match self.cardinality() {
+            None => Err(CodegenError::Unbounded),
+            Some(count) if count > max_values => Err(CodegenError::TooLarge(max_values)),
+            Some(_) => Ok(lang.render(name, &self.iter().collect::<Vec<i32>>())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CodegenError, CodegenLang, Domain, Value};
+
+    #[test]
+    fn test_snapshots() {
+        let domain = Domain::Union(vec![
+            Domain::between(10, 12),
+            Domain::Domain(Value::Secluded(-3), Value::Secluded(0)),
+            Domain::between(11, 13),
+            Domain::None,
+        ]);
+        assert_eq!(
+            domain.codegen_values(CodegenLang::TypeScript, "$allowed_1").unwrap(),
+            "export const $allowed_1 = [-2,-1,10,11,12,13] as const;"
+        );
+        assert_eq!(
+            domain.codegen_values(CodegenLang::Rust, "ALLOWED_1").unwrap(),
+            "pub const ALLOWED_1: &[i32] = &[-2, -1, 10, 11, 12, 13];"
+        );
+
+        assert_eq!(Domain::None.codegen_values(CodegenLang::TypeScript, "NONE").unwrap(), "export const NONE = [] as const;");
+        assert_eq!(Domain::between(5, 1).codegen_values(CodegenLang::Rust, "NONE").unwrap(), "pub const NONE: &[i32] = &[];");
+
+        let limits = Domain::Union(vec![Domain::between(i32::MIN, i32::MIN), Domain::between(i32::MAX, i32::MAX)]);
+        assert_eq!(
+            limits.codegen_values(CodegenLang::Rust, "LIMITS").unwrap(),
+            "pub const LIMITS: &[i32] = &[-2147483648, 2147483647];"
+        );
+    }
+
+    #[test]
+    fn test_names() {
+        let domain = Domain::between(0, 1);
+        for name in &["", "1st", "has space", "dash-ed", "é", "const", "export"] {
+            assert_eq!(domain.codegen_values(CodegenLang::TypeScript, name), Err(CodegenError::InvalidName(name.to_string())));
+        }
+        for name in &["", "$dollar", "fn", "Self", "_", "r#raw", "const"] {
+            assert_eq!(domain.codegen_values(CodegenLang::Rust, name), Err(CodegenError::InvalidName(name.to_string())));
+        }
+        assert!(domain.codegen_values(CodegenLang::TypeScript, "_").is_ok());
+        assert!(domain.codegen_values(CodegenLang::Rust, "_private").is_ok());
+    }
+
+    #[test]
+    fn test_limits() {
+        assert!(Domain::between(1, 1024).codegen_values(CodegenLang::Rust, "A").is_ok());
+        assert_eq!(Domain::between(1, 1025).codegen_values(CodegenLang::Rust, "A"), Err(CodegenError::TooLarge(1024)));
+        assert_eq!(Domain::between(1, 1).codegen_values_limited(CodegenLang::Rust, "A", 0), Err(CodegenError::TooLarge(0)));
+        assert!(Domain::None.codegen_values_limited(CodegenLang::Rust, "A", 0).is_ok());
+
+        // Admits only i32::MAX but the border is infinite
+        let past = Domain::at_least(i32::MAX);
+        assert_eq!(past.codegen_values(CodegenLang::TypeScript, "A"), Err(CodegenError::Unbounded));
+        assert_eq!(Domain::new().codegen_values(CodegenLang::Rust, "A"), Err(CodegenError::Unbounded));
+        assert_eq!(Domain::new().codegen_values(CodegenLang::Rust, "fn"), Err(CodegenError::InvalidName("fn".to_string())));
+    }
+}
@@ -0,0 +1,605 @@
+use std::iter::FromIterator;
+use std::ops::RangeInclusive;
+
+use crate::util;
+use crate::{Domain, Value};
+
+/// Buffered out-of-order runs a `DomainAccumulator` holds before merging
+/// them into its sorted runs, unless it holds more sorted runs. Pending
+/// members of `Domain::union_assign_deferred` are settled by the same rule
+const COMPACT_AT: usize = 1024;
+
+/// Merges closed runs ascending by start, runs separated by at most
+/// `gap` missing values becoming one
+fn merge_runs(runs: impl IntoIterator<Item = (i32, i32)>, gap: u32) -> Vec<(i32, i32)> {
+    let mut merged: Vec<(i32, i32)> = Vec::new();
+    for (lo, hi) in runs {
+        if let Some(last) = merged.last_mut() {
+            if lo as i64 - last.1 as i64 - 1 <= gap as i64 {
+                last.1 = last.1.max(hi);
+                continue;
+            }
+        }
+        merged.push((lo, hi));
+    }
+    merged
+}
+
+/// Unites closed runs ascending by start, merging runs separated by at
+/// most `gap` missing values into one member
+fn from_runs(runs: impl IntoIterator<Item = (i32, i32)>, gap: u32) -> Domain<i32> {
+    let merged = merge_runs(runs, gap);
+    util::from_members(
+        merged
+            .into_iter()
+            .map(|(lo, hi)| (Value::Included(lo), Value::Included(hi)))
+            .collect()
+    )
+}
+
+/// Members of the union `Domain::union_assign_deferred` keeps: the union
+/// of the members of the settled `domain` first, nothing pending after it
+fn settled(domain: Domain<i32>) -> Vec<Domain<i32>> {
+    let members = match domain {
+        Domain::Union(members) => members,
+        Domain::None => Vec::new(),
+        member => vec![member],
+    };
+    vec![Domain::Union(members)]
+}
+
+/// Union of all `domains`, united pairwise level by level. `union` spells
+/// borders the same whatever the order, so this is the domain of uniting
+/// them one after another
+fn united(mut domains: Vec<Domain<i32>>) -> Domain<i32> {
+    while domains.len() > 1 {
+        let mut pairs = domains.into_iter();
+        let mut next = Vec::new();
+        while let Some(first) = pairs.next() {
+            next.push(match pairs.next() {
+                Some(second) => first.union(&second).into_inner(),
+                None => first,
+            });
+        }
+        domains = next;
+    }
+    domains.pop().unwrap_or(Domain::None).union(&Domain::None).into_inner()
+}
+
+impl Domain<i32> {
+
+    /// Closed hull `[min;max]` of the observed values,
+    /// `Domain::None` for no values
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::from_min_max(&[7, -2, 4]).repr(), "[-2;7]");
+    /// ```
+    pub fn from_min_max(values: &[i32]) -> Self {
+        match (values.iter().min(), values.iter().max()) {
+            (Some(min), Some(max)) => Domain::between(*min, *max),
+            _ => Domain::None,
+        }
+    }
+
+    /// Exactly the observed values, as a normalized union of runs
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::from_values_exact(&[3, 1, 2, 7]).repr(), "[1;3]⋃[7;7]");
+    /// ```
+    pub fn from_values_exact(values: &[i32]) -> Self {
+        Domain::from_values_with_tolerance(values, 0)
+    }
+
+    /// Like `from_values_exact`, but runs separated by at most `gap`
+    /// missing values are merged into one member
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::from_values_with_tolerance(&[1, 2, 5, 6, 20], 2);
+    /// assert_eq!(domain.repr(), "[1;6]⋃[20;20]");
+    /// ```
+    pub fn from_values_with_tolerance(values: &[i32], gap: u32) -> Self {
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        from_runs(sorted.into_iter().map(|value| (value, value)), gap)
+    }
+
+    /// Values of `window` accepted by `pred`, which is called once for
+    /// every value in ascending order. Consecutive accepted values form
+    /// the runs of the normalized result
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::from_predicate(0..=20, |n| n % 10 < 3);
+    /// assert_eq!(domain.repr(), "[0;2]⋃[10;12]⋃[20;20]");
+    /// ```
+    pub fn from_predicate(window: RangeInclusive<i32>, mut pred: impl FnMut(i32) -> bool) -> Self {
+        from_runs(window.filter(|value| pred(*value)).map(|value| (value, value)), 0)
+    }
+
+    /// Adds the values of `other` without normalizing every time, for
+    /// loops uniting one record after another. The domain becomes a union
+    /// led by its settled members, followed by the domains added since.
+    /// Those are settled once there are more of them than settled members,
+    /// and more than a thousand, so `n` calls take `O(n log² n)` in total
+    /// rather than the `O(n²)` of `domain = domain.union(&other)`. Queries
+    /// give the answers of that eagerly united domain whatever is pending,
+    /// and `flush` settles everything into it, as `repr` writes the members
+    /// as they are.
+    ///
+    /// `union` spells a border `(-∞` or `∞)` met by `[-2147483648` or
+    /// `2147483647]` the finite way, which `cardinality` tells apart, so an
+    /// `other` reaching an `i32` limit is settled right away
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let mut seen = Domain::None;
+    /// for value in [7, 1, 2, 3, 9] {
+    ///     seen.union_assign_deferred(Domain::between(value, value));
+    /// }
+    /// assert!(seen.contains(2) && !seen.contains(5));
+    /// assert_eq!(seen.cardinality(), Some(5));
+    /// seen.flush();
+    /// assert_eq!(seen.repr(), "[1;3]⋃[7;7]⋃[9;9]");
+    /// ```
+    pub fn union_assign_deferred(&mut self, other: Domain<i32>) {
+        let mut members = match std::mem::replace(self, Domain::None) {
+            Domain::Union(members) if matches!(members.first(), Some(Domain::Union(_))) => members,
+            domain => settled(domain.union(&Domain::None).into_inner()),
+        };
+        let runs = util::i32_runs(&other);
+        let limit = runs.first().is_some_and(|(lo, _)| *lo == i32::MIN as i64)
+            || runs.last().is_some_and(|(_, hi)| *hi == i32::MAX as i64);
+        members.push(other);
+
+        let settled_members = match &members[0] {
+            Domain::Union(settled) => settled.len(),
+            _ => 0,
+        };
+        *self = match limit || members.len() - 1 > settled_members.max(COMPACT_AT) {
+            true => Domain::Union(settled(united(members))),
+            false => Domain::Union(members),
+        };
+    }
+
+    /// Settles what `union_assign_deferred` left pending, leaving the
+    /// domain `union` would have given. Any other domain is normalized the
+    /// way `union` normalizes
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let mut domain = Domain::between(0, 4);
+    /// domain.union_assign_deferred(Domain::between(5, 9));
+    /// domain.flush();
+    /// assert_eq!(domain.repr(), "[0;9]");
+    /// ```
+    pub fn flush(&mut self) {
+        *self = match std::mem::replace(self, Domain::None) {
+            Domain::Union(members) if matches!(members.first(), Some(Domain::Union(_))) => united(members),
+            domain => domain.union(&Domain::None).into_inner(),
+        };
+    }
+
+    /// Approximate `from_predicate` for huge windows: `pred` is only called
+    /// on every `step`-th value from the start of the window, and an accepted
+    /// sample stands for itself and the values up to the next sample.
+    /// Changes between two samples are missed. A `step` of 0 is taken as 1
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::from_predicate_sampled(0..=99, 10, |n| n < 35);
+    /// assert_eq!(domain.repr(), "[0;39]");
+    /// ```
+    pub fn from_predicate_sampled(
+        window: RangeInclusive<i32>,
+        step: u32,
+        mut pred: impl FnMut(i32) -> bool,
+    ) -> Self {
+        let (start, end) = (*window.start() as i64, *window.end() as i64);
+        let step = step.max(1) as i64;
+
+        let samples = (0..)
+            .map(|i| start + i * step)
+            .take_while(|sample| *sample <= end)
+            .filter(|sample| pred(*sample as i32))
+            .map(|sample| (sample as i32, (sample + step - 1).min(end) as i32));
+        from_runs(samples, 0)
+    }
+}
+
+/// Builds the exact domain of a stream of values and ranges without
+/// collecting them. Input ascending by start extends the last run or
+/// starts a new one, so memory stays proportional to the intervals of the
+/// result. Anything arriving out of order is buffered, and the buffer is
+/// sorted and merged into the runs whenever it outgrows them
+/// ```
+/// use dynamic_domain::{Domain, DomainAccumulator};
+/// let mut accumulator = DomainAccumulator::new();
+/// accumulator.extend(vec![1, 2, 3, 10]);
+/// accumulator.push_range(20, 29);
+/// accumulator.push(4);
+/// assert_eq!(accumulator.finish().repr(), "[1;4]⋃[10;10]⋃[20;29]");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DomainAccumulator {
+    /// Disjoint, not touching, ascending
+    runs: Vec<(i32, i32)>,
+    /// Out-of-order runs, in arrival order
+    buffer: Vec<(i32, i32)>,
+}
+
+impl DomainAccumulator {
+
+    /// Accumulator of no values
+    /// ```
+    /// use dynamic_domain::{Domain, DomainAccumulator};
+    /// assert_eq!(DomainAccumulator::new().finish(), Domain::None);
+    /// ```
+    pub fn new() -> Self {
+        DomainAccumulator { runs: Vec::new(), buffer: Vec::new() }
+    }
+
+    /// Adds a single value
+    /// ```
+    /// use dynamic_domain::DomainAccumulator;
+    /// let mut accumulator = DomainAccumulator::new();
+    /// accumulator.push(7);
+    /// accumulator.push(5);
+    /// accumulator.push(6);
+    /// assert_eq!(accumulator.finish().repr(), "[5;7]");
+    /// ```
+    pub fn push(&mut self, v: i32) {
+        self.push_range(v, v);
+    }
+
+    /// Adds the values from `lo` to `hi`, both included, nothing when
+    /// `lo` is above `hi`
+    /// ```
+    /// use dynamic_domain::DomainAccumulator;
+    /// let mut accumulator = DomainAccumulator::new();
+    /// accumulator.push_range(0, 9);
+    /// accumulator.push_range(5, 4);
+    /// accumulator.push_range(10, 12);
+    /// assert_eq!(accumulator.finish().repr(), "[0;12]");
+    /// ```
+    pub fn push_range(&mut self, lo: i32, hi: i32) {
+        if lo > hi {
+            return;
+        }
+        match self.runs.last_mut() {
+            Some(last) if lo < last.0 => {
+                self.buffer.push((lo, hi));
+                if self.buffer.len() > self.runs.len().max(COMPACT_AT) {
+                    self.compact();
+                }
+            },
+            Some(last) if lo as i64 <= last.1 as i64 + 1 => last.1 = last.1.max(hi),
+            _ => self.runs.push((lo, hi)),
+        }
+    }
+
+    /// Merges the buffer into the runs
+    fn compact(&mut self) {
+        let mut all = std::mem::take(&mut self.buffer);
+        all.append(&mut self.runs);
+        all.sort_unstable();
+        self.runs = merge_runs(all, 0);
+    }
+
+    /// The normalized union of everything added
+    /// ```
+    /// use dynamic_domain::DomainAccumulator;
+    /// let mut accumulator = DomainAccumulator::new();
+    /// accumulator.extend((0..5).rev());
+    /// assert_eq!(accumulator.finish().repr(), "[0;4]");
+    /// ```
+    pub fn finish(mut self) -> Domain<i32> {
+        self.compact();
+        from_runs(self.runs, 0)
+    }
+}
+
+/// `DomainAccumulator::push` for every value
+/// ```
+/// use dynamic_domain::DomainAccumulator;
+/// let mut accumulator = DomainAccumulator::new();
+/// accumulator.extend(vec![3, 1, 2]);
+/// assert_eq!(accumulator.finish().repr(), "[1;3]");
+/// ```
+impl Extend<i32> for DomainAccumulator {
+    fn extend<I: IntoIterator<Item = i32>>(&mut self, iter: I) {
+        for v in iter {
+            self.push(v);
+        }
+    }
+}
+
+/// Collects observed values into the exact domain,
+/// same as `Domain::from_values_exact`, through a `DomainAccumulator`
+/// ```
+/// use dynamic_domain::Domain;
+/// let domain: Domain<i32> = vec![4, 5, 9].into_iter().collect();
+/// assert_eq!(domain.repr(), "[4;5]⋃[9;9]");
+/// ```
+impl FromIterator<i32> for Domain<i32> {
+    fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> Self {
+        let mut accumulator = DomainAccumulator::new();
+        accumulator.extend(iter);
+        accumulator.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::{border, union_of};
+    use crate::{Domain, DomainAccumulator, Notation, Value};
+
+    /// Step of an ingestion loop: a union, a query or a flush
+    #[derive(Clone, Debug)]
+    enum Step {
+        Union(Domain<i32>),
+        Query(i32, u64),
+        Flush,
+    }
+
+    fn step() -> impl Strategy<Value = Step> {
+        let value = prop_oneof![8 => -35..35, 1 => Just(i32::MIN), 1 => Just(i32::MAX)];
+        prop_oneof![
+            4 => union_of(border(30), 0..4).prop_map(Step::Union),
+            2 => (value, 0..70u64).prop_map(|(value, n)| Step::Query(value, n)),
+            1 => Just(Step::Flush),
+        ]
+    }
+
+    /// Answers of `contains`, `cardinality`, `nth`, `rank`, `clamp`,
+    /// `is_empty` and `active_window`
+    type Answers = (bool, Option<u64>, Option<i32>, u64, Option<i32>, bool, Option<(i32, i32)>);
+
+    /// Answers of the queries of `domain` about `value` and the `n`-th value
+    fn answers(domain: &Domain<i32>, value: i32, n: u64) -> Answers {
+        (
+            domain.contains(value),
+            domain.cardinality(),
+            domain.nth(n),
+            domain.rank(value),
+            domain.clamp(value),
+            domain.is_empty(),
+            domain.active_window(),
+        )
+    }
+
+    #[test]
+    fn test_from_min_max() {
+        assert_eq!(Domain::from_min_max(&[]).repr(), "∅");
+        assert_eq!(Domain::from_min_max(&[5]).repr(), "[5;5]");
+        assert_eq!(Domain::from_min_max(&[i32::MAX, i32::MIN]).repr(), "[-2147483648;2147483647]");
+    }
+
+    #[test]
+    fn test_from_values_exact() {
+        let domain = Domain::from_values_exact(&[10, 3, 4, 4, 5, 12, 11, -1]);
+        assert_eq!(domain.repr(), "[-1;-1]⋃[3;5]⋃[10;12]");
+        assert_eq!(Domain::from_values_exact(&[]), Domain::None);
+
+        let extremes = Domain::from_values_exact(&[i32::MAX, i32::MIN, i32::MAX - 1]);
+        assert_eq!(extremes.repr(), "[-2147483648;-2147483648]⋃[2147483646;2147483647]");
+    }
+
+    #[test]
+    fn test_tolerance_noisy_samples() {
+        // A sensor reporting every value in [0;99] with a few samples lost,
+        // plus two outliers far away
+        let samples: Vec<i32> = (0..100)
+            .filter(|n| ![13, 14, 50, 77, 78, 79].contains(n))
+            .chain(vec![500, 503])
+            .collect();
+
+        let exact = Domain::from_values_exact(&samples);
+        assert_eq!(exact.repr(), "[0;12]⋃[15;49]⋃[51;76]⋃[80;99]⋃[500;500]⋃[503;503]");
+
+        let tolerant = Domain::from_values_with_tolerance(&samples, 3);
+        assert_eq!(tolerant.repr(), "[0;99]⋃[500;503]");
+
+        let strict = Domain::from_values_with_tolerance(&samples, 2);
+        assert_eq!(strict.repr(), "[0;76]⋃[80;99]⋃[500;503]");
+
+        let lenient = Domain::from_values_with_tolerance(&samples, u32::MAX);
+        assert_eq!(lenient, Domain::from_min_max(&samples));
+    }
+
+    fn is_leap(year: i32) -> bool {
+        year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+    }
+
+    #[test]
+    fn test_from_predicate_leap_years() {
+        let leap = Domain::from_predicate(1900..=2100, is_leap);
+        assert_eq!(leap.cardinality(), Some(49));
+        assert!(leap.contains(2000) && leap.contains(1904) && leap.contains(2096));
+        assert!(!leap.contains(1900) && !leap.contains(2100) && !leap.contains(2001));
+        assert_eq!(leap, (1900..=2100).filter(|year| is_leap(*year)).collect::<Domain<i32>>());
+
+        let mut calls = 0;
+        let all = Domain::from_predicate(1900..=2100, |_| {
+            calls += 1;
+            true
+        });
+        assert_eq!((all.repr(), calls), ("[1900;2100]".to_string(), 201));
+    }
+
+    #[test]
+    fn test_from_predicate_windows() {
+        let (lo, hi) = (5, 4);
+        assert_eq!(Domain::from_predicate(lo..=hi, |_| true), Domain::None);
+        assert_eq!(Domain::from_predicate_sampled(lo..=hi, 1, |_| true), Domain::None);
+        assert_eq!(Domain::from_predicate(0..=9, |_| false), Domain::None);
+        assert_eq!(
+            Domain::from_predicate(i32::MAX - 2..=i32::MAX, |_| true).repr(),
+            "[2147483645;2147483647]"
+        );
+        assert_eq!(
+            Domain::from_predicate(i32::MIN..=i32::MIN + 3, |n| n != i32::MIN + 1).repr(),
+            "[-2147483648;-2147483648]⋃[-2147483646;-2147483645]"
+        );
+    }
+
+    #[test]
+    fn test_from_predicate_sampled() {
+        let mut calls = 0;
+        let domain = Domain::from_predicate_sampled(0..=1_000_000, 1_000, |n| {
+            calls += 1;
+            !(250_000..500_000).contains(&n)
+        });
+        assert_eq!(calls, 1_001);
+        assert_eq!(domain.repr(), "[0;249999]⋃[500000;1000000]");
+
+        assert_eq!(
+            Domain::from_predicate_sampled(0..=20, 0, |n| n % 10 < 3),
+            Domain::from_predicate(0..=20, |n| n % 10 < 3)
+        );
+        assert_eq!(
+            Domain::from_predicate_sampled(i32::MAX - 4..=i32::MAX, 3, |_| true).repr(),
+            "[2147483643;2147483647]"
+        );
+        let full = Domain::from_predicate_sampled(i32::MIN..=i32::MAX, u32::MAX, |_| true);
+        assert_eq!(full, Domain::new());
+    }
+
+    #[test]
+    fn test_collect() {
+        let domain: Domain<i32> = Domain::between(1, 5).iter().filter(|n| n % 2 == 1).collect();
+        assert_eq!(domain.repr(), "[1;1]⋃[3;3]⋃[5;5]");
+    }
+
+    fn accumulate(values: impl IntoIterator<Item = i32>) -> DomainAccumulator {
+        let mut accumulator = DomainAccumulator::new();
+        accumulator.extend(values);
+        accumulator
+    }
+
+    proptest! {
+        #[test]
+        fn test_accumulator_order_free(values in prop::collection::vec(-50..50i32, 0..200).prop_shuffle()) {
+            let mut sorted = values.clone();
+            sorted.sort_unstable();
+            let expected = Domain::from_values_exact(&values);
+
+            prop_assert_eq!(accumulate(sorted.iter().copied()).finish(), expected.clone());
+            prop_assert_eq!(accumulate(sorted.iter().rev().copied()).finish(), expected.clone());
+            prop_assert_eq!(accumulate(values.iter().copied()).finish(), expected);
+        }
+
+        #[test]
+        fn test_accumulator_ranges(ranges in prop::collection::vec((-50..50i32, -50..50i32), 0..20)) {
+            let mut accumulator = DomainAccumulator::new();
+            for (lo, hi) in &ranges {
+                accumulator.push_range(*lo, *hi);
+            }
+            let expected = Domain::Union(ranges.iter().map(|(lo, hi)| Domain::between(*lo, *hi)).collect());
+            prop_assert_eq!(accumulator.finish(), expected);
+        }
+
+        #[test]
+        fn test_deferred_matches_eager(start in union_of(border(30), 0..4), steps in prop::collection::vec(step(), 0..30)) {
+            let (mut deferred, mut eager) = (start.clone(), start);
+            for step in steps {
+                match step {
+                    Step::Union(other) => {
+                        eager = eager.union(&other).into_inner();
+                        deferred.union_assign_deferred(other);
+                    },
+                    Step::Query(value, n) => {
+                        prop_assert_eq!(answers(&deferred, value, n), answers(&eager, value, n));
+                        prop_assert_eq!(&deferred, &eager);
+                    },
+                    Step::Flush => {
+                        deferred.flush();
+                        prop_assert_eq!(deferred.repr_with(&Notation::DEFAULT), eager.union(&Domain::None).repr_with(&Notation::DEFAULT));
+                    },
+                }
+            }
+            deferred.flush();
+            prop_assert_eq!(deferred.repr_with(&Notation::DEFAULT), eager.union(&Domain::None).repr_with(&Notation::DEFAULT));
+        }
+    }
+
+    #[test]
+    fn test_deferred_settles() {
+        // Points two apart never merge, so the settled members keep growing
+        let mut deferred = Domain::None;
+        for i in 0..10_000 {
+            deferred.union_assign_deferred(Domain::between(i * 2, i * 2));
+            let members = match &deferred {
+                Domain::Union(members) => members,
+                _ => panic!("not deferred: {:?}", deferred),
+            };
+            let settled = match &members[0] {
+                Domain::Union(settled) => settled.len(),
+                _ => panic!("not settled first: {:?}", members[0]),
+            };
+            assert!(members.len() - 1 <= settled.max(super::COMPACT_AT), "{} pending", members.len() - 1);
+            if i % 997 == 0 {
+                assert_eq!((deferred.cardinality(), deferred.contains(i * 2), deferred.contains(i * 2 + 1)), (Some(i as u64 + 1), true, false));
+            }
+        }
+        assert_eq!(deferred.nth(9_999), Some(19_998));
+        deferred.flush();
+        assert!(matches!(&deferred, Domain::Union(members) if members.len() == 10_000));
+
+        // Adjacent points merge into one settled member
+        let mut deferred = Domain::None;
+        for i in (0..5_000).rev() {
+            deferred.union_assign_deferred(Domain::between(i, i));
+        }
+        deferred.flush();
+        assert_eq!(deferred.repr(), "[0;4999]");
+    }
+
+    #[test]
+    fn test_deferred_edges() {
+        let mut domain = Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite);
+        domain.union_assign_deferred(Domain::at_most(i32::MIN));
+        domain.union_assign_deferred(Domain::between(i32::MAX, i32::MAX));
+        assert_eq!(domain.cardinality(), None);
+        domain.flush();
+        assert_eq!(domain.repr(), "(-∞;-2147483648]⋃[2147483647;2147483647]");
+
+        let mut domain = Domain::between(5, 1);
+        domain.union_assign_deferred(Domain::None);
+        assert!(domain.is_empty());
+        domain.union_assign_deferred(Domain::new());
+        assert_eq!(domain, Domain::new());
+
+        let mut plain = Domain::Union(vec![Domain::between(3, 4), Domain::between(0, 2)]);
+        plain.flush();
+        assert_eq!(plain.repr(), "[0;4]");
+    }
+
+    #[test]
+    fn test_accumulator_memory() {
+        // A sorted cursor over 10 runs of 100k ids each never buffers
+        let ids = (0..10).flat_map(|run| run * 1_000_000..run * 1_000_000 + 100_000);
+        let accumulator = accumulate(ids);
+        assert_eq!((accumulator.runs.len(), accumulator.buffer.len()), (10, 0));
+
+        // Reversed input is buffered, but compacted once it outgrows the runs
+        let accumulator = accumulate((0..1_000_000).rev());
+        assert!(accumulator.runs.len() + accumulator.buffer.len() <= 2 * super::COMPACT_AT + 2);
+        assert_eq!(accumulator.finish(), Domain::between(0, 999_999));
+
+        let shuffled = (0..100_000).map(|i| (i * 7_919) % 100_000);
+        assert_eq!(accumulate(shuffled).finish(), Domain::between(0, 99_999));
+    }
+
+    #[test]
+    fn test_accumulator_edges() {
+        let mut accumulator = accumulate(vec![i32::MAX, i32::MIN, i32::MAX - 1, i32::MAX]);
+        accumulator.push_range(i32::MAX, i32::MIN);
+        accumulator.push_range(i32::MIN + 1, i32::MIN + 1);
+        assert_eq!(accumulator.finish().repr(), "[-2147483648;-2147483647]⋃[2147483646;2147483647]");
+
+        let mut full = DomainAccumulator::new();
+        full.push_range(0, i32::MAX);
+        full.push_range(i32::MIN, -1);
+        assert_eq!(full.finish(), Domain::new());
+    }
+}
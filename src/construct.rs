@@ -0,0 +1,407 @@
+use std::ops::{Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use crate::util;
+use crate::{Domain, DomainError, Point, Side, Value};
+
+/// Runs `Point::check` on the point of the border, if any
+fn check<T: Point>(value: &Value<T>) -> Result<(), DomainError> {
+    value.as_inner().map_or(Ok(()), Point::check)
+}
+
+impl<T: Point> Domain<T> {
+
+    /// Validated interval between the borders, rejects a starting point
+    /// past the ending one, a secluded border on a single point and points
+    /// refused by `Point::check`, like `f64::NAN` and `f64::INFINITY`
+    /// ```
+    /// use dynamic_domain::{Domain, DomainError, Value};
+    /// let domain = Domain::interval(Value::Included(1), Value::Secluded(5)).unwrap();
+    /// assert_eq!(domain.repr(), "[1;5)");
+    /// assert_eq!(
+    ///     Domain::interval(Value::Included(5), Value::Included(1)).unwrap_err(),
+    ///     DomainError::Reversed
+    /// );
+    /// ```
+    pub fn interval(lo: Value<T>, hi: Value<T>) -> Result<Self, DomainError> {
+        check(&lo)?;
+        check(&hi)?;
+        if let (Some(l), Some(r)) = (lo.as_inner(), hi.as_inner()) {
+            if l > r {
+                return Err(DomainError::Reversed);
+            }
+            if l == r && !(lo.is_included() && hi.is_included()) {
+                return Err(DomainError::Empty);
+            }
+        }
+        Ok(Domain::Domain(lo, hi))
+    }
+
+    /// Interval between two borders given in either order, the one with
+    /// the smaller point becomes the starting border. Two infinite borders
+    /// span everything, while an infinite border next to a finite one gives
+    /// no way to tell on which side of the point it lies
+    /// ```
+    /// use dynamic_domain::{Domain, DomainError, Value};
+    /// let domain = Domain::spanning_values(Value::Secluded(9), Value::Included(2)).unwrap();
+    /// assert_eq!(domain.repr(), "[2;9)");
+    /// assert_eq!(
+    ///     Domain::spanning_values(Value::Included(2), Value::Infinite).unwrap_err(),
+    ///     DomainError::Ambiguous
+    /// );
+    /// ```
+    pub fn spanning_values(a: Value<T>, b: Value<T>) -> Result<Self, DomainError> {
+        check(&a)?;
+        check(&b)?;
+        match (a.as_inner(), b.as_inner()) {
+            (Some(l), Some(r)) if l > r => Domain::interval(b, a),
+            (Some(_), Some(_)) | (None, None) => Domain::interval(a, b),
+            _ => Err(DomainError::Ambiguous),
+        }
+    }
+}
+
+impl<T> Domain<T> {
+
+    /// Borders of a single interval as `std::ops::Bound`s,
+    /// `None` for a union and for `Domain::None`
+    /// ```
+    /// use std::ops::Bound;
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::at_least(5).bounds(), Some((Bound::Included(&5), Bound::Unbounded)));
+    /// assert_eq!(Domain::<i32>::None.bounds(), None);
+    /// ```
+    pub fn bounds(&self) -> Option<(Bound<&T>, Bound<&T>)> {
+        match self {
+            Domain::Domain(l, r) => Some((l.as_bound(), r.as_bound())),
+            _ => None,
+        }
+    }
+
+    /// Single interval between two `std::ops::Bound`s, not validated
+    /// like `Domain::interval`
+    /// ```
+    /// use std::ops::Bound;
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::from_bounds(Bound::Excluded(1), Bound::Included(4));
+    /// assert_eq!(domain.repr(), "(1;4]");
+    /// ```
+    pub fn from_bounds(start: Bound<T>, end: Bound<T>) -> Self {
+        Domain::Domain(Value::from_bound(start, Side::Lower), Value::from_bound(end, Side::Upper))
+    }
+}
+
+/// `a..b` is `[a;b)`, every range converts through `Domain::from_bounds`
+/// ```
+/// use dynamic_domain::Domain;
+/// assert_eq!(Domain::from(1..5).repr(), "[1;5)");
+/// assert_eq!(Domain::from(1..=5).repr(), "[1;5]");
+/// assert_eq!(Domain::from(..5).repr(), "(-∞;5)");
+/// assert_eq!(Domain::<i32>::from(..).repr(), "(-∞;∞)");
+/// ```
+impl<T> From<Range<T>> for Domain<T> {
+    fn from(range: Range<T>) -> Self {
+        Domain::from_bounds(Bound::Included(range.start), Bound::Excluded(range.end))
+    }
+}
+
+impl<T> From<RangeInclusive<T>> for Domain<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        Domain::from_bounds(Bound::Included(start), Bound::Included(end))
+    }
+}
+
+impl<T> From<RangeFrom<T>> for Domain<T> {
+    fn from(range: RangeFrom<T>) -> Self {
+        Domain::from_bounds(Bound::Included(range.start), Bound::Unbounded)
+    }
+}
+
+impl<T> From<RangeTo<T>> for Domain<T> {
+    fn from(range: RangeTo<T>) -> Self {
+        Domain::from_bounds(Bound::Unbounded, Bound::Excluded(range.end))
+    }
+}
+
+impl<T> From<RangeToInclusive<T>> for Domain<T> {
+    fn from(range: RangeToInclusive<T>) -> Self {
+        Domain::from_bounds(Bound::Unbounded, Bound::Included(range.end))
+    }
+}
+
+impl<T> From<RangeFull> for Domain<T> {
+    fn from(_: RangeFull) -> Self {
+        Domain::from_bounds(Bound::Unbounded, Bound::Unbounded)
+    }
+}
+
+impl Domain<i32> {
+
+    /// Closed interval between `a` and `b` given in either order
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::spanning(9, 2).repr(), "[2;9]");
+    /// assert_eq!(Domain::spanning(2, 9), Domain::between(2, 9));
+    /// ```
+    pub fn spanning(a: i32, b: i32) -> Self {
+        Domain::between(a.min(b), a.max(b))
+    }
+
+    /// Unites `members` into a flat normalized domain, nested unions
+    /// are flattened and empty members dropped
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::union_of(vec![
+    ///     Domain::between(5, 9),
+    ///     Domain::Union(vec![Domain::between(1, 4), Domain::between(20, 10)]),
+    /// ]);
+    /// assert_eq!(domain.repr(), "[1;9]");
+    /// ```
+    pub fn union_of(members: impl IntoIterator<Item = Domain<i32>>) -> Self {
+        let members = members
+            .into_iter()
+            .flat_map(util::into_members)
+            .collect();
+        util::from_members(util::normalize_members(members))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use std::ops::Bound;
+
+    use crate::{Constraint, Domain, DomainError, DomainIndex, Value};
+
+    /// Floats with NaN, both infinities and both zeroes thrown in often
+    fn float() -> impl Strategy<Value = f64> {
+        prop_oneof![
+            4 => -10.0..10.0,
+            1 => Just(f64::NAN),
+            1 => Just(f64::INFINITY),
+            1 => Just(f64::NEG_INFINITY),
+            1 => Just(0.0),
+            1 => Just(-0.0),
+            1 => Just(f64::MAX),
+            1 => Just(f64::MIN_POSITIVE),
+        ]
+    }
+
+    fn float_value() -> impl Strategy<Value = Value<f64>> {
+        prop_oneof![
+            1 => Just(Value::Infinite),
+            3 => float().prop_map(Value::Included),
+            3 => float().prop_map(Value::Secluded),
+        ]
+    }
+
+    /// Finite points ordered from the starting border to the ending one
+    fn well_formed(domain: &Domain<f64>) -> bool {
+        match domain {
+            Domain::Domain(l, r) => {
+                let finite = |value: &Value<f64>| value.as_inner().is_none_or(|x| x.is_finite());
+                let ordered = match (l.as_inner(), r.as_inner()) {
+                    (Some(l), Some(r)) => l <= r,
+                    _ => true,
+                };
+                finite(l) && finite(r) && ordered
+            },
+            _ => false,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_float_entry_points(a in float_value(), b in float_value(), x in float()) {
+            for built in [Domain::interval(a.clone(), b.clone()), Domain::spanning_values(a.clone(), b.clone())] {
+                match built {
+                    Ok(domain) => {
+                        prop_assert!(well_formed(&domain), "{}", domain.clone().repr());
+                        prop_assert!(!domain.contains(f64::NAN));
+                        if domain.contains(x) {
+                            prop_assert!(!x.is_nan());
+                        }
+                        let fixed = domain.repr_with_fmt(|x| format!("{:.1}", x));
+                        prop_assert!(!fixed.contains("NaN") && !domain.repr().contains("NaN"));
+                    },
+                    Err(error) => prop_assert!(!error.to_string().is_empty()),
+                }
+            }
+
+            let broken = [&a, &b].iter().any(|value| value.as_inner().is_some_and(|x| !x.is_finite()));
+            if broken {
+                prop_assert!(Domain::interval(a.clone(), b.clone()).is_err());
+                prop_assert!(Domain::spanning_values(a, b).is_err());
+            }
+        }
+    }
+
+    /// Nested unions, reversed borders and empty members together
+    fn malformed() -> Domain<i32> {
+        Domain::Union(vec![
+            Domain::between(10, 5),
+            Domain::Union(vec![
+                Domain::between(0, 2),
+                Domain::Union(vec![Domain::Domain(Value::Secluded(3), Value::Secluded(4))]),
+                Domain::None,
+            ]),
+            Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite),
+            Domain::between(20, 22),
+        ])
+    }
+
+    #[test]
+    fn test_interval() {
+        assert_eq!(
+            Domain::interval(Value::Included(3), Value::Included(3)).unwrap(),
+            Domain::between(3, 3)
+        );
+        assert_eq!(
+            Domain::interval(Value::Included(3), Value::Secluded(3)).unwrap_err(),
+            DomainError::Empty
+        );
+        assert_eq!(
+            Domain::interval(Value::Secluded(i32::MAX), Value::Included(i32::MIN)).unwrap_err(),
+            DomainError::Reversed
+        );
+        assert!(Domain::interval(Value::Infinite, Value::Included(i32::MIN)).is_ok());
+        assert!(Domain::interval(Value::Included('b'), Value::Included('a')).is_err());
+    }
+
+    #[test]
+    fn test_float_infinities() {
+        assert_eq!(
+            Domain::interval(Value::Included(f64::NEG_INFINITY), Value::Included(0.0)).unwrap_err(),
+            DomainError::InfinitePoint
+        );
+        assert_eq!(
+            Domain::spanning_values(Value::Secluded(1.0f32), Value::Included(f32::INFINITY)).unwrap_err(),
+            DomainError::InfinitePoint
+        );
+        assert_eq!(
+            Domain::interval(Value::Infinite, Value::Included(f64::MAX)).unwrap().repr_with_fmt(|x| format!("{:e}", x)),
+            "(-∞;1.7976931348623157e308]"
+        );
+        let zeroes = Domain::interval(Value::Included(0.0), Value::Included(-0.0)).unwrap();
+        assert!(zeroes.contains(0.0) && zeroes.contains(-0.0));
+    }
+
+    #[test]
+    fn test_not_comparable() {
+        assert_eq!(
+            Domain::interval(Value::Included(f64::NAN), Value::Infinite).unwrap_err(),
+            DomainError::NotComparable
+        );
+        assert_eq!(
+            Domain::interval(Value::Secluded(0.0), Value::Secluded(f64::NAN)).unwrap_err(),
+            DomainError::NotComparable
+        );
+        assert_eq!(
+            Domain::spanning_values(Value::Included(f64::NAN), Value::Infinite).unwrap_err(),
+            DomainError::NotComparable
+        );
+        assert_eq!(
+            Domain::interval(Value::Included(0.5), Value::Secluded(0.5)).unwrap_err(),
+            DomainError::Empty
+        );
+        let domain = Domain::spanning_values(Value::Secluded(2.5), Value::Included(-1.0)).unwrap();
+        assert_eq!(domain.repr(), "[-1;2.5)");
+    }
+
+    #[test]
+    fn test_spanning() {
+        assert_eq!(Domain::spanning(5, -5).repr(), "[-5;5]");
+        assert_eq!(Domain::spanning(-5, 5).repr(), "[-5;5]");
+        assert_eq!(Domain::spanning(7, 7).repr(), "[7;7]");
+        assert_eq!(Domain::spanning(i32::MAX, i32::MIN).cardinality(), Some(1 << 32));
+    }
+
+    #[test]
+    fn test_spanning_values() {
+        let swapped = Domain::spanning_values(Value::Included(i32::MAX), Value::Secluded(i32::MIN)).unwrap();
+        assert_eq!(swapped, Domain::Domain(Value::Secluded(i32::MIN), Value::Included(i32::MAX)));
+        assert_eq!(
+            Domain::spanning_values(Value::Secluded(1), Value::Secluded(4)).unwrap().repr(),
+            "(1;4)"
+        );
+        assert_eq!(
+            Domain::spanning_values(Value::Included(3), Value::Included(3)).unwrap(),
+            Domain::between(3, 3)
+        );
+        assert_eq!(
+            Domain::spanning_values(Value::Secluded(3), Value::Included(3)).unwrap_err(),
+            DomainError::Empty
+        );
+        assert_eq!(
+            Domain::spanning_values(Value::<i32>::Infinite, Value::Infinite).unwrap(),
+            Domain::new()
+        );
+        assert_eq!(
+            Domain::spanning_values(Value::Infinite, Value::Secluded(0)).unwrap_err(),
+            DomainError::Ambiguous
+        );
+        assert!(Domain::spanning_values(Value::Included('z'), Value::Included('a')).is_ok());
+    }
+
+    #[test]
+    fn test_bounds() {
+        let domain = Domain::Domain(Value::Secluded(i32::MIN), Value::Included(i32::MAX));
+        let (start, end) = domain.bounds().unwrap();
+        assert_eq!(Domain::from_bounds(start.cloned(), end.cloned()), domain);
+        assert_eq!(Domain::Union(vec![Domain::between(1, 2)]).bounds(), None);
+        assert_eq!(Domain::new().bounds(), Some((Bound::Unbounded, Bound::Unbounded)));
+    }
+
+    #[test]
+    fn test_from_ranges() {
+        assert_eq!(Domain::from(0..10), Domain::between(0, 9));
+        assert_eq!(Domain::from(i32::MIN..=i32::MAX), Domain::new());
+        assert_eq!(Domain::from(5..5), Domain::None);
+        assert_eq!(Domain::from(i32::MAX..), Domain::between(i32::MAX, i32::MAX));
+        assert_eq!(Domain::from(..=i32::MIN).repr(), "(-∞;-2147483648]");
+        assert_eq!(Domain::from('a'..'d').repr(), "[a;d)");
+        assert_eq!(Domain::<i32>::from(..), Domain::new());
+    }
+
+    #[test]
+    fn test_union_of() {
+        assert_eq!(Domain::union_of(Vec::new()), Domain::None);
+        assert_eq!(Domain::union_of(vec![Domain::between(5, 1)]), Domain::None);
+        assert_eq!(Domain::union_of(vec![malformed()]).repr(), "[0;2]⋃[20;22]");
+        assert_eq!(
+            Domain::union_of(vec![Domain::at_most(0), Domain::at_least(1)]).repr(),
+            "(-∞;∞)"
+        );
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let domain = malformed();
+        let clean = Domain::union_of(vec![domain.clone()]);
+
+        assert_eq!(domain.normalize(), clean);
+        assert!(domain.contains(21) && !domain.contains(7) && !domain.contains(3));
+        assert_eq!(domain.clamp(8), Some(2));
+        assert_eq!(domain.intersect(&Domain::new()), clean);
+        assert_eq!(domain.union(&Domain::None), clean);
+        assert_eq!(domain.complement().complement(), clean);
+        assert_eq!(domain.difference(&Domain::between(1, 21)).repr(), "[0;1)⋃(21;22]");
+        assert_eq!(domain.remove_value(0), clean.remove_value(0));
+        assert_eq!(domain.iter().collect::<Vec<_>>(), vec![0, 1, 2, 20, 21, 22]);
+        assert_eq!(domain.runs().count(), 2);
+        assert_eq!(domain.cardinality(), Some(6));
+        assert_eq!(domain.sum(), Some(66));
+        assert_eq!(domain.histogram(2).unwrap().iter().map(|(_, n)| n).sum::<u64>(), 6);
+        assert_eq!(domain.max_by_key(|n| n as i64, 100), Ok(22));
+        assert_eq!(domain.gt(Value::Secluded(1)).normalize().repr(), "(1;2]⋃[20;22]");
+        assert_eq!(domain.lt(Value::Secluded(1)).normalize().repr(), "[0;1)");
+        assert_eq!(domain.tighten_all(&[Constraint::Ne(21)]).repr(), "[0;2]⋃[20;21)⋃(21;22]");
+        assert_eq!(Domain::from_bytes(&domain.to_bytes()).unwrap(), clean);
+        assert_eq!(domain.to_sql_predicate("x"), clean.to_sql_predicate("x"));
+        assert_eq!(DomainIndex::build(&domain).rank(22), 5);
+        assert!(domain.covered_by(std::slice::from_ref(&clean)).is_ok());
+        assert_eq!(domain.div_const(2), clean.div_const(2));
+        assert_eq!(domain.rem_euclid_const(4).unwrap(), clean.rem_euclid_const(4).unwrap());
+    }
+}
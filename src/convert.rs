@@ -0,0 +1,209 @@
+//! Conversions between point widths. Widening can not lose anything,
+//! narrowing either refuses a border the narrower type can not hold or
+//! clamps the domain to the window of the narrower type first
+
+use std::any::type_name;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::{Bounded, Domain, NarrowError, Value};
+
+/// What `Domain::narrow` does with a finite border out of the range of
+/// the narrower type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NarrowPolicy {
+    /// Refuses with `NarrowError`
+    Error,
+    /// Intersects every member with the window of the narrower type,
+    /// members entirely outside of it are dropped. Negative borders of
+    /// an unsigned target are clipped to 0
+    Clamp,
+}
+
+/// Same point in the narrower type, refused when it does not fit
+fn point<T: fmt::Display + Clone, U: TryFrom<T>>(point: &T) -> Result<U, NarrowError> {
+    U::try_from(point.clone()).map_err(|_| NarrowError::new(point.to_string(), type_name::<U>()))
+}
+
+/// Border in the narrower type, `None` once `Clamp` finds the member
+/// entirely past the window. A limit of `U` the wider type can not hold
+/// is never crossed, so it clamps nothing
+fn narrow_border<T, U>(border: &Value<T>, starting: bool, policy: NarrowPolicy) -> Result<Option<Value<U>>, NarrowError>
+where
+    T: Bounded + fmt::Display + TryFrom<U>,
+    U: Bounded + TryFrom<T>,
+{
+    let p = match border {
+        Value::Included(p) | Value::Secluded(p) => p,
+        Value::Infinite => return Ok(Some(Value::Infinite)),
+    };
+
+    if policy == NarrowPolicy::Clamp {
+        let below = T::try_from(U::LOWEST).is_ok_and(|lowest| *p < lowest);
+        let above = T::try_from(U::HIGHEST).is_ok_and(|highest| *p > highest);
+        match (below, above, starting) {
+            (true, _, true) => return Ok(Some(Value::Included(U::LOWEST))),
+            (_, true, false) => return Ok(Some(Value::Included(U::HIGHEST))),
+            (true, _, false) | (_, true, true) => return Ok(None),
+            _ => (),
+        }
+    }
+
+    Ok(Some(match border {
+        Value::Included(p) => Value::Included(point(p)?),
+        Value::Secluded(p) => Value::Secluded(point(p)?),
+        Value::Infinite => Value::Infinite,
+    }))
+}
+
+/// `narrow` keeping the shape of unions, `None` for a member `Clamp`
+/// dropped
+fn narrow_domain<T, U>(domain: &Domain<T>, policy: NarrowPolicy) -> Result<Option<Domain<U>>, NarrowError>
+where
+    T: Bounded + fmt::Display + TryFrom<U>,
+    U: Bounded + TryFrom<T>,
+{
+    Ok(match domain {
+        Domain::Union(domains) => {
+            let mut members = Vec::with_capacity(domains.len());
+            for domain in domains {
+                members.extend(narrow_domain(domain, policy)?);
+            }
+            Some(Domain::Union(members))
+        },
+        Domain::Domain(l, r) => {
+            match (narrow_border(l, true, policy)?, narrow_border(r, false, policy)?) {
+                (Some(l), Some(r)) => Some(Domain::Domain(l, r)),
+                _ => None,
+            }
+        },
+        Domain::None => Some(Domain::None),
+    })
+}
+
+fn widen_value<T: Clone, U: From<T>>(value: &Value<T>) -> Value<U> {
+    match value {
+        Value::Included(p) => Value::Included(U::from(p.clone())),
+        Value::Secluded(p) => Value::Secluded(U::from(p.clone())),
+        Value::Infinite => Value::Infinite,
+    }
+}
+
+impl<T: Clone> Domain<T> {
+
+    /// Same domain over a wider point type, keeping the structure and
+    /// the notation of every border
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let wide: Domain<i64> = Domain::Domain(Value::Included(i32::MIN), Value::Secluded(0)).widen();
+    /// assert_eq!(wide.repr_with_fmt(i64::to_string), "[-2147483648;0)");
+    /// ```
+    pub fn widen<U: From<T>>(&self) -> Domain<U> {
+        match self {
+            Domain::Union(domains) => Domain::Union(domains.iter().map(Domain::widen).collect()),
+            Domain::Domain(l, r) => Domain::Domain(widen_value(l), widen_value(r)),
+            Domain::None => Domain::None,
+        }
+    }
+}
+
+impl<T: Bounded + fmt::Display> Domain<T> {
+
+    /// Same domain over a narrower point type. Under `NarrowPolicy::Error`
+    /// any finite border out of the range of `U` is refused, under
+    /// `NarrowPolicy::Clamp` the domain is intersected with the range of
+    /// `U` first, so a border past it becomes the closed limit of `U` and
+    /// members entirely past it are dropped. Infinite borders pass
+    /// through, unions keep their shape
+    /// ```
+    /// use dynamic_domain::{Domain, NarrowPolicy, Value};
+    /// let wide = Domain::Union(vec![
+    ///     Domain::Domain(Value::Included(-5i64), Value::Secluded(3_000_000_000)),
+    ///     Domain::Domain(Value::Secluded(5_000_000_000), Value::Infinite),
+    /// ]);
+    /// assert_eq!(wide.narrow::<i32>(NarrowPolicy::Error).unwrap_err().border(), "3000000000");
+    /// let narrow = wide.narrow::<i32>(NarrowPolicy::Clamp).unwrap();
+    /// assert_eq!(narrow.repr(), "[-5;2147483647]");
+    /// let unsigned = wide.narrow::<u32>(NarrowPolicy::Clamp).unwrap();
+    /// assert_eq!(unsigned.repr_with_fmt(u32::to_string), "[0;3000000000)");
+    /// ```
+    pub fn narrow<U>(&self, policy: NarrowPolicy) -> Result<Domain<U>, NarrowError>
+    where
+        T: TryFrom<U>,
+        U: Bounded + TryFrom<T>,
+    {
+        Ok(narrow_domain(self, policy)?.unwrap_or(Domain::None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::{border, union_of};
+    use crate::{Domain, NarrowPolicy, Value};
+
+    fn raw<T: ToString>(domain: &Domain<T>) -> String {
+        domain.repr_with_fmt(T::to_string)
+    }
+
+    proptest! {
+        #[test]
+        fn test_widen_then_narrow(domain in union_of(border(20), 0..5)) {
+            let wide: Domain<i64> = domain.widen();
+            for policy in [NarrowPolicy::Error, NarrowPolicy::Clamp].iter() {
+                let back = wide.narrow::<i32>(*policy).unwrap();
+                prop_assert!(back.structurally_equal(&domain), "{} came back as {}", raw(&domain), raw(&back));
+            }
+        }
+    }
+
+    #[test]
+    fn test_target_limits() {
+        let max = i32::MAX as i64;
+        let min = i32::MIN as i64;
+        let fits = Domain::Domain(Value::Included(min), Value::Included(max));
+        assert_eq!(fits.narrow::<i32>(NarrowPolicy::Error).unwrap(), Domain::between(i32::MIN, i32::MAX));
+
+        let past = Domain::Domain(Value::Secluded(min - 1), Value::Secluded(max + 1));
+        let error = past.narrow::<i32>(NarrowPolicy::Error).unwrap_err();
+        assert_eq!((error.border(), error.target()), ("-2147483649", "i32"));
+        assert_eq!(error.to_string(), "border -2147483649 does not fit into i32");
+        assert_eq!(past.narrow::<i32>(NarrowPolicy::Clamp).unwrap().repr(), "[-2147483648;2147483647]");
+
+        // Members entirely past the window are dropped, not clipped to a point
+        let outside = Domain::Union(vec![
+            Domain::Domain(Value::Included(max + 1), Value::Infinite),
+            Domain::Domain(Value::Infinite, Value::Secluded(min)),
+            Domain::Domain(Value::Included(0), Value::Included(0)),
+        ]);
+        assert_eq!(outside.narrow::<i32>(NarrowPolicy::Clamp).unwrap().repr(), "(-∞;-2147483648)⋃[0;0]");
+        let beyond = Domain::Domain(Value::Included(max + 1), Value::Included(max + 5));
+        assert_eq!(beyond.narrow::<i32>(NarrowPolicy::Clamp).unwrap().repr(), "∅");
+    }
+
+    #[test]
+    fn test_unsigned_target() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Infinite, Value::Included(-1i64)),
+            Domain::Domain(Value::Secluded(-1), Value::Included(u32::MAX as i64)),
+            Domain::Domain(Value::Included(-7), Value::Infinite),
+        ]);
+        assert_eq!(domain.narrow::<u32>(NarrowPolicy::Error).unwrap_err().border(), "-1");
+        let clamped = domain.narrow::<u32>(NarrowPolicy::Clamp).unwrap();
+        assert_eq!(raw(&clamped), "[0;4294967295]⋃[0;∞)");
+
+        let past = Domain::Domain(Value::Included(0i64), Value::Secluded(u32::MAX as i64 + 1));
+        assert_eq!(raw(&past.narrow::<u32>(NarrowPolicy::Clamp).unwrap()), "[0;4294967295]");
+        assert_eq!(raw(&Domain::between(-5, 5).narrow::<u32>(NarrowPolicy::Clamp).unwrap()), "[0;5]");
+        assert!(Domain::<i64>::None.narrow::<u32>(NarrowPolicy::Error).unwrap().structurally_equal(&Domain::None));
+    }
+
+    #[test]
+    fn test_widen_keeps_structure() {
+        let domain = Domain::Union(vec![Domain::Union(vec![Domain::at_most(i32::MIN)]), Domain::None]);
+        assert_eq!(raw(&domain.widen::<i64>()), raw(&domain));
+        let unsigned = Domain::Domain(Value::Secluded(u32::MAX), Value::Infinite);
+        assert_eq!(raw(&unsigned.widen::<i64>()), "(4294967295;∞)");
+    }
+}
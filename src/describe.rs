@@ -0,0 +1,199 @@
+//! One-line English summaries of domains for people rather than parsers.
+//! The phrasing is documented on `Domain::describe`. Every word comes
+//! from `english`, the one place to translate
+
+use crate::util::{self, first_admitted, last_admitted, Member};
+use crate::{Domain, Value};
+
+/// Members summarized one by one, more are counted
+const ENUMERATED: usize = 3;
+
+/// Finite border of a member: the point and whether it is secluded
+type Bound = (i32, bool);
+
+/// What a summary says, before it is put into words
+enum Phrase {
+    Anything,
+    Nothing,
+    Exactly(i32),
+    Above(Bound),
+    Below(Bound),
+    Between(Bound, Bound),
+    AnyOf(Vec<Phrase>),
+    Ranges(usize, Option<i32>, Option<i32>),
+}
+
+/// The words of `phrase`
+fn english(phrase: &Phrase) -> String {
+    match phrase {
+        Phrase::Anything => "any value".to_string(),
+        Phrase::Nothing => "no values allowed".to_string(),
+        Phrase::Exactly(point) => format!("exactly {}", point),
+        Phrase::Above((point, false)) => format!("at least {}", point),
+        Phrase::Above((point, true)) => format!("greater than {}", point),
+        Phrase::Below((point, false)) => format!("at most {}", point),
+        Phrase::Below((point, true)) => format!("less than {}", point),
+        Phrase::Between((lo, false), (hi, false)) => format!("between {} and {} inclusive", lo, hi),
+        Phrase::Between((lo, true), (hi, true)) => format!("between {} and {} exclusive", lo, hi),
+        Phrase::Between((lo, true), (hi, false)) => format!("between {} (exclusive) and {}", lo, hi),
+        Phrase::Between((lo, false), (hi, true)) => format!("between {} and {} (exclusive)", lo, hi),
+        Phrase::AnyOf(phrases) => {
+            let words: Vec<String> = phrases.iter().map(english).collect();
+            match words.split_last() {
+                Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+                _ => words.concat(),
+            }
+        },
+        Phrase::Ranges(count, Some(first), Some(last)) => format!("one of {} ranges between {} and {}", count, first, last),
+        Phrase::Ranges(count, Some(first), None) => format!("one of {} ranges from {} up", count, first),
+        Phrase::Ranges(count, None, Some(last)) => format!("one of {} ranges up to {}", count, last),
+        Phrase::Ranges(count, None, None) => format!("one of {} ranges", count),
+    }
+}
+
+/// Phrase of a member admitting some `i32`
+fn member_phrase((l, r): &Member) -> Phrase {
+    let (first, last) = util::clipped_run(l, r);
+    if first == last {
+        return Phrase::Exactly(first as i32);
+    }
+    // Borders reaching the i32 limits bound nothing
+    let bound = |value: &Value<i32>| match value {
+        Value::Included(point) => Some((*point, false)),
+        Value::Secluded(point) => Some((*point, true)),
+        Value::Infinite => None,
+    };
+    let lo = if first == i32::MIN as i64 { None } else { bound(l) };
+    let hi = if last == i32::MAX as i64 { None } else { bound(r) };
+    match (lo, hi) {
+        (Some(lo), Some(hi)) => Phrase::Between(lo, hi),
+        (Some(lo), None) => Phrase::Above(lo),
+        (None, Some(hi)) => Phrase::Below(hi),
+        (None, None) => Phrase::Anything,
+    }
+}
+
+impl Domain<i32> {
+
+    /// One-line English summary of the admitted values, picked by the
+    /// shape of the normalized domain:
+    /// - nothing admitted: "no values allowed", every `i32`: "any value"
+    /// - one value, however the borders are written: "exactly 7"
+    /// - one member bounded on both sides: "between 5 and 10 inclusive",
+    ///   "between 5 and 10 exclusive", or the open border marked as in
+    ///   "between 5 (exclusive) and 10"
+    /// - one member bounded on one side: "at least 5", "greater than 5",
+    ///   "at most 10", "less than 10"
+    /// - up to three members, each of them as above: "exactly 1, between
+    ///   3 and 5 inclusive or at least 9"
+    /// - more members, their count and the first and last admitted
+    ///   values: "one of 4 ranges between 0 and 99", "one of 4 ranges
+    ///   from 0 up", "one of 4 ranges up to 99" or "one of 4 ranges"
+    ///
+    /// A border at the `i32` limit bounds nothing, so `[-2147483648;5]`
+    /// reads "at most 5" like `(-∞;5]`
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// assert_eq!(Domain::between(5, 10).describe(), "between 5 and 10 inclusive");
+    /// assert_eq!(Domain::Domain(Value::Secluded(5), Value::Included(10)).describe(), "between 5 (exclusive) and 10");
+    /// assert_eq!(Domain::at_least(5).describe(), "at least 5");
+    /// assert_eq!(Domain::between(7, 7).describe(), "exactly 7");
+    /// assert_eq!(Domain::new().describe(), "any value");
+    /// assert_eq!(Domain::None.describe(), "no values allowed");
+    /// let ranges = Domain::Union((0..4).map(|i| Domain::between(i * 30, i * 30 + 9)).collect());
+    /// assert_eq!(ranges.describe(), "one of 4 ranges between 0 and 99");
+    /// ```
+    pub fn describe(&self) -> String {
+        let members = util::normalized(self);
+        let phrase = match members.as_slice() {
+            [] => Phrase::Nothing,
+            [member] => member_phrase(member),
+            members if members.len() <= ENUMERATED => Phrase::AnyOf(members.iter().map(member_phrase).collect()),
+            members => {
+                let first = first_admitted(&members[0].0);
+                let last = last_admitted(&members[members.len() - 1].1);
+                Phrase::Ranges(
+                    members.len(),
+                    Some(first).filter(|first| *first > i32::MIN as i64).map(|first| first as i32),
+                    Some(last).filter(|last| *last < i32::MAX as i64).map(|last| last as i32),
+                )
+            },
+        };
+        english(&phrase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Domain, Value};
+
+    fn open(lo: i32, hi: i32) -> Domain<i32> {
+        Domain::Domain(Value::Secluded(lo), Value::Secluded(hi))
+    }
+
+    #[test]
+    fn test_phrases() {
+        let cases = vec![
+            (Domain::None, "no values allowed"),
+            (Domain::between(5, 1), "no values allowed"),
+            (open(5, 6), "no values allowed"),
+            (Domain::new(), "any value"),
+            (Domain::between(7, 7), "exactly 7"),
+            (open(6, 8), "exactly 7"),
+            (Domain::between(5, 10), "between 5 and 10 inclusive"),
+            (open(5, 10), "between 5 and 10 exclusive"),
+            (Domain::Domain(Value::Secluded(5), Value::Included(10)), "between 5 (exclusive) and 10"),
+            (Domain::Domain(Value::Included(5), Value::Secluded(10)), "between 5 and 10 (exclusive)"),
+            (Domain::at_least(5), "at least 5"),
+            (Domain::new().gt(Value::Secluded(5)), "greater than 5"),
+            (Domain::at_most(10), "at most 10"),
+            (Domain::new().lt(Value::Secluded(-10)), "less than -10"),
+            (Domain::Union(vec![Domain::between(1, 2), Domain::between(3, 5)]), "between 1 and 5 inclusive"),
+            (Domain::Union(vec![Domain::at_least(9), Domain::between(1, 1)]), "exactly 1 or at least 9"),
+            (
+                Domain::Union(vec![Domain::between(1, 1), Domain::between(3, 5), Domain::at_least(9)]),
+                "exactly 1, between 3 and 5 inclusive or at least 9",
+            ),
+            (
+                Domain::Union(vec![Domain::at_most(-1), open(0, 9), Domain::between(20, 20), Domain::at_least(30)]),
+                "one of 4 ranges",
+            ),
+            (
+                Domain::Union(vec![Domain::between(-5, -3), Domain::between(0, 1), Domain::between(4, 4), Domain::at_least(9)]),
+                "one of 4 ranges from -5 up",
+            ),
+            (
+                Domain::Union(vec![Domain::at_most(-5), Domain::between(0, 1), Domain::between(4, 4), open(8, 12)]),
+                "one of 4 ranges up to 11",
+            ),
+        ];
+        for (domain, expected) in cases {
+            assert_eq!(domain.describe(), expected, "{}", domain.clone().repr());
+        }
+    }
+
+    #[test]
+    fn test_saturated_borders() {
+        let cases = vec![
+            (Domain::between(i32::MIN, i32::MAX), "any value"),
+            (Domain::between(i32::MIN, 5), "at most 5"),
+            (Domain::between(5, i32::MAX), "at least 5"),
+            (Domain::Domain(Value::Secluded(i32::MIN), Value::Infinite), "greater than -2147483648"),
+            (Domain::Domain(Value::Infinite, Value::Secluded(i32::MAX)), "less than 2147483647"),
+            (Domain::at_least(i32::MAX), "exactly 2147483647"),
+            (Domain::Domain(Value::Infinite, Value::Secluded(i32::MIN + 1)), "exactly -2147483648"),
+            (Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite), "no values allowed"),
+            (
+                Domain::Union(vec![Domain::at_most(i32::MIN), Domain::between(i32::MAX, i32::MAX)]),
+                "exactly -2147483648 or exactly 2147483647",
+            ),
+            (
+                Domain::Union((0..5).map(|i| Domain::between(i32::MIN + i * 2, i32::MIN + i * 2)).collect()),
+                "one of 5 ranges up to -2147483640",
+            ),
+        ];
+        for (domain, expected) in cases {
+            assert_eq!(domain.describe(), expected, "{}", domain.clone().repr());
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Integer semantics versus continuous semantics.
+//!
+//! Merging adjacent members, treating `(5;6)` as empty, counting and
+//! indexing values all assume every point has a next one. Those methods
+//! live on the discrete domains only, while the ones comparing points with
+//! the borders (`contains`, `repr`) are available for any ordered points
+//! ```
+//! use dynamic_domain::{Domain, Value};
+//! let domain = Domain::Domain(Value::Included(0.5), Value::Secluded(1.5));
+//! assert!(domain.contains(1.0));
+//! assert_eq!(domain.repr(), "[0.5;1.5)");
+//! ```
+//! Counting the values of a continuous domain does not compile
+//! ```compile_fail
+//! use dynamic_domain::{Domain, Value};
+//! let domain = Domain::Domain(Value::Included(0.5), Value::Secluded(1.5));
+//! domain.cardinality();
+//! ```
+//! and neither does merging its adjacent members
+//! ```compile_fail
+//! use dynamic_domain::{Domain, Value};
+//! let domain = Domain::Domain(Value::Secluded(5.0), Value::Secluded(6.0));
+//! domain.normalize();
+//! ```
+
+/// Steps between neighbouring points, `None` past the last point
+pub trait DiscreteStep: Ord + Clone {
+    /// Smallest point greater than this one
+    /// ```
+    /// use dynamic_domain::DiscreteStep;
+    /// assert_eq!(5.successor(), Some(6));
+    /// assert_eq!(i32::MAX.successor(), None);
+    /// ```
+    fn successor(&self) -> Option<Self>;
+
+    /// Greatest point smaller than this one
+    /// ```
+    /// use dynamic_domain::DiscreteStep;
+    /// assert_eq!('b'.predecessor(), Some('a'));
+    /// assert_eq!('\u{0}'.predecessor(), None);
+    /// ```
+    fn predecessor(&self) -> Option<Self>;
+}
+
+/// Marker of points with integer semantics, to bound generic code which
+/// steps between neighbouring points. Implemented for every `DiscreteStep`
+/// ```
+/// use dynamic_domain::Discrete;
+/// fn discrete<T: Discrete>() {}
+/// discrete::<i32>();
+/// discrete::<char>();
+/// ```
+pub trait Discrete: DiscreteStep {}
+
+impl<T: DiscreteStep> Discrete for T {}
+
+/// Discrete points with a smallest and a greatest one, the window
+/// `Domain::narrow` clamps to
+/// ```
+/// use dynamic_domain::Bounded;
+/// assert_eq!(<u32 as Bounded>::LOWEST, 0);
+/// assert_eq!(<i64 as Bounded>::HIGHEST, i64::MAX);
+/// assert_eq!(<u8 as Bounded>::MIN_VALUE, Some(0));
+/// ```
+pub trait Bounded: DiscreteStep {
+    /// Smallest point
+    const LOWEST: Self;
+    /// Greatest point
+    const HIGHEST: Self;
+    /// Natural lower edge, written as an included starting border rather
+    /// than -∞: `Some(0)` for the unsigned integers, `None` when the
+    /// smallest point is a limit of the type nobody thinks of as an edge
+    const MIN_VALUE: Option<Self> = None;
+}
+
+macro_rules! integer_points {
+    ($($int:ty => $min_value:expr),*) => {$(
+        impl DiscreteStep for $int {
+            fn successor(&self) -> Option<$int> {
+                self.checked_add(1)
+            }
+
+            fn predecessor(&self) -> Option<$int> {
+                self.checked_sub(1)
+            }
+        }
+
+        impl Bounded for $int {
+            const LOWEST: $int = <$int>::MIN;
+            const HIGHEST: $int = <$int>::MAX;
+            const MIN_VALUE: Option<$int> = $min_value;
+        }
+    )*};
+}
+
+integer_points!(
+    i8 => None, i16 => None, i32 => None, i64 => None,
+    u8 => Some(0), u16 => Some(0), u32 => Some(0), u64 => Some(0)
+);
+
+/// Steps over the surrogate code points, which are not `char`s
+impl DiscreteStep for char {
+    fn successor(&self) -> Option<char> {
+        match *self {
+            '\u{D7FF}' => Some('\u{E000}'),
+            c => char::from_u32(c as u32 + 1),
+        }
+    }
+
+    fn predecessor(&self) -> Option<char> {
+        match *self {
+            '\u{E000}' => Some('\u{D7FF}'),
+            c => (c as u32).checked_sub(1).and_then(char::from_u32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DiscreteStep;
+
+    #[test]
+    fn test_i32_limits() {
+        assert_eq!(i32::MIN.predecessor(), None);
+        assert_eq!(i32::MIN.successor(), Some(i32::MIN + 1));
+        assert_eq!(i32::MAX.predecessor(), Some(i32::MAX - 1));
+        assert_eq!(i32::MAX.successor(), None);
+        assert_eq!(0u32.predecessor(), None);
+        assert_eq!(i64::MAX.successor(), None);
+        assert_eq!((i32::MAX as i64).successor(), Some(i32::MAX as i64 + 1));
+    }
+
+    #[test]
+    fn test_char_surrogates() {
+        assert_eq!('\u{D7FF}'.successor(), Some('\u{E000}'));
+        assert_eq!('\u{E000}'.predecessor(), Some('\u{D7FF}'));
+        assert_eq!(char::MAX.successor(), None);
+        assert_eq!(char::MAX.predecessor(), Some('\u{10FFFE}'));
+        assert_eq!('a'.successor().and_then(|c| c.predecessor()), Some('a'));
+    }
+}
@@ -0,0 +1,193 @@
+//! Plain data form of `Domain<i32>` for crate boundaries. Downstream crates
+//! accept a `DomainDto` instead of the `Domain` enum, which keeps
+//! evolving. The layout of the DTOs and the meaning of their fields are
+//! the stable interchange format: changes to them are breaking changes of
+//! this crate. The serde representation is stable too
+
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Serialize};
+
+use crate::util;
+use crate::{Domain, DomainError, Value};
+
+/// One interval of a `DomainDto`. An absent bound is infinite and never
+/// inclusive, a present one admits its value when inclusive. Built with
+/// `IntervalDto::new` outside of the crate so fields may be added without
+/// a breaking change
+/// ```
+/// use dynamic_domain::IntervalDto;
+/// let interval = IntervalDto::new(Some(0), None, true, false);
+/// assert_eq!((interval.lo, interval.hi, interval.lo_inclusive), (Some(0), None, true));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct IntervalDto {
+    /// Starting bound, `None` for minus infinity
+    pub lo: Option<i64>,
+    /// Ending bound, `None` for infinity
+    pub hi: Option<i64>,
+    /// Whether `lo` is admitted
+    pub lo_inclusive: bool,
+    /// Whether `hi` is admitted
+    pub hi_inclusive: bool,
+}
+
+impl IntervalDto {
+
+    /// Interval between `lo` and `hi`, taken as given, `Domain::try_from`
+    /// checks it
+    /// ```
+    /// use dynamic_domain::IntervalDto;
+    /// assert!(IntervalDto::new(Some(1), Some(5), true, false).hi_inclusive == false);
+    /// ```
+    pub fn new(lo: Option<i64>, hi: Option<i64>, lo_inclusive: bool, hi_inclusive: bool) -> Self {
+        IntervalDto { lo, hi, lo_inclusive, hi_inclusive }
+    }
+}
+
+/// Intervals of a domain, the stable interchange format of `Domain<i32>`.
+/// Converting a domain gives its normalized members, sorted, disjoint and
+/// not touching, with borders as `Domain::normalize` writes them
+/// ```
+/// use std::convert::TryFrom;
+/// use dynamic_domain::{Domain, DomainDto, IntervalDto};
+/// let domain = Domain::Union(vec![Domain::between(1, 5), Domain::at_least(10)]);
+/// let dto = DomainDto::from(&domain);
+/// assert_eq!(dto, DomainDto(vec![IntervalDto::new(Some(1), Some(5), true, true), IntervalDto::new(Some(10), None, true, false)]));
+/// assert_eq!(Domain::try_from(dto), Ok(domain));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DomainDto(pub Vec<IntervalDto>);
+
+/// Starting or ending border of an interval, bounds out of `i32` are
+/// `DomainError::OutOfRange` and inclusive absent ones
+/// `DomainError::InclusiveInfinity`
+fn border(bound: Option<i64>, inclusive: bool) -> Result<Value<i32>, DomainError> {
+    match bound {
+        Some(value) => {
+            let point = i32::try_from(value).map_err(|_| DomainError::OutOfRange(value))?;
+            Ok(if inclusive { Value::Included(point) } else { Value::Secluded(point) })
+        },
+        None if inclusive => Err(DomainError::InclusiveInfinity),
+        None => Ok(Value::Infinite),
+    }
+}
+
+/// DTO bound of a border and whether it is inclusive
+fn bound(value: &Value<i32>) -> (Option<i64>, bool) {
+    match value {
+        Value::Included(i) => (Some(*i as i64), true),
+        Value::Secluded(i) => (Some(*i as i64), false),
+        Value::Infinite => (None, false),
+    }
+}
+
+/// The normalized members of the domain, losslessly: converting the DTO
+/// back gives a domain admitting the same values
+/// ```
+/// use dynamic_domain::{Domain, DomainDto};
+/// assert_eq!(DomainDto::from(&Domain::between(5, 1)), DomainDto(vec![]));
+/// assert_eq!(DomainDto::from(&Domain::new()).0[0].lo, None);
+/// ```
+impl From<&Domain<i32>> for DomainDto {
+    fn from(domain: &Domain<i32>) -> Self {
+        let intervals = util::normalized(domain).iter().map(|(l, r)| {
+            let ((lo, lo_inclusive), (hi, hi_inclusive)) = (bound(l), bound(r));
+            IntervalDto { lo, hi, lo_inclusive, hi_inclusive }
+        }).collect();
+        DomainDto(intervals)
+    }
+}
+
+/// Domain admitting the values of any of the intervals, normalized. Each
+/// interval is checked like `Domain::interval` checks its borders, after
+/// its bounds are checked to fit `i32` and absent ones to be exclusive.
+/// The intervals may come in any order and overlap
+/// ```
+/// use std::convert::TryFrom;
+/// use dynamic_domain::{Domain, DomainDto, DomainError, IntervalDto};
+/// let dto = DomainDto(vec![IntervalDto::new(Some(3), Some(9), false, true), IntervalDto::new(None, Some(4), false, false)]);
+/// assert_eq!(Domain::try_from(dto).unwrap().repr(), "(-∞;9]");
+/// let reversed = DomainDto(vec![IntervalDto::new(Some(9), Some(3), true, true)]);
+/// assert_eq!(Domain::try_from(reversed), Err(DomainError::Reversed));
+/// let wide = DomainDto(vec![IntervalDto::new(Some(1 << 40), None, true, false)]);
+/// assert_eq!(Domain::try_from(wide), Err(DomainError::OutOfRange(1 << 40)));
+/// ```
+impl TryFrom<DomainDto> for Domain<i32> {
+    type Error = DomainError;
+
+    fn try_from(dto: DomainDto) -> Result<Self, Self::Error> {
+        let mut members = Vec::with_capacity(dto.0.len());
+        for interval in &dto.0 {
+            let lo = border(interval.lo, interval.lo_inclusive)?;
+            let hi = border(interval.hi, interval.hi_inclusive)?;
+            Domain::interval(lo.clone(), hi.clone())?;
+            members.push((lo, hi));
+        }
+        Ok(util::from_members(util::normalize_members(members)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use proptest::prelude::*;
+
+    use crate::algebra::{border, union_of};
+    use crate::{Domain, DomainDto, DomainError, IntervalDto, Value};
+
+    proptest! {
+        #[test]
+        fn test_roundtrip(domain in union_of(border(20), 0..5)) {
+            let dto = DomainDto::from(&domain);
+            prop_assert_eq!(Domain::try_from(dto.clone()), Ok(domain.clone()));
+
+            let json = serde_json::to_string(&dto).unwrap();
+            let back: DomainDto = serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(&back, &dto);
+            prop_assert_eq!(Domain::try_from(back), Ok(domain.clone()));
+
+            // The DTO of a normalized domain is the same
+            prop_assert_eq!(DomainDto::from(&domain.normalize().into_inner()), dto);
+        }
+    }
+
+    #[test]
+    fn test_serde_format() {
+        let dto = DomainDto::from(&Domain::Domain(Value::Secluded(-1), Value::Infinite));
+        let json = serde_json::to_string(&dto).unwrap();
+        assert_eq!(json, r#"[{"lo":-1,"hi":null,"lo_inclusive":false,"hi_inclusive":false}]"#);
+        assert_eq!(serde_json::to_string(&DomainDto::default()).unwrap(), "[]");
+        let from_json: DomainDto = serde_json::from_str(r#"[{"lo":-1,"hi":null,"lo_inclusive":false,"hi_inclusive":false}]"#).unwrap();
+        assert_eq!(from_json, dto);
+    }
+
+    #[test]
+    fn test_checks() {
+        let single = |lo, hi, lo_inclusive, hi_inclusive| {
+            Domain::try_from(DomainDto(vec![IntervalDto::new(lo, hi, lo_inclusive, hi_inclusive)]))
+        };
+        let past = i32::MAX as i64 + 1;
+        let below = i32::MIN as i64 - 1;
+        assert_eq!(single(Some(0), Some(past), true, true), Err(DomainError::OutOfRange(past)));
+        assert_eq!(single(Some(below), None, false, false), Err(DomainError::OutOfRange(below)));
+        assert_eq!(single(None, Some(0), true, true), Err(DomainError::InclusiveInfinity));
+        assert_eq!(single(Some(0), None, true, true), Err(DomainError::InclusiveInfinity));
+        assert_eq!(single(Some(5), Some(5), true, false), Err(DomainError::Empty));
+        assert_eq!(single(Some(5), Some(4), true, true), Err(DomainError::Reversed));
+
+        // Valid intervals admitting no i32 are fine, they give nothing
+        assert_eq!(single(Some(5), Some(6), false, false), Ok(Domain::None));
+        assert_eq!(single(Some(i32::MAX as i64), None, false, false), Ok(Domain::None));
+        assert_eq!(Domain::try_from(DomainDto::default()), Ok(Domain::None));
+
+        let full = single(Some(i32::MIN as i64), Some(i32::MAX as i64), true, true).unwrap();
+        assert_eq!(full, Domain::new());
+        // Borders keep their spelling, finite or not
+        let edges = IntervalDto::new(Some(i32::MIN as i64), Some(i32::MAX as i64), true, true);
+        assert_eq!(DomainDto::from(&full), DomainDto(vec![edges]));
+        assert_eq!(DomainDto::from(&Domain::new()), DomainDto(vec![IntervalDto::new(None, None, false, false)]));
+    }
+}
@@ -0,0 +1,151 @@
+//! Every builder and transform keeps an empty domain empty, however the
+//! emptiness is written. Only `complement` turns it into the full domain
+
+use std::ops::Bound;
+
+use crate::{Constraint, Domain, DomainBuilder, Limit, OverflowPolicy, Rounding, Value};
+
+/// Domains admitting no `i32`: `Domain::None` and the spellings which
+/// only normalization finds empty
+fn empties() -> Vec<Domain<i32>> {
+    vec![
+        Domain::None,
+        Domain::Union(vec![]),
+        Domain::Union(vec![Domain::None, Domain::None]),
+        Domain::Union(vec![Domain::Union(vec![Domain::None]), Domain::Union(vec![])]),
+        Domain::between(5, 1),
+        Domain::Domain(Value::Secluded(5), Value::Secluded(6)),
+        Domain::Domain(Value::Infinite, Value::Secluded(i32::MIN)),
+        Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite),
+        Domain::Union(vec![Domain::between(9, 0), Domain::None, Domain::Domain(Value::Included(3), Value::Secluded(3))]),
+    ]
+}
+
+/// Operands for the binary transforms, empty and not
+fn others() -> Vec<Domain<i32>> {
+    vec![Domain::None, Domain::new(), Domain::between(-3, 7), Domain::at_least(i32::MAX), Domain::between(2, 1)]
+}
+
+const POLICIES: [OverflowPolicy; 3] = [OverflowPolicy::Error, OverflowPolicy::Saturate, OverflowPolicy::Unbounded];
+
+const ROUNDINGS: [Rounding; 5] = [Rounding::Floor, Rounding::Ceil, Rounding::Nearest, Rounding::Inward, Rounding::Outward];
+
+/// Checks the result of `call` on the empty `domain` admits nothing
+fn assert_empty(result: &Domain<i32>, call: &str, domain: &Domain<i32>) {
+    assert!(result.is_empty(), "{} on {:?} gives {:?}", call, domain, result);
+}
+
+#[test]
+fn test_borders_keep_empty() {
+    let borders = [Value::Included(0), Value::Secluded(0), Value::Included(i32::MIN), Value::Secluded(i32::MAX), Value::Infinite];
+    for domain in &empties() {
+        for border in borders.iter() {
+            assert_empty(&domain.gt(border.clone()), "gt", domain);
+            assert_empty(&domain.lt(border.clone()), "lt", domain);
+        }
+        for bound in [Bound::Included(4), Bound::Excluded(4), Bound::Unbounded].iter() {
+            assert_empty(&domain.gt(*bound), "gt", domain);
+            assert_empty(&domain.lt(*bound), "lt", domain);
+        }
+        assert_empty(&domain.gt(-1).lt(10), "gt and lt", domain);
+    }
+}
+
+#[test]
+fn test_set_operations_keep_empty() {
+    for domain in &empties() {
+        assert_empty(&domain.clone().normalize().into_inner(), "normalize", domain);
+        assert_empty(&domain.coalesce(u32::MAX).into_inner(), "coalesce", domain);
+        assert_empty(&domain.remove_value(0).into_inner(), "remove_value", domain);
+        for other in &others() {
+            assert_empty(&domain.intersect(other).into_inner(), "intersect", domain);
+            assert_empty(&other.intersect(domain).into_inner(), "intersect from the other side", domain);
+            assert_empty(&domain.intersect_limited(other, 0).unwrap(), "intersect_limited", domain);
+            assert_empty(&domain.difference(other).into_inner(), "difference", domain);
+            assert_eq!(domain.union(other).into_inner(), other.clone(), "union of {:?}", domain);
+            assert_eq!(other.difference(domain).into_inner(), other.clone(), "difference from {:?}", domain);
+        }
+        assert_empty(&Domain::intersect_many(vec![domain, &Domain::new()]).into_inner(), "intersect_many", domain);
+        assert_empty(&Domain::union_many(vec![domain, domain]).into_inner(), "union_many", domain);
+
+        // The one transform documented to bring values: nothing excluded
+        assert_eq!(domain.complement().into_inner(), Domain::new());
+    }
+}
+
+#[test]
+fn test_constraints_keep_empty() {
+    let constraints = vec![
+        Constraint::Gt(Value::Infinite),
+        Constraint::Lt(Value::Included(i32::MAX)),
+        Constraint::Ne(0),
+        Constraint::InsideOf(Domain::new()),
+        Constraint::OutsideOf(Domain::None),
+    ];
+    for domain in &empties() {
+        for constraint in &constraints {
+            assert_empty(&constraint.apply(domain), "Constraint::apply", domain);
+        }
+        assert_empty(&domain.tighten_all(&constraints), "tighten_all", domain);
+        assert_empty(&domain.tighten_all(&[]), "tighten_all", domain);
+
+        let built = DomainBuilder::new().inside(domain.clone()).build();
+        assert!(built.is_err(), "build inside {:?}", domain);
+    }
+}
+
+#[test]
+fn test_arithmetic_keeps_empty() {
+    let points = [0, 1, -1, 7, i32::MIN, i32::MAX];
+    for domain in &empties() {
+        for n in points.iter().copied() {
+            for policy in POLICIES.iter().copied() {
+                assert_empty(&domain.shift_with(n, policy).unwrap(), "shift_with", domain);
+                assert_empty(&domain.scale_with(n, policy).unwrap(), "scale_with", domain);
+            }
+            assert_empty(&domain.shift(n).unwrap(), "shift", domain);
+            assert_empty(&domain.scale(n).unwrap(), "scale", domain);
+            assert_empty(&domain.div_const(n), "div_const", domain);
+            if let Ok(result) = domain.rem_euclid_const(n) {
+                assert_empty(&result, "rem_euclid_const", domain);
+            }
+            for mode in ROUNDINGS.iter().copied() {
+                if let Ok(result) = domain.div_round(n, mode) {
+                    assert_empty(&result, "div_round", domain);
+                }
+            }
+        }
+        for other in &others() {
+            for policy in POLICIES.iter().copied() {
+                assert_empty(&domain.add_with(other, policy).unwrap(), "add_with", domain);
+                assert_empty(&other.add_with(domain, policy).unwrap(), "add_with from the other side", domain);
+                assert_empty(&domain.mul_with(other, policy).unwrap(), "mul_with", domain);
+                assert_empty(&other.mul_with(domain, policy).unwrap(), "mul_with from the other side", domain);
+            }
+            assert_empty(&domain.add(other).unwrap(), "add", domain);
+            assert_empty(&domain.mul(other).unwrap(), "mul", domain);
+            assert_empty(&domain.div(other), "div", domain);
+            assert_empty(&other.div(domain), "div by it", domain);
+        }
+    }
+}
+
+#[test]
+fn test_mappings_keep_empty() {
+    let limits = [Limit::Infinite, Limit::Asymptote(0), Limit::Asymptote(i32::MAX)];
+    for domain in &empties() {
+        for at_neg_inf in limits.iter().copied() {
+            for at_pos_inf in limits.iter().copied() {
+                for increasing in [true, false].iter().copied() {
+                    let image = domain.apply_monotonic(|x| x.wrapping_mul(3), increasing, at_neg_inf, at_pos_inf);
+                    assert_empty(&image, "apply_monotonic", domain);
+                    let image = domain.apply_piecewise_monotonic(|x| x / 2, &[-5, 0, 5], increasing, at_neg_inf, at_pos_inf);
+                    assert_empty(&image.unwrap(), "apply_piecewise_monotonic", domain);
+                }
+            }
+        }
+        assert_eq!(domain.chunks(3), Some(vec![]), "chunks of {:?}", domain);
+        assert_eq!(domain.iter().next(), None);
+        assert_eq!(domain.iter_in(i32::MIN, i32::MAX).next(), None);
+    }
+}
@@ -0,0 +1,221 @@
+//! Compact binary encoding independent of any serialization framework.
+//!
+//! Version 1 layout:
+//! * one byte with the format version
+//! * varint with the number of intervals
+//! * per interval a tag byte, the lower border kind in bits 0-1 and the
+//!   upper border kind in bits 2-3 (0 infinite, 1 included, 2 secluded),
+//!   followed by the zigzag varints of its finite borders
+//!
+//! Varints are LEB128, 7 bits per byte, least significant group first
+
+use crate::error::DecodeError;
+use crate::util::{self, Member};
+use crate::{Domain, Value};
+
+const VERSION: u8 = 1;
+
+const INFINITE: u8 = 0;
+const INCLUDED: u8 = 1;
+const SECLUDED: u8 = 2;
+
+fn write_varint(out: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn write_border(out: &mut Vec<u8>, value: &Value<i32>) {
+    if let Value::Included(i) | Value::Secluded(i) = value {
+        let zigzag = ((*i << 1) ^ (*i >> 31)) as u32;
+        write_varint(out, zigzag as u64);
+    }
+}
+
+fn kind(value: &Value<i32>) -> u8 {
+    match value {
+        Value::Infinite => INFINITE,
+        Value::Included(_) => INCLUDED,
+        Value::Secluded(_) => SECLUDED,
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        let byte = *self.bytes
+            .get(self.position)
+            .ok_or(DecodeError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn varint(&mut self, max_bits: u32) -> Result<u64, DecodeError> {
+        let mut n: u64 = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.byte()?;
+            let group = (byte & 0x7f) as u64;
+
+            if shift >= max_bits || (max_bits - shift < 7 && group >> (max_bits - shift) != 0) {
+                return Err(DecodeError::VarintOverflow);
+            }
+            n |= group << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+            shift += 7;
+        }
+    }
+
+    fn border(&mut self, kind: u8) -> Result<Value<i32>, DecodeError> {
+        if kind == INFINITE {
+            return Ok(Value::Infinite);
+        }
+
+        let zigzag = self.varint(32)? as u32;
+        let i = ((zigzag >> 1) as i32) ^ -((zigzag & 1) as i32);
+
+        Ok(if kind == INCLUDED { Value::Included(i) } else { Value::Secluded(i) })
+    }
+}
+
+impl Domain<i32> {
+
+    /// Encodes the domain into the compact binary format,
+    /// nested unions are flattened
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Included(5), Value::Secluded(10));
+    /// assert_eq!(domain.to_bytes(), vec![1, 1, 9, 10, 20]);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let members = util::members(self);
+        let mut out = Vec::with_capacity(2 + members.len() * 7);
+
+        out.push(VERSION);
+        write_varint(&mut out, members.len() as u64);
+
+        for (l, r) in &members {
+            out.push(kind(l) | kind(r) << 2);
+            write_border(&mut out, l);
+            write_border(&mut out, r);
+        }
+
+        out
+    }
+
+    /// Decodes a domain produced by `to_bytes`. Hostile input leads to
+    /// an error, never to a panic or to an allocation larger than the input
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::from_bytes(&[1, 1, 9, 10, 20]).unwrap();
+    /// assert_eq!(domain.repr(), "[5;10)");
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = Reader { bytes, position: 0 };
+
+        let version = reader.byte()?;
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+
+        let count = reader.varint(64)?;
+        // every interval takes at least its tag byte
+        if count > reader.remaining() as u64 {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let mut members: Vec<Member> = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let tag = reader.byte()?;
+            let (l, r) = (tag & 0b11, tag >> 2);
+            if l > SECLUDED || r > SECLUDED {
+                return Err(DecodeError::InvalidTag(tag));
+            }
+            members.push((reader.border(l)?, reader.border(r)?));
+        }
+
+        if reader.remaining() != 0 {
+            return Err(DecodeError::TrailingBytes(reader.remaining()));
+        }
+
+        Ok(util::from_members(members))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::error::DecodeError;
+    use crate::{Domain, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            Just(Value::Infinite),
+            any::<i32>().prop_map(Value::Included),
+            any::<i32>().prop_map(Value::Secluded),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..8).prop_map(|domain| domain.normalize().into_inner())
+    }
+
+    #[test]
+    fn test_layout() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Infinite, Value::Included(-1)),
+            Domain::Domain(Value::Secluded(64), Value::Infinite),
+        ]);
+        assert_eq!(domain.to_bytes(), vec![1, 2, 0b0100, 1, 0b0010, 128, 1]);
+        assert_eq!(Domain::None.to_bytes(), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases = vec![
+            (vec![], DecodeError::UnexpectedEnd),
+            (vec![2, 0], DecodeError::UnsupportedVersion(2)),
+            (vec![1, 1, 0b1100], DecodeError::InvalidTag(0b1100)),
+            (vec![1, 1, 0b0001], DecodeError::UnexpectedEnd),
+            (vec![1, 0, 0], DecodeError::TrailingBytes(1)),
+            (vec![1, 1, 1, 0xff, 0xff, 0xff, 0xff, 0x7f], DecodeError::VarintOverflow),
+            (vec![1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01], DecodeError::UnexpectedEnd),
+            (vec![1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f], DecodeError::VarintOverflow),
+        ];
+
+        for (bytes, error) in cases {
+            assert_eq!(Domain::from_bytes(&bytes).unwrap_err(), error, "{:?}", bytes);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_roundtrip(domain in domain()) {
+            let bytes = domain.to_bytes();
+            let decoded = Domain::from_bytes(&bytes).unwrap();
+            prop_assert_eq!(decoded.clone().repr(), domain.clone().repr());
+            prop_assert_eq!(decoded, domain);
+        }
+
+        #[test]
+        fn test_arbitrary_bytes(bytes in prop::collection::vec(any::<u8>(), 0..32)) {
+            let _ = Domain::from_bytes(&bytes);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::error::TooLarge;
+use crate::Domain;
+
+impl Domain<i32> {
+
+    /// Admitted values in ascending order, each once and paired with its
+    /// `label`, for offering the domain as a list of choices. The values
+    /// are those of `iter`, overlapping members give a value once. Domains
+    /// admitting more than `cap` values, unbounded ones included, are
+    /// refused before `label` is called
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let levels = Domain::Union(vec![Domain::between(3, 4), Domain::between(0, 1), Domain::between(1, 1)]);
+    /// let choices = levels.enumerate_labeled(|v| format!("Level {}", v), 10).unwrap();
+    /// assert_eq!(choices, vec![
+    ///     (0, "Level 0".to_string()),
+    ///     (1, "Level 1".to_string()),
+    ///     (3, "Level 3".to_string()),
+    ///     (4, "Level 4".to_string()),
+    /// ]);
+    /// let error = levels.enumerate_labeled(|v| v.to_string(), 3).unwrap_err();
+    /// assert_eq!((error.cap(), error.cardinality()), (3, Some(4)));
+    /// assert_eq!(Domain::at_least(0).enumerate_labeled(|v| v.to_string(), 3).unwrap_err().cardinality(), None);
+    /// ```
+    pub fn enumerate_labeled(&self, label: impl Fn(i32) -> String, cap: usize) -> Result<Vec<(i32, String)>, TooLarge> {
+        match self.cardinality() {
+            Some(count) if count <= cap as u64 => {
+                let mut choices = Vec::with_capacity(count as usize);
+                choices.extend(self.iter().map(|value| (value, label(value))));
+                Ok(choices)
+            },
+            cardinality => Err(TooLarge::new(cap, cardinality)),
+        }
+    }
+
+    /// Whether `enumerate_labeled` accepts the domain under `cap`: it is
+    /// bounded and admits at most `cap` values
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert!(Domain::between(1, 5).is_enumerable(5));
+    /// assert!(!Domain::between(1, 5).is_enumerable(4));
+    /// assert!(Domain::None.is_enumerable(0));
+    /// assert!(!Domain::at_most(0).is_enumerable(usize::MAX));
+    /// ```
+    pub fn is_enumerable(&self, cap: usize) -> bool {
+        self.cardinality().is_some_and(|count| count <= cap as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::{border, members};
+    use crate::{Domain, Value};
+
+    proptest! {
+        #[test]
+        fn test_choices_follow_iter(members in members(border(30), 0..5), cap in 0..80usize) {
+            let domain = Domain::Union(members);
+            let choices = domain.enumerate_labeled(|v| format!("#{}", v), cap);
+            prop_assert_eq!(choices.is_ok(), domain.is_enumerable(cap));
+            let choices = match choices {
+                Ok(choices) => choices,
+                Err(error) => {
+                    prop_assert_eq!((error.cap(), error.cardinality()), (cap, domain.cardinality()));
+                    return Ok(());
+                },
+            };
+
+            let values: Vec<i32> = choices.iter().map(|(value, _)| *value).collect();
+            prop_assert_eq!(&values, &domain.iter().collect::<Vec<i32>>());
+            for (value, label) in &choices {
+                prop_assert_eq!(label, &format!("#{}", value));
+            }
+            // Ascending without repeats, and a skip only over values not admitted
+            for pair in values.windows(2) {
+                prop_assert!(pair[0] < pair[1]);
+                prop_assert!((pair[0] as i64 + 1..pair[1] as i64).all(|gap| !domain.contains(gap as i32)));
+            }
+        }
+
+        #[test]
+        fn test_choices_stable(members in members(border(30), 0..5).prop_shuffle(), split in 0..5usize) {
+            // The same values whatever the order and grouping of the members
+            let domain = Domain::Union(members.clone());
+            let (front, back) = members.split_at(split.min(members.len()));
+            let regrouped = Domain::Union(vec![Domain::Union(back.to_vec()), Domain::Union(front.to_vec())]);
+            let label = |v: i32| v.to_string();
+            prop_assert_eq!(domain.enumerate_labeled(label, 64), regrouped.enumerate_labeled(label, 64));
+            prop_assert_eq!(domain.enumerate_labeled(label, 64), domain.normalize().into_inner().enumerate_labeled(label, 64));
+        }
+    }
+
+    #[test]
+    fn test_enumerate_limits() {
+        // An infinite border is unbounded even where the i32 range ends
+        assert_eq!(Domain::at_least(i32::MAX - 1).enumerate_labeled(|_| unreachable!(), 2).unwrap_err().cardinality(), None);
+        let top = Domain::between(i32::MAX - 1, i32::MAX);
+        assert_eq!(top.enumerate_labeled(|v| v.to_string(), 2).unwrap(), vec![
+            (i32::MAX - 1, "2147483646".to_string()),
+            (i32::MAX, "2147483647".to_string()),
+        ]);
+        let bottom = Domain::Domain(Value::Included(i32::MIN), Value::Secluded(i32::MIN + 1));
+        assert_eq!(bottom.enumerate_labeled(|_| "min".to_string(), 1).unwrap(), vec![(i32::MIN, "min".to_string())]);
+
+        assert_eq!(Domain::None.enumerate_labeled(|_| unreachable!(), 0), Ok(Vec::new()));
+        assert_eq!(Domain::between(5, 1).enumerate_labeled(|_| unreachable!(), 0), Ok(Vec::new()));
+        let error = Domain::new().enumerate_labeled(|_| unreachable!(), usize::MAX).unwrap_err();
+        assert_eq!((error.cap(), error.cardinality()), (usize::MAX, None));
+        let error = Domain::between(i32::MIN, i32::MAX).enumerate_labeled(|_| unreachable!(), 10).unwrap_err();
+        assert_eq!(error.cardinality(), Some(1 << 32));
+        assert!(!Domain::between(i32::MIN, i32::MAX).is_enumerable(u32::MAX as usize));
+    }
+}
@@ -0,0 +1,145 @@
+//! Domains over epoch seconds written as RFC 3339 timestamps, enabled by
+//! the `time` feature. Points are UNIX seconds, which have no leap
+//! seconds: a `23:59:60` leap second reads as `23:59:59` of the same day,
+//! nothing is smeared. The rest of the crate knows nothing of dates
+
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::{Domain, DomainError, EpochError, Value};
+
+/// UNIX seconds of an RFC 3339 timestamp in any offset, a fraction of a
+/// second dropped
+fn parse(timestamp: &str) -> Result<i64, EpochError> {
+    OffsetDateTime::parse(timestamp, &Rfc3339)
+        .map(OffsetDateTime::unix_timestamp)
+        .map_err(|error| EpochError::new(timestamp, error))
+}
+
+/// RFC 3339 timestamp in UTC of `seconds`, `None` past the years RFC 3339
+/// can write
+fn format(seconds: i64) -> Option<String> {
+    OffsetDateTime::from_unix_timestamp(seconds).ok()?.format(&Rfc3339).ok()
+}
+
+fn shift_border(value: &Value<i64>, secs: i64) -> Option<Value<i64>> {
+    Some(match value {
+        Value::Included(i) => Value::Included(i.checked_add(secs)?),
+        Value::Secluded(i) => Value::Secluded(i.checked_add(secs)?),
+        Value::Infinite => Value::Infinite,
+    })
+}
+
+fn shift(domain: &Domain<i64>, secs: i64) -> Option<Domain<i64>> {
+    Some(match domain {
+        Domain::Union(domains) => Domain::Union(domains.iter().map(|domain| shift(domain, secs)).collect::<Option<_>>()?),
+        Domain::Domain(l, r) => Domain::Domain(shift_border(l, secs)?, shift_border(r, secs)?),
+        Domain::None => Domain::None,
+    })
+}
+
+impl Domain<i64> {
+
+    /// Closed domain of the epoch seconds from `start` to `end`, both
+    /// RFC 3339 timestamps, like `Domain::between`. Offsets are applied and
+    /// fractions of a second dropped
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let january = Domain::epoch_between("2024-01-01T00:00:00Z", "2024-01-31T23:59:59Z").unwrap();
+    /// assert!(january.contains(1_704_067_200));
+    /// assert_eq!(january.repr_epoch(), "[2024-01-01T00:00:00Z;2024-01-31T23:59:59Z]");
+    /// assert!(Domain::epoch_between("2024-01-01", "2024-02-01T00:00:00Z").is_err());
+    /// ```
+    pub fn epoch_between(start: &str, end: &str) -> Result<Domain<i64>, EpochError> {
+        Ok(Domain::Domain(Value::Included(parse(start)?), Value::Included(parse(end)?)))
+    }
+
+    /// `repr` with every point as an RFC 3339 timestamp in UTC. Points
+    /// past the year 9999 or before the year 0 stay raw integers
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Secluded(0), Value::Infinite);
+    /// assert_eq!(domain.repr_epoch(), "(1970-01-01T00:00:00Z;∞)");
+    /// assert_eq!(Domain::Domain(Value::Included(i64::MAX), Value::Infinite).repr_epoch(), "[9223372036854775807;∞)");
+    /// ```
+    pub fn repr_epoch(&self) -> String {
+        self.repr_with_fmt(|seconds| format(*seconds).unwrap_or_else(|| seconds.to_string()))
+    }
+
+    /// Same domain moved by `secs` seconds, `DomainError::Overflow` when a
+    /// border leaves the `i64` range
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let day = Domain::epoch_between("2024-03-30T00:00:00Z", "2024-03-30T23:59:59Z").unwrap();
+    /// let next = day.shift_duration(86_400).unwrap();
+    /// assert_eq!(next.repr_epoch(), "[2024-03-31T00:00:00Z;2024-03-31T23:59:59Z]");
+    /// assert!(next.shift_duration(i64::MAX).is_err());
+    /// ```
+    pub fn shift_duration(&self, secs: i64) -> Result<Domain<i64>, DomainError> {
+        shift(self, secs).ok_or_else(|| DomainError::Overflow {
+            operation: "shift_duration",
+            operands: format!("{} and {}", self.repr_with_fmt(i64::to_string), secs),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Domain, DomainError, Value};
+
+    /// 2016-12-31T23:59:59Z, the last second before the leap second
+    /// inserted at the end of 2016
+    const BEFORE_LEAP: i64 = 1_483_228_799;
+
+    /// `repr` with the raw seconds, `Domain<i64>` has no set equality
+    fn raw(domain: &Domain<i64>) -> String {
+        domain.repr_with_fmt(i64::to_string)
+    }
+
+    #[test]
+    fn test_leap_second_boundaries() {
+        let last = Domain::epoch_between("2016-12-31T23:59:59Z", "2017-01-01T00:00:00Z").unwrap();
+        assert_eq!(raw(&last), "[1483228799;1483228800]");
+        assert_eq!(last.repr_epoch(), "[2016-12-31T23:59:59Z;2017-01-01T00:00:00Z]");
+
+        // The leap second itself is the second before it, not smeared
+        let leap = Domain::epoch_between("2016-12-31T23:59:60Z", "2016-12-31T23:59:60Z").unwrap();
+        assert_eq!(raw(&leap), "[1483228799;1483228799]");
+        assert_eq!(leap.repr_epoch(), "[2016-12-31T23:59:59Z;2016-12-31T23:59:59Z]");
+
+        // Fractions are dropped and offsets applied
+        let fraction = Domain::epoch_between("2016-12-31T23:59:59.999Z", "2017-01-01T01:00:00+01:00").unwrap();
+        assert_eq!(raw(&fraction), raw(&last));
+        let west = Domain::epoch_between("2016-12-31T18:59:59-05:00", "2016-12-31T19:00:00-05:00").unwrap();
+        assert_eq!(west.repr_epoch(), last.repr_epoch());
+    }
+
+    #[test]
+    fn test_invalid_timestamps() {
+        for input in ["", "2017-01-01T00:00:00", "2017-02-30T00:00:00Z", "2017-01-01T24:00:00Z", "1483228800"].iter() {
+            let error = Domain::epoch_between(input, "2017-01-01T00:00:00Z").unwrap_err();
+            assert_eq!(error.input(), *input);
+            assert!(error.to_string().starts_with("invalid RFC 3339 timestamp"), "{}", error);
+        }
+        // A leap second anywhere but the end of a UTC day
+        assert!(Domain::epoch_between("2016-12-31T12:59:60Z", "2017-01-01T00:00:00Z").is_err());
+    }
+
+    #[test]
+    fn test_shift_duration() {
+        let union = Domain::Union(vec![
+            Domain::Domain(Value::Infinite, Value::Secluded(0)),
+            Domain::Domain(Value::Included(BEFORE_LEAP), Value::Included(BEFORE_LEAP)),
+        ]);
+        let shifted = union.shift_duration(1).unwrap();
+        assert_eq!(shifted.repr_epoch(), "(-∞;1970-01-01T00:00:01Z)⋃[2017-01-01T00:00:00Z;2017-01-01T00:00:00Z]");
+        assert_eq!(shifted.shift_duration(-1).unwrap().repr_epoch(), union.repr_epoch());
+        assert_eq!(Domain::None.shift_duration(i64::MAX).unwrap().repr_epoch(), "∅");
+
+        let edge = Domain::Domain(Value::Included(i64::MIN), Value::Infinite);
+        assert_eq!(
+            edge.shift_duration(-1).unwrap_err(),
+            DomainError::Overflow { operation: "shift_duration", operands: "[-9223372036854775808;∞) and -1".to_string() }
+        );
+    }
+}
@@ -0,0 +1,603 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Domain, IterOrder};
+
+/// Error returned when a textual domain description can not be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    position: usize,
+    kind: ParseErrorKind,
+}
+
+/// What exactly went wrong while parsing
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// Character which does not start any token
+    UnexpectedChar(char),
+    /// Token which does not fit the grammar at its position
+    UnexpectedToken,
+    /// Input ended while more was expected
+    UnexpectedEnd,
+    /// Integer literal does not fit into `i32`
+    IntegerOverflow,
+    /// Infinite border on the wrong side or enclosed by a closed bracket
+    InvalidInfinity,
+    /// Comparison references a variable other than the expected one
+    UnknownVariable(String),
+    /// Comparison does not reference the variable on exactly one side
+    InvalidComparison,
+    /// Character class range whose start is above its end
+    InvalidRange,
+    /// Unknown backslash escape or `\u{...}` not naming a scalar value
+    InvalidEscape,
+    /// Parentheses nested deeper than the parser allows
+    NestingTooDeep,
+    /// Negative slice index without a length to count it from the end
+    NegativeIndex,
+    /// Negative length given to resolve slice indices against
+    InvalidLength,
+    /// Slice step other than 1, there is no strided domain to map it onto
+    UnsupportedStep,
+    /// Point which its type does not read back, the text as written
+    InvalidPoint(String),
+}
+
+impl ParseError {
+    pub(crate) fn new(position: usize, kind: ParseErrorKind) -> Self {
+        ParseError { position, kind }
+    }
+
+    /// Byte offset in the input where the error was found
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {:?}", c),
+            ParseErrorKind::UnexpectedToken => write!(f, "unexpected token"),
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseErrorKind::IntegerOverflow => write!(f, "integer does not fit into i32"),
+            ParseErrorKind::InvalidInfinity => {
+                write!(f, "infinite border must be open and on its own side")
+            },
+            ParseErrorKind::UnknownVariable(name) => write!(f, "unknown variable `{}`", name),
+            ParseErrorKind::InvalidComparison => {
+                write!(f, "comparison must reference the variable on exactly one side")
+            },
+            ParseErrorKind::InvalidRange => write!(f, "range start is above its end"),
+            ParseErrorKind::InvalidEscape => write!(f, "invalid escape sequence"),
+            ParseErrorKind::NestingTooDeep => write!(f, "parentheses nested too deeply"),
+            ParseErrorKind::NegativeIndex => write!(f, "negative index needs a length to count from"),
+            ParseErrorKind::InvalidLength => write!(f, "length is negative"),
+            ParseErrorKind::UnsupportedStep => write!(f, "slice step must be 1"),
+            ParseErrorKind::InvalidPoint(text) => write!(f, "invalid point {:?}", text),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Error returned by `Domain::from_bytes`
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// Input ended in the middle of the encoding
+    UnexpectedEnd,
+    /// Leading version byte is not known to this release
+    UnsupportedVersion(u8),
+    /// Interval tag byte holds an unknown border kind
+    InvalidTag(u8),
+    /// Varint does not fit into its target type
+    VarintOverflow,
+    /// Bytes left after the last interval
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            DecodeError::UnsupportedVersion(v) => write!(f, "unsupported format version {}", v),
+            DecodeError::InvalidTag(tag) => write!(f, "invalid interval tag {:#04x}", tag),
+            DecodeError::VarintOverflow => write!(f, "varint overflows its type"),
+            DecodeError::TrailingBytes(n) => write!(f, "{} trailing bytes", n),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// Error returned by the SQL predicate builders when the column
+/// is not a plain, optionally dotted, identifier
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdentifierError {
+    identifier: String,
+}
+
+impl IdentifierError {
+    pub(crate) fn new(identifier: &str) -> Self {
+        IdentifierError { identifier: identifier.to_string() }
+    }
+
+    /// The rejected identifier
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+}
+
+impl fmt::Display for IdentifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid SQL identifier {:?}", self.identifier)
+    }
+}
+
+impl Error for IdentifierError {}
+
+/// Error returned by `Domain::codegen_values`
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CodegenError {
+    /// The constant name is not an identifier of the target language,
+    /// or is one of its keywords
+    InvalidName(String),
+    /// The domain has an infinite border
+    Unbounded,
+    /// The domain admits more values than the given limit
+    TooLarge(u64),
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodegenError::InvalidName(name) => write!(f, "invalid constant name {:?}", name),
+            CodegenError::Unbounded => write!(f, "domain is unbounded"),
+            CodegenError::TooLarge(limit) => write!(f, "domain has more than {} values", limit),
+        }
+    }
+}
+
+impl Error for CodegenError {}
+
+/// Error returned by aggregates which have to visit every admitted value
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AggregateError {
+    /// The domain admits no value
+    Empty,
+    /// The domain has an infinite border
+    Unbounded,
+    /// The domain admits more values than the given cap
+    TooLarge(u64),
+    /// Histogram asked for no buckets at all
+    ZeroBuckets,
+    /// Histogram bucket count does not fit into `i64`
+    TooManyBuckets,
+}
+
+impl fmt::Display for AggregateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregateError::Empty => write!(f, "domain is empty"),
+            AggregateError::Unbounded => write!(f, "domain is unbounded"),
+            AggregateError::TooLarge(cap) => write!(f, "domain has more than {} values", cap),
+            AggregateError::ZeroBuckets => write!(f, "histogram needs at least one bucket"),
+            AggregateError::TooManyBuckets => write!(f, "too many histogram buckets"),
+        }
+    }
+}
+
+impl Error for AggregateError {}
+
+/// Error returned once a capped enumeration would go past its cap
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapExceeded {
+    emitted: u64,
+    next: i32,
+}
+
+impl CapExceeded {
+    pub(crate) fn new(emitted: u64, next: i32) -> Self {
+        CapExceeded { emitted, next }
+    }
+
+    /// Number of values emitted before stopping, the cap itself
+    pub fn emitted(&self) -> u64 {
+        self.emitted
+    }
+
+    /// Value which would have been emitted next
+    pub fn next(&self) -> i32 {
+        self.next
+    }
+}
+
+impl fmt::Display for CapExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stopped after {} values before emitting {}", self.emitted, self.next)
+    }
+}
+
+impl Error for CapExceeded {}
+
+/// Error returned by `Domain::intersect_limited` once the result would
+/// have more members than allowed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TooComplex {
+    max_output_members: usize,
+}
+
+impl TooComplex {
+    pub(crate) fn new(max_output_members: usize) -> Self {
+        TooComplex { max_output_members }
+    }
+
+    /// The limit which was exceeded
+    pub fn max_output_members(&self) -> usize {
+        self.max_output_members
+    }
+}
+
+impl fmt::Display for TooComplex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "result has more than {} members", self.max_output_members)
+    }
+}
+
+impl Error for TooComplex {}
+
+/// Error returned by `Domain::enumerate_labeled` for a domain admitting
+/// more values than its cap
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TooLarge {
+    cap: usize,
+    cardinality: Option<u64>,
+}
+
+impl TooLarge {
+    pub(crate) fn new(cap: usize, cardinality: Option<u64>) -> Self {
+        TooLarge { cap, cardinality }
+    }
+
+    /// The cap which was exceeded
+    pub fn cap(&self) -> usize {
+        self.cap
+    }
+
+    /// Number of admitted values, `None` for an unbounded domain
+    pub fn cardinality(&self) -> Option<u64> {
+        self.cardinality
+    }
+}
+
+impl fmt::Display for TooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.cardinality {
+            Some(cardinality) => write!(f, "domain has {} values, more than {}", cardinality, self.cap),
+            None => write!(f, "domain is unbounded, it has more than {} values", self.cap),
+        }
+    }
+}
+
+impl Error for TooLarge {}
+
+/// Error returned by `Domain::iter_ordered` for an order starting
+/// from an infinite border of the domain
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderError {
+    order: IterOrder,
+}
+
+impl OrderError {
+    pub(crate) fn new(order: IterOrder) -> Self {
+        OrderError { order }
+    }
+
+    /// Order which was refused
+    pub fn order(&self) -> IterOrder {
+        self.order
+    }
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let border = match self.order {
+            IterOrder::Ascending => "starting border",
+            IterOrder::Descending => "ending border",
+            _ => "pair of borders",
+        };
+        write!(f, "{:?} order has no first value without a finite {}", self.order, border)
+    }
+}
+
+impl Error for OrderError {}
+
+/// Error returned by `Domain::narrow` under `NarrowPolicy::Error` for a
+/// finite border the narrower type can not hold
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NarrowError {
+    border: String,
+    target: &'static str,
+}
+
+impl NarrowError {
+    pub(crate) fn new(border: String, target: &'static str) -> Self {
+        NarrowError { border, target }
+    }
+
+    /// The point of the border which does not fit, as written by `Display`
+    pub fn border(&self) -> &str {
+        &self.border
+    }
+
+    /// Name of the narrower type, like `"i32"`
+    pub fn target(&self) -> &'static str {
+        self.target
+    }
+}
+
+impl fmt::Display for NarrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "border {} does not fit into {}", self.border, self.target)
+    }
+}
+
+impl Error for NarrowError {}
+
+/// Error returned by `Domain::interval` and `Domain::spanning_values`
+/// for borders which can not form an interval
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DomainError {
+    /// The starting point is past the ending point
+    Reversed,
+    /// Both borders sit on the same point and one of them is secluded
+    Empty,
+    /// An infinite border next to a finite one, either could come first
+    Ambiguous,
+    /// A point is not comparable even with itself, like `f64::NAN`
+    NotComparable,
+    /// A float infinity given as a point, `Value::Infinite` stands for it
+    InfinitePoint,
+    /// An arithmetic operation moved a border past the `i32` range
+    /// under `OverflowPolicy::Error`
+    Overflow {
+        /// Name of the method, like `"shift"`
+        operation: &'static str,
+        /// The domain and the other operand, as written by `repr`
+        operands: String,
+    },
+    /// A divisor, scale or modulus which must be positive is 0 or negative
+    NotPositive,
+    /// A wider integer given as a bound does not fit in `i32`
+    OutOfRange(i64),
+    /// An absent, infinite, bound marked as admitted
+    InclusiveInfinity,
+    /// An inversion list with a boundary left without its pair, of the
+    /// given length
+    OddLength(usize),
+    /// A boundary of an inversion list, at the given index, not past the
+    /// one before it
+    Unsorted(usize),
+}
+
+impl DomainError {
+    pub(crate) fn overflow(operation: &'static str, domain: &Domain<i32>, operand: impl fmt::Display) -> Self {
+        DomainError::Overflow { operation, operands: format!("{} and {}", domain.clone().repr(), operand) }
+    }
+}
+
+impl fmt::Display for DomainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DomainError::Reversed => write!(f, "starting border is past the ending border"),
+            DomainError::Empty => write!(f, "interval admits no value"),
+            DomainError::Ambiguous => write!(f, "infinite border could lie on either side of the other one"),
+            DomainError::NotComparable => write!(f, "point is not comparable with itself"),
+            DomainError::InfinitePoint => write!(f, "infinite point, use an infinite border instead"),
+            DomainError::Overflow { operation, operands } => {
+                write!(f, "{} of {} overflows i32", operation, operands)
+            },
+            DomainError::NotPositive => write!(f, "divisor or modulus is not positive"),
+            DomainError::OutOfRange(value) => write!(f, "bound {} is out of i32", value),
+            DomainError::InclusiveInfinity => write!(f, "infinite bound can not be inclusive"),
+            DomainError::OddLength(len) => write!(f, "inversion list of odd length {}", len),
+            DomainError::Unsorted(index) => write!(f, "inversion list boundary {} is not past the one before it", index),
+        }
+    }
+}
+
+impl Error for DomainError {}
+
+/// Error returned by `Domain::epoch_between` for a timestamp which is not
+/// RFC 3339
+#[cfg(feature = "time")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpochError {
+    input: String,
+    reason: String,
+}
+
+#[cfg(feature = "time")]
+impl EpochError {
+    pub(crate) fn new(input: &str, reason: impl fmt::Display) -> Self {
+        EpochError { input: input.to_string(), reason: reason.to_string() }
+    }
+
+    /// The rejected timestamp
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+#[cfg(feature = "time")]
+impl fmt::Display for EpochError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid RFC 3339 timestamp {:?}: {}", self.input, self.reason)
+    }
+}
+
+#[cfg(feature = "time")]
+impl Error for EpochError {}
+
+/// Which way a value rejected by `Domain::validate_value` misses the domain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Violation {
+    /// Smaller than every admitted value
+    BelowMin,
+    /// Greater than every admitted value
+    AboveMax,
+    /// Between two members of the domain
+    InGap,
+    /// The domain admits no value at all
+    Empty,
+}
+
+/// Error returned by `Domain::validate_value` for a value
+/// the domain does not admit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutOfDomain {
+    value: i32,
+    domain: String,
+    nearest: Option<i32>,
+    violation: Violation,
+}
+
+impl OutOfDomain {
+    pub(crate) fn new(value: i32, domain: String, nearest: Option<i32>, violation: Violation) -> Self {
+        OutOfDomain { value, domain, nearest, violation }
+    }
+
+    /// The rejected value
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// Representation of the normalized domain
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Admitted value closest to the rejected one, `None` for an empty domain
+    pub fn nearest(&self) -> Option<i32> {
+        self.nearest
+    }
+
+    /// Which way the value misses the domain
+    pub fn violation(&self) -> Violation {
+        self.violation
+    }
+}
+
+impl fmt::Display for OutOfDomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not allowed: must be in {}", self.value, self.domain)?;
+        match self.nearest {
+            Some(nearest) => write!(f, ", nearest allowed value is {}", nearest),
+            None => write!(f, ", which admits no value"),
+        }
+    }
+}
+
+impl Error for OutOfDomain {}
+
+/// Error returned by `DomainMap::insert` for a key sharing values with
+/// the keys already mapped
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyOverlap {
+    overlap: Domain<i32>,
+}
+
+impl KeyOverlap {
+    pub(crate) fn new(overlap: Domain<i32>) -> Self {
+        KeyOverlap { overlap }
+    }
+
+    /// Values of the key which are already mapped
+    pub fn overlap(&self) -> &Domain<i32> {
+        &self.overlap
+    }
+}
+
+impl fmt::Display for KeyOverlap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key overlaps the mapped keys in {}", self.overlap)
+    }
+}
+
+impl Error for KeyOverlap {}
+
+/// Error returned by `DomainBuilder::build` once a constraint leaves no value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildConflict {
+    index: usize,
+    label: Option<String>,
+    before: Domain<i32>,
+    conflicting: Vec<(usize, Option<String>)>,
+}
+
+impl BuildConflict {
+    pub(crate) fn new(
+        index: usize,
+        label: Option<String>,
+        before: Domain<i32>,
+        conflicting: Vec<(usize, Option<String>)>,
+    ) -> Self {
+        BuildConflict { index, label, before, conflicting }
+    }
+
+    /// Position of the first constraint leaving no value
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Label of that constraint, if it has one
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Domain left by the constraints before it
+    pub fn before(&self) -> &Domain<i32> {
+        &self.before
+    }
+
+    /// Positions and labels of a minimal set of constraints leaving
+    /// no value together, in recording order
+    pub fn conflicting(&self) -> &[(usize, Option<String>)] {
+        &self.conflicting
+    }
+}
+
+impl fmt::Display for BuildConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |index: usize, label: &Option<String>| match label {
+            Some(label) => format!("{} ({})", index, label),
+            None => index.to_string(),
+        };
+        let conflicting: Vec<String> = self.conflicting.iter().map(|(i, label)| name(*i, label)).collect();
+        write!(
+            f,
+            "constraint {} leaves no value in {}, conflicting constraints: {}",
+            name(self.index, &self.label),
+            self.before.clone().repr(),
+            conflicting.join(", ")
+        )
+    }
+}
+
+impl Error for BuildConflict {}
@@ -0,0 +1,347 @@
+use crate::error::{ParseError, ParseErrorKind};
+use crate::{Domain, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Cmp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Cmp {
+    /// Same comparison with the operands swapped: `5 < x` is `x > 5`
+    fn flip(self) -> Self {
+        match self {
+            Cmp::Gt => Cmp::Lt,
+            Cmp::Ge => Cmp::Le,
+            Cmp::Lt => Cmp::Gt,
+            Cmp::Le => Cmp::Ge,
+            other => other,
+        }
+    }
+
+    /// Domain of the variable satisfying `var <op> n`
+    fn domain(self, n: i32) -> Domain<i32> {
+        match self {
+            Cmp::Gt => Domain::Domain(Value::Secluded(n), Value::Infinite),
+            Cmp::Ge => Domain::Domain(Value::Included(n), Value::Infinite),
+            Cmp::Lt => Domain::Domain(Value::Infinite, Value::Secluded(n)),
+            Cmp::Le => Domain::Domain(Value::Infinite, Value::Included(n)),
+            Cmp::Eq => Domain::Domain(Value::Included(n), Value::Included(n)),
+            Cmp::Ne => Domain::new().remove_value(n).into_inner(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i32),
+    Cmp(Cmp),
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+fn tokenize(expr: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let bytes = expr.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let c = bytes[i];
+        let next = bytes.get(i + 1).copied();
+
+        let token = match (c, next) {
+            (b' ', _) | (b'\t', _) | (b'\n', _) | (b'\r', _) => {
+                i += 1;
+                continue;
+            },
+            (b'&', Some(b'&')) => Token::And,
+            (b'|', Some(b'|')) => Token::Or,
+            (b'>', Some(b'=')) => Token::Cmp(Cmp::Ge),
+            (b'<', Some(b'=')) => Token::Cmp(Cmp::Le),
+            (b'=', Some(b'=')) => Token::Cmp(Cmp::Eq),
+            (b'!', Some(b'=')) => Token::Cmp(Cmp::Ne),
+            (b'>', _) => Token::Cmp(Cmp::Gt),
+            (b'<', _) => Token::Cmp(Cmp::Lt),
+            (b'(', _) => Token::Open,
+            (b')', _) => Token::Close,
+            (b'-', Some(d)) | (d, _) if d.is_ascii_digit() => {
+                let negative = c == b'-';
+                if negative {
+                    i += 1;
+                }
+
+                let mut n: i64 = 0;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    n = n * 10 + (bytes[i] - b'0') as i64;
+                    if n > i32::MAX as i64 + 1 {
+                        return Err(ParseError::new(start, ParseErrorKind::IntegerOverflow));
+                    }
+                    i += 1;
+                }
+
+                let n = if negative { -n } else { n };
+                if n > i32::MAX as i64 {
+                    return Err(ParseError::new(start, ParseErrorKind::IntegerOverflow));
+                }
+
+                tokens.push((start, Token::Int(n as i32)));
+                continue;
+            },
+            (c, _) if c.is_ascii_alphabetic() || c == b'_' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                tokens.push((start, Token::Ident(expr[start..i].to_string())));
+                continue;
+            },
+            _ => {
+                let c = expr[start..].chars().next().unwrap_or_default();
+                return Err(ParseError::new(start, ParseErrorKind::UnexpectedChar(c)));
+            },
+        };
+
+        i += match token {
+            Token::Cmp(Cmp::Gt) | Token::Cmp(Cmp::Lt) | Token::Open | Token::Close => 1,
+            _ => 2,
+        };
+        tokens.push((start, token));
+    }
+
+    Ok(tokens)
+}
+
+/// Parentheses nested deeper than this are rejected instead
+/// of risking a stack overflow on hostile input
+const MAX_DEPTH: usize = 256;
+
+/// Recursive descent over the grammar:
+/// ```text
+/// or         := and ( "||" and )*
+/// and        := atom ( "&&" atom )*
+/// atom       := "(" or ")" | comparison
+/// comparison := operand cmp operand
+/// operand    := identifier | integer
+/// ```
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    index: usize,
+    end: usize,
+    depth: usize,
+    var: &'a str,
+}
+
+enum Operand {
+    Var,
+    Int(i32),
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.index)
+            .map(|(position, _)| *position)
+            .unwrap_or(self.end)
+    }
+
+    fn error(&self) -> ParseError {
+        match self.peek() {
+            Some(_) => ParseError::new(self.position(), ParseErrorKind::UnexpectedToken),
+            None => ParseError::new(self.end, ParseErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn or(&mut self) -> Result<Domain<i32>, ParseError> {
+        let mut domain = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.index += 1;
+            domain = domain.union(&self.and()?).into_inner();
+        }
+        Ok(domain)
+    }
+
+    fn and(&mut self) -> Result<Domain<i32>, ParseError> {
+        let mut domain = self.atom()?;
+        while self.peek() == Some(&Token::And) {
+            self.index += 1;
+            domain = domain.intersect(&self.atom()?).into_inner();
+        }
+        Ok(domain)
+    }
+
+    fn atom(&mut self) -> Result<Domain<i32>, ParseError> {
+        if self.peek() == Some(&Token::Open) {
+            if self.depth == MAX_DEPTH {
+                return Err(ParseError::new(self.position(), ParseErrorKind::NestingTooDeep));
+            }
+            self.index += 1;
+            self.depth += 1;
+            let domain = self.or()?;
+            self.depth -= 1;
+            if self.peek() != Some(&Token::Close) {
+                return Err(self.error());
+            }
+            self.index += 1;
+            return Ok(domain);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Domain<i32>, ParseError> {
+        let start = self.position();
+        let left = self.operand()?;
+
+        let cmp = match self.peek() {
+            Some(Token::Cmp(cmp)) => *cmp,
+            _ => return Err(self.error()),
+        };
+        self.index += 1;
+
+        let right = self.operand()?;
+
+        match (left, right) {
+            (Operand::Var, Operand::Int(n)) => Ok(cmp.domain(n)),
+            (Operand::Int(n), Operand::Var) => Ok(cmp.flip().domain(n)),
+            _ => Err(ParseError::new(start, ParseErrorKind::InvalidComparison)),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, ParseError> {
+        let operand = match self.peek() {
+            Some(Token::Int(n)) => Operand::Int(*n),
+            Some(Token::Ident(name)) if name == self.var => Operand::Var,
+            Some(Token::Ident(name)) => {
+                return Err(ParseError::new(
+                    self.position(),
+                    ParseErrorKind::UnknownVariable(name.clone())
+                ));
+            },
+            _ => return Err(self.error()),
+        };
+        self.index += 1;
+        Ok(operand)
+    }
+}
+
+impl Domain<i32> {
+
+    /// Parses a comparison expression over the variable `var`.
+    /// Supported are `>`, `>=`, `<`, `<=`, `==`, `!=`, the
+    /// connectives `&&` (intersection) and `||` (union) and parentheses.
+    /// `&&` binds tighter than `||`, parentheses nest at most 256 deep
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::from_expr("x > 5 && x <= 100 || x == 250", "x").unwrap();
+    /// assert_eq!(domain.repr(), "(5;100]⋃[250;250]");
+    /// ```
+    pub fn from_expr(expr: &str, var: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser {
+            tokens: tokenize(expr)?,
+            index: 0,
+            end: expr.len(),
+            depth: 0,
+            var,
+        };
+
+        let domain = parser.or()?;
+        if parser.peek().is_some() {
+            return Err(parser.error());
+        }
+
+        Ok(domain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ParseErrorKind;
+    use crate::{Domain, Value};
+
+    #[test]
+    fn test_comparisons() {
+        let cases = vec![
+            ("x > 5", Domain::Domain(Value::Secluded(5), Value::Infinite)),
+            ("x >= 5", Domain::Domain(Value::Included(5), Value::Infinite)),
+            ("x < -5", Domain::Domain(Value::Infinite, Value::Secluded(-5))),
+            ("-5 >= x", Domain::Domain(Value::Infinite, Value::Included(-5))),
+            ("x == 7", Domain::Domain(Value::Included(7), Value::Included(7))),
+            ("x != 7", Domain::new().remove_value(7).into_inner()),
+        ];
+
+        for (expr, expected) in cases {
+            assert_eq!(Domain::from_expr(expr, "x").unwrap(), expected, "{}", expr);
+        }
+    }
+
+    #[test]
+    fn test_precedence() {
+        let domain = Domain::from_expr("x > 5 && x <= 100 || x == 250", "x").unwrap();
+        let expected = Domain::Union(vec![
+            Domain::Domain(Value::Secluded(5), Value::Included(100)),
+            Domain::Domain(Value::Included(250), Value::Included(250)),
+        ]);
+        assert_eq!(domain, expected);
+
+        let domain = Domain::from_expr("x == 250 || x > 5 && x <= 100", "x").unwrap();
+        assert_eq!(domain, expected);
+
+        let domain = Domain::from_expr("(x == 250 || x > 5) && x <= 100", "x").unwrap();
+        assert_eq!(domain, Domain::Domain(Value::Secluded(5), Value::Included(100)));
+    }
+
+    #[test]
+    fn test_not_equal() {
+        let domain = Domain::from_expr("x >= 0 && x <= 10 && x != 5", "x").unwrap();
+        assert_eq!(domain.repr(), "[0;5)⋃(5;10]");
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = Domain::from_expr("x > 5 && y < 3", "x").unwrap_err();
+        assert_eq!(error.position(), 9);
+        assert_eq!(error.kind(), &ParseErrorKind::UnknownVariable("y".to_string()));
+
+        let error = Domain::from_expr("x > 5 &&", "x").unwrap_err();
+        assert_eq!((error.position(), error.kind()), (8, &ParseErrorKind::UnexpectedEnd));
+
+        let error = Domain::from_expr("(x > 5", "x").unwrap_err();
+        assert_eq!(error.kind(), &ParseErrorKind::UnexpectedEnd);
+
+        let error = Domain::from_expr("x > 5)", "x").unwrap_err();
+        assert_eq!((error.position(), error.kind()), (5, &ParseErrorKind::UnexpectedToken));
+
+        let error = Domain::from_expr("3 < 5", "x").unwrap_err();
+        assert_eq!((error.position(), error.kind()), (0, &ParseErrorKind::InvalidComparison));
+
+        let error = Domain::from_expr("x # 5", "x").unwrap_err();
+        assert_eq!((error.position(), error.kind()), (2, &ParseErrorKind::UnexpectedChar('#')));
+
+        let error = Domain::from_expr("x > 2147483648", "x").unwrap_err();
+        assert_eq!((error.position(), error.kind()), (4, &ParseErrorKind::IntegerOverflow));
+
+        assert!(Domain::from_expr("x > -2147483648", "x").is_ok());
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |depth: usize| format!("{}x > 1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(Domain::from_expr(&nested(256), "x").unwrap().repr(), "(1;∞)");
+
+        let error = Domain::from_expr(&nested(257), "x").unwrap_err();
+        assert_eq!((error.position(), error.kind()), (256, &ParseErrorKind::NestingTooDeep));
+
+        let error = Domain::from_expr(&"(".repeat(200_000), "x").unwrap_err();
+        assert_eq!(error.kind(), &ParseErrorKind::NestingTooDeep);
+    }
+}
@@ -0,0 +1,229 @@
+//! C-compatible interface, enabled by the `ffi` feature.
+//!
+//! Domains are passed around as opaque `DdDomain` handles.
+//! Ownership rules:
+//! * handles returned by `dd_domain_new` and `dd_domain_intersect` are
+//!   owned by the caller and must be released with `dd_domain_free`
+//! * every other function only borrows the handles passed to it
+//!
+//! All functions report failures through `DdStatus`, panics are caught
+//! at the boundary and reported as `DD_STATUS_PANIC`.
+//! The header is generated by cbindgen from this module into
+//! `include/dynamic_domain.h`
+
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::{Domain, Value};
+
+/// Opaque domain handle
+pub struct DdDomain {
+    inner: Domain<i32>,
+}
+
+/// Result of every fallible call
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DdStatus {
+    Ok = 0,
+    /// A required pointer argument was NULL
+    NullPointer = 1,
+    /// The output buffer can not hold the result, see the length out parameter
+    BufferTooSmall = 2,
+    /// The call panicked internally, output parameters are unchanged
+    Panic = 3,
+}
+
+fn guard(f: impl FnOnce() -> DdStatus) -> DdStatus {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(DdStatus::Panic)
+}
+
+fn border(value: i32, inclusive: bool) -> Value<i32> {
+    if inclusive {
+        Value::Included(value)
+    } else {
+        Value::Secluded(value)
+    }
+}
+
+/// Creates the full domain `(-∞;∞)`. Returns NULL on failure
+#[no_mangle]
+pub extern "C" fn dd_domain_new() -> *mut DdDomain {
+    panic::catch_unwind(|| Box::into_raw(Box::new(DdDomain { inner: Domain::new() })))
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Releases a handle, NULL is ignored
+///
+/// # Safety
+/// `domain` must be NULL or a handle which was not freed yet
+#[no_mangle]
+pub unsafe extern "C" fn dd_domain_free(domain: *mut DdDomain) {
+    if !domain.is_null() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(domain))));
+    }
+}
+
+/// Restricts the domain to values greater than `value`
+/// (or equal when `inclusive`)
+///
+/// # Safety
+/// `domain` must be NULL or a live handle
+#[no_mangle]
+pub unsafe extern "C" fn dd_domain_gt(domain: *mut DdDomain, value: i32, inclusive: bool) -> DdStatus {
+    let domain = match domain.as_mut() {
+        Some(domain) => domain,
+        None => return DdStatus::NullPointer,
+    };
+    guard(|| {
+        domain.inner = domain.inner.gt(border(value, inclusive));
+        DdStatus::Ok
+    })
+}
+
+/// Restricts the domain to values less than `value`
+/// (or equal when `inclusive`)
+///
+/// # Safety
+/// `domain` must be NULL or a live handle
+#[no_mangle]
+pub unsafe extern "C" fn dd_domain_lt(domain: *mut DdDomain, value: i32, inclusive: bool) -> DdStatus {
+    let domain = match domain.as_mut() {
+        Some(domain) => domain,
+        None => return DdStatus::NullPointer,
+    };
+    guard(|| {
+        domain.inner = domain.inner.lt(border(value, inclusive));
+        DdStatus::Ok
+    })
+}
+
+/// Writes whether `value` is admitted by the domain into `out`
+///
+/// # Safety
+/// `domain` must be NULL or a live handle, `out` NULL or writable
+#[no_mangle]
+pub unsafe extern "C" fn dd_domain_contains(domain: *const DdDomain, value: i32, out: *mut bool) -> DdStatus {
+    let (domain, out) = match (domain.as_ref(), out.as_mut()) {
+        (Some(domain), Some(out)) => (domain, out),
+        _ => return DdStatus::NullPointer,
+    };
+    guard(|| {
+        *out = domain.inner.contains(value);
+        DdStatus::Ok
+    })
+}
+
+/// Writes the NUL-terminated UTF-8 representation into `buf`.
+/// `out_len` always receives the representation length in bytes
+/// without the terminator; when `buf_len` is not larger than that,
+/// nothing is written and `DD_STATUS_BUFFER_TOO_SMALL` is returned.
+/// `buf` may be NULL when `buf_len` is 0 to query the length
+///
+/// # Safety
+/// `domain` must be NULL or a live handle, `buf` must be valid for
+/// `buf_len` bytes and `out_len` NULL or writable
+#[no_mangle]
+pub unsafe extern "C" fn dd_domain_repr(
+    domain: *const DdDomain,
+    buf: *mut c_char,
+    buf_len: usize,
+    out_len: *mut usize,
+) -> DdStatus {
+    let (domain, out_len) = match (domain.as_ref(), out_len.as_mut()) {
+        (Some(domain), Some(out_len)) => (domain, out_len),
+        _ => return DdStatus::NullPointer,
+    };
+    if buf.is_null() && buf_len > 0 {
+        return DdStatus::NullPointer;
+    }
+    guard(|| {
+        let repr = domain.inner.clone().repr();
+        *out_len = repr.len();
+
+        if buf_len <= repr.len() {
+            return DdStatus::BufferTooSmall;
+        }
+
+        ptr::copy_nonoverlapping(repr.as_ptr() as *const c_char, buf, repr.len());
+        *buf.add(repr.len()) = 0;
+        DdStatus::Ok
+    })
+}
+
+/// Stores a new handle holding the intersection of `a` and `b` into `out`
+///
+/// # Safety
+/// `a` and `b` must be NULL or live handles, `out` NULL or writable
+#[no_mangle]
+pub unsafe extern "C" fn dd_domain_intersect(
+    a: *const DdDomain,
+    b: *const DdDomain,
+    out: *mut *mut DdDomain,
+) -> DdStatus {
+    let (a, b, out) = match (a.as_ref(), b.as_ref(), out.as_mut()) {
+        (Some(a), Some(b), Some(out)) => (a, b, out),
+        _ => return DdStatus::NullPointer,
+    };
+    guard(|| {
+        let inner = a.inner.intersect(&b.inner).into_inner();
+        *out = Box::into_raw(Box::new(DdDomain { inner }));
+        DdStatus::Ok
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use super::*;
+
+    #[test]
+    fn test_handles() {
+        unsafe {
+            let a = dd_domain_new();
+            let b = dd_domain_new();
+            assert_eq!(dd_domain_gt(a, 5, true), DdStatus::Ok);
+            assert_eq!(dd_domain_lt(b, 10, false), DdStatus::Ok);
+
+            let mut both = ptr::null_mut();
+            assert_eq!(dd_domain_intersect(a, b, &mut both), DdStatus::Ok);
+
+            let mut contained = false;
+            assert_eq!(dd_domain_contains(both, 9, &mut contained), DdStatus::Ok);
+            assert!(contained);
+            assert_eq!(dd_domain_contains(both, 10, &mut contained), DdStatus::Ok);
+            assert!(!contained);
+
+            let mut len = 0;
+            assert_eq!(dd_domain_repr(both, ptr::null_mut(), 0, &mut len), DdStatus::BufferTooSmall);
+            assert_eq!(len, "[5;10)".len());
+
+            let mut buf = vec![0 as c_char; len + 1];
+            assert_eq!(dd_domain_repr(both, buf.as_mut_ptr(), buf.len(), &mut len), DdStatus::Ok);
+            let repr: Vec<u8> = buf.iter().map(|c| *c as u8).collect();
+            assert_eq!(&repr, b"[5;10)\0");
+
+            dd_domain_free(a);
+            dd_domain_free(b);
+            dd_domain_free(both);
+            dd_domain_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_null_pointers() {
+        unsafe {
+            let mut out = false;
+            assert_eq!(dd_domain_gt(ptr::null_mut(), 1, true), DdStatus::NullPointer);
+            assert_eq!(dd_domain_contains(ptr::null(), 1, &mut out), DdStatus::NullPointer);
+
+            let domain = dd_domain_new();
+            let mut len = 0;
+            assert_eq!(dd_domain_repr(domain, ptr::null_mut(), 8, &mut len), DdStatus::NullPointer);
+            assert_eq!(dd_domain_intersect(domain, ptr::null(), ptr::null_mut()), DdStatus::NullPointer);
+            dd_domain_free(domain);
+        }
+    }
+}
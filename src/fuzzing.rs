@@ -0,0 +1,952 @@
+//! Corpus helpers for the fuzz targets in `fuzz/`, enabled by the
+//! `fuzzing` feature. `Session` and `run` drive sequences of public calls
+//! for the `api` target, which must neither panic nor hang.
+//!
+//! A session is written as a script, which is also its `Debug` form, so
+//! `cargo fuzz fmt api <artifact>` prints an input ready to paste into
+//! `tests/regressions.rs`. Statements are separated by `;`, the first one
+//! is the starting domain, `new` or a repr, every following one a call:
+//! ```text
+//! [0;10]; gt S5; lt I9; shift 2147483647 saturate; intersect [1;2]U[9;9]
+//! ```
+//! Borders are `I5`, `S5` or `inf`, domains are reprs without spaces,
+//! `#` starts a comment running to the end of the line
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{ApproxDirection, BoundedUniverse, BudgetPolicy, CodegenLang, Constraint, Domain, DomainAccumulator, DomainBuilder, DomainDto, DomainIndex, DomainMap, IterOrder, LabeledDomain, MonotoneOutcome, NarrowPolicy, Notation, OverflowPolicy, ReprOptions, Rounding, Value};
+
+/// Unions are nested at most this deep
+const MAX_DEPTH: u32 = 3;
+
+/// Integer drawn near 0 or the `i32` limits half of the time, where
+/// the ±1 border arithmetic and overflows happen
+fn point(u: &mut Unstructured<'_>) -> Result<i32> {
+    let offset = u.int_in_range(0..=8)?;
+    Ok(match u.int_in_range(0..=5)? {
+        0 => i32::MIN + offset,
+        1 => i32::MAX - offset,
+        2 => offset - 4,
+        _ => u.arbitrary()?,
+    })
+}
+
+impl<'a> Arbitrary<'a> for Value<i32> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Value::Infinite,
+            1 => Value::Included(point(u)?),
+            _ => Value::Secluded(point(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Domain<i32> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_domain(u, 0)
+    }
+}
+
+fn arbitrary_domain(u: &mut Unstructured<'_>, depth: u32) -> Result<Domain<i32>> {
+    let variants = if depth < MAX_DEPTH { 3 } else { 2 };
+
+    Ok(match u.int_in_range(0..=variants - 1)? {
+        0 => Domain::None,
+        1 => Domain::Domain(u.arbitrary()?, u.arbitrary()?),
+        _ => {
+            let len = u.arbitrary_len::<Value<i32>>()?;
+            let mut domains = Vec::with_capacity(len);
+            for _ in 0..len {
+                domains.push(arbitrary_domain(u, depth + 1)?);
+            }
+            Domain::Union(domains)
+        },
+    })
+}
+
+/// Most values taken from an iterator by one `Call`, so a sequence of
+/// calls finishes in bounded steps even over unbounded domains
+const MAX_STEPS: u16 = 512;
+
+/// Calls in a `Session`
+const MAX_CALLS: usize = 64;
+
+/// Bytes a nested argument like a domain is built from, so it does not
+/// swallow the input left for the following calls
+const MAX_ARGUMENT_BYTES: usize = 64;
+
+/// Builds a `T` from a length-prefixed part of the input
+fn bounded<'a, T: Arbitrary<'a>>(u: &mut Unstructured<'a>) -> Result<T> {
+    let len = u.int_in_range(0..=MAX_ARGUMENT_BYTES)?;
+    let bytes = u.bytes(len.min(u.len()))?;
+    T::arbitrary(&mut Unstructured::new(bytes))
+}
+
+/// Public call on the domain under test, driven by the `api` fuzz target.
+/// Arguments putting a bound on the work, like step counts and bucket
+/// numbers, are kept small by `Arbitrary`
+#[derive(Clone, Debug)]
+pub enum Call {
+    Gt(Value<i32>),
+    Lt(Value<i32>),
+    Tighten(Vec<Constraint>),
+    Build(Vec<Constraint>),
+    Intersect(Domain<i32>, u8),
+    Union(Domain<i32>),
+    Difference(Domain<i32>),
+    Complement,
+    Coalesce(u32),
+    Budget(u8, BudgetPolicy),
+    RemoveValue(i32),
+    Shift(i32, OverflowPolicy),
+    Add(Domain<i32>, OverflowPolicy),
+    Scale(i32, OverflowPolicy),
+    Mul(Domain<i32>, OverflowPolicy),
+    Div(Domain<i32>),
+    DivConst(i32),
+    DivRound(i32, Rounding),
+    RemEuclid(i32),
+    Repr,
+    Parse(String),
+    Iterate(IterOrder, u16),
+    IterIn(i32, i32, u16),
+    Many(Vec<Domain<i32>>),
+    Generate(u16),
+    Query(i32, u64),
+    Aggregate(u8),
+    Encode,
+    Decode(Vec<u8>),
+}
+
+impl<'a> Arbitrary<'a> for OverflowPolicy {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => OverflowPolicy::Error,
+            1 => OverflowPolicy::Saturate,
+            _ => OverflowPolicy::Unbounded,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for BudgetPolicy {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => BudgetPolicy::Error,
+            1 => BudgetPolicy::Outward,
+            _ => BudgetPolicy::Inward,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Rounding {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => Rounding::Floor,
+            1 => Rounding::Ceil,
+            2 => Rounding::Nearest,
+            3 => Rounding::Inward,
+            _ => Rounding::Outward,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for IterOrder {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=3)? {
+            0 => IterOrder::Ascending,
+            1 => IterOrder::Descending,
+            2 => IterOrder::FromMidpoint,
+            _ => IterOrder::FromValue(point(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Constraint {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => Constraint::Gt(u.arbitrary()?),
+            1 => Constraint::Lt(u.arbitrary()?),
+            2 => Constraint::Ne(point(u)?),
+            3 => Constraint::InsideOf(bounded(u)?),
+            _ => Constraint::OutsideOf(bounded(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Call {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=28)? {
+            0 => Call::Gt(u.arbitrary()?),
+            1 => Call::Lt(u.arbitrary()?),
+            2 => Call::Tighten(bounded(u)?),
+            3 => Call::Build(bounded(u)?),
+            4 => Call::Intersect(bounded(u)?, u.arbitrary()?),
+            5 => Call::Union(bounded(u)?),
+            6 => Call::Difference(bounded(u)?),
+            7 => Call::Complement,
+            8 => Call::RemoveValue(point(u)?),
+            9 => Call::Shift(point(u)?, u.arbitrary()?),
+            10 => Call::Add(bounded(u)?, u.arbitrary()?),
+            11 => Call::Scale(point(u)?, u.arbitrary()?),
+            12 => Call::Mul(bounded(u)?, u.arbitrary()?),
+            13 => Call::Div(bounded(u)?),
+            14 => Call::DivConst(point(u)?),
+            15 => Call::RemEuclid(point(u)?),
+            16 => Call::Repr,
+            17 => Call::Parse(bounded(u)?),
+            18 => Call::Iterate(u.arbitrary()?, u.int_in_range(0..=MAX_STEPS)?),
+            19 => Call::Generate(u.int_in_range(0..=MAX_STEPS)?),
+            20 => Call::Query(point(u)?, u.arbitrary()?),
+            21 => Call::Aggregate(u.int_in_range(0..=16)?),
+            22 => Call::Encode,
+            23 => Call::IterIn(point(u)?, point(u)?, u.int_in_range(0..=MAX_STEPS)?),
+            24 => Call::Many(bounded(u)?),
+            25 => Call::DivRound(point(u)?, u.arbitrary()?),
+            26 => Call::Coalesce(u.arbitrary()?),
+            27 => Call::Budget(u.arbitrary()?, u.arbitrary()?),
+            _ => Call::Decode(bounded(u)?),
+        })
+    }
+}
+
+/// Starting domain and the calls made on it, the input of the `api`
+/// fuzz target. `Debug` and `Display` write its script, `FromStr` reads
+/// it back
+/// ```
+/// use dynamic_domain::fuzzing::Session;
+/// let session: Session = "new; gt S5; lt I9; repr".parse().unwrap();
+/// assert_eq!(session.calls.len(), 3);
+/// assert_eq!(session.to_string(), "(-∞;∞); gt S5; lt I9; repr");
+/// ```
+#[derive(Clone)]
+pub struct Session {
+    pub domain: Domain<i32>,
+    pub calls: Vec<Call>,
+}
+
+impl<'a> Arbitrary<'a> for Session {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let domain = bounded(u)?;
+        let mut calls = Vec::new();
+        while calls.len() < MAX_CALLS && !u.is_empty() {
+            calls.push(u.arbitrary()?);
+        }
+        Ok(Session { domain, calls })
+    }
+}
+
+/// Runs the calls in turn, every call replacing the domain with its
+/// result when it gives a domain, and gives the last domain. Errors
+/// returned by the calls are expected, a panic or a call not returning is
+/// a bug
+/// ```
+/// use dynamic_domain::fuzzing::{run, Call, Session};
+/// use dynamic_domain::{Domain, OverflowPolicy};
+/// let domain = run(&Session {
+///     domain: Domain::at_least(i32::MAX - 1),
+///     calls: vec![Call::Shift(1, OverflowPolicy::Error), Call::RemEuclid(0), Call::Generate(3)],
+/// });
+/// assert_eq!(domain.repr(), "∅");
+/// ```
+pub fn run(session: &Session) -> Domain<i32> {
+    let mut domain = session.domain.clone();
+    for call in &session.calls {
+        domain = match call {
+            Call::Gt(value) => domain.gt(value.clone()),
+            Call::Lt(value) => domain.lt(value.clone()),
+            Call::Tighten(constraints) => domain.tighten_all(constraints),
+            Call::Build(constraints) => {
+                let builder = constraints.iter().fold(DomainBuilder::new(), |builder, constraint| {
+                    match constraint {
+                        Constraint::Gt(Value::Included(i)) => builder.ge(*i),
+                        Constraint::Gt(Value::Secluded(i)) => builder.gt(*i),
+                        Constraint::Lt(Value::Included(i)) => builder.le(*i),
+                        Constraint::Lt(Value::Secluded(i)) => builder.lt(*i),
+                        Constraint::Gt(Value::Infinite) | Constraint::Lt(Value::Infinite) => builder,
+                        Constraint::Ne(i) => builder.ne(*i),
+                        Constraint::InsideOf(inside) => builder.inside(inside.clone()),
+                        Constraint::OutsideOf(outside) => builder.outside(outside.clone()),
+                    }
+                });
+                match builder.build() {
+                    Ok(built) => built.intersect(&domain).into_inner(),
+                    Err(conflict) => {
+                        let _ = conflict.to_string();
+                        domain
+                    },
+                }
+            },
+            Call::Intersect(other, limit) => {
+                let _ = domain.intersect_limited(other, *limit as usize);
+                domain.intersect(other).into_inner()
+            },
+            Call::Union(other) => {
+                let _ = (domain.touches(other), domain.structurally_equal(other));
+                let mut segments = 0usize;
+                domain.merge_join(other, |segment| segments += segment.in_left as usize);
+                let mut labeled = LabeledDomain::new();
+                labeled.push_domain(&domain, 0u8);
+                labeled.push_domain(other, 1u8);
+                let labeled = labeled.normalize().difference(other);
+                let _ = (labeled.contains_labeled(0), labeled.nearest_labeled(0), labeled.repr_with_labels());
+                let universe = BoundedUniverse::new(other);
+                let _ = (universe.complement(&domain), universe.is_full(&domain), universe.sample(&domain, u64::MAX));
+                domain.structural_hash(&mut std::collections::hash_map::DefaultHasher::new());
+                domain.union(other).into_inner()
+            },
+            Call::Difference(other) => {
+                let _ = (domain.lt_domain(other, true), domain.gt_domain(other, false));
+                domain.difference(other).into_inner()
+            },
+            Call::Complement => domain.complement().into_inner(),
+            Call::Coalesce(max_gap) => domain.coalesce(*max_gap).into_inner(),
+            Call::Budget(max_members, policy) => {
+                let _ = (
+                    domain.approximate(*max_members as usize, ApproxDirection::Outward),
+                    domain.approximate(*max_members as usize, ApproxDirection::Inward),
+                );
+                match domain.normalize_with_budget(*max_members as usize, *policy) {
+                    Ok((approximation, _)) => approximation.into_inner(),
+                    Err(_) => domain,
+                }
+            },
+            Call::RemoveValue(value) => domain.remove_value(*value).into_inner(),
+            Call::Shift(delta, policy) => domain.shift_with(*delta, *policy).unwrap_or(domain),
+            Call::Add(other, policy) => domain.add_with(other, *policy).unwrap_or(domain),
+            Call::Scale(k, policy) => domain.scale_with(*k, *policy).unwrap_or(domain),
+            Call::Mul(other, policy) => domain.mul_with(other, *policy).unwrap_or(domain),
+            Call::Div(other) => domain.div(other),
+            Call::DivConst(k) => domain.div_const(*k),
+            Call::DivRound(k, mode) => domain.div_round(*k, *mode).unwrap_or(domain),
+            Call::RemEuclid(m) => domain.rem_euclid_const(*m).unwrap_or(domain),
+            Call::Repr => {
+                let repr = domain.clone().repr();
+                domain.repr_into(&mut String::new());
+                let _ = domain.repr_with_options(&ReprOptions { canonical_closed: true, ..ReprOptions::DEFAULT });
+                let _ = (domain.to_range_list(), domain.to_sql_predicate("x"), domain.to_sql_predicate_params("x"));
+                let _ = domain.to_slice_notation().map(|slice| Domain::from_slice_notation(&slice, None));
+                let _ = Domain::parse_with(&domain.repr_with(&Notation::ASCII), &Notation::ASCII);
+                repr.parse().unwrap_or(domain)
+            },
+            Call::Parse(input) => {
+                let _ = (Domain::from_range_list(input), Domain::<char>::from_char_class(input).map(|class| class.to_char_class()));
+                let _ = (Domain::from_slice_notation(input, None), Domain::from_slice_notation(input, Some(input.len() as i32)));
+                if let Ok(points) = Domain::<String>::parse_points(input, &Notation::ASCII) {
+                    let _ = Domain::<String>::parse_points(&points.repr_with(&Notation::DEFAULT), &Notation::DEFAULT);
+                }
+                let _ = Domain::<f64>::parse_points(input, &Notation::DEFAULT);
+                match Domain::from_expr(input, "x") {
+                    Ok(parsed) => parsed,
+                    Err(_) => input.parse().unwrap_or(domain),
+                }
+            },
+            Call::Iterate(order, steps) => {
+                let _ = domain.iter().take(*steps as usize).count();
+                let _ = domain.iter().rev().take(*steps as usize).count();
+                if let Ok(iter) = domain.iter_ordered(*order) {
+                    let mut accumulator = DomainAccumulator::new();
+                    accumulator.extend(iter.take(*steps as usize));
+                    let _ = accumulator.finish();
+                }
+                let _ = domain.iter().capped(*steps as u64).count();
+                domain
+            },
+            Call::IterIn(lo, hi, steps) => {
+                let _ = domain.iter_in(*lo, *hi).take(*steps as usize).count();
+                let _ = domain.runs().take(*steps as usize).count();
+                let _ = domain.boundary_events().take(*steps as usize).count();
+                domain
+            },
+            Call::Many(others) => {
+                let _ = domain.covered_by(others);
+                let _ = Domain::quantile_across(others, 0.5);
+                let all: Vec<&Domain<i32>> = others.iter().chain(std::iter::once(&domain)).collect();
+                let _ = Domain::intersect_many(all.iter().copied());
+                let mut map = DomainMap::new();
+                for (value, other) in others.iter().enumerate() {
+                    if map.insert(other.clone(), value).is_err() {
+                        map.insert_replacing(other.clone(), value);
+                    }
+                }
+                let _ = (map.get(0), map.range_query(&domain), map.iter().count());
+                let mut deferred = domain.clone();
+                for other in others {
+                    deferred.union_assign_deferred(other.clone());
+                }
+                let _ = (deferred.contains(0), deferred.cardinality());
+                deferred.flush();
+                Domain::union_many(all).into_inner()
+            },
+            Call::Generate(steps) => {
+                let _ = domain.generate_capped(*steps as u64, |_| ());
+                let mut left = *steps;
+                let _ = domain.try_generate(|_| left.checked_sub(1).map(|rest| left = rest).ok_or(()));
+                domain
+            },
+            Call::Query(value, n) => {
+                let _ = (domain.contains(*value), domain.clamp(*value), domain.validate_value(*value));
+                let _ = (domain.cardinality(), domain.rank(*value), domain.nth(*n));
+                let _ = (Domain::const_point(*value).intersect(&domain), Domain::const_at_least(*value).difference(&domain));
+                let _ = (Domain::const_between(*value, i32::MAX).union(&Domain::const_empty()), Domain::const_full().difference(&domain));
+                let _ = (domain.complement_within_type(), domain.clamp_within_type(*value), domain.cardinality_within_type());
+                let _ = (domain.is_bounded(), domain.is_bounded_below(), domain.is_bounded_above(), domain.active_window());
+                let intervals = domain.intervals();
+                for interval in &intervals {
+                    let _ = (interval.contains(*value), interval.cardinality(), interval.repr());
+                }
+                for pair in intervals.windows(2) {
+                    let _ = (pair[0].is_adjacent_to(&pair[1]), pair[1].merge_with(&pair[0]));
+                }
+                let _ = domain.partition_intervals(|interval| interval.contains(*value));
+                let _ = domain.widen::<i64>().narrow::<u32>(NarrowPolicy::Clamp);
+                let _ = domain.min_adjustment_for(*value);
+                let _ = domain.describe();
+                let _ = domain.ge(*value).le(value.saturating_add(9)).ne(*value);
+                let _ = (domain.try_refine_with_observation(*value, *n % 2 == 0), domain.refine_monotone(*value, MonotoneOutcome::TooLow));
+                let _ = domain.codegen_values_limited(CodegenLang::TypeScript, "VALUES", (*n).min(MAX_STEPS as u64));
+                let index = DomainIndex::build(&domain);
+                let _ = (index.contains(*value), index.rank(*value), index.nearest(*value));
+                index.contains_many(&[*value, value.wrapping_add(1)], &mut [false]);
+                let values = [*value, i32::MIN, value.wrapping_sub(1), i32::MAX];
+                let _ = (domain.contains_all(&values), domain.contains_any(&values), domain.partition_contained(&values));
+                let view = domain.view_below(*value);
+                let _ = (view.view_above(value.wrapping_sub(*n as i32)).cardinality(), view.contains(*value), view.is_empty());
+                let _ = (view.iter().take(MAX_STEPS as usize).count(), view.to_domain(), domain.view_above(*value).to_domain());
+                domain
+            },
+            Call::Aggregate(buckets) => {
+                let _ = (domain.sum(), domain.histogram(*buckets as usize));
+                let _ = domain.min_by_key(|x| x as i64, MAX_STEPS as u64);
+                let _ = (domain.is_enumerable(*buckets as usize), domain.enumerate_labeled(|x| x.to_string(), *buckets as usize));
+                if domain.cardinality().is_some_and(|n| n <= MAX_STEPS as u64) {
+                    let _ = domain.chunks(*buckets as u64 + 1);
+                }
+                domain
+            },
+            Call::Encode => {
+                let domain = Domain::from_bytes(&domain.to_bytes()).unwrap_or(domain);
+                let domain = match domain.to_inversion_list() {
+                    Some(list) => Domain::from_inversion_list(&list).unwrap_or(domain),
+                    None => domain,
+                };
+                let domain = Domain::try_from(DomainDto::from(&domain)).unwrap_or(domain);
+                match domain.to_schema_bounds() {
+                    Some(b) => Domain::from_schema_bounds(b.minimum, b.maximum, b.exclusive_minimum, b.exclusive_maximum)
+                        .unwrap_or(domain),
+                    None => domain,
+                }
+            },
+            Call::Decode(bytes) => {
+                let _ = Domain::from_inversion_list(&bytes.iter().map(|b| *b as i8 as i64).collect::<Vec<_>>());
+                Domain::from_bytes(bytes).unwrap_or(domain)
+            },
+        };
+    }
+    domain
+}
+
+/// Error reading a `Session` script
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptError {
+    statement: usize,
+    message: String,
+}
+
+impl ScriptError {
+    /// Index of the statement which could not be read, 0 for the
+    /// starting domain
+    pub fn statement(&self) -> usize {
+        self.statement
+    }
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in statement {}", self.message, self.statement)
+    }
+}
+
+impl Error for ScriptError {}
+
+fn write_value(f: &mut fmt::Formatter<'_>, value: &Value<i32>) -> fmt::Result {
+    match value {
+        Value::Included(i) => write!(f, "I{}", i),
+        Value::Secluded(i) => write!(f, "S{}", i),
+        Value::Infinite => write!(f, "inf"),
+    }
+}
+
+fn write_constraints(f: &mut fmt::Formatter<'_>, constraints: &[Constraint]) -> fmt::Result {
+    for constraint in constraints {
+        match constraint {
+            Constraint::Gt(value) => {
+                write!(f, " gt:")?;
+                write_value(f, value)?;
+            },
+            Constraint::Lt(value) => {
+                write!(f, " lt:")?;
+                write_value(f, value)?;
+            },
+            Constraint::Ne(i) => write!(f, " ne:{}", i)?,
+            Constraint::InsideOf(domain) => write!(f, " inside:{}", domain)?,
+            Constraint::OutsideOf(domain) => write!(f, " outside:{}", domain)?,
+        }
+    }
+    Ok(())
+}
+
+fn overflow_name(policy: OverflowPolicy) -> &'static str {
+    match policy {
+        OverflowPolicy::Error => "error",
+        OverflowPolicy::Saturate => "saturate",
+        OverflowPolicy::Unbounded => "unbounded",
+    }
+}
+
+fn budget_name(policy: BudgetPolicy) -> &'static str {
+    match policy {
+        BudgetPolicy::Error => "error",
+        BudgetPolicy::Outward => "outward",
+        BudgetPolicy::Inward => "inward",
+    }
+}
+
+fn rounding_name(mode: Rounding) -> &'static str {
+    match mode {
+        Rounding::Floor => "floor",
+        Rounding::Ceil => "ceil",
+        Rounding::Nearest => "nearest",
+        Rounding::Inward => "inward",
+        Rounding::Outward => "outward",
+    }
+}
+
+/// The statement of the call in a script
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Call::Gt(value) | Call::Lt(value) => {
+                write!(f, "{} ", if let Call::Gt(_) = self { "gt" } else { "lt" })?;
+                write_value(f, value)
+            },
+            Call::Tighten(constraints) => {
+                write!(f, "tighten")?;
+                write_constraints(f, constraints)
+            },
+            Call::Build(constraints) => {
+                write!(f, "build")?;
+                write_constraints(f, constraints)
+            },
+            Call::Intersect(other, limit) => write!(f, "intersect {} {}", other, limit),
+            Call::Union(other) => write!(f, "union {}", other),
+            Call::Difference(other) => write!(f, "difference {}", other),
+            Call::Complement => write!(f, "complement"),
+            Call::Coalesce(max_gap) => write!(f, "coalesce {}", max_gap),
+            Call::Budget(max_members, policy) => write!(f, "budget {} {}", max_members, budget_name(*policy)),
+            Call::RemoveValue(value) => write!(f, "remove {}", value),
+            Call::Shift(delta, policy) => write!(f, "shift {} {}", delta, overflow_name(*policy)),
+            Call::Add(other, policy) => write!(f, "add {} {}", other, overflow_name(*policy)),
+            Call::Scale(k, policy) => write!(f, "scale {} {}", k, overflow_name(*policy)),
+            Call::Mul(other, policy) => write!(f, "mul {} {}", other, overflow_name(*policy)),
+            Call::Div(other) => write!(f, "div {}", other),
+            Call::DivConst(k) => write!(f, "div_const {}", k),
+            Call::DivRound(k, mode) => write!(f, "div_round {} {}", k, rounding_name(*mode)),
+            Call::RemEuclid(m) => write!(f, "rem_euclid {}", m),
+            Call::Repr => write!(f, "repr"),
+            Call::Parse(input) => write!(f, "parse {:?}", input),
+            Call::Iterate(order, steps) => {
+                match order {
+                    IterOrder::Ascending => write!(f, "iterate ascending")?,
+                    IterOrder::Descending => write!(f, "iterate descending")?,
+                    IterOrder::FromMidpoint => write!(f, "iterate midpoint")?,
+                    IterOrder::FromValue(value) => write!(f, "iterate from:{}", value)?,
+                }
+                write!(f, " {}", steps)
+            },
+            Call::IterIn(lo, hi, steps) => write!(f, "iter_in {} {} {}", lo, hi, steps),
+            Call::Many(others) => {
+                write!(f, "many")?;
+                others.iter().try_for_each(|other| write!(f, " {}", other))
+            },
+            Call::Generate(steps) => write!(f, "generate {}", steps),
+            Call::Query(value, n) => write!(f, "query {} {}", value, n),
+            Call::Aggregate(buckets) => write!(f, "aggregate {}", buckets),
+            Call::Encode => write!(f, "encode"),
+            Call::Decode(bytes) => {
+                write!(f, "decode ")?;
+                bytes.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+            },
+        }
+    }
+}
+
+/// The script: the starting domain and the calls, separated by `; `
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.domain)?;
+        self.calls.iter().try_for_each(|call| write!(f, "; {}", call))
+    }
+}
+
+/// The script, as `Display`, so a crashing input prints ready to paste
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// Statements of `script` without comments, split at the `;` outside of
+/// brackets and quoted strings
+fn statements(script: &str) -> Vec<String> {
+    let (mut statements, mut current) = (Vec::new(), String::new());
+    let (mut depth, mut quoted, mut escaped, mut comment) = (0i32, false, false, false);
+    for c in script.chars() {
+        if comment {
+            comment = c != '\n';
+            continue;
+        }
+        if quoted {
+            current.push(c);
+            quoted = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+            continue;
+        }
+        match c {
+            '#' => comment = true,
+            ';' if depth <= 0 => statements.push(std::mem::take(&mut current)),
+            _ => {
+                depth += match c {
+                    '[' | '(' => 1,
+                    ']' | ')' => -1,
+                    _ => 0,
+                };
+                quoted = c == '"';
+                current.push(c);
+            },
+        }
+    }
+    statements.push(current);
+    statements.into_iter().map(|statement| statement.trim().to_string()).filter(|statement| !statement.is_empty()).collect()
+}
+
+/// Words of a statement split at whitespace, a quoted string is one
+/// word with its quotes
+fn words(statement: &str) -> Vec<String> {
+    let (mut words, mut current) = (Vec::new(), String::new());
+    let (mut quoted, mut escaped) = (false, false);
+    for c in statement.chars() {
+        if quoted {
+            current.push(c);
+            quoted = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else if c.is_whitespace() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else {
+            quoted = c == '"';
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// The string a `{:?}` quoted word stands for
+fn unquote(word: &str) -> std::result::Result<String, String> {
+    let inner = word
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or_else(|| format!("expected a quoted string, found `{}`", word))?;
+    let mut out = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('0') => '\0',
+            Some('u') => {
+                let code: String = chars.by_ref().skip(1).take_while(|c| *c != '}').collect();
+                u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid escape `\\u{{{}}}`", code))?
+            },
+            Some(c @ ('\\' | '"' | '\'')) => c,
+            _ => return Err(format!("invalid escape in {}", word)),
+        });
+    }
+    Ok(out)
+}
+
+/// Arguments of one statement, read in turn
+struct Args<'a> {
+    words: std::slice::Iter<'a, String>,
+}
+
+impl<'a> Args<'a> {
+    fn next(&mut self, what: &str) -> std::result::Result<&'a str, String> {
+        self.words.next().map(String::as_str).ok_or_else(|| format!("missing {}", what))
+    }
+
+    fn number<N: FromStr>(&mut self, what: &str) -> std::result::Result<N, String> {
+        let word = self.next(what)?;
+        word.parse().map_err(|_| format!("expected {}, found `{}`", what, word))
+    }
+
+    fn value(&mut self) -> std::result::Result<Value<i32>, String> {
+        parse_value(self.next("a border")?)
+    }
+
+    fn domain(&mut self) -> std::result::Result<Domain<i32>, String> {
+        parse_domain(self.next("a domain")?)
+    }
+
+    fn rest(&mut self) -> std::slice::Iter<'a, String> {
+        std::mem::replace(&mut self.words, [].iter())
+    }
+
+    fn overflow(&mut self) -> std::result::Result<OverflowPolicy, String> {
+        Ok(match self.next("an overflow policy")? {
+            "error" => OverflowPolicy::Error,
+            "saturate" => OverflowPolicy::Saturate,
+            "unbounded" => OverflowPolicy::Unbounded,
+            word => return Err(format!("unknown overflow policy `{}`", word)),
+        })
+    }
+}
+
+fn parse_value(word: &str) -> std::result::Result<Value<i32>, String> {
+    let point = |digits: &str| digits.parse().map_err(|_| format!("expected a border, found `{}`", word));
+    match word.split_at(word.len().min(1)) {
+        ("I", digits) => Ok(Value::Included(point(digits)?)),
+        ("S", digits) => Ok(Value::Secluded(point(digits)?)),
+        _ if word == "inf" => Ok(Value::Infinite),
+        _ => Err(format!("expected a border, found `{}`", word)),
+    }
+}
+
+fn parse_domain(word: &str) -> std::result::Result<Domain<i32>, String> {
+    if word == "new" {
+        return Ok(Domain::new());
+    }
+    word.parse().map_err(|error| format!("invalid domain `{}`: {}", word, error))
+}
+
+fn parse_constraint(word: &str) -> std::result::Result<Constraint, String> {
+    let (kind, argument) = word.split_once(':').ok_or_else(|| format!("expected a constraint, found `{}`", word))?;
+    Ok(match kind {
+        "gt" => Constraint::Gt(parse_value(argument)?),
+        "lt" => Constraint::Lt(parse_value(argument)?),
+        "ne" => Constraint::Ne(argument.parse().map_err(|_| format!("expected an integer, found `{}`", argument))?),
+        "inside" => Constraint::InsideOf(parse_domain(argument)?),
+        "outside" => Constraint::OutsideOf(parse_domain(argument)?),
+        _ => return Err(format!("unknown constraint `{}`", kind)),
+    })
+}
+
+fn parse_call(words: &[String]) -> std::result::Result<Call, String> {
+    let (name, rest) = words.split_first().ok_or("empty statement")?;
+    let mut args = Args { words: rest.iter() };
+    let call = match name.as_str() {
+        "gt" => Call::Gt(args.value()?),
+        "lt" => Call::Lt(args.value()?),
+        "tighten" => Call::Tighten(args.rest().map(|word| parse_constraint(word)).collect::<std::result::Result<_, _>>()?),
+        "build" => Call::Build(args.rest().map(|word| parse_constraint(word)).collect::<std::result::Result<_, _>>()?),
+        "intersect" => {
+            let other = args.domain()?;
+            let limit = if args.words.len() == 0 { u8::MAX } else { args.number("a member limit")? };
+            Call::Intersect(other, limit)
+        },
+        "union" => Call::Union(args.domain()?),
+        "difference" => Call::Difference(args.domain()?),
+        "complement" => Call::Complement,
+        "coalesce" => Call::Coalesce(args.number("a gap")?),
+        "budget" => {
+            let max_members = args.number("a member count")?;
+            let policy = match args.next("a budget policy")? {
+                "error" => BudgetPolicy::Error,
+                "outward" => BudgetPolicy::Outward,
+                "inward" => BudgetPolicy::Inward,
+                word => return Err(format!("unknown budget policy `{}`", word)),
+            };
+            Call::Budget(max_members, policy)
+        },
+        "remove" => Call::RemoveValue(args.number("an integer")?),
+        "shift" => Call::Shift(args.number("an integer")?, args.overflow()?),
+        "add" => Call::Add(args.domain()?, args.overflow()?),
+        "scale" => Call::Scale(args.number("an integer")?, args.overflow()?),
+        "mul" => Call::Mul(args.domain()?, args.overflow()?),
+        "div" => Call::Div(args.domain()?),
+        "div_const" => Call::DivConst(args.number("an integer")?),
+        "div_round" => {
+            let k = args.number("an integer")?;
+            let mode = match args.next("a rounding")? {
+                "floor" => Rounding::Floor,
+                "ceil" => Rounding::Ceil,
+                "nearest" => Rounding::Nearest,
+                "inward" => Rounding::Inward,
+                "outward" => Rounding::Outward,
+                word => return Err(format!("unknown rounding `{}`", word)),
+            };
+            Call::DivRound(k, mode)
+        },
+        "rem_euclid" => Call::RemEuclid(args.number("an integer")?),
+        "repr" => Call::Repr,
+        "parse" => Call::Parse(unquote(args.next("a quoted string")?)?),
+        "iterate" => {
+            let order = match args.next("an order")? {
+                "ascending" => IterOrder::Ascending,
+                "descending" => IterOrder::Descending,
+                "midpoint" => IterOrder::FromMidpoint,
+                word => match word.strip_prefix("from:").map(str::parse) {
+                    Some(Ok(value)) => IterOrder::FromValue(value),
+                    _ => return Err(format!("unknown order `{}`", word)),
+                },
+            };
+            Call::Iterate(order, args.number("a step count")?)
+        },
+        "iter_in" => Call::IterIn(args.number("an integer")?, args.number("an integer")?, args.number("a step count")?),
+        "many" => Call::Many(args.rest().map(|word| parse_domain(word)).collect::<std::result::Result<_, _>>()?),
+        "generate" => Call::Generate(args.number("a step count")?),
+        "query" => Call::Query(args.number("an integer")?, args.number("an index")?),
+        "aggregate" => Call::Aggregate(args.number("a bucket count")?),
+        "encode" => Call::Encode,
+        "decode" => {
+            let hex = if args.words.len() == 0 { "" } else { args.next("bytes")? };
+            let bytes = (0..hex.len())
+                .step_by(2)
+                .map(|i| hex.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+                .collect::<Option<Vec<u8>>>()
+                .ok_or_else(|| format!("expected hex bytes, found `{}`", hex))?;
+            Call::Decode(bytes)
+        },
+        _ => return Err(format!("unknown call `{}`", name)),
+    };
+    match args.words.next() {
+        Some(word) => Err(format!("unexpected `{}`", word)),
+        None => Ok(call),
+    }
+}
+
+/// Reads a script as written by `Display`, see the module documentation
+/// ```
+/// use dynamic_domain::fuzzing::{run, Session};
+/// let session: Session = "[0;10]; gt S5   # drops 0 through 5\n; intersect [1;7]U[9;9]".parse().unwrap();
+/// assert_eq!(run(&session).repr(), "(5;7]⋃[9;9]");
+/// let error = "[0;10]; gt 5".parse::<Session>().unwrap_err();
+/// assert_eq!(error.to_string(), "expected a border, found `5` in statement 1");
+/// ```
+impl FromStr for Session {
+    type Err = ScriptError;
+
+    fn from_str(script: &str) -> std::result::Result<Self, Self::Err> {
+        let statements = statements(script);
+        let error = |statement, message| ScriptError { statement, message };
+        let (start, calls) = statements.split_first().ok_or_else(|| error(0, "missing starting domain".to_string()))?;
+        let domain = parse_domain(start).map_err(|message| error(0, message))?;
+        let calls = calls
+            .iter()
+            .enumerate()
+            .map(|(i, statement)| parse_call(&words(statement)).map_err(|message| error(i + 1, message)))
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(Session { domain, calls })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use super::{run, Call, Session};
+    use crate::Domain;
+
+    /// Same calls as the `api` fuzz target on bytes of a fixed generator,
+    /// so every `cargo test --features fuzzing` repeats a short fuzz run
+    #[test]
+    fn test_call_sequences() {
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut bytes = vec![0u8; 4096];
+        for _ in 0..2000 {
+            for byte in bytes.iter_mut() {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                *byte = state as u8;
+            }
+            let mut u = Unstructured::new(&bytes);
+            if let Ok(session) = Session::arbitrary(&mut u) {
+                run(&session);
+            }
+        }
+    }
+
+    /// Every script `Display` writes reads back to a session writing the
+    /// same script, and running both ends the same
+    #[test]
+    fn test_scripts_round_trip() {
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut bytes = vec![0u8; 1024];
+        for _ in 0..500 {
+            for byte in bytes.iter_mut() {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                *byte = state as u8;
+            }
+            let session = match Session::arbitrary(&mut Unstructured::new(&bytes)) {
+                Ok(session) => session,
+                Err(_) => continue,
+            };
+            let script = session.to_string();
+            let read: Session = script.parse().unwrap_or_else(|error| panic!("{}: {}", error, script));
+            assert_eq!(read.to_string(), script);
+            assert_eq!(run(&read).repr(), run(&session).repr(), "{}", script);
+        }
+    }
+
+    #[test]
+    fn test_script_syntax() {
+        let session: Session = "\n  # no calls yet\n[1;2]U(5;inf) ;; ".parse().unwrap();
+        assert_eq!(format!("{:?}", session), "[1;2]⋃(5;∞)");
+
+        let session: Session = r#"new; parse "a;b\"[#\u{301}"; decode 01ff; tighten; many ∅ [1;2]"#.parse().unwrap();
+        match &session.calls[..] {
+            [Call::Parse(input), Call::Decode(bytes), Call::Tighten(constraints), Call::Many(domains)] => {
+                assert_eq!(input, "a;b\"[#\u{301}");
+                assert_eq!(bytes, &[1, 255]);
+                assert!(constraints.is_empty());
+                assert_eq!(domains, &[Domain::None, Domain::between(1, 2)]);
+            },
+            calls => panic!("{:?}", calls),
+        }
+        assert_eq!(session.to_string(), r#"(-∞;∞); parse "a;b\"[#\u{301}"; decode 01ff; tighten; many ∅ [1;2]"#);
+
+        let errors = [
+            ("", 0, "missing starting domain"),
+            ("[1;2", 0, "invalid domain"),
+            ("new; gt I5 I6", 1, "unexpected `I6`"),
+            ("new; repr; fly", 2, "unknown call `fly`"),
+            ("new; shift 1", 1, "missing an overflow policy"),
+            ("new; parse \"\\q\"", 1, "invalid escape"),
+            ("new; decode 0", 1, "expected hex bytes"),
+        ];
+        for (script, statement, message) in errors.iter() {
+            let error = script.parse::<Session>().unwrap_err();
+            assert_eq!(error.statement(), *statement, "{}", script);
+            assert!(error.to_string().starts_with(message), "{}: {}", script, error);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Frozen corpus of domains and every textual form of them. The strings
+//! are load-bearing for users diffing them between versions, so they are
+//! part of the semver contract: a change to any of them is a breaking
+//! change and must show up in `tests/golden/repr.txt`. After a deliberate
+//! formatting change, rewrite the snapshot with
+//! `cargo test --features bless golden` and review its diff
+
+use crate::{Domain, Notation, Value};
+
+const SNAPSHOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/repr.txt");
+
+/// Integer domains built by hand, through the builders and parsed
+fn corpus() -> Vec<(&'static str, Domain<i32>)> {
+    let parsed = |input: &str| input.parse::<Domain<i32>>().unwrap();
+    vec![
+        ("new", Domain::new()),
+        ("none", Domain::None),
+        ("empty union", Domain::Union(vec![])),
+        ("between", Domain::between(1, 5)),
+        ("reversed", Domain::between(5, 1)),
+        ("point", Domain::between(-7, -7)),
+        ("half open", Domain::Domain(Value::Included(0), Value::Secluded(10))),
+        ("open on adjacent points", Domain::Domain(Value::Secluded(5), Value::Secluded(6))),
+        ("at least", Domain::at_least(3)),
+        ("at most", Domain::at_most(-3)),
+        ("i32 range", Domain::between(i32::MIN, i32::MAX)),
+        ("secluded at the limits", Domain::Domain(Value::Secluded(i32::MIN), Value::Secluded(i32::MAX))),
+        ("gt included", Domain::new().gt(Value::Included(5))),
+        ("gt secluded", Domain::new().gt(Value::Secluded(5))),
+        ("gt bare", Domain::between(0, 9).gt(5)),
+        ("lt included", Domain::between(0, 9).lt(Value::Included(5))),
+        ("gt and lt", Domain::new().lt(Value::Included(5)).gt(Value::Secluded(3)).gt(Value::Secluded(1))),
+        ("gt infinite", Domain::new().gt(Value::Infinite)),
+        (
+            "unsorted union",
+            Domain::Union(vec![Domain::between(20, 29), Domain::None, Domain::between(0, 9), Domain::between(8, 12)]),
+        ),
+        ("nested union", Domain::Union(vec![Domain::Union(vec![Domain::at_most(-1)]), Domain::at_least(1)])),
+        ("many members", Domain::Union((0..10).map(|i| Domain::between(i * 3, i * 3 + 1)).collect())),
+        ("normalized", Domain::Union(vec![Domain::between(5, 9), Domain::between(1, 6)]).normalize().into_inner()),
+        ("complement", Domain::between(0, 9).complement().into_inner()),
+        ("parsed", parsed("[1;5)⋃(7;∞)")),
+        ("parsed ascii", parsed("(-inf;-1]U{}")),
+        ("parsed empty", parsed("∅")),
+        ("parsed spaced", parsed(" [ -3 ; 3 ] ∪ [ 10 ; 10 ] ")),
+    ]
+}
+
+/// Float domains, written in the shortest form reading back to the same
+/// value: an integral float prints without a fraction like an integer
+fn float_corpus() -> Vec<(&'static str, Domain<f64>)> {
+    vec![
+        ("integral floats", Domain::Domain(Value::Included(5.0), Value::Secluded(10.0))),
+        ("sum", Domain::Domain(Value::Included(0.1), Value::Secluded(0.1 + 0.2))),
+        ("negative zero", Domain::Domain(Value::Included(-0.0), Value::Infinite)),
+        ("large and small", Domain::Domain(Value::Secluded(1e-7), Value::Included(1e21))),
+        ("union", Domain::Union(vec![Domain::Domain(Value::Infinite, Value::Included(-1.5)), Domain::None])),
+    ]
+}
+
+/// Every form of every domain of the corpus, one line each
+fn render() -> String {
+    let mut lines = Vec::new();
+    for (name, domain) in corpus() {
+        lines.push(format!("== {}", name));
+        lines.push(format!("repr: {}", domain.clone().repr()));
+        lines.push(format!("display: {}", domain));
+        lines.push(format!("display alternate: {:#}", domain));
+        lines.push(format!("ascii: {}", domain.repr_with(&Notation::ASCII)));
+        lines.push(format!("abbreviated: {}", domain.repr_abbreviated(4)));
+        lines.push(format!("range list: {}", domain.to_range_list().unwrap_or_else(|| "unbounded".to_string())));
+        lines.push(format!("sql: {}", domain.to_sql_predicate("x").unwrap()));
+    }
+    for (name, domain) in float_corpus() {
+        lines.push(format!("== {} (f64)", name));
+        lines.push(format!("repr: {}", domain.clone().repr()));
+        lines.push(format!("fixed: {}", domain.repr_with_fmt(|x| format!("{:.2}", x))));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[test]
+fn test_goldens() {
+    let actual = render();
+    if cfg!(feature = "bless") {
+        std::fs::write(SNAPSHOT, &actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(SNAPSHOT).unwrap_or_default();
+    for (number, (actual, expected)) in actual.lines().zip(expected.lines()).enumerate() {
+        assert_eq!(
+            actual,
+            expected,
+            "{} line {} changed, run `cargo test --features bless golden` if the change is deliberate",
+            SNAPSHOT,
+            number + 1
+        );
+    }
+    assert_eq!(actual.lines().count(), expected.lines().count(), "{} has another corpus", SNAPSHOT);
+}
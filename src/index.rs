@@ -0,0 +1,200 @@
+use crate::util;
+use crate::Domain;
+
+/// Read-only lookup structure for answering many point queries against
+/// one domain without allocating. Holds the normalized runs as flat sorted
+/// arrays; infinite borders stop at the `i32` limits like `Domain::iter`
+/// ```
+/// use dynamic_domain::{Domain, DomainIndex};
+/// let domain = Domain::Union(vec![Domain::between(0, 9), Domain::between(20, 29)]);
+/// let index = DomainIndex::build(&domain);
+/// assert!(index.contains(25));
+/// assert_eq!(index.rank(21), 11);
+/// assert_eq!(index.nearest(14), Some(9));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DomainIndex {
+    starts: Vec<i32>,
+    ends: Vec<i32>,
+    /// Values admitted by the runs before each run
+    before: Vec<u64>,
+}
+
+impl DomainIndex {
+
+    /// Normalizes `domain` once and lays its runs out for lookups
+    /// ```
+    /// use dynamic_domain::{Domain, DomainIndex};
+    /// let index = DomainIndex::build(&Domain::between(1, 5));
+    /// assert!(index.contains(3));
+    /// ```
+    pub fn build(domain: &Domain<i32>) -> Self {
+        let runs = util::i32_runs(domain);
+        let mut before = Vec::with_capacity(runs.len());
+        let mut total = 0;
+
+        for (lo, hi) in &runs {
+            before.push(total);
+            total += (hi - lo + 1) as u64;
+        }
+
+        DomainIndex {
+            starts: runs.iter().map(|(lo, _)| *lo as i32).collect(),
+            ends: runs.iter().map(|(_, hi)| *hi as i32).collect(),
+            before,
+        }
+    }
+
+    /// Position of the last run starting at or before `value`
+    fn run_of(&self, value: i32) -> Option<usize> {
+        self.starts.partition_point(|start| *start <= value).checked_sub(1)
+    }
+
+    /// Checks whether `value` is admitted, same as `Domain::contains`
+    /// ```
+    /// use dynamic_domain::{Domain, DomainIndex, Value};
+    /// let index = DomainIndex::build(&Domain::Domain(Value::Included(1), Value::Secluded(5)));
+    /// assert!(index.contains(1));
+    /// assert!(!index.contains(5));
+    /// ```
+    pub fn contains(&self, value: i32) -> bool {
+        self.run_of(value).is_some_and(|run| value <= self.ends[run])
+    }
+
+    /// Number of admitted values below `value`
+    /// ```
+    /// use dynamic_domain::{Domain, DomainIndex};
+    /// let domain = Domain::Union(vec![Domain::between(0, 4), Domain::between(10, 14)]);
+    /// let index = DomainIndex::build(&domain);
+    /// assert_eq!(index.rank(0), 0);
+    /// assert_eq!(index.rank(7), 5);
+    /// assert_eq!(index.rank(12), 7);
+    /// ```
+    pub fn rank(&self, value: i32) -> u64 {
+        match self.run_of(value) {
+            Some(run) => {
+                let below = (value as i64).min(self.ends[run] as i64 + 1) - self.starts[run] as i64;
+                self.before[run] + below as u64
+            },
+            None => 0,
+        }
+    }
+
+    /// Admitted value closest to `value`, the smaller one on a tie,
+    /// same as `Domain::clamp`
+    /// ```
+    /// use dynamic_domain::{Domain, DomainIndex};
+    /// let domain = Domain::Union(vec![Domain::between(0, 4), Domain::between(10, 14)]);
+    /// let index = DomainIndex::build(&domain);
+    /// assert_eq!(index.nearest(7), Some(4));
+    /// assert_eq!(index.nearest(8), Some(10));
+    /// assert_eq!(DomainIndex::build(&Domain::None).nearest(8), None);
+    /// ```
+    pub fn nearest(&self, value: i32) -> Option<i32> {
+        let next = self.starts.partition_point(|start| *start <= value);
+
+        let below = next.checked_sub(1).map(|run| self.ends[run].min(value));
+        let above = self.starts.get(next).copied();
+
+        match (below, above) {
+            (Some(below), Some(above)) => {
+                if value as i64 - below as i64 <= above as i64 - value as i64 {
+                    Some(below)
+                } else {
+                    Some(above)
+                }
+            },
+            (below, above) => below.or(above),
+        }
+    }
+
+    /// Writes `contains` of every value into the matching slot of `out`.
+    /// When the lengths differ only the shorter one is covered, the extra
+    /// values are skipped and the extra slots left untouched
+    /// ```
+    /// use dynamic_domain::{Domain, DomainIndex};
+    /// let index = DomainIndex::build(&Domain::between(0, 9));
+    /// let mut out = [false; 3];
+    /// index.contains_many(&[-1, 0, 10], &mut out);
+    /// assert_eq!(out, [false, true, false]);
+    /// ```
+    pub fn contains_many(&self, values: &[i32], out: &mut [bool]) {
+        for (value, slot) in values.iter().zip(out.iter_mut()) {
+            *slot = self.contains(*value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::{Domain, DomainIndex, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            Just(Value::Infinite),
+            (-1000..1000).prop_map(Value::Included),
+            (-1000..1000).prop_map(Value::Secluded),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..20)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_domain(domain in domain(), queries in prop::collection::vec(-1200..1200, 50)) {
+            let index = DomainIndex::build(&domain);
+            let mut out = vec![false; queries.len()];
+            index.contains_many(&queries, &mut out);
+
+            for (query, found) in queries.iter().zip(out) {
+                prop_assert_eq!(found, domain.contains(*query));
+                prop_assert_eq!(index.contains(*query), found);
+                prop_assert_eq!(index.nearest(*query), domain.clamp(*query));
+                let below: i64 = domain
+                    .runs()
+                    .map(|run| (*run.end()).min(*query as i64 - 1) - run.start() + 1)
+                    .filter(|count| *count > 0)
+                    .sum();
+                prop_assert_eq!(index.rank(*query), below as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_extremes() {
+        let full = DomainIndex::build(&Domain::new());
+        assert!(full.contains(i32::MIN) && full.contains(i32::MAX));
+        assert_eq!(full.rank(i32::MAX), u32::MAX as u64);
+        assert_eq!(full.nearest(0), Some(0));
+
+        let empty = DomainIndex::build(&Domain::None);
+        assert!(!empty.contains(0));
+        assert_eq!(empty.rank(i32::MAX), 0);
+        assert_eq!(empty.nearest(0), None);
+
+        let edges = DomainIndex::build(&Domain::Union(vec![
+            Domain::between(i32::MIN, i32::MIN),
+            Domain::between(i32::MAX, i32::MAX),
+        ]));
+        assert_eq!(edges.nearest(-1), Some(i32::MIN));
+        assert_eq!(edges.nearest(0), Some(i32::MAX));
+        assert_eq!(edges.rank(i32::MAX), 1);
+    }
+
+    #[test]
+    fn test_contains_many_length() {
+        let index = DomainIndex::build(&Domain::between(2, 5));
+        let mut out = [false];
+        index.contains_many(&[2, 9], &mut out);
+        assert_eq!(out, [true]);
+
+        let mut out = [false, true, true];
+        index.contains_many(&[3], &mut out);
+        assert_eq!(out, [true, true, true]);
+    }
+}
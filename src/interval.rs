@@ -0,0 +1,713 @@
+use std::fmt;
+
+use crate::notation;
+use crate::util;
+use crate::{DiscreteStep, Domain, Notation, Value};
+
+/// Checks whether `value` lies between the borders `l` and `r`, shared by
+/// `Interval::contains` and `Domain::contains`
+pub(crate) fn admits<T: PartialOrd>(l: &Value<T>, r: &Value<T>, value: &T) -> bool {
+    let above = match l {
+        Value::Included(l) => l <= value,
+        Value::Secluded(l) => l < value,
+        Value::Infinite => true,
+    };
+    let below = match r {
+        Value::Included(r) => value <= r,
+        Value::Secluded(r) => value < r,
+        Value::Infinite => true,
+    };
+    above && below
+}
+
+/// Message of a `Display` implementation failing to write into a
+/// `String`, which `to_string` panics with as well
+pub(crate) const WRITE_FAILED: &str = "a Display implementation returned an error unexpectedly";
+
+/// Bytes of a point `write_point` holds back to write it with a single
+/// call of `point`, a longer one is written by calling it again
+const HELD: usize = 48;
+
+/// Writer taking in a point to find out whether it has to be quoted,
+/// holding it back while it is short
+struct Scan<'a> {
+    notation: &'a Notation,
+    /// `Notation::reserved_ascii` of `notation`
+    ascii: u128,
+    held: [u8; HELD],
+    len: usize,
+    overflow: bool,
+    reserved: bool,
+}
+
+impl fmt::Write for Scan<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.reserved |= match s.is_ascii() {
+            true => s.bytes().any(|b| self.ascii & 1 << b != 0),
+            false => s.chars().any(|c| self.notation.reserves(c)),
+        };
+        if self.overflow || self.len + s.len() > HELD {
+            self.overflow = true;
+        } else {
+            self.held[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+        }
+        Ok(())
+    }
+}
+
+/// Writer of the inside of a quoted point, `"` and `\` escaped by a `\`
+struct Escaped<'a>(&'a mut dyn fmt::Write);
+
+impl fmt::Write for Escaped<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if c == '"' || c == '\\' {
+                self.0.write_char('\\')?;
+            }
+            self.0.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the point `p` by `point`, quoted when it is empty or has a
+/// character `notation` reserves, so `Domain::parse_points` reads it back
+fn write_point<T>(
+    out: &mut dyn fmt::Write,
+    p: &T,
+    point: &dyn Fn(&mut dyn fmt::Write, &T) -> fmt::Result,
+    notation: &Notation,
+    ascii: u128,
+) -> fmt::Result {
+    let mut scan = Scan { notation, ascii, held: [0; HELD], len: 0, overflow: false, reserved: false };
+    point(&mut scan, p)?;
+    let held = match scan.overflow {
+        false => std::str::from_utf8(&scan.held[..scan.len]).ok(),
+        true => None,
+    };
+    if !scan.reserved && held != Some("") {
+        return match held {
+            Some(text) => out.write_str(text),
+            None => point(out, p),
+        };
+    }
+
+    out.write_char('"')?;
+    let mut escaped = Escaped(out);
+    match held {
+        Some(text) => fmt::Write::write_str(&mut escaped, text)?,
+        None => point(&mut escaped, p)?,
+    }
+    out.write_char('"')
+}
+
+/// Writes `notation` for the borders `l` and `r` into `out`, every point
+/// written by `point` and quoted by `write_point` against the
+/// `Notation::reserved_ascii` of `notation`, without allocating on its own
+pub(crate) fn write_borders<T>(
+    out: &mut dyn fmt::Write,
+    l: &Value<T>,
+    r: &Value<T>,
+    point: &dyn Fn(&mut dyn fmt::Write, &T) -> fmt::Result,
+    notation: &Notation,
+    ascii: u128,
+) -> fmt::Result {
+    let (included, secluded) = (notation.included, notation.secluded);
+    match l {
+        Value::Included(i) => {
+            out.write_char(included.0)?;
+            write_point(out, i, point, notation, ascii)?;
+        },
+        Value::Secluded(i) => {
+            out.write_char(secluded.0)?;
+            write_point(out, i, point, notation, ascii)?;
+        },
+        Value::Infinite => {
+            out.write_char(secluded.0)?;
+            out.write_char('-')?;
+            out.write_str(notation.infinity)?;
+        },
+    }
+    out.write_str(notation.separator)?;
+    match r {
+        Value::Included(i) => {
+            write_point(out, i, point, notation, ascii)?;
+            out.write_char(included.1)
+        },
+        Value::Secluded(i) => {
+            write_point(out, i, point, notation, ascii)?;
+            out.write_char(secluded.1)
+        },
+        Value::Infinite => {
+            out.write_str(notation.infinity)?;
+            out.write_char(secluded.1)
+        },
+    }
+}
+
+/// Single interval between a starting and an ending border, the unit
+/// `Domain::Domain` is made of. Not validated, like `Domain::Domain`, so
+/// it can be empty
+/// ```
+/// use dynamic_domain::{Domain, Interval, Value};
+/// let interval = Interval::new(Value::Included(1), Value::Secluded(5));
+/// assert!(interval.contains(4) && !interval.contains(5));
+/// assert_eq!(Domain::from(interval.clone()), Domain::Domain(Value::Included(1), Value::Secluded(5)));
+/// assert_eq!(interval.repr(), "[1;5)");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Interval<T> {
+    lo: Value<T>,
+    hi: Value<T>,
+}
+
+impl<T> Interval<T> {
+
+    /// Interval from the starting border `lo` to the ending border `hi`
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// let interval = Interval::new(Value::Infinite, Value::Included(0));
+    /// assert_eq!(interval.hi(), &Value::Included(0));
+    /// ```
+    pub fn new(lo: Value<T>, hi: Value<T>) -> Self {
+        Interval { lo, hi }
+    }
+
+    /// The starting border
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// assert_eq!(Interval::new(Value::Secluded(2), Value::Infinite).lo(), &Value::Secluded(2));
+    /// ```
+    pub fn lo(&self) -> &Value<T> {
+        &self.lo
+    }
+
+    /// The ending border
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// assert_eq!(Interval::new(Value::Secluded(2), Value::Infinite).hi(), &Value::Infinite);
+    /// ```
+    pub fn hi(&self) -> &Value<T> {
+        &self.hi
+    }
+
+    /// Both borders, starting one first
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// let (lo, hi) = Interval::new(Value::Included(1), Value::Included(2)).into_borders();
+    /// assert_eq!((lo, hi), (Value::Included(1), Value::Included(2)));
+    /// ```
+    pub fn into_borders(self) -> (Value<T>, Value<T>) {
+        (self.lo, self.hi)
+    }
+
+    /// Same notation as `Domain::repr_with_fmt`
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// let interval = Interval::new(Value::Included(0.5), Value::Infinite);
+    /// assert_eq!(interval.repr_with_fmt(|x| format!("{:.2}", x)), "[0.50;∞)");
+    /// ```
+    pub fn repr_with_fmt(&self, point: impl Fn(&T) -> String) -> String {
+        let mut out = String::new();
+        write_borders(&mut out, &self.lo, &self.hi, &|out, p| out.write_str(&point(p)), &Notation::DEFAULT, notation::DEFAULT_RESERVED).expect(WRITE_FAILED);
+        out
+    }
+}
+
+impl<T: PartialOrd> Interval<T> {
+
+    /// Checks whether `value` is admitted, same as `Domain::contains`
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// let interval = Interval::new(Value::Secluded(0.5), Value::Included(1.0));
+    /// assert!(interval.contains(1.0));
+    /// assert!(!interval.contains(0.5));
+    /// assert!(!interval.contains(f64::NAN));
+    /// ```
+    pub fn contains(&self, value: T) -> bool {
+        value.partial_cmp(&value).is_some() && admits(&self.lo, &self.hi, &value)
+    }
+}
+
+impl<T: Ord> Interval<T> {
+
+    /// Values admitted by both intervals, the tighter border on each
+    /// side. Empty when they do not overlap, check with `is_empty`
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// let a = Interval::new(Value::Included(0), Value::Secluded(10));
+    /// let b = Interval::new(Value::Secluded(5), Value::Infinite);
+    /// assert_eq!(a.intersect(&b).repr(), "(5;10)");
+    /// ```
+    pub fn intersect(&self, other: &Interval<T>) -> Interval<T>
+    where
+        T: Clone,
+    {
+        Interval {
+            lo: self.lo.clone().max_lower(other.lo.clone()),
+            hi: self.hi.clone().min_upper(other.hi.clone()),
+        }
+    }
+
+    /// Smallest interval holding both, the looser border on each side.
+    /// Also spans the gap between intervals which do not overlap
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// let a = Interval::new(Value::Included(0), Value::Included(2));
+    /// let b = Interval::new(Value::Included(7), Value::Secluded(9));
+    /// assert_eq!(a.hull_with(&b).repr(), "[0;9)");
+    /// ```
+    pub fn hull_with(&self, other: &Interval<T>) -> Interval<T>
+    where
+        T: Clone,
+    {
+        Interval {
+            lo: self.lo.clone().min_lower(other.lo.clone()),
+            hi: self.hi.clone().max_upper(other.hi.clone()),
+        }
+    }
+}
+
+impl<T: std::fmt::Display> Interval<T> {
+
+    /// Same notation as `Domain::repr`
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// assert_eq!(Interval::new(Value::Infinite, Value::Included(3)).repr(), "(-∞;3]");
+    /// ```
+    pub fn repr(&self) -> String {
+        let mut out = String::new();
+        write_borders(&mut out, &self.lo, &self.hi, &|out, p| write!(out, "{}", p), &Notation::DEFAULT, notation::DEFAULT_RESERVED).expect(WRITE_FAILED);
+        out
+    }
+}
+
+impl Interval<i32> {
+
+    /// Checks whether the interval admits no `i32`, same as
+    /// `Domain::is_empty`
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// assert!(Interval::new(Value::Secluded(3), Value::Secluded(4)).is_empty());
+    /// assert!(Interval::new(Value::Included(5), Value::Included(1)).is_empty());
+    /// assert!(!Interval::new(Value::Included(3), Value::Included(3)).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        let (first, last) = util::clipped_run(&self.lo, &self.hi);
+        first > last
+    }
+
+    /// Number of admitted values, `None` for an infinite border, same as
+    /// `Domain::cardinality`
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// assert_eq!(Interval::new(Value::Secluded(0), Value::Included(10)).cardinality(), Some(10));
+    /// assert_eq!(Interval::new(Value::Included(5), Value::Included(1)).cardinality(), Some(0));
+    /// assert_eq!(Interval::new(Value::Included(0), Value::Infinite).cardinality(), None);
+    /// ```
+    pub fn cardinality(&self) -> Option<u64> {
+        if self.is_empty() {
+            return Some(0);
+        }
+        if self.lo == Value::Infinite || self.hi == Value::Infinite {
+            return None;
+        }
+        let (first, last) = util::clipped_run(&self.lo, &self.hi);
+        Some((last - first + 1) as u64)
+    }
+}
+
+/// Admitted point of a discrete interval, with the ends of an infinite
+/// border below and above every point
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Step<T> {
+    Below,
+    At(T),
+    Above,
+}
+
+impl<T: DiscreteStep> Interval<T> {
+
+    /// First and last admitted points, `None` when the interval admits
+    /// nothing
+    fn steps(&self) -> Option<(Step<T>, Step<T>)> {
+        let first = match &self.lo {
+            Value::Included(point) => Step::At(point.clone()),
+            Value::Secluded(point) => Step::At(point.successor()?),
+            Value::Infinite => Step::Below,
+        };
+        let last = match &self.hi {
+            Value::Included(point) => Step::At(point.clone()),
+            Value::Secluded(point) => Step::At(point.predecessor()?),
+            Value::Infinite => Step::Above,
+        };
+        if first <= last { Some((first, last)) } else { None }
+    }
+
+    /// Checks whether the intervals admit no common point and no point
+    /// lies between them, integer semantics: `[1;5]` and `[6;9]`, or
+    /// `[1;5)` and `[5;9]`. Never true for an empty interval
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// let low = Interval::new(Value::Included(1), Value::Included(5));
+    /// assert!(low.is_adjacent_to(&Interval::new(Value::Included(6), Value::Included(9))));
+    /// assert!(low.is_adjacent_to(&Interval::new(Value::Secluded(5), Value::Infinite)));
+    /// assert!(!low.is_adjacent_to(&Interval::new(Value::Included(5), Value::Included(9))));
+    /// assert!(!low.is_adjacent_to(&Interval::new(Value::Included(7), Value::Included(9))));
+    /// ```
+    pub fn is_adjacent_to(&self, other: &Interval<T>) -> bool {
+        let next = |last: &Step<T>, first: &Step<T>| match (last, first) {
+            (Step::At(last), Step::At(first)) => last.successor().as_ref() == Some(first),
+            _ => false,
+        };
+        match (self.steps(), other.steps()) {
+            (Some((a_first, a_last)), Some((b_first, b_last))) => next(&a_last, &b_first) || next(&b_last, &a_first),
+            _ => false,
+        }
+    }
+
+    /// The interval admitting the points of both, `None` unless they
+    /// overlap or are adjacent. Each border is spelled as the one of the
+    /// interval reaching further on that side, of `self` on a tie
+    /// ```
+    /// use dynamic_domain::{Interval, Value};
+    /// let low = Interval::new(Value::Included(1), Value::Secluded(5));
+    /// let high = Interval::new(Value::Included(5), Value::Included(9));
+    /// assert_eq!(low.merge_with(&high).unwrap().repr(), "[1;9]");
+    /// assert_eq!(high.merge_with(&Interval::new(Value::Secluded(0), Value::Included(6))).unwrap().repr(), "(0;9]");
+    /// assert_eq!(low.merge_with(&Interval::new(Value::Secluded(5), Value::Included(9))), None);
+    /// ```
+    pub fn merge_with(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let ((a_first, a_last), (b_first, b_last)) = (self.steps()?, other.steps()?);
+        let overlap = a_first <= b_last && b_first <= a_last;
+        if !overlap && !self.is_adjacent_to(other) {
+            return None;
+        }
+        Some(Interval {
+            lo: if b_first < a_first { other.lo.clone() } else { self.lo.clone() },
+            hi: if b_last > a_last { other.hi.clone() } else { self.hi.clone() },
+        })
+    }
+}
+
+/// Checks whether some point lies between a starting and an ending border
+/// of a continuous interval, a `NaN` border admitting nothing
+fn meets<T: PartialOrd>(lo: &Value<T>, hi: &Value<T>) -> bool {
+    let number = |point: &T| point.partial_cmp(point).is_some();
+    match (lo, hi) {
+        (Value::Infinite, Value::Infinite) => true,
+        (Value::Infinite, Value::Included(point))
+        | (Value::Infinite, Value::Secluded(point))
+        | (Value::Included(point), Value::Infinite)
+        | (Value::Secluded(point), Value::Infinite) => number(point),
+        (Value::Included(a), Value::Included(b)) => a <= b,
+        (Value::Included(a), Value::Secluded(b))
+        | (Value::Secluded(a), Value::Included(b))
+        | (Value::Secluded(a), Value::Secluded(b)) => a < b,
+    }
+}
+
+macro_rules! continuous_points {
+    ($($float:ty),*) => {$(
+        impl Interval<$float> {
+
+            /// Checks whether the intervals admit no common point and meet
+            /// at a shared endpoint closed on one side, order-only
+            /// semantics: `[1;5)` and `[5;9]`, but never `(1;5)` and `(5;9)`
+            /// ```
+            /// use dynamic_domain::{Interval, Value};
+            #[doc = concat!("let low = Interval::new(Value::Included(1.0), Value::Secluded(5.0_", stringify!($float), "));")]
+            /// assert!(low.is_adjacent_to(&Interval::new(Value::Included(5.0), Value::Included(9.0))));
+            /// assert!(!low.is_adjacent_to(&Interval::new(Value::Secluded(5.0), Value::Included(9.0))));
+            /// assert!(!low.is_adjacent_to(&Interval::new(Value::Included(4.0), Value::Included(9.0))));
+            /// ```
+            pub fn is_adjacent_to(&self, other: &Interval<$float>) -> bool {
+                let touch = |hi: &Value<$float>, lo: &Value<$float>| match (hi, lo) {
+                    (Value::Included(a), Value::Included(b))
+                    | (Value::Included(a), Value::Secluded(b))
+                    | (Value::Secluded(a), Value::Included(b)) => a == b,
+                    _ => false,
+                };
+                self.is_some() && other.is_some() && !self.overlaps(other)
+                    && (touch(&self.hi, &other.lo) || touch(&other.hi, &self.lo))
+            }
+
+            /// The interval admitting the points of both, `None` unless they
+            /// overlap or are adjacent. Each border is spelled as the one of
+            /// the interval reaching further on that side, of `self` on a tie
+            /// ```
+            /// use dynamic_domain::{Interval, Value};
+            #[doc = concat!("let low = Interval::new(Value::Included(1.0), Value::Secluded(5.0_", stringify!($float), "));")]
+            /// assert_eq!(low.merge_with(&Interval::new(Value::Included(5.0), Value::Infinite)).unwrap().repr(), "[1;∞)");
+            /// assert_eq!(low.merge_with(&Interval::new(Value::Secluded(5.0), Value::Infinite)), None);
+            /// ```
+            pub fn merge_with(&self, other: &Interval<$float>) -> Option<Interval<$float>> {
+                if !self.overlaps(other) && !self.is_adjacent_to(other) {
+                    return None;
+                }
+                let lo = match (&self.lo, &other.lo) {
+                    (Value::Infinite, _) => &self.lo,
+                    (_, Value::Infinite) => &other.lo,
+                    (Value::Secluded(a), Value::Included(b)) if a == b => &other.lo,
+                    (Value::Included(a), Value::Included(b))
+                    | (Value::Included(a), Value::Secluded(b))
+                    | (Value::Secluded(a), Value::Included(b))
+                    | (Value::Secluded(a), Value::Secluded(b)) => if b < a { &other.lo } else { &self.lo },
+                };
+                let hi = match (&self.hi, &other.hi) {
+                    (Value::Infinite, _) => &self.hi,
+                    (_, Value::Infinite) => &other.hi,
+                    (Value::Secluded(a), Value::Included(b)) if a == b => &other.hi,
+                    (Value::Included(a), Value::Included(b))
+                    | (Value::Included(a), Value::Secluded(b))
+                    | (Value::Secluded(a), Value::Included(b))
+                    | (Value::Secluded(a), Value::Secluded(b)) => if b > a { &other.hi } else { &self.hi },
+                };
+                Some(Interval { lo: lo.clone(), hi: hi.clone() })
+            }
+
+            /// Checks whether the interval admits at least one point
+            fn is_some(&self) -> bool {
+                meets(&self.lo, &self.hi)
+            }
+
+            /// Checks whether both intervals admit a common point
+            fn overlaps(&self, other: &Interval<$float>) -> bool {
+                self.is_some() && other.is_some() && meets(&self.lo, &other.hi) && meets(&other.lo, &self.hi)
+            }
+        }
+    )*};
+}
+
+continuous_points!(f32, f64);
+
+/// The interval as a `Domain::Domain`
+/// ```
+/// use dynamic_domain::{Domain, Interval, Value};
+/// let domain = Domain::from(Interval::new(Value::Included(1), Value::Included(2)));
+/// assert_eq!(domain.repr(), "[1;2]");
+/// ```
+impl<T> From<Interval<T>> for Domain<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Domain::Domain(interval.lo, interval.hi)
+    }
+}
+
+impl<T: Clone> Domain<T> {
+
+    /// The borders of a `Domain::Domain` as an `Interval`, `None` for a
+    /// union and for `Domain::None`, even a union of one interval
+    /// ```
+    /// use dynamic_domain::{Domain, Interval, Value};
+    /// assert_eq!(Domain::between(1, 5).as_interval(), Some(Interval::new(Value::Included(1), Value::Included(5))));
+    /// assert_eq!(Domain::Union(vec![Domain::between(1, 5)]).as_interval(), None);
+    /// ```
+    pub fn as_interval(&self) -> Option<Interval<T>> {
+        match self {
+            Domain::Domain(l, r) => Some(Interval::new(l.clone(), r.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl Domain<i32> {
+
+    /// Members of the normalized domain as intervals, in ascending order
+    /// with gaps between them and none of them empty
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(7, 9), Domain::between(5, 1), Domain::between(0, 3)]);
+    /// let intervals: Vec<String> = domain.intervals().iter().map(|interval| interval.repr()).collect();
+    /// assert_eq!(intervals, vec!["[0;3]", "[7;9]"]);
+    /// ```
+    pub fn intervals(&self) -> Vec<Interval<i32>> {
+        util::normalized(self).into_iter().map(|(l, r)| Interval::new(l, r)).collect()
+    }
+
+    /// Splits the normalized domain into the members `pred` accepts and
+    /// the ones it rejects, both normalized. `pred` is called once for
+    /// every member of `intervals`, in ascending order
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let windows = Domain::Union(vec![Domain::between(0, 999), Domain::between(2000, 2004), Domain::at_least(5000)]);
+    /// let (bulk, exceptions) = windows.partition_intervals(|interval| interval.cardinality().map_or(true, |n| n > 100));
+    /// assert_eq!(bulk.repr(), "[0;999]⋃[5000;∞)");
+    /// assert_eq!(exceptions.repr(), "[2000;2004]");
+    /// ```
+    pub fn partition_intervals(&self, pred: impl Fn(&Interval<i32>) -> bool) -> (Domain<i32>, Domain<i32>) {
+        let (matching, rest): (Vec<Interval<i32>>, Vec<Interval<i32>>) = self.intervals().into_iter().partition(|interval| pred(interval));
+        let join = |intervals: Vec<Interval<i32>>| util::from_members(intervals.into_iter().map(Interval::into_borders).collect());
+        (join(matching), join(rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::util;
+    use crate::{assert_domain_eq, Domain, Interval, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> {
+        prop_oneof![
+            1 => Just(Value::Infinite),
+            3 => (-20..20).prop_map(Value::Included),
+            3 => (-20..20).prop_map(Value::Secluded),
+            1 => Just(Value::Included(i32::MIN)),
+            1 => Just(Value::Secluded(i32::MAX)),
+        ]
+    }
+
+    fn interval() -> impl Strategy<Value = Interval<i32>> {
+        (value(), value()).prop_map(|(lo, hi)| Interval::new(lo, hi))
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_domain(a in interval(), b in interval()) {
+            let (da, db) = (Domain::from(a.clone()), Domain::from(b.clone()));
+            prop_assert_eq!(a.is_empty(), da.is_empty());
+            prop_assert_eq!(a.cardinality(), da.cardinality());
+            prop_assert_eq!(a.repr(), da.clone().repr());
+            prop_assert_eq!(Domain::from(a.intersect(&b)), da.intersect(&db).into_inner());
+
+            let hull = Domain::from(a.hull_with(&b));
+            prop_assert!(da.difference(&hull).is_empty() && db.difference(&hull).is_empty());
+            for x in (-25..25).chain(vec![i32::MIN, i32::MAX]) {
+                prop_assert_eq!(a.contains(x), da.contains(x));
+            }
+        }
+
+        #[test]
+        fn test_merge_matches_union(a in interval(), b in interval()) {
+            let (da, db) = (Domain::from(a.clone()), Domain::from(b.clone()));
+            let union = Domain::Union(vec![da.clone(), db.clone()]).normalize();
+            let single = !a.is_empty() && !b.is_empty() && union.members().len() == 1;
+            match a.merge_with(&b) {
+                Some(merged) => {
+                    prop_assert!(single);
+                    prop_assert_eq!(Domain::from(merged), union.into_inner());
+                },
+                None => prop_assert!(!single),
+            }
+            prop_assert_eq!(a.is_adjacent_to(&b), single && da.intersect(&db).is_empty());
+            prop_assert_eq!(a.is_adjacent_to(&b), b.is_adjacent_to(&a));
+        }
+
+        #[test]
+        fn test_partition_splits(members in prop::collection::vec(interval(), 0..6), split in -25..25) {
+            let domain = Domain::Union(members.into_iter().map(Domain::from).collect());
+            let (matching, rest) = domain.partition_intervals(|interval| interval.contains(split));
+            prop_assert!(matching.intersect(&rest).is_empty());
+            prop_assert_eq!(Domain::Union(vec![matching.clone(), rest.clone()]), domain);
+            for half in &[matching, rest] {
+                prop_assert_eq!(half, &half.normalize().into_inner());
+            }
+        }
+    }
+
+    #[test]
+    fn test_intervals() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Secluded(10), Value::Infinite),
+            Domain::between(0, 3),
+            Domain::between(4, 6),
+        ]);
+        let intervals = domain.intervals();
+        assert_eq!(intervals, vec![Interval::new(Value::Included(0), Value::Included(6)), Interval::new(Value::Secluded(10), Value::Infinite)]);
+        let back = Domain::Union(intervals.into_iter().map(Domain::from).collect());
+        assert_domain_eq!(back, domain);
+        assert!(Domain::None.intervals().is_empty());
+        assert_eq!(Domain::<i32>::None.as_interval(), None);
+    }
+
+    #[test]
+    fn test_limits() {
+        let full = Interval::new(Value::Included(i32::MIN), Value::Included(i32::MAX));
+        assert_eq!(full.cardinality(), Some(1 << 32));
+        assert!(full.contains(i32::MIN) && full.contains(i32::MAX));
+        let past = Interval::new(Value::Secluded(i32::MAX), Value::Infinite);
+        assert!(past.is_empty());
+        assert_eq!(past.cardinality(), Some(0));
+        assert_eq!(past.intersect(&full).repr(), "(2147483647;2147483647]");
+        assert_eq!(past.hull_with(&full), Interval::new(Value::Included(i32::MIN), Value::Infinite));
+    }
+
+    #[test]
+    fn test_adjacent_limits() {
+        let low = Interval::new(Value::Infinite, Value::Included(i32::MIN));
+        let rest = Interval::new(Value::Secluded(i32::MIN), Value::Infinite);
+        assert!(low.is_adjacent_to(&rest) && rest.is_adjacent_to(&low));
+        assert_eq!(low.merge_with(&rest), Some(Interval::new(Value::Infinite, Value::Infinite)));
+
+        // Admits no i32, so it is next to nothing
+        let past = Interval::new(Value::Secluded(i32::MAX), Value::Infinite);
+        let top = Interval::new(Value::Included(0), Value::Included(i32::MAX));
+        assert!(!top.is_adjacent_to(&past) && !past.is_adjacent_to(&top));
+        assert_eq!(top.merge_with(&past), None);
+        assert_eq!(Interval::new(Value::Included(5), Value::Included(1)).merge_with(&top), None);
+
+        // Same points, spelled as in `self` on a tie
+        let closed = Interval::new(Value::Included(1), Value::Included(4));
+        let open = Interval::new(Value::Secluded(0), Value::Secluded(5));
+        assert_eq!(closed.merge_with(&open), Some(closed.clone()));
+        assert_eq!(open.merge_with(&closed), Some(open));
+        let letters = Interval::new(Value::Included('a'), Value::Included('f'));
+        assert!(letters.is_adjacent_to(&Interval::new(Value::Included('g'), Value::Infinite)));
+    }
+
+    #[test]
+    fn test_adjacent_continuous() {
+        let low = Interval::new(Value::Included(1.0_f64), Value::Included(5.0));
+        let open = Interval::new(Value::Secluded(5.0_f64), Value::Included(9.0));
+        let closed = Interval::new(Value::Included(5.0_f64), Value::Included(9.0));
+        assert!(low.is_adjacent_to(&open) && open.is_adjacent_to(&low));
+        assert_eq!(low.merge_with(&open).unwrap().repr(), "[1;9]");
+        // Share 5, so they overlap
+        assert!(!low.is_adjacent_to(&closed));
+        assert_eq!(low.merge_with(&closed).unwrap().repr(), "[1;9]");
+
+        // 5 is in neither
+        let below = Interval::new(Value::Included(1.0_f64), Value::Secluded(5.0));
+        assert!(!below.is_adjacent_to(&open));
+        assert_eq!(below.merge_with(&open), None);
+        // Unlike integers, (5;6) is not empty and [6;7] is not next to [1;5]
+        assert!(Interval::new(Value::Secluded(5.0_f64), Value::Secluded(6.0)).merge_with(&low).is_some());
+        assert!(!low.is_adjacent_to(&Interval::new(Value::Included(6.0_f64), Value::Included(7.0))));
+
+        let nan = Interval::new(Value::Included(f64::NAN), Value::Infinite);
+        assert!(!nan.is_adjacent_to(&low) && nan.merge_with(&low).is_none());
+        let empty = Interval::new(Value::Included(5.0_f32), Value::Secluded(5.0));
+        assert_eq!(empty.merge_with(&Interval::new(Value::Included(5.0), Value::Included(5.0))), None);
+        assert_eq!(Interval::new(Value::Infinite, Value::Secluded(0.0_f32)).merge_with(&Interval::new(Value::Included(0.0), Value::Infinite)).unwrap().repr(), "(-∞;∞)");
+    }
+
+    #[test]
+    fn test_partition_intervals() {
+        let domain = Domain::Union(vec![
+            Domain::between(50, 60),
+            Domain::Domain(Value::Infinite, Value::Secluded(-10)),
+            Domain::between(0, 10),
+            Domain::between(5, 1),
+            Domain::between(11, 12),
+        ]);
+        let above_zero = |interval: &Interval<i32>| util::first_admitted(interval.lo()) >= 0;
+
+        let (all, none) = domain.partition_intervals(|_| true);
+        assert_eq!((all, none.clone()), (domain.normalize().into_inner(), Domain::None));
+        let (none, all) = domain.partition_intervals(|_| false);
+        assert_eq!((none, all), (Domain::None, domain.normalize().into_inner()));
+
+        // [0;10] and [11;12] merged before the predicate sees them
+        let (above, below) = domain.partition_intervals(above_zero);
+        assert_eq!(above.repr(), "[0;12]⋃[50;60]");
+        assert_eq!(below.repr(), "(-∞;-10)");
+
+        let single = Domain::between(-5, 5);
+        assert_eq!(single.partition_intervals(|interval| interval.contains(0)), (single.clone(), Domain::None));
+        assert_eq!(single.partition_intervals(|interval| interval.contains(9)), (Domain::None, single));
+        assert_eq!(Domain::None.partition_intervals(|_| true), (Domain::None, Domain::None));
+        assert_eq!(Domain::between(3, 2).partition_intervals(|_| false), (Domain::None, Domain::None));
+    }
+}
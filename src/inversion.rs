@@ -0,0 +1,167 @@
+use crate::util;
+use crate::{Domain, DomainError, Value};
+
+/// One past the greatest `i32`, the exclusive end of a run reaching it
+const END: i64 = i32::MAX as i64 + 1;
+
+impl Domain<i32> {
+
+    /// Inversion list of the admitted values, as ICU and Unicode property
+    /// tables store sets: ascending boundaries, each even index starting a
+    /// run and each odd one ending it, exclusive. A run reaching
+    /// `i32::MAX` ends at `i32::MAX + 1`, hence `i64`. `None` when the
+    /// domain is unbounded, the empty domain gives the empty list
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Union(vec![
+    ///     Domain::Domain(Value::Secluded(0), Value::Included(5)),
+    ///     Domain::between(8, 8),
+    /// ]);
+    /// assert_eq!(domain.to_inversion_list(), Some(vec![1, 6, 8, 9]));
+    /// assert_eq!(Domain::between(0, i32::MAX).to_inversion_list(), Some(vec![0, 1 << 31]));
+    /// assert_eq!(Domain::at_least(0).to_inversion_list(), None);
+    /// ```
+    pub fn to_inversion_list(&self) -> Option<Vec<i64>> {
+        let members = util::normalized(self);
+        if util::is_unbounded(&members) {
+            return None;
+        }
+
+        Some(
+            util::runs_of(&members)
+                .into_iter()
+                .flat_map(|(lo, hi)| [lo, hi + 1])
+                .collect()
+        )
+    }
+
+    /// Domain of an inversion list, as given by `to_inversion_list`. The
+    /// boundaries must be strictly ascending, come in pairs and lie within
+    /// `i32::MIN` and `i32::MAX + 1`
+    /// ```
+    /// use dynamic_domain::{Domain, DomainError};
+    /// let letters = Domain::from_inversion_list(&[0x41, 0x5B, 0x61, 0x7B]).unwrap();
+    /// assert_eq!(letters.repr(), "[65;90]⋃[97;122]");
+    /// assert_eq!(Domain::from_inversion_list(&[]), Ok(Domain::None));
+    /// assert_eq!(Domain::from_inversion_list(&[1, 5, 3, 7]), Err(DomainError::Unsorted(2)));
+    /// assert_eq!(Domain::from_inversion_list(&[1, 5, 7]), Err(DomainError::OddLength(3)));
+    /// assert_eq!(Domain::from_inversion_list(&[0, 1 << 32]), Err(DomainError::OutOfRange(1 << 32)));
+    /// ```
+    pub fn from_inversion_list(list: &[i64]) -> Result<Domain<i32>, DomainError> {
+        if list.len() % 2 == 1 {
+            return Err(DomainError::OddLength(list.len()));
+        }
+        for (i, boundary) in list.iter().enumerate() {
+            if !(i32::MIN as i64..=END).contains(boundary) {
+                return Err(DomainError::OutOfRange(*boundary));
+            }
+            if i > 0 && *boundary <= list[i - 1] {
+                return Err(DomainError::Unsorted(i));
+            }
+        }
+
+        Ok(util::from_members(
+            list.chunks(2)
+                .map(|run| (Value::Included(run[0] as i32), Value::Included((run[1] - 1) as i32)))
+                .collect()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::{Domain, DomainError, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            (-1000..1000).prop_map(Value::Included),
+            (-1000..1000).prop_map(Value::Secluded),
+            Just(Value::Included(i32::MIN)),
+            Just(Value::Included(i32::MAX)),
+            Just(Value::Secluded(i32::MAX)),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..8)
+    }
+
+    fn boundary() -> impl Strategy<Value = i64> {
+        prop_oneof![
+            8 => -20..20i64,
+            1 => Just(i32::MIN as i64),
+            1 => Just(i32::MIN as i64 - 1),
+            1 => Just(i32::MAX as i64),
+            1 => Just(i32::MAX as i64 + 1),
+            1 => Just(i32::MAX as i64 + 2),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_roundtrip(domain in domain()) {
+            let list = domain.to_inversion_list().unwrap();
+            let runs: Vec<i64> = domain.runs().flat_map(|run| [*run.start(), *run.end() + 1]).collect();
+            prop_assert_eq!(&list, &runs);
+
+            let parsed = Domain::from_inversion_list(&list).unwrap();
+            prop_assert_eq!(&parsed, &domain);
+            prop_assert_eq!(parsed.to_inversion_list().unwrap(), list);
+        }
+
+        #[test]
+        fn test_validation(list in prop::collection::vec(boundary(), 0..6)) {
+            let valid = list.len() % 2 == 0
+                && list.iter().all(|b| (i32::MIN as i64..=i32::MAX as i64 + 1).contains(b))
+                && list.windows(2).all(|pair| pair[0] < pair[1]);
+            match Domain::from_inversion_list(&list) {
+                Ok(domain) => {
+                    prop_assert!(valid);
+                    prop_assert_eq!(domain.to_inversion_list().unwrap(), list);
+                },
+                Err(_) => prop_assert!(!valid),
+            }
+        }
+    }
+
+    #[test]
+    fn test_unicode_blocks() {
+        // Basic Latin, then Greek and Coptic with Cyrillic right after it,
+        // then CJK Unified Ideographs
+        let blocks = [0x0000, 0x0080, 0x0370, 0x0400, 0x0400, 0x0500, 0x4E00, 0xA000];
+        assert_eq!(Domain::from_inversion_list(&blocks), Err(DomainError::Unsorted(4)));
+
+        let scripts = Domain::from_inversion_list(&[0x0000, 0x0080, 0x0370, 0x0500, 0x4E00, 0xA000]).unwrap();
+        assert_eq!(scripts.clone().repr(), "[0;127]⋃[880;1279]⋃[19968;40959]");
+        assert!(scripts.contains('λ' as i32) && scripts.contains('ж' as i32) && scripts.contains('字' as i32));
+        assert!(!scripts.contains('é' as i32));
+        assert_eq!(scripts.cardinality(), Some(0x80 + 0x190 + 0x5200));
+
+        let separate = Domain::Union(vec![
+            Domain::between(0x0000, 0x007F),
+            Domain::between(0x0370, 0x03FF),
+            Domain::between(0x0400, 0x04FF),
+            Domain::between(0x4E00, 0x9FFF),
+        ]);
+        assert_eq!(separate.to_inversion_list(), Some(vec![0x0000, 0x0080, 0x0370, 0x0500, 0x4E00, 0xA000]));
+    }
+
+    #[test]
+    fn test_limits() {
+        let full = Domain::between(i32::MIN, i32::MAX);
+        assert_eq!(full.to_inversion_list(), Some(vec![i32::MIN as i64, i32::MAX as i64 + 1]));
+        assert_eq!(Domain::from_inversion_list(&[i32::MIN as i64, i32::MAX as i64 + 1]), Ok(full));
+        assert_eq!(Domain::new().to_inversion_list(), None);
+        assert_eq!(Domain::None.to_inversion_list(), Some(vec![]));
+        assert_eq!(Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite).to_inversion_list(), Some(vec![]));
+
+        let top = Domain::from_inversion_list(&[i32::MAX as i64, i32::MAX as i64 + 1]).unwrap();
+        assert_eq!(top.repr(), "[2147483647;2147483647]");
+        assert_eq!(Domain::from_inversion_list(&[i32::MIN as i64 - 1, 0]), Err(DomainError::OutOfRange(i32::MIN as i64 - 1)));
+        assert_eq!(Domain::from_inversion_list(&[5, 5]), Err(DomainError::Unsorted(1)));
+        assert_eq!(Domain::from_inversion_list(&[0]), Err(DomainError::OddLength(1)));
+    }
+}
@@ -0,0 +1,637 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::iter::FusedIterator;
+use std::ops::RangeInclusive;
+use std::slice;
+
+use crate::util;
+use crate::{CapExceeded, Domain, OrderError};
+
+/// Order in which `Domain::iter_ordered` yields the admitted values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IterOrder {
+    /// Smallest value first, needs a finite starting border
+    Ascending,
+    /// Greatest value first, needs a finite ending border
+    Descending,
+    /// Nearest to the middle of the domain first, needs both borders finite
+    FromMidpoint,
+    /// Nearest to the given value first, the greater one on a tie
+    FromValue(i32),
+}
+
+/// How `DomainIter` picks the next value
+#[derive(Clone, Copy, Debug)]
+enum Strategy {
+    Ascending,
+    Descending,
+    /// Nearest to the point first, values past it are in `above`
+    Outward(i64),
+}
+
+/// Ascending closed runs consumed from both ends
+#[derive(Clone, Debug)]
+struct Runs {
+    runs: Vec<(i64, i64)>,
+    front: usize,
+    back: usize,
+}
+
+impl Runs {
+    fn new(runs: Vec<(i64, i64)>) -> Self {
+        Runs { back: runs.len(), runs, front: 0 }
+    }
+
+    /// Smallest value left, exhausted runs are skipped on the way
+    fn first(&mut self) -> Option<i64> {
+        while self.front < self.back {
+            let (lo, hi) = self.runs[self.front];
+            if lo <= hi {
+                return Some(lo);
+            }
+            self.front += 1;
+        }
+        None
+    }
+
+    /// Greatest value left, exhausted runs are skipped on the way
+    fn last(&mut self) -> Option<i64> {
+        while self.front < self.back {
+            let (lo, hi) = self.runs[self.back - 1];
+            if lo <= hi {
+                return Some(hi);
+            }
+            self.back -= 1;
+        }
+        None
+    }
+
+    fn pop_first(&mut self) -> Option<i32> {
+        let value = self.first()?;
+        self.runs[self.front].0 += 1;
+        Some(value as i32)
+    }
+
+    fn pop_last(&mut self) -> Option<i32> {
+        let value = self.last()?;
+        self.runs[self.back - 1].1 -= 1;
+        Some(value as i32)
+    }
+
+    /// Number of values left, exact even when it does not fit into `usize`
+    fn remaining(&self) -> u64 {
+        self.runs[self.front..self.back]
+            .iter()
+            .filter(|(lo, hi)| lo <= hi)
+            .map(|(lo, hi)| (hi - lo + 1) as u64)
+            .sum()
+    }
+}
+
+/// Iterator over the values admitted by a domain, created by `Domain::iter`
+/// in ascending order or by `Domain::iter_ordered`
+#[derive(Clone, Debug)]
+pub struct DomainIter {
+    strategy: Strategy,
+    /// Every run in the ascending and descending order,
+    /// the values from the point on in the outward one
+    above: Runs,
+    /// Values before the point in the outward order
+    below: Runs,
+}
+
+impl DomainIter {
+    pub(crate) fn new(runs: Vec<(i64, i64)>) -> Self {
+        DomainIter::with(Strategy::Ascending, runs)
+    }
+
+    fn with(strategy: Strategy, runs: Vec<(i64, i64)>) -> Self {
+        let below = match strategy {
+            Strategy::Outward(point) => runs
+                .iter()
+                .filter(|(lo, _)| *lo < point)
+                .map(|(lo, hi)| (*lo, (*hi).min(point - 1)))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let above = match strategy {
+            Strategy::Outward(point) => runs
+                .into_iter()
+                .filter(|(_, hi)| *hi >= point)
+                .map(|(lo, hi)| (lo.max(point), hi))
+                .collect(),
+            _ => runs,
+        };
+        DomainIter { strategy, above: Runs::new(above), below: Runs::new(below) }
+    }
+
+    /// Yields at most `max` values, then a single `CapExceeded` error
+    /// when there are more left
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let mut capped = Domain::at_least(0).iter().capped(2);
+    /// assert_eq!(capped.next(), Some(Ok(0)));
+    /// assert_eq!(capped.next(), Some(Ok(1)));
+    /// let error = capped.next().unwrap().unwrap_err();
+    /// assert_eq!((error.emitted(), error.next()), (2, 2));
+    /// assert_eq!(capped.next(), None);
+    /// ```
+    pub fn capped(self, max: u64) -> Capped {
+        Capped { iter: self, max, emitted: 0, done: false }
+    }
+
+    /// Number of values left, exact even when it does not fit into `usize`
+    fn remaining(&self) -> u64 {
+        self.above.remaining() + self.below.remaining()
+    }
+}
+
+impl Iterator for DomainIter {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        match self.strategy {
+            Strategy::Ascending => self.above.pop_first(),
+            Strategy::Descending => self.above.pop_last(),
+            Strategy::Outward(point) => match (self.above.first(), self.below.last()) {
+                (Some(above), Some(below)) if point - below < above - point => self.below.pop_last(),
+                (Some(_), _) => self.above.pop_first(),
+                (None, _) => self.below.pop_last(),
+            },
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        match usize::try_from(remaining) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+/// Yields the same values backwards, the farthest
+/// from the point first in the outward order
+impl DoubleEndedIterator for DomainIter {
+    fn next_back(&mut self) -> Option<i32> {
+        match self.strategy {
+            Strategy::Ascending => self.above.pop_last(),
+            Strategy::Descending => self.above.pop_first(),
+            Strategy::Outward(point) => match (self.below.first(), self.above.last()) {
+                (Some(below), Some(above)) if above - point > point - below => self.above.pop_last(),
+                (Some(_), _) => self.below.pop_first(),
+                (None, _) => self.above.pop_last(),
+            },
+        }
+    }
+}
+
+impl FusedIterator for DomainIter {}
+
+/// Iterator over at most a given number of admitted values,
+/// created by `DomainIter::capped`
+#[derive(Clone, Debug)]
+pub struct Capped {
+    iter: DomainIter,
+    max: u64,
+    emitted: u64,
+    done: bool,
+}
+
+impl Iterator for Capped {
+    type Item = Result<i32, CapExceeded>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let value = self.iter.next();
+        if self.emitted == self.max || value.is_none() {
+            self.done = true;
+            return value.map(|next| Err(CapExceeded::new(self.emitted, next)));
+        }
+
+        self.emitted += 1;
+        value.map(Ok)
+    }
+}
+
+impl FusedIterator for Capped {}
+
+impl Domain<i32> {
+
+    /// Iterates admitted values in ascending order, overlapping members
+    /// yield their values once. Infinite borders stop at the `i32` limits
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Union(vec![
+    ///     Domain::Domain(Value::Secluded(5), Value::Included(7)),
+    ///     Domain::Domain(Value::Included(1), Value::Included(2)),
+    /// ]);
+    /// assert_eq!(domain.iter().collect::<Vec<i32>>(), vec![1, 2, 6, 7]);
+    /// ```
+    pub fn iter(&self) -> DomainIter {
+        DomainIter::new(util::i32_runs(self))
+    }
+
+    /// Iterates admitted values in the given order, overlapping members
+    /// yield their values once and infinite borders stop at the `i32`
+    /// limits like `iter`. An order starting from an infinite border has
+    /// no first value and is refused with `OrderError`
+    /// ```
+    /// use dynamic_domain::{Domain, IterOrder};
+    /// let domain = Domain::Union(vec![Domain::between(1, 2), Domain::between(5, 6)]);
+    /// let values = |order| domain.iter_ordered(order).unwrap().collect::<Vec<i32>>();
+    /// assert_eq!(values(IterOrder::Descending), vec![6, 5, 2, 1]);
+    /// assert_eq!(values(IterOrder::FromMidpoint), vec![2, 5, 1, 6]);
+    /// assert_eq!(values(IterOrder::FromValue(5)), vec![5, 6, 2, 1]);
+    ///
+    /// let below = Domain::at_most(10);
+    /// assert!(below.iter_ordered(IterOrder::Ascending).is_err());
+    /// assert_eq!(below.iter_ordered(IterOrder::Descending).unwrap().nth(3), Some(7));
+    /// ```
+    pub fn iter_ordered(&self, order: IterOrder) -> Result<DomainIter, OrderError> {
+        let members = util::normalized(self);
+        let lower = members.first().is_some_and(|(l, _)| l.is_infinite());
+        let upper = members.last().is_some_and(|(_, r)| r.is_infinite());
+        let runs = util::runs_of(&members);
+
+        let strategy = match order {
+            IterOrder::Ascending if !lower => Strategy::Ascending,
+            IterOrder::Descending if !upper => Strategy::Descending,
+            IterOrder::FromMidpoint if !lower && !upper => {
+                let first = runs.first().map_or(0, |(lo, _)| *lo);
+                let last = runs.last().map_or(0, |(_, hi)| *hi);
+                Strategy::Outward((first + last).div_euclid(2))
+            },
+            IterOrder::FromValue(value) => Strategy::Outward(value as i64),
+            _ => return Err(OrderError::new(order)),
+        };
+        Ok(DomainIter::with(strategy, runs))
+    }
+
+    /// Iterates maximal blocks of consecutive admitted values in ascending
+    /// order, overlapping and adjacent members form a single run.
+    /// Infinite borders stop at the `i32` limits like `iter`
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Union(vec![
+    ///     Domain::Domain(Value::Included(20), Value::Included(30)),
+    ///     Domain::Domain(Value::Secluded(4), Value::Secluded(10)),
+    /// ]);
+    /// assert_eq!(domain.runs().collect::<Vec<_>>(), vec![5..=9, 20..=30]);
+    /// ```
+    pub fn runs(&self) -> impl Iterator<Item = RangeInclusive<i64>> {
+        util::i32_runs(self)
+            .into_iter()
+            .map(|(lo, hi)| lo..=hi)
+    }
+
+    /// Iterates admitted values within [lo;hi] in ascending order, same as
+    /// iterating `intersect` with the window. Normalized domains, as given by
+    /// `normalize` and the set operations, are walked in place starting
+    /// from a binary search, others are normalized first
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(0, 9), Domain::between(20, 29)]);
+    /// assert_eq!(domain.iter_in(8, 21).collect::<Vec<i32>>(), vec![8, 9, 20, 21]);
+    /// assert_eq!(domain.iter_in(10, 19).count(), 0);
+    /// ```
+    pub fn iter_in(&self, lo: i32, hi: i32) -> impl Iterator<Item = i32> + '_ {
+        let members: Cow<'_, [Domain<i32>]> = match self {
+            Domain::Union(domains) if util::is_normalized(domains) => Cow::Borrowed(domains),
+            Domain::Union(_) => Cow::Owned(
+                util::normalized(self)
+                    .into_iter()
+                    .map(|(l, r)| Domain::Domain(l, r))
+                    .collect()
+            ),
+            Domain::Domain(..) => Cow::Borrowed(slice::from_ref(self)),
+            Domain::None => Cow::Borrowed(&[]),
+        };
+
+        let run = |domain: &Domain<i32>| match domain {
+            Domain::Domain(l, r) => util::clipped_run(l, r),
+            _ => (1, 0),
+        };
+        let (lo, hi) = (lo as i64, hi as i64);
+        let start = members.partition_point(|domain| run(domain).1 < lo);
+
+        (start..members.len())
+            .map(move |i| run(&members[i]))
+            .take_while(move |(first, _)| *first <= hi)
+            .flat_map(move |(first, last)| first.max(lo)..=last.min(hi))
+            .map(|value| value as i32)
+    }
+}
+
+/// Owning iteration moves the members out of the domain
+/// instead of cloning them
+/// ```
+/// use dynamic_domain::Domain;
+/// let values: Vec<i32> = Domain::between(1, 3).into_iter().collect();
+/// assert_eq!(values, vec![1, 2, 3]);
+/// ```
+impl IntoIterator for Domain<i32> {
+    type Item = i32;
+    type IntoIter = DomainIter;
+
+    fn into_iter(self) -> DomainIter {
+        let members = util::normalize_members(util::into_members(self));
+        DomainIter::new(util::runs_of(&members))
+    }
+}
+
+/// Same as `Domain::iter`
+/// ```
+/// use dynamic_domain::Domain;
+/// let domain = Domain::between(1, 3);
+/// for value in &domain {
+///     assert!(domain.contains(value));
+/// }
+/// ```
+impl IntoIterator for &Domain<i32> {
+    type Item = i32;
+    type IntoIter = DomainIter;
+
+    fn into_iter(self) -> DomainIter {
+        self.iter()
+    }
+}
+
+/// Values are plain integers, so there is nothing to mutate:
+/// same as `Domain::iter`
+impl IntoIterator for &mut Domain<i32> {
+    type Item = i32;
+    type IntoIter = DomainIter;
+
+    fn into_iter(self) -> DomainIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::{Domain, IterOrder, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            1 => Just(Value::Infinite),
+            2 => (-100..100).prop_map(Value::Included),
+            2 => (-100..100).prop_map(Value::Secluded),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..6)
+    }
+
+    proptest! {
+        #[test]
+        fn test_iter_in_matches_intersect(domain in domain(), lo in -120..120, len in 0..100, normalize: bool) {
+            let domain = if normalize { domain.normalize().into_inner() } else { domain };
+            let hi = lo + len - 10;
+            let expected: Vec<i32> = domain.intersect(&Domain::between(lo, hi)).iter().collect();
+            prop_assert_eq!(domain.iter_in(lo, hi).collect::<Vec<i32>>(), expected);
+        }
+    }
+
+    /// Values 1, 2, 5, 6, 9 and 10 written with open borders and unsorted
+    fn gappy() -> Domain<i32> {
+        Domain::Union(vec![
+            Domain::Domain(Value::Secluded(8), Value::Included(10)),
+            Domain::Domain(Value::Secluded(0), Value::Secluded(3)),
+            Domain::Union(vec![Domain::between(5, 6)]),
+            Domain::Domain(Value::Included(5), Value::Secluded(6)),
+        ])
+    }
+
+    fn ordered(domain: &Domain<i32>, order: IterOrder) -> Vec<i32> {
+        domain.iter_ordered(order).unwrap().collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_orders_match_iter(domain in domain(), value in -120..120) {
+            let domain = domain.intersect(&Domain::between(-150, 150));
+            let ascending: Vec<i32> = domain.iter().collect();
+            for order in [IterOrder::Ascending, IterOrder::Descending, IterOrder::FromMidpoint, IterOrder::FromValue(value)] {
+                let mut values = ordered(&domain, order);
+                let mut backwards: Vec<i32> = domain.iter_ordered(order).unwrap().rev().collect();
+                backwards.reverse();
+                prop_assert_eq!(&backwards, &values);
+                values.sort_unstable();
+                prop_assert_eq!(&values, &ascending);
+            }
+
+            let outward = ordered(&domain, IterOrder::FromValue(value));
+            let distance = |x: &i32| ((*x - value).abs(), -*x);
+            prop_assert!(outward.windows(2).all(|pair| distance(&pair[0]) < distance(&pair[1])));
+        }
+    }
+
+    #[test]
+    fn test_ascending_and_descending() {
+        let domain = gappy();
+        assert_eq!(ordered(&domain, IterOrder::Ascending), vec![1, 2, 5, 6, 9, 10]);
+        assert_eq!(ordered(&domain, IterOrder::Descending), vec![10, 9, 6, 5, 2, 1]);
+        assert_eq!(domain.iter_ordered(IterOrder::Descending).unwrap().size_hint(), (6, Some(6)));
+
+        let mut iter = domain.iter_ordered(IterOrder::Descending).unwrap();
+        assert_eq!((iter.next(), iter.next_back(), iter.next()), (Some(10), Some(1), Some(9)));
+    }
+
+    #[test]
+    fn test_from_midpoint() {
+        let domain = gappy();
+        assert_eq!(ordered(&domain, IterOrder::FromMidpoint), vec![5, 6, 2, 9, 1, 10]);
+        assert_eq!(
+            domain.iter_ordered(IterOrder::FromMidpoint).unwrap().rev().collect::<Vec<i32>>(),
+            vec![10, 1, 9, 2, 6, 5]
+        );
+        assert_eq!(ordered(&Domain::between(-3, -2), IterOrder::FromMidpoint), vec![-3, -2]);
+    }
+
+    #[test]
+    fn test_from_value() {
+        let domain = gappy();
+        assert_eq!(ordered(&domain, IterOrder::FromValue(4)), vec![5, 6, 2, 1, 9, 10]);
+        assert_eq!(ordered(&domain, IterOrder::FromValue(8)), vec![9, 10, 6, 5, 2, 1]);
+        assert_eq!(ordered(&domain, IterOrder::FromValue(100)), vec![10, 9, 6, 5, 2, 1]);
+        assert_eq!(ordered(&domain, IterOrder::FromValue(-100)), vec![1, 2, 5, 6, 9, 10]);
+
+        let mut iter = domain.iter_ordered(IterOrder::FromValue(4)).unwrap();
+        assert_eq!((iter.next(), iter.next_back(), iter.next_back()), (Some(5), Some(10), Some(9)));
+        assert_eq!(iter.collect::<Vec<i32>>(), vec![6, 2, 1]);
+    }
+
+    #[test]
+    fn test_orders_on_infinite() {
+        let below = Domain::Union(vec![Domain::at_most(0), Domain::between(5, 6)]);
+        let error = below.iter_ordered(IterOrder::Ascending).unwrap_err();
+        assert_eq!(error.order(), IterOrder::Ascending);
+        assert!(below.iter_ordered(IterOrder::FromMidpoint).is_err());
+        let descending: Vec<i32> = below.iter_ordered(IterOrder::Descending).unwrap().take(4).collect();
+        assert_eq!(descending, vec![6, 5, 0, -1]);
+
+        let above = Domain::Domain(Value::Secluded(3), Value::Infinite);
+        assert!(above.iter_ordered(IterOrder::Descending).is_err());
+        let ascending: Vec<i32> = above.iter_ordered(IterOrder::Ascending).unwrap().take(2).collect();
+        assert_eq!(ascending, vec![4, 5]);
+
+        let full = Domain::new();
+        let outward: Vec<i32> = full.iter_ordered(IterOrder::FromValue(0)).unwrap().take(5).collect();
+        assert_eq!(outward, vec![0, 1, -1, 2, -2]);
+        let edge: Vec<i32> = full.iter_ordered(IterOrder::FromValue(i32::MAX)).unwrap().take(2).collect();
+        assert_eq!(edge, vec![i32::MAX, i32::MAX - 1]);
+        let edge: Vec<i32> = full.iter_ordered(IterOrder::FromValue(i32::MIN)).unwrap().take(2).collect();
+        assert_eq!(edge, vec![i32::MIN, i32::MIN + 1]);
+        assert_eq!(full.iter_ordered(IterOrder::FromValue(0)).unwrap().next_back(), Some(i32::MIN));
+    }
+
+    #[test]
+    fn test_orders_on_empty() {
+        let empty = Domain::Domain(Value::Infinite, Value::Secluded(i32::MIN));
+        for order in [IterOrder::Ascending, IterOrder::Descending, IterOrder::FromMidpoint, IterOrder::FromValue(0)] {
+            assert_eq!(empty.iter_ordered(order).unwrap().next(), None);
+            assert_eq!(Domain::None.iter_ordered(order).unwrap().next(), None);
+        }
+    }
+
+    fn total(values: impl IntoIterator<Item = i32>) -> i64 {
+        values.into_iter().map(|value| value as i64).sum()
+    }
+
+    #[test]
+    fn test_iter() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Included(3), Value::Included(6)),
+            Domain::Domain(Value::Secluded(0), Value::Secluded(4)),
+            Domain::Domain(Value::Included(10), Value::Secluded(10)),
+        ]);
+        assert_eq!(domain.iter().collect::<Vec<i32>>(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(domain.iter().rev().collect::<Vec<i32>>(), vec![6, 5, 4, 3, 2, 1]);
+        assert_eq!(domain.iter().size_hint(), (6, Some(6)));
+        assert_eq!(Domain::None.iter().next(), None);
+    }
+
+    #[test]
+    fn test_runs_merge() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Included(6), Value::Included(8)),
+            Domain::Domain(Value::Included(1), Value::Included(3)),
+            Domain::Domain(Value::Secluded(3), Value::Secluded(6)),
+            Domain::Domain(Value::Included(2), Value::Included(2)),
+        ]);
+        assert_eq!(domain.runs().collect::<Vec<_>>(), vec![1..=8]);
+
+        let apart = Domain::Union(vec![Domain::between(1, 3), Domain::between(5, 6)]);
+        assert_eq!(apart.runs().collect::<Vec<_>>(), vec![1..=3, 5..=6]);
+    }
+
+    #[test]
+    fn test_runs_empty_and_full() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Secluded(3), Value::Secluded(4)),
+            Domain::Domain(Value::Secluded(7), Value::Included(8)),
+            Domain::Domain(Value::Included(10), Value::Secluded(10)),
+        ]);
+        assert_eq!(domain.runs().collect::<Vec<_>>(), vec![8..=8]);
+        assert_eq!(Domain::None.runs().count(), 0);
+        assert_eq!(
+            Domain::new().runs().collect::<Vec<_>>(),
+            vec![i32::MIN as i64..=i32::MAX as i64]
+        );
+    }
+
+    #[test]
+    fn test_iter_both_ends() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Included(1), Value::Included(2)),
+            Domain::Domain(Value::Included(5), Value::Included(5)),
+        ]);
+        let mut iter = domain.iter();
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let mut domain = Domain::Union(vec![
+            Domain::between(1, 3),
+            Domain::Union(vec![Domain::between(10, 11)]),
+            Domain::between(2, 4),
+        ]);
+        assert_eq!(total(&domain), 31);
+        assert_eq!(total(&mut domain), 31);
+        assert_eq!(total(domain.clone()), 31);
+
+        let mut values = Vec::new();
+        for value in domain {
+            values.push(value);
+        }
+        assert_eq!(values, vec![1, 2, 3, 4, 10, 11]);
+    }
+
+    #[test]
+    fn test_capped() {
+        let domain = Domain::Union(vec![Domain::between(1, 2), Domain::between(5, 6)]);
+        let values: Vec<_> = domain.iter().capped(4).collect();
+        assert_eq!(values, vec![Ok(1), Ok(2), Ok(5), Ok(6)]);
+
+        let values: Vec<_> = domain.iter().capped(3).collect();
+        assert_eq!(values[..3], [Ok(1), Ok(2), Ok(5)]);
+        let error = values[3].clone().unwrap_err();
+        assert_eq!((error.emitted(), error.next()), (3, 6));
+        assert_eq!(values.len(), 4);
+
+        let mut empty = Domain::None.iter().capped(0);
+        assert_eq!(empty.next(), None);
+
+        let mut full = Domain::new().iter().capped(0);
+        assert_eq!(full.next().unwrap().unwrap_err().next(), i32::MIN);
+        assert_eq!(full.next(), None);
+    }
+
+    #[test]
+    fn test_iter_in_limits() {
+        let full = Domain::new();
+        assert_eq!(full.iter_in(i32::MAX - 1, i32::MAX).collect::<Vec<i32>>(), vec![i32::MAX - 1, i32::MAX]);
+        assert_eq!(full.iter_in(i32::MIN, i32::MIN).collect::<Vec<i32>>(), vec![i32::MIN]);
+        assert_eq!(full.iter_in(5, 4).count(), 0);
+        assert_eq!(Domain::None.iter_in(i32::MIN, i32::MAX).count(), 0);
+
+        let empty = Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite);
+        assert_eq!(empty.iter_in(i32::MIN, i32::MAX).count(), 0);
+
+        let unsorted = Domain::Union(vec![
+            Domain::between(20, 29),
+            Domain::Union(vec![Domain::between(0, 9)]),
+            Domain::between(5, 12),
+        ]);
+        assert_eq!(unsorted.iter_in(11, 20).collect::<Vec<i32>>(), vec![11, 12, 20]);
+    }
+
+    #[test]
+    fn test_iter_limits() {
+        let domain = Domain::Domain(Value::Secluded(i32::MAX - 2), Value::Infinite);
+        assert_eq!(domain.iter().collect::<Vec<i32>>(), vec![i32::MAX - 1, i32::MAX]);
+
+        let domain = Domain::new().lt(Value::Included(i32::MIN + 1));
+        assert_eq!(domain.iter().collect::<Vec<i32>>(), vec![i32::MIN, i32::MIN + 1]);
+
+        let (lower, upper) = Domain::new().iter().size_hint();
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!((lower as u64, upper.map(|n| n as u64)), (1 << 32, Some(1 << 32)));
+        } else {
+            assert_eq!((lower, upper), (usize::MAX, None));
+        }
+    }
+}
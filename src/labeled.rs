@@ -0,0 +1,347 @@
+use std::fmt;
+
+use crate::util::{self, first_admitted, last_admitted};
+use crate::{Domain, Interval, NormalizedDomain, UNION};
+
+/// Union whose every interval carries a label, like the upstream rule
+/// which admitted it, so a value can be traced back to its source.
+/// Operations cutting intervals keep the label on every fragment, and
+/// `normalize` never merges intervals of different labels: they may keep
+/// overlapping
+/// ```
+/// use dynamic_domain::{Domain, LabeledDomain};
+/// let mut rules = LabeledDomain::new();
+/// rules.push_domain(&Domain::between(0, 9), "small");
+/// rules.push_domain(&Domain::at_least(100), "large");
+/// assert_eq!(rules.contains_labeled(5), Some(&"small"));
+/// assert_eq!(rules.nearest_labeled(42), Some((9, &"small")));
+/// assert_eq!(rules.repr_with_labels(), "[0;9]{small}⋃[100;∞){large}");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LabeledDomain<T, L> {
+    members: Vec<(Interval<T>, L)>,
+}
+
+impl<T, L> Default for LabeledDomain<T, L> {
+    fn default() -> Self {
+        LabeledDomain { members: Vec::new() }
+    }
+}
+
+impl<T, L> LabeledDomain<T, L> {
+
+    /// Labeled domain without any interval, admitting nothing
+    /// ```
+    /// use dynamic_domain::LabeledDomain;
+    /// let domain = LabeledDomain::<i32, &str>::new();
+    /// assert!(domain.members().is_empty());
+    /// ```
+    pub fn new() -> Self {
+        LabeledDomain::default()
+    }
+
+    /// Adds `interval` labeled `label` after the others, as given
+    /// ```
+    /// use dynamic_domain::{Interval, LabeledDomain, Value};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push(Interval::new(Value::Included(1), Value::Secluded(5)), 'a');
+    /// assert_eq!(domain.members()[0].1, 'a');
+    /// ```
+    pub fn push(&mut self, interval: Interval<T>, label: L) {
+        self.members.push((interval, label));
+    }
+
+    /// The labeled intervals in their order
+    /// ```
+    /// use dynamic_domain::{Domain, Interval, LabeledDomain, Value};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push_domain(&Domain::between(0, 3), 1);
+    /// assert_eq!(domain.members(), &[(Interval::new(Value::Included(0), Value::Included(3)), 1)]);
+    /// ```
+    pub fn members(&self) -> &[(Interval<T>, L)] {
+        &self.members
+    }
+}
+
+impl<L> LabeledDomain<i32, L> {
+
+    /// Adds every member of the normalized `domain`, all labeled `label`
+    /// ```
+    /// use dynamic_domain::{Domain, LabeledDomain};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push_domain(&Domain::Union(vec![Domain::between(5, 9), Domain::between(0, 1)]), "rule");
+    /// assert_eq!(domain.repr_with_labels(), "[0;1]{rule}⋃[5;9]{rule}");
+    /// ```
+    pub fn push_domain(&mut self, domain: &Domain<i32>, label: L)
+    where
+        L: Clone,
+    {
+        for interval in domain.intervals() {
+            self.members.push((interval, label.clone()));
+        }
+    }
+
+    /// Values admitted by any of the intervals, labels dropped
+    /// ```
+    /// use dynamic_domain::{Domain, LabeledDomain};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push_domain(&Domain::between(0, 5), 'a');
+    /// domain.push_domain(&Domain::between(3, 9), 'b');
+    /// assert_eq!(domain.to_domain().repr(), "[0;9]");
+    /// ```
+    pub fn to_domain(&self) -> NormalizedDomain<i32> {
+        NormalizedDomain::from_members(
+            self.members.iter().map(|(interval, _)| (interval.lo().clone(), interval.hi().clone())).collect()
+        )
+    }
+
+    /// Label of the first interval admitting `value`
+    /// ```
+    /// use dynamic_domain::{Domain, LabeledDomain};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push_domain(&Domain::between(0, 5), 'a');
+    /// domain.push_domain(&Domain::between(3, 9), 'b');
+    /// assert_eq!(domain.contains_labeled(4), Some(&'a'));
+    /// assert_eq!(domain.contains_labeled(7), Some(&'b'));
+    /// assert_eq!(domain.contains_labeled(10), None);
+    /// ```
+    pub fn contains_labeled(&self, value: i32) -> Option<&L> {
+        self.members.iter().find(|(interval, _)| interval.contains(value)).map(|(_, label)| label)
+    }
+
+    /// Admitted value closest to `value` with the label of its interval,
+    /// ties going to the first interval. Infinite borders stop at the
+    /// `i32` limits
+    /// ```
+    /// use dynamic_domain::{Domain, LabeledDomain};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push_domain(&Domain::between(0, 9), "low");
+    /// domain.push_domain(&Domain::between(20, 29), "high");
+    /// assert_eq!(domain.nearest_labeled(16), Some((20, &"high")));
+    /// assert_eq!(domain.nearest_labeled(5), Some((5, &"low")));
+    /// ```
+    pub fn nearest_labeled(&self, value: i32) -> Option<(i32, &L)> {
+        let value = value as i64;
+        self.members
+            .iter()
+            .filter_map(|(interval, label)| {
+                let (first, last) = util::clipped_run(interval.lo(), interval.hi());
+                if first > last {
+                    return None;
+                }
+                let nearest = value.max(first).min(last);
+                Some(((nearest - value).abs(), nearest as i32, label))
+            })
+            .min_by_key(|(distance, _, _)| *distance)
+            .map(|(_, nearest, label)| (nearest, label))
+    }
+
+    /// Every interval cut down to the values `other` admits, each
+    /// fragment keeping the label of the interval it came from
+    /// ```
+    /// use dynamic_domain::{Domain, LabeledDomain};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push_domain(&Domain::between(0, 29), "rule");
+    /// let cut = domain.intersect(&Domain::Union(vec![Domain::between(5, 9), Domain::at_least(25)]));
+    /// assert_eq!(cut.repr_with_labels(), "[5;9]{rule}⋃[25;29]{rule}");
+    /// ```
+    pub fn intersect(&self, other: &Domain<i32>) -> LabeledDomain<i32, L>
+    where
+        L: Clone,
+    {
+        let other = other.normalize();
+        self.fragments(|interval| interval.intersect(&other))
+    }
+
+    /// Every interval without the values `other` admits, splitting an
+    /// interval keeps its label on both pieces
+    /// ```
+    /// use dynamic_domain::{Domain, LabeledDomain};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push_domain(&Domain::between(0, 9), 'a');
+    /// assert_eq!(domain.difference(&Domain::between(3, 4)).repr_with_labels(), "[0;3){a}⋃(4;9]{a}");
+    /// ```
+    pub fn difference(&self, other: &Domain<i32>) -> LabeledDomain<i32, L>
+    where
+        L: Clone,
+    {
+        let other = other.normalize();
+        self.fragments(|interval| interval.difference(&other))
+    }
+
+    /// Replaces every interval by the members of `cut` of it, labeled like it
+    fn fragments(&self, cut: impl Fn(&Domain<i32>) -> NormalizedDomain<i32>) -> LabeledDomain<i32, L>
+    where
+        L: Clone,
+    {
+        let mut fragments = LabeledDomain::new();
+        for (interval, label) in &self.members {
+            fragments.push_domain(&cut(&Domain::from(interval.clone())), label.clone());
+        }
+        fragments
+    }
+
+    /// Sorts the intervals by starting border, drops the ones admitting
+    /// no `i32` and merges overlapping or adjacent ones of equal labels.
+    /// Intervals of different labels are never merged, so the result may
+    /// still overlap where two labels admit the same values
+    /// ```
+    /// use dynamic_domain::{Domain, LabeledDomain};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push_domain(&Domain::between(5, 9), 'a');
+    /// domain.push_domain(&Domain::between(0, 4), 'a');
+    /// domain.push_domain(&Domain::between(3, 7), 'b');
+    /// domain.push_domain(&Domain::between(5, 1), 'c');
+    /// assert_eq!(domain.normalize().repr_with_labels(), "[0;9]{a}⋃[3;7]{b}");
+    /// ```
+    pub fn normalize(&self) -> LabeledDomain<i32, L>
+    where
+        L: Clone + PartialEq,
+    {
+        let mut groups: Vec<(&L, Vec<util::Member>)> = Vec::new();
+        for (interval, label) in &self.members {
+            let member = (interval.lo().clone(), interval.hi().clone());
+            match groups.iter_mut().find(|(group, _)| *group == label) {
+                Some((_, members)) => members.push(member),
+                None => groups.push((label, vec![member])),
+            }
+        }
+
+        let mut members: Vec<(Interval<i32>, L)> = groups
+            .into_iter()
+            .flat_map(|(label, members)| {
+                util::normalize_members(members).into_iter().map(move |(l, r)| (Interval::new(l, r), label.clone()))
+            })
+            .collect();
+        members.sort_by_key(|(interval, _)| (first_admitted(interval.lo()), last_admitted(interval.hi())));
+        LabeledDomain { members }
+    }
+
+    /// The intervals in `repr` notation, labels dropped and nothing
+    /// normalized
+    /// ```
+    /// use dynamic_domain::{Domain, LabeledDomain};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push_domain(&Domain::between(0, 9), 'a');
+    /// assert_eq!(domain.repr(), "[0;9]");
+    /// assert_eq!(LabeledDomain::<i32, char>::new().repr(), "∅");
+    /// ```
+    pub fn repr(&self) -> String {
+        self.repr_labels(|_| String::new())
+    }
+
+    /// `repr` followed by the label of every interval in braces
+    /// ```
+    /// use dynamic_domain::{Domain, LabeledDomain};
+    /// let mut domain = LabeledDomain::new();
+    /// domain.push_domain(&Domain::at_most(-1), "negative");
+    /// assert_eq!(domain.repr_with_labels(), "(-∞;-1]{negative}");
+    /// ```
+    pub fn repr_with_labels(&self) -> String
+    where
+        L: fmt::Display,
+    {
+        self.repr_labels(|label| format!("{{{}}}", label))
+    }
+
+    fn repr_labels(&self, label: impl Fn(&L) -> String) -> String {
+        if self.members.is_empty() {
+            return "∅".to_string();
+        }
+        self.members
+            .iter()
+            .map(|(interval, l)| format!("{}{}", interval.repr(), label(l)))
+            .collect::<Vec<_>>()
+            .join(&UNION.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::{border, union_of};
+    use crate::{Domain, Interval, LabeledDomain, Value};
+
+    fn labeled() -> impl Strategy<Value = LabeledDomain<i32, u8>> {
+        prop::collection::vec((border(30), border(30), 0..3u8), 0..6).prop_map(|members| {
+            let mut domain = LabeledDomain::new();
+            for (l, r, label) in members {
+                domain.push(Interval::new(l, r), label);
+            }
+            domain
+        })
+    }
+
+    /// Values admitted under `label`, -35..35 and the limits
+    fn admitted(domain: &LabeledDomain<i32, u8>, label: u8) -> Vec<i32> {
+        (-35..35)
+            .chain(vec![i32::MIN, i32::MAX])
+            .filter(|v| domain.members().iter().any(|(interval, l)| *l == label && interval.contains(*v)))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_labels_survive(labeled in labeled(), other in union_of(border(30), 0..4)) {
+            let normalized = labeled.normalize();
+            let intersection = labeled.intersect(&other);
+            let difference = labeled.difference(&other);
+            prop_assert_eq!(normalized.to_domain(), labeled.to_domain());
+            prop_assert_eq!(intersection.to_domain(), labeled.to_domain().intersect(&other));
+            prop_assert_eq!(difference.to_domain(), labeled.to_domain().difference(&other));
+
+            for label in 0..3 {
+                let values = admitted(&labeled, label);
+                prop_assert_eq!(admitted(&normalized, label), values.clone());
+                let inside: Vec<i32> = values.iter().cloned().filter(|v| other.contains(*v)).collect();
+                let outside: Vec<i32> = values.iter().cloned().filter(|v| !other.contains(*v)).collect();
+                prop_assert_eq!(admitted(&intersection, label), inside);
+                prop_assert_eq!(admitted(&difference, label), outside);
+            }
+
+            // Intervals of one label are disjoint and apart once normalized
+            for (i, (a, label)) in normalized.members().iter().enumerate() {
+                for (b, _) in normalized.members()[i + 1..].iter().filter(|(_, other)| other == label) {
+                    prop_assert!(Domain::from(a.clone()).intersect(&Domain::from(b.clone())).is_empty());
+                    prop_assert!(!Domain::from(a.clone()).touches(&Domain::from(b.clone())));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlapping_labels() {
+        let mut rules = LabeledDomain::new();
+        rules.push_domain(&Domain::between(0, 10), "rule 1");
+        rules.push_domain(&Domain::between(5, 20), "rule 2");
+        rules.push_domain(&Domain::between(11, 12), "rule 1");
+
+        let normalized = rules.normalize();
+        assert_eq!(normalized.repr_with_labels(), "[0;12]{rule 1}⋃[5;20]{rule 2}");
+        assert_eq!(normalized.repr(), "[0;12]⋃[5;20]");
+        assert_eq!(normalized.contains_labeled(12), Some(&"rule 1"));
+        assert_eq!(normalized.contains_labeled(13), Some(&"rule 2"));
+        assert_eq!(normalized.nearest_labeled(25), Some((20, &"rule 2")));
+
+        // The hole splits both labels
+        let cut = normalized.difference(&Domain::between(8, 9));
+        assert_eq!(cut.repr_with_labels(), "[0;8){rule 1}⋃(9;12]{rule 1}⋃[5;8){rule 2}⋃(9;20]{rule 2}");
+        assert_eq!(cut.normalize().repr_with_labels(), "[0;8){rule 1}⋃[5;8){rule 2}⋃(9;12]{rule 1}⋃(9;20]{rule 2}");
+        assert_eq!(cut.contains_labeled(9), None);
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut domain = LabeledDomain::new();
+        domain.push(Interval::new(Value::Secluded(i32::MAX), Value::Infinite), 'x');
+        domain.push(Interval::new(Value::Infinite, Value::Included(i32::MIN)), 'y');
+        assert_eq!(domain.contains_labeled(i32::MAX), None);
+        assert_eq!(domain.contains_labeled(i32::MIN), Some(&'y'));
+        assert_eq!(domain.nearest_labeled(i32::MAX), Some((i32::MIN, &'y')));
+        assert_eq!(domain.normalize().repr_with_labels(), "(-∞;-2147483648]{y}");
+        assert_eq!(domain.intersect(&Domain::new()).repr_with_labels(), "(-∞;-2147483648]{y}");
+        assert_eq!(LabeledDomain::<i32, char>::new().nearest_labeled(0), None);
+        assert_eq!(domain.difference(&Domain::new()).repr(), "∅");
+    }
+}
@@ -1,14 +1,140 @@
-use std::collections::HashMap;
-
+mod adjust;
+mod aggregate;
+#[cfg(test)]
+mod algebra;
+mod arith;
+mod boundary;
+mod budget;
+mod builder;
+mod bulk;
+mod chars;
+mod codegen;
+mod collect;
+mod construct;
+mod convert;
+mod describe;
+mod discrete;
+mod dto;
+#[cfg(test)]
+mod empty;
+mod encoding;
+mod enumerate;
+#[cfg(feature = "time")]
+mod epoch;
+mod error;
+mod expr;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+#[cfg(test)]
+mod golden;
+mod index;
+mod interval;
+mod inversion;
+mod iter;
+mod labeled;
+#[cfg(test)]
+mod limits;
+#[doc(hidden)]
+pub mod literal;
+#[cfg(test)]
+mod malformed;
+mod map;
+mod monotonic;
+mod normalized;
+mod notation;
+mod observe;
+mod ops;
+mod options;
+mod order;
+mod parse;
+mod point;
+mod range_list;
+mod reason;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "serde")]
+mod serde_adapters;
+mod slice;
+mod sql;
+mod store;
+mod structure;
+mod sweep;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testutil;
+mod tighten;
+mod tracked;
+#[cfg(test)]
+mod roundtrip;
+mod schema;
+mod universe;
+mod unsigned;
 mod util;
+mod validate;
+mod value;
+mod view;
+mod walk;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use adjust::Adjustment;
+pub use arith::{OverflowPolicy, Rounding};
+pub use boundary::BoundaryKind;
+pub use budget::{ApproxDirection, BudgetAction, BudgetPolicy};
+pub use builder::DomainBuilder;
+pub use codegen::CodegenLang;
+pub use collect::DomainAccumulator;
+pub use convert::NarrowPolicy;
+pub use discrete::{Bounded, Discrete, DiscreteStep};
+pub use dto::{DomainDto, IntervalDto};
+pub use error::{
+    AggregateError, BuildConflict, CapExceeded, CodegenError, DecodeError, DomainError, IdentifierError, KeyOverlap, NarrowError,
+    OrderError, OutOfDomain, ParseError, ParseErrorKind, TooComplex, TooLarge, Violation,
+};
+pub use index::DomainIndex;
+pub use interval::Interval;
+pub use iter::{Capped, DomainIter, IterOrder};
+pub use labeled::LabeledDomain;
+pub use map::DomainMap;
+pub use monotonic::Limit;
+pub use normalized::NormalizedDomain;
+pub use notation::Notation;
+pub use observe::MonotoneOutcome;
+pub use options::ReprOptions;
+pub use point::Point;
+pub use reason::EmptyReason;
+pub use schema::SchemaBounds;
+pub use store::DomainStore;
+pub use sweep::Segment;
+pub use tighten::Constraint;
+pub use tracked::TrackedDomain;
+pub use view::DomainView;
+pub use universe::BoundedUniverse;
+pub use value::{IntoBound, Side};
+#[cfg(feature = "serde")]
+pub use serde_adapters::{
+    intervals as serde_intervals,
+    object as serde_object,
+    string as serde_string,
+};
+#[cfg(feature = "time")]
+pub use error::EpochError;
+
+use std::convert::Infallible;
+use std::fmt;
+
+use walk::Walk;
 
-const EMPTY: char = '∅';
 const UNION: char = '⋃';
-const INFINITY: char = '∞';
+const ELLIPSIS: char = '…';
+
+/// Members shown by the alternate `{:#}` form of `Display`
+const ABBREVIATED_MEMBERS: usize = 6;
 
 /// `Value` is referencing points. Its type should
 /// also implement comparision methods
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Value<T> {
     Included(T),
     Secluded(T),
@@ -19,12 +145,15 @@ pub enum Value<T> {
 /// * `Union` - which means that domains inside are united
 /// * `Domain` - single domain with start end end `Value`
 /// * `None` - which means empty set (function does not take any value)
+///
+/// Building the variants directly is discouraged, nothing stops a nested
+/// union or reversed borders. Prefer `Domain::interval` and `Domain::union_of`
 /// ```
 /// use dynamic_domain::Domain;
 /// let domain = Domain::<i32>::new();
 /// println!("Domain is {}", domain.repr());
 /// ```
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub enum Domain<T> {
     /// Domains inside of the vector are united into single domain
     /// Union inside Union is prohibited
@@ -38,8 +167,184 @@ pub enum Domain<T> {
     None,
 }
 
+impl Default for Domain<i32> {
+    fn default() -> Self {
+        Domain::new()
+    }
+}
+
+impl<T> Domain<T> {
+
+    /// Same notation as `repr` with every point rendered by `point`,
+    /// for fixed decimals or custom formats. Rendered points are quoted
+    /// like `repr` quotes them, `point` may be called twice for one point
+    /// and a panic of it is not caught
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Included(0.1), Value::Secluded(0.1 + 0.2));
+    /// assert_eq!(domain.repr_with_fmt(|x| format!("{:.2}", x)), "[0.10;0.30)");
+    /// assert_eq!(domain.repr_with_fmt(|x| format!("{:.1} m", x)), r#"["0.1 m";"0.3 m")"#);
+    /// ```
+    pub fn repr_with_fmt(&self, point: impl Fn(&T) -> String) -> String {
+        self.repr_points(&point, &Notation::DEFAULT)
+    }
+
+    fn repr_points(&self, point: &dyn Fn(&T) -> String, notation: &Notation) -> String {
+        let mut out = String::new();
+        self.write_points(&mut out, &|out, p| out.write_str(&point(p)), notation).expect(interval::WRITE_FAILED);
+        out
+    }
+
+    /// Writes the repr into `out` member by member, every point written by
+    /// `point`, without allocating on its own
+    fn write_points(
+        &self,
+        out: &mut dyn fmt::Write,
+        point: &dyn Fn(&mut dyn fmt::Write, &T) -> fmt::Result,
+        notation: &Notation,
+    ) -> fmt::Result {
+        self.write_members(out, point, notation, notation.reserved_ascii())
+    }
+
+    /// `write_points` with the `Notation::reserved_ascii` of `notation`
+    fn write_members(
+        &self,
+        out: &mut dyn fmt::Write,
+        point: &dyn Fn(&mut dyn fmt::Write, &T) -> fmt::Result,
+        notation: &Notation,
+        ascii: u128,
+    ) -> fmt::Result {
+        match self {
+            Domain::Union(domains) if domains.is_empty() => out.write_str(notation.empty),
+            Domain::Union(domains) => {
+                for (i, domain) in domains.iter().enumerate() {
+                    if i > 0 {
+                        out.write_str(notation.union)?;
+                    }
+                    domain.write_members(out, point, notation, ascii)?;
+                }
+                Ok(())
+            },
+            Domain::Domain(left, right) => interval::write_borders(out, left, right, point, notation, ascii),
+            Domain::None => out.write_str(notation.empty),
+        }
+    }
+}
+
+impl<T: fmt::Display> Domain<T> {
+
+    /// Performing value representation based on
+    /// popular math notations. Floats are written in the shortest
+    /// form reading back to the same value. The strings are stable, a
+    /// change to them is breaking and shows in `tests/golden/repr.txt`.
+    ///
+    /// A point whose `Display` is empty or writes whitespace, a `"`, a
+    /// `\\`, a bracket or a character of `⋃`, `;`, `∞` or `∅` is quoted,
+    /// with `\"` and `\\` inside, so `Domain::parse_points` reads it back.
+    /// Numbers are never quoted. `Display` may be called twice for a long
+    /// point and a panic of it is not caught
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::new();
+    /// println!("{}", domain.repr());
+    ///
+    /// let domain = Domain::Domain(Value::Included(0.1), Value::Secluded(0.1 + 0.2));
+    /// assert_eq!(domain.repr(), "[0.1;0.30000000000000004)");
+    ///
+    /// let domain = Domain::Domain(Value::Included("a;b"), Value::Included("c"));
+    /// assert_eq!(domain.repr(), r#"["a;b";c]"#);
+    /// ```
+    pub fn repr(self) -> String {
+        let mut out = String::new();
+        self.repr_into(&mut out);
+        out
+    }
+
+    /// Appends `repr` to `out`. Nothing is allocated when `out` has the
+    /// capacity, so a buffer reused across calls makes repeated reprs of
+    /// a domain free of allocations
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let mut out = String::with_capacity(64);
+    /// Domain::between(1, 5).repr_into(&mut out);
+    /// out.push_str(" and ");
+    /// Domain::Union(vec![Domain::at_most(0), Domain::None]).repr_into(&mut out);
+    /// assert_eq!(out, "[1;5] and (-∞;0]⋃∅");
+    /// ```
+    pub fn repr_into(&self, out: &mut String) {
+        self.write_members(out, &display, &Notation::DEFAULT, notation::DEFAULT_RESERVED).expect(interval::WRITE_FAILED);
+    }
+
+    /// `repr` in another notation, read back by `Domain::parse_with`
+    /// ```
+    /// use dynamic_domain::{Domain, Notation};
+    /// let domain = Domain::Union(vec![Domain::at_most(-1), Domain::None]);
+    /// assert_eq!(domain.repr_with(&Notation::ASCII), "(-inf;-1]U{}");
+    /// ```
+    pub fn repr_with(&self, notation: &Notation) -> String {
+        let mut out = String::new();
+        self.write_points(&mut out, &display, notation).expect(interval::WRITE_FAILED);
+        out
+    }
+}
+
+/// Writes `point` as `Display` does, the point writer of `repr`
+fn display<T: fmt::Display>(out: &mut dyn fmt::Write, point: &T) -> fmt::Result {
+    write!(out, "{}", point)
+}
+
+/// `repr` of the domain as written, the alternate form `{:#}` is
+/// `repr_abbreviated` showing at most six members
+/// ```
+/// use dynamic_domain::Domain;
+/// let domain = Domain::Union((0..100).map(|i| Domain::between(i * 10, i * 10 + 1)).collect());
+/// assert_eq!(format!("{}", Domain::between(1, 3)), "[1;3]");
+/// assert_eq!(format!("{:#}", domain), "[0;1]⋃[10;11]⋃[20;21]⋃…(94 more)…⋃[970;971]⋃[980;981]⋃[990;991] (hull [0;991])");
+/// ```
+impl fmt::Display for Domain<i32> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", self.repr_abbreviated(ABBREVIATED_MEMBERS))
+        } else {
+            self.write_members(f, &display, &Notation::DEFAULT, notation::DEFAULT_RESERVED)
+        }
+    }
+}
+
 impl Domain<i32> {
 
+    /// `repr` of the normalized domain cut down to at most `max_members`
+    /// members for logging huge unions. The first half, rounded up, and
+    /// the last half of the members are shown around an ellipsis with the
+    /// count of hidden ones, followed by the hull. The hidden members are
+    /// counted without being formatted. A domain with no more members than
+    /// `max_members` is written in full
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union((0..50_000).map(|i| Domain::between(i * 2, i * 2)).collect());
+    /// assert_eq!(domain.repr_abbreviated(3), "[0;0]⋃[2;2]⋃…(49997 more)…⋃[99998;99998] (hull [0;99998])");
+    /// assert_eq!(Domain::between(5, 1).repr_abbreviated(0), "∅");
+    /// ```
+    pub fn repr_abbreviated(&self, max_members: usize) -> String {
+        let members = util::normalized(self);
+        if members.len() <= max_members {
+            return util::from_members(members).repr();
+        }
+
+        let head = max_members - max_members / 2;
+        let tail = max_members / 2;
+        let shown = |members: &[util::Member]| {
+            members.iter().map(|(l, r)| Domain::Domain(l.clone(), r.clone()).repr()).collect::<Vec<String>>()
+        };
+
+        let mut parts = shown(&members[..head]);
+        parts.push(format!("{}({} more){}", ELLIPSIS, members.len() - head - tail, ELLIPSIS));
+        parts.extend(shown(&members[members.len() - tail..]));
+
+        let hull = Domain::Domain(members[0].0.clone(), members[members.len() - 1].1.clone());
+        format!("{} (hull {})", parts.join(UNION.to_string().as_str()), hull.repr())
+    }
+
     /// Creates new `Domain` with starting border = -infinity;
     /// ending border = infinity
     /// ```
@@ -50,159 +355,178 @@ impl Domain<i32> {
         Domain::Domain(Value::Infinite, Value::Infinite)
     }
 
-    /// Value starting border is `value`
+    /// Creates the closed `Domain` [lo;hi]
     /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::between(1, 5).repr(), "[1;5]");
+    /// ```
+    pub fn between(lo: i32, hi: i32) -> Self {
+        Domain::Domain(Value::Included(lo), Value::Included(hi))
+    }
+
+    /// Creates `Domain` [lo;∞)
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::at_least(1).repr(), "[1;∞)");
+    /// ```
+    pub fn at_least(lo: i32) -> Self {
+        Domain::Domain(Value::Included(lo), Value::Infinite)
+    }
+
+    /// Creates `Domain` (-∞;hi]
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::at_most(5).repr(), "(-∞;5]");
+    /// ```
+    pub fn at_most(hi: i32) -> Self {
+        Domain::Domain(Value::Infinite, Value::Included(hi))
+    }
+
+    /// `between` usable in constants and statics. The const constructors
+    /// build a single interval or `Domain::None`, never a union, so they
+    /// hold nothing on the heap. Working with the domain may still
+    /// allocate: `contains`, `Display` and `repr_into` into a buffer with
+    /// room do not, while `is_empty`, `cardinality`, `nth`, `iter` and the
+    /// other queries going by the normalized members allocate them for the
+    /// call, and the set operations allocate their result
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// const PORT_RANGE: Domain<i32> = Domain::const_between(1, 65535);
+    /// assert!(PORT_RANGE.contains(8080) && !PORT_RANGE.contains(0));
+    /// assert_eq!(PORT_RANGE, Domain::between(1, 65535));
+    /// ```
+    pub const fn const_between(lo: i32, hi: i32) -> Self {
+        Domain::Domain(Value::Included(lo), Value::Included(hi))
+    }
+
+    /// `at_least` usable in constants, see `const_between`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// const UNPRIVILEGED: Domain<i32> = Domain::const_at_least(1024);
+    /// assert_eq!(UNPRIVILEGED.repr(), "[1024;∞)");
+    /// ```
+    pub const fn const_at_least(lo: i32) -> Self {
+        Domain::Domain(Value::Included(lo), Value::Infinite)
+    }
+
+    /// The domain admitting only `value`, usable in constants, see
+    /// `const_between`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// const ANSWER: Domain<i32> = Domain::const_point(42);
+    /// assert_eq!(ANSWER.repr(), "[42;42]");
+    /// ```
+    pub const fn const_point(value: i32) -> Self {
+        Domain::Domain(Value::Included(value), Value::Included(value))
+    }
+
+    /// `Domain::None` usable in constants, see `const_between`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// const NOTHING: Domain<i32> = Domain::const_empty();
+    /// assert!(NOTHING.is_empty());
+    /// ```
+    pub const fn const_empty() -> Self {
+        Domain::None
+    }
+
+    /// `new` usable in constants, admitting every value, see
+    /// `const_between`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// const ANY: Domain<i32> = Domain::const_full();
+    /// assert_eq!(ANY, Domain::new());
+    /// ```
+    pub const fn const_full() -> Self {
+        Domain::Domain(Value::Infinite, Value::Infinite)
+    }
+
+    /// Value starting border is `value`, applied to every member and
+    /// kept as written, which `repr` prints back. Takes any `IntoBound`,
+    /// a bare `i32` is an included border, so `gt(5)` keeps 5 unlike
+    /// `DomainBuilder::gt`
+    /// ```
+    /// use std::ops::Bound;
     /// use dynamic_domain::{Domain, Value};
     /// let domain = Domain::<i32>::new()
     ///     .gt(Value::Secluded(5));
+    /// assert_eq!(domain.repr(), "(5;∞)");
+    /// assert_eq!(Domain::between(0, 9).gt(5).repr(), "[5;9]");
+    /// assert_eq!(Domain::between(0, 9).gt(Bound::Excluded(5)).repr(), "(5;9]");
     /// ```
-    pub fn gt(&self, value: Value<i32>) -> Self {
+    pub fn gt(&self, value: impl IntoBound) -> Self {
 
-        let secluded_value = match util::convert_to_secluded(value, true) {
-            Some(i) => i,
-            None => return Domain::None,
+        let lower = match value.into_bound() {
+            Value::Infinite => return Domain::None,
+            value => value,
         };
 
-        match self.clone() {
-            Domain::Domain(
-                left,
-                right
-            ) => {
-
-                match left {
-
-                    Value::Included(i) => {
-                        if secluded_value > i.clone() {
-                            return Domain::Domain(
-                                Value::Included(secluded_value),
-                                right
-                            );
-                        }
-                    },
-                    Value::Secluded(i) => {
-                        if secluded_value > i.clone() {
-                            return Domain::Domain(
-                                Value::Secluded(secluded_value),
-                                right
-                            );
-                        }
-                    },
-
-                    Value::Infinite => {
-                        return Domain::Domain(
-                            Value::Secluded(secluded_value),
-                            right,
-                        );
-                    }
-                }
-            },
-            _ => (),
-        }
-
-        return self.clone();
+        util::map_intervals(self, &|l, r| (l.max_lower(lower.clone()), r))
     }
 
-    /// Value ending border is `value`
+    /// Value ending border is `value`, applied to every member.
+    /// Takes any `IntoBound` like `gt`
     /// ```
+    /// use std::ops::Bound;
     /// use dynamic_domain::{Domain, Value};
     /// let domain = Domain::<i32>::new()
     ///     .lt(Value::Secluded(5));
+    /// assert_eq!(domain.repr(), "(-∞;5)");
+    /// assert_eq!(Domain::between(0, 9).lt(5).repr(), "[0;5]");
+    /// assert_eq!(Domain::between(0, 9).lt(Bound::Included(5)).repr(), "[0;5]");
     /// ```
-    pub fn lt(&self, value: Value<i32>) -> Self {
+    pub fn lt(&self, value: impl IntoBound) -> Self {
 
-        let secluded_value = match util::convert_to_secluded(value, false) {
-            Some(i) => i,
-            None => return Domain::None,
+        let upper = match value.into_bound() {
+            Value::Infinite => return Domain::None,
+            value => value,
         };
 
-        match self.clone() {
-            Domain::Domain(
-                left,
-                right
-            ) => {
-
-                match right {
-
-                    Value::Included(i) => {
-                        if secluded_value < i.clone() {
-                            return Domain::Domain(
-                                left,
-                                Value::Included(secluded_value)
-                            );
-                        }
-                    },
-                    Value::Secluded(i) => {
-                        if secluded_value < i.clone() {
-                            return Domain::Domain(
-                                left,
-                                Value::Secluded(secluded_value)
-                            );
-                        }
-                    },
-
-                    Value::Infinite => {
-                        return Domain::Domain(
-                            left,
-                            Value::Secluded(secluded_value)
-                        );
-                    }
-                }
-            },
-            _ => (),
-        }
-
-        return self.clone();
+        util::map_intervals(self, &|l, r| (l, r.min_upper(upper.clone())))
     }
 
-    /// Performing value representation based on
-    /// popular math notations
+    /// Keeps the values greater than or equal to `value`, `gt` with an
+    /// included border spelled as in `DomainBuilder`
     /// ```
     /// use dynamic_domain::Domain;
-    /// let domain = Domain::new();
-    /// println!("{}", domain.repr());
+    /// assert_eq!(Domain::new().ge(0).repr(), "[0;∞)");
     /// ```
-    pub fn repr(self) -> String {
-        match self {
-            Domain::Union(
-                domains
-            ) => {
-
-                let domain_reprs = domains
-                    .iter()
-                    .map(|domain| domain.clone().repr())
-                    .collect::<Vec<String>>();
-
-                domain_reprs.join(
-                    UNION.to_string().as_str()
-                )
-            },
-
-            Domain::Domain(left, right) => {
-                format!(
-
-                    "{};{}",
-
-                    match left {
-                        Value::Included(i) => format!("[{}", i),
-                        Value::Secluded(i) => format!("({}", i),
-                        Value::Infinite => format!("(-{}", INFINITY),
-                    },
+    pub fn ge(&self, value: i32) -> Self {
+        self.gt(Value::Included(value))
+    }
 
-                    match right {
-                        Value::Included(i) => format!("{}]", i),
-                        Value::Secluded(i) => format!("{})", i),
-                        Value::Infinite => format!("{})", INFINITY),
-                    }
+    /// Keeps the values less than or equal to `value`, `lt` with an
+    /// included border spelled as in `DomainBuilder`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::new().ge(0).le(100).repr(), "[0;100]");
+    /// ```
+    pub fn le(&self, value: i32) -> Self {
+        self.lt(Value::Included(value))
+    }
 
-                )
-            },
-            Domain::None => EMPTY.to_string()
-        }
+    /// Punches a hole at `value` without normalizing: the member holding
+    /// it is split in place, a member of a union into two members of that
+    /// union, and the domain is left as it is when it does not admit
+    /// `value`. `gt` and `lt` after it apply to every member again
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::new().ge(0).le(100).ne(50);
+    /// assert_eq!(domain.clone().repr(), "[0;50)⋃(50;100]");
+    /// assert_eq!(domain.ne(70).repr(), "[0;50)⋃(50;70)⋃(70;100]");
+    /// assert_eq!(domain.ne(500), domain);
+    /// ```
+    pub fn ne(&self, value: i32) -> Self {
+        util::punch_hole(self, value)
     }
 
     /// Generates integers by the domain,
-    /// sends integers to receiver
+    /// sends integers to receiver. An interval with a finite starting
+    /// border is walked upwards, one with only a finite ending border
+    /// downwards and a fully infinite one outwards from 0. Runs on
+    /// `for_each`, so the values and their order are the same
     /// ```
+    /// # #![allow(deprecated)]
     /// use dynamic_domain::{Domain, Value};
     /// let domain = Domain::new()
     ///     .gt(Value::Secluded(5))
@@ -210,92 +534,148 @@ impl Domain<i32> {
     /// fn rec(n: i32, c: &()) { println!("Some stuff with {}", n); }
     /// domain.generate(rec, &());
     /// ```
+    #[deprecated(note = "use for_each / iter()")]
     pub fn generate<Context>(&self, receiver: fn(i32, &Context), context: &Context) {
+        self.generate_with_context(receiver, context)
+    }
 
-        match self {
-            Domain::Union(
-                domains
-            ) => {
-                for domain in domains {
-                    domain.generate(receiver, context)
-                }
-            },
-
-            Domain::Domain(l, r) => {
-                let mut from_l = true;
-                let mut from_r = false;
-
-                match l.clone() {
-                    Value::Infinite => {
-                        from_l = false;
-                    },
-                    _ => (),
-                }
-
-                match r.clone() {
-                    Value::Infinite => {
-                        from_r = false;
-                    },
-                    _ => from_r = true
-                }
-
-                if !from_l && !from_r {
-                    return;
-                }
-
-                let (mut v, b, f) = if from_l {
-
-                    let v = match l {
-                        Value::Included(n) => *n,
-                        Value::Secluded(n) => (*n) + 1,
-                        _ => 0,
-                    };
-
-                    let b = match r {
-                        Value::Included(n) => Some(*n),
-                        Value::Secluded(n) => Some((*n) - 1),
-                        _ => None,
-                    };
-
-                    (v, b, (|n: i32| -> i32 { n + 1 }) as fn(i32) -> i32)
-
-                } else {
-
-                    let b = match l {
-                        Value::Included(n) => Some(*n),
-                        Value::Secluded(n) => Some((*n) + 1),
-                        _ => None,
-                    };
-
-                    let v = match r {
-                        Value::Included(n) => *n,
-                        Value::Secluded(n) => (*n) - 1,
-                        _ => 0,
-                    };
+    /// `for_each` handing `context` to `receiver` along with every value,
+    /// the shape of the deprecated `generate` for receivers which are
+    /// written against a context
+    /// ```
+    /// use std::cell::RefCell;
+    /// use dynamic_domain::Domain;
+    /// let seen = RefCell::new(Vec::new());
+    /// Domain::between(1, 3).generate_with_context(|n, seen: &RefCell<Vec<i32>>| seen.borrow_mut().push(n), &seen);
+    /// assert_eq!(seen.into_inner(), vec![1, 2, 3]);
+    /// ```
+    pub fn generate_with_context<Context>(&self, mut receiver: impl FnMut(i32, &Context), context: &Context) {
+        self.for_each(|v| receiver(v, context))
+    }
 
-                    (v, b, (|n: i32| -> i32 { n - 1 }) as fn(i32) -> i32)
-                };
+    /// Hands every value of every member to `f`, member by member in
+    /// their order. An interval with a finite starting border is walked
+    /// upwards, one with only a finite ending border downwards and a fully
+    /// infinite one outwards from 0. Members sharing values are normalized
+    /// first, so every value comes once, with the members ascending
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let mut seen = Vec::new();
+    /// Domain::Union(vec![Domain::between(5, 6), Domain::between(1, 2)]).for_each(|n| seen.push(n));
+    /// assert_eq!(seen, vec![5, 6, 1, 2]);
+    ///
+    /// seen.clear();
+    /// Domain::Union(vec![Domain::between(5, 6), Domain::between(1, 5)]).for_each(|n| seen.push(n));
+    /// assert_eq!(seen, vec![1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn for_each(&self, mut f: impl FnMut(i32)) {
+        self.try_generate(|v| -> Result<(), Infallible> {
+            f(v);
+            Ok(())
+        }).unwrap_or_else(|never| match never {})
+    }
 
-                loop {
-                    receiver(v, context);
+    /// Same as `generate` handing the values to `f`, stops with an error
+    /// instead of emitting more than `max` values. The cap counts the
+    /// values of all union members together
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let mut seen = Vec::new();
+    /// let domain = Domain::Union(vec![Domain::between(1, 2), Domain::between(10, 20)]);
+    /// let error = domain.generate_capped(3, |n| seen.push(n)).unwrap_err();
+    /// assert_eq!(seen, vec![1, 2, 10]);
+    /// assert_eq!((error.emitted(), error.next()), (3, 11));
+    /// assert!(Domain::between(1, 3).generate_capped(3, |_| ()).is_ok());
+    /// ```
+    pub fn generate_capped(&self, max: u64, mut f: impl FnMut(i32)) -> Result<(), CapExceeded> {
+        let mut emitted = 0;
 
-                    if let Some(bound) = b {
-                        if v == bound { break; }
-                    }
+        self.try_generate(|v| {
+            if emitted == max {
+                return Err(CapExceeded::new(emitted, v));
+            }
+            f(v);
+            emitted += 1;
+            Ok(())
+        })
+    }
 
-                    v = f(v)
+    /// Same as `generate` handing the values to a fallible `f`, stops at
+    /// the first error and returns it, later union members are not
+    /// walked. For a normalized domain with finite starting borders the
+    /// values come in the order of `iter`, so `iter().try_for_each(f)`
+    /// visits and fails the same way
+    /// ```
+    /// use std::io::{self, Write};
+    /// use dynamic_domain::Domain;
+    ///
+    /// fn write_all(domain: &Domain<i32>, out: &mut impl Write) -> io::Result<()> {
+    ///     domain.try_generate(|n| writeln!(out, "{}", n))?;
+    ///     out.flush()
+    /// }
+    ///
+    /// let mut out = Vec::new();
+    /// write_all(&Domain::Union(vec![Domain::between(1, 2), Domain::between(5, 5)]), &mut out).unwrap();
+    /// assert_eq!(out, b"1\n2\n5\n");
+    /// ```
+    pub fn try_generate<E>(&self, mut f: impl FnMut(i32) -> Result<(), E>) -> Result<(), E> {
+        let mut members = util::members(self);
+        // Copies of a member would walk its values again, as many times
+        // as it is repeated
+        if util::overlapping(&members) {
+            members = util::normalize_members(members);
+        }
+        for (l, r) in members {
+            if let Some(walk) = Walk::of(&l, &r) {
+                for v in walk.values() {
+                    f(v)?;
                 }
-
             }
-            Domain::None => (),
         }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::ops::Bound;
+
     use crate::{Domain, Value};
 
+    const PORT_RANGE: Domain<i32> = Domain::const_between(1, 65535);
+    static EVERY: [Domain<i32>; 5] = [
+        Domain::const_between(i32::MIN, i32::MAX),
+        Domain::const_at_least(i32::MAX),
+        Domain::const_point(i32::MIN),
+        Domain::const_empty(),
+        Domain::const_full(),
+    ];
+
+    // The const constructors build the plain variants, checked at compile time
+    const BUILT: [&Domain<i32>; 3] = [&Domain::const_between(1, 65535), &Domain::const_point(7), &Domain::const_empty()];
+    const _: () = assert!(matches!(BUILT[0], Domain::Domain(Value::Included(1), Value::Included(65535))));
+    const _: () = assert!(matches!(BUILT[1], Domain::Domain(Value::Included(7), Value::Included(7))));
+    const _: () = assert!(matches!(BUILT[2], Domain::None));
+
+    #[test]
+    fn test_const_constructors() {
+        let runtime = [
+            Domain::between(i32::MIN, i32::MAX),
+            Domain::at_least(i32::MAX),
+            Domain::between(i32::MIN, i32::MIN),
+            Domain::None,
+            Domain::new(),
+        ];
+        for (constant, runtime) in EVERY.iter().zip(&runtime) {
+            assert_eq!(constant, runtime);
+            assert_eq!(constant.clone().repr(), runtime.clone().repr());
+        }
+        assert_eq!(PORT_RANGE.cardinality(), Some(65535));
+        assert_eq!(Domain::const_between(5, 1), Domain::None);
+        assert_eq!(Domain::const_between(0, 9).intersect(&PORT_RANGE).repr(), "[1;9]");
+    }
+
     #[test]
     fn test_manual_domain() {
         let domain = Domain::Domain(
@@ -305,6 +685,64 @@ mod tests {
         assert_eq!(domain.repr(), "[5;10)".to_string())
     }
 
+    #[test]
+    fn test_repr_floats() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Included(0.1), Value::Secluded(0.1 + 0.2)),
+            Domain::Domain(Value::Secluded(1.0), Value::Infinite),
+        ]);
+        assert_eq!(domain.clone().repr(), "[0.1;0.30000000000000004)⋃(1;∞)".to_string());
+        assert_eq!(domain.repr_with_fmt(|x| format!("{:.2}", x)), "[0.10;0.30)⋃(1.00;∞)".to_string());
+        assert_eq!(domain.repr_with_fmt(|x| format!("{:e}", x)), "[1e-1;3.0000000000000004e-1)⋃(1e0;∞)".to_string());
+        assert_eq!(Domain::<f64>::None.repr_with_fmt(|x| x.to_string()), "∅".to_string());
+    }
+
+    #[test]
+    fn test_repr_into_appends() {
+        let domains = vec![
+            Domain::Union(vec![]),
+            Domain::Union(vec![Domain::None, Domain::new()]),
+            Domain::Union(vec![Domain::between(i32::MIN, -1), Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite)]),
+            Domain::between(5, 1),
+        ];
+        let mut out = "reprs:".to_string();
+        for domain in &domains {
+            domain.repr_into(&mut out);
+            assert_eq!(format!("{}", domain), domain.clone().repr());
+        }
+        assert_eq!(out, "reprs:∅∅⋃(-∞;∞)[-2147483648;-1]⋃(2147483647;∞)[5;1]");
+    }
+
+    #[test]
+    fn test_repr_abbreviated() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Secluded(20), Value::Infinite),
+            Domain::between(10, 12),
+            Domain::Domain(Value::Infinite, Value::Secluded(0)),
+            Domain::between(5, 5),
+            Domain::between(11, 15),
+        ]);
+        assert_eq!(domain.repr_abbreviated(4), "(-∞;0)⋃[5;5]⋃[10;15]⋃(20;∞)");
+        assert_eq!(domain.repr_abbreviated(3), "(-∞;0)⋃[5;5]⋃…(1 more)…⋃(20;∞) (hull (-∞;∞))");
+        assert_eq!(domain.repr_abbreviated(1), "(-∞;0)⋃…(3 more)… (hull (-∞;∞))");
+        assert_eq!(domain.repr_abbreviated(0), "…(4 more)… (hull (-∞;∞))");
+
+        let edges = Domain::Union(vec![Domain::between(i32::MAX, i32::MAX), Domain::between(0, 0), Domain::between(i32::MIN, i32::MIN)]);
+        assert_eq!(edges.repr_abbreviated(2), "[-2147483648;-2147483648]⋃…(1 more)…⋃[2147483647;2147483647] (hull [-2147483648;2147483647])");
+        assert_eq!(Domain::None.repr_abbreviated(0), "∅");
+    }
+
+    #[test]
+    fn test_display() {
+        let domain = Domain::Union(vec![Domain::between(7, 9), Domain::between(1, 3)]);
+        assert_eq!(format!("{}", domain), "[7;9]⋃[1;3]");
+        assert_eq!(format!("{:#}", domain), "[1;3]⋃[7;9]");
+        assert_eq!(domain.to_string(), domain.clone().repr());
+
+        let huge = Domain::Union((0..7).rev().map(|i| Domain::between(i * 3, i * 3)).collect());
+        assert_eq!(format!("{:#}", huge), "[0;0]⋃[3;3]⋃[6;6]⋃…(1 more)…⋃[12;12]⋃[15;15]⋃[18;18] (hull [0;18])");
+    }
+
     #[test]
     fn test_new() {
         let domain = Domain::new();
@@ -313,29 +751,339 @@ mod tests {
 
     #[test]
     fn test_gt() {
-        let mut domain = Domain::new()
+        let domain = Domain::new()
             .gt(Value::Secluded(5));
         assert_eq!(domain.repr(), "(5;∞)".to_string())
     }
 
     #[test]
     fn test_lt() {
-        let mut domain = Domain::new()
+        let domain = Domain::new()
             .lt(Value::Included(5))
             .gt(Value::Secluded(3))
             .gt(Value::Secluded(1));
-        assert_eq!(domain.repr(), "(3;6)".to_string())
+        assert_eq!(domain.repr(), "(3;5]".to_string())
+    }
+
+    #[test]
+    fn test_bound_kinds_admit() {
+        let generated = |domain: Domain<i32>| {
+            let mut values = Vec::new();
+            domain.for_each(|i| values.push(i));
+            values.sort_unstable();
+            values
+        };
+        let digits = Domain::between(0, 9);
+
+        // An included border admits its point, a secluded one does not
+        assert_eq!(generated(digits.gt(Value::Included(5))), vec![5, 6, 7, 8, 9]);
+        assert_eq!(generated(digits.gt(Value::Secluded(5))), vec![6, 7, 8, 9]);
+        assert_eq!(generated(digits.lt(Value::Included(5))), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(generated(digits.lt(Value::Secluded(5))), vec![0, 1, 2, 3, 4]);
+        assert_eq!(generated(Domain::new().lt(Value::Included(5)).gt(Value::Secluded(3)).gt(Value::Secluded(1))), vec![4, 5]);
+        assert_eq!(generated(Domain::new().gt(Value::Included(5)).lt(Value::Included(5))), vec![5]);
+        assert!(generated(Domain::new().gt(Value::Secluded(5)).lt(Value::Secluded(6))).is_empty());
+
+        // Tightening an open border with a closed one on the same point keeps it open
+        let open = Domain::Domain(Value::Secluded(3), Value::Secluded(7));
+        assert_eq!(generated(open.gt(Value::Included(3)).lt(Value::Included(7))), vec![4, 5, 6]);
+        assert_eq!(generated(open.gt(Value::Included(4)).lt(Value::Included(6))), vec![4, 5, 6]);
+
+        assert_eq!(generated(Domain::new().gt(Value::Secluded(i32::MAX - 2))), vec![i32::MAX - 1, i32::MAX]);
+        assert_eq!(generated(Domain::new().gt(Value::Included(i32::MAX))), vec![i32::MAX]);
+        assert!(generated(Domain::new().gt(Value::Secluded(i32::MAX))).is_empty());
+        assert_eq!(generated(Domain::new().lt(Value::Secluded(i32::MIN + 2))), vec![i32::MIN, i32::MIN + 1]);
+        assert_eq!(generated(Domain::new().lt(Value::Included(i32::MIN))), vec![i32::MIN]);
+        assert!(generated(Domain::new().lt(Value::Secluded(i32::MIN))).is_empty());
+    }
+
+    #[test]
+    fn test_ne_chains() {
+        let values = |domain: &Domain<i32>| domain.iter().collect::<Vec<i32>>();
+
+        let domain = Domain::new().ge(0).le(10).ne(5).ne(7);
+        assert_eq!(domain.clone().repr(), "[0;5)⋃(5;7)⋃(7;10]");
+        assert_eq!(values(&domain), vec![0, 1, 2, 3, 4, 6, 8, 9, 10]);
+
+        // gt and lt after ne reach every member of the union
+        let narrowed = domain.gt(Value::Secluded(2)).lt(Value::Secluded(9));
+        assert_eq!(values(&narrowed), vec![3, 4, 6, 8]);
+        assert!(narrowed.structurally_equal(&Domain::Union(vec![
+            Domain::Domain(Value::Secluded(2), Value::Secluded(5)),
+            Domain::Domain(Value::Secluded(5), Value::Secluded(7)),
+            Domain::Domain(Value::Secluded(7), Value::Secluded(9)),
+        ])));
+
+        // ne after gt and lt, twice on the same value and on the borders
+        let domain = Domain::new().gt(Value::Secluded(0)).lt(20).ne(10).ne(10).ne(1).ne(20);
+        assert_eq!(domain.clone().repr(), "(1;10)⋃(10;20)");
+        assert_eq!(domain.ne(0), domain);
+        assert_eq!(Domain::between(4, 4).ne(4), Domain::None);
+        assert_eq!(Domain::None.ne(4), Domain::None);
+
+        // Overlapping members all lose the value
+        let overlapping = Domain::Union(vec![Domain::between(0, 6), Domain::Union(vec![Domain::between(4, 9)])]).ne(5);
+        assert_eq!(values(&overlapping), vec![0, 1, 2, 3, 4, 6, 7, 8, 9]);
+        assert!(overlapping.structurally_equal(&Domain::Union(vec![
+            Domain::Domain(Value::Included(0), Value::Secluded(5)),
+            Domain::Domain(Value::Secluded(5), Value::Included(6)),
+            Domain::Union(vec![
+                Domain::Domain(Value::Included(4), Value::Secluded(5)),
+                Domain::Domain(Value::Secluded(5), Value::Included(9)),
+            ]),
+        ])));
+    }
+
+    #[test]
+    fn test_ne_limits() {
+        let domain = Domain::new().ne(i32::MIN).ne(i32::MAX);
+        assert_eq!(domain.clone().repr(), "(-2147483648;2147483647)");
+        assert_eq!(domain.cardinality(), Some((1 << 32) - 2));
+        assert_eq!(Domain::at_least(i32::MAX).ne(i32::MAX), Domain::None);
+        let past = Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite);
+        assert!(past.ne(i32::MAX).structurally_equal(&past));
+    }
+
+    #[test]
+    fn test_gt_over_included() {
+        let domain = Domain::between(0, 10).gt(Value::Secluded(5));
+        assert_eq!(domain.repr(), "(5;10]".to_string());
+
+        let domain = Domain::between(0, 10).gt(Value::Included(5));
+        assert!(domain.contains(5) && !domain.contains(4));
+
+        let domain = Domain::between(0, 10).lt(Value::Secluded(5));
+        assert_eq!(domain.repr(), "[0;5)".to_string());
+    }
+
+    #[test]
+    fn test_into_bound_forms() {
+        let chained = Domain::new().gt(5).lt(Value::Secluded(10)).lt(Bound::Excluded(8));
+        assert_eq!(chained.repr(), "[5;8)".to_string());
+        for bare in -3..3 {
+            assert_eq!(Domain::between(-5, 5).gt(bare), Domain::between(-5, 5).gt(Value::Included(bare)));
+            assert_eq!(Domain::between(-5, 5).lt(bare), Domain::between(-5, 5).lt(Bound::Included(bare)));
+        }
+
+        // A bare integer is included and kept as written, a strict border
+        // already in place at the same point wins
+        let open = Domain::Domain(Value::Secluded(0), Value::Secluded(10));
+        assert_eq!(open.gt(0).repr(), "(0;10)".to_string());
+        assert_eq!(open.gt(1).repr(), "[1;10)".to_string());
+        assert_eq!(open.gt(2).repr(), "[2;10)".to_string());
+        assert_eq!(open.lt(10).repr(), "(0;10)".to_string());
+        assert_eq!(open.lt(9).repr(), "(0;9]".to_string());
+        assert!(open.lt(8).contains(8) && !open.lt(8).contains(9));
+
+        assert_eq!(Domain::new().gt(i32::MIN).repr(), "[-2147483648;∞)".to_string());
+        assert_eq!(Domain::new().lt(i32::MAX).repr(), "(-∞;2147483647]".to_string());
+        assert_eq!(Domain::new().gt(Bound::Unbounded), Domain::new().gt(Value::Infinite));
+    }
+
+    #[test]
+    fn test_bounds_at_limits() {
+        assert_eq!(Domain::new().gt(Value::Included(i32::MIN)).repr(), "[-2147483648;∞)".to_string());
+        assert_eq!(Domain::new().lt(Value::Included(i32::MAX)).repr(), "(-∞;2147483647]".to_string());
+        assert_eq!(Domain::new().gt(Value::Included(i32::MAX)).repr(), "[2147483647;∞)".to_string());
+        assert_eq!(Domain::new().gt(Value::Secluded(i32::MAX)), Domain::None);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_generate_at_limits() {
+
+        fn rec(n: i32, c: &std::cell::RefCell<Vec<i32>>) {
+            c.borrow_mut().push(n);
+        }
+
+        let seen = std::cell::RefCell::new(Vec::new());
+        Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite).generate(rec, &seen);
+        Domain::Domain(Value::Infinite, Value::Secluded(i32::MIN)).generate(rec, &seen);
+        assert!(seen.borrow().is_empty());
+
+        Domain::Domain(Value::Secluded(i32::MAX - 2), Value::Included(i32::MAX)).generate(rec, &seen);
+        Domain::Domain(Value::Included(i32::MIN), Value::Secluded(i32::MIN + 2)).generate(rec, &seen);
+        assert_eq!(*seen.borrow(), vec![i32::MAX - 1, i32::MAX, i32::MIN, i32::MIN + 1]);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_generate_directions() {
+
+        fn rec(n: i32, c: &std::cell::RefCell<Vec<i32>>) {
+            c.borrow_mut().push(n);
+        }
+
+        let seen = std::cell::RefCell::new(Vec::new());
+        Domain::Domain(Value::Secluded(1), Value::Secluded(4)).generate(rec, &seen);
+        assert_eq!(seen.replace(Vec::new()), vec![2, 3]);
+
+        Domain::Domain(Value::Secluded(i32::MAX - 3), Value::Infinite).generate(rec, &seen);
+        assert_eq!(seen.replace(Vec::new()), vec![i32::MAX - 2, i32::MAX - 1, i32::MAX]);
+
+        Domain::Domain(Value::Infinite, Value::Secluded(i32::MIN + 3)).generate(rec, &seen);
+        assert_eq!(seen.replace(Vec::new()), vec![i32::MIN + 2, i32::MIN + 1, i32::MIN]);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_generate_skips_empty() {
+
+        fn rec(n: i32, _c: &()) {
+            panic!("{} generated from an empty interval", n);
+        }
+
+        Domain::between(10, 5).generate(rec, &());
+        Domain::Domain(Value::Secluded(5), Value::Secluded(6)).generate(rec, &());
+        Domain::Domain(Value::Secluded(5), Value::Secluded(5)).generate(rec, &());
+        Domain::Domain(Value::Included(5), Value::Secluded(5)).generate(rec, &());
+        Domain::Domain(Value::Secluded(5), Value::Included(5)).generate(rec, &());
+        Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite).generate(rec, &());
+        Domain::Union(vec![
+            Domain::between(10, 5),
+            Domain::Union(vec![Domain::Domain(Value::Secluded(5), Value::Secluded(6))]),
+            Domain::None,
+        ]).generate(rec, &());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_generate_union_members() {
+
+        fn rec(n: i32, c: &std::cell::RefCell<Vec<i32>>) {
+            c.borrow_mut().push(n);
+        }
+
+        let seen = std::cell::RefCell::new(Vec::new());
+        Domain::Union(vec![
+            Domain::between(3, 4),
+            Domain::between(10, 5),
+            Domain::Domain(Value::Included(7), Value::Secluded(7)),
+            Domain::between(1, 1),
+        ]).generate(rec, &seen);
+        assert_eq!(*seen.borrow(), vec![3, 4, 1]);
+    }
+
+    #[test]
+    fn test_generate_capped() {
+        let mut seen = Vec::new();
+        let error = Domain::at_least(0).generate_capped(4, |n| seen.push(n)).unwrap_err();
+        assert_eq!(seen, vec![0, 1, 2, 3]);
+        assert_eq!((error.emitted(), error.next()), (4, 4));
+
+        let mut seen = Vec::new();
+        let error = Domain::new().generate_capped(3, |n| seen.push(n)).unwrap_err();
+        assert_eq!(seen, vec![0, 1, -1]);
+        assert_eq!(error.next(), 2);
+
+        let domain = Domain::Union(vec![
+            Domain::between(1, 2),
+            Domain::between(10, 5),
+            Domain::Union(vec![Domain::at_most(i32::MIN + 1)]),
+        ]);
+        let mut seen = Vec::new();
+        assert!(domain.generate_capped(4, |n| seen.push(n)).is_ok());
+        assert_eq!(seen, vec![1, 2, i32::MIN + 1, i32::MIN]);
+
+        let mut count = 0;
+        let error = domain.generate_capped(0, |_| count += 1).unwrap_err();
+        assert_eq!((count, error.emitted(), error.next()), (0, 0, 1));
+        assert!(Domain::None.generate_capped(0, |_| ()).is_ok());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_generate_matches() {
+        use std::cell::RefCell;
+
+        fn rec(n: i32, c: &RefCell<Vec<i32>>) {
+            c.borrow_mut().push(n);
+        }
+
+        let domains = vec![
+            Domain::None,
+            Domain::between(3, 1),
+            Domain::Domain(Value::Secluded(-3), Value::Included(3)),
+            Domain::Union(vec![Domain::between(5, 9), Domain::between(0, 6), Domain::Union(vec![Domain::between(-2, -1)])]),
+            Domain::Domain(Value::Secluded(i32::MAX - 3), Value::Infinite),
+            Domain::Domain(Value::Infinite, Value::Secluded(i32::MIN + 3)),
+            Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite),
+        ];
+        for domain in &domains {
+            let generated = RefCell::new(Vec::new());
+            domain.generate(rec, &generated);
+            let with_context = RefCell::new(Vec::new());
+            domain.generate_with_context(rec, &with_context);
+            let mut each = Vec::new();
+            domain.for_each(|n| each.push(n));
+            assert_eq!(generated.borrow().clone(), each, "{:?}", domain);
+            assert_eq!(with_context.into_inner(), each, "{:?}", domain);
+        }
+
+        // Overlapping members are normalized, their common values come once
+        let overlapping = Domain::Union(vec![Domain::between(1, 3), Domain::between(0, 2)]);
+        let mut each = Vec::new();
+        overlapping.for_each(|n| each.push(n));
+        assert_eq!(each, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_try_generate_propagates() {
+        use std::io::Write;
+
+        // Room for "1\n2\n3\n" only, writing 10 fails
+        let mut buffer = [0u8; 6];
+        let mut sink = &mut buffer[..];
+        let domain = Domain::Union(vec![Domain::between(1, 3), Domain::between(10, 20), Domain::at_least(100)]);
+        let mut seen = Vec::new();
+        let error = domain.try_generate(|n| {
+            seen.push(n);
+            writeln!(sink, "{}", n)
+        }).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(seen, vec![1, 2, 3, 10]);
+        assert_eq!(&buffer, b"1\n2\n3\n");
+
+        let mut iterated = Vec::new();
+        let mut sink = &mut [0u8; 6][..];
+        let result = domain.iter().try_for_each(|n| {
+            iterated.push(n);
+            writeln!(sink, "{}", n)
+        });
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(iterated, seen);
+
+        let mut count = 0;
+        assert_eq!(Domain::between(5, 1).try_generate(|_| {
+            count += 1;
+            Err(())
+        }), Ok(()));
+        assert_eq!(Domain::at_most(i32::MIN).try_generate(|n| if n == i32::MIN { Err(n) } else { Ok(()) }), Err(i32::MIN));
+        assert_eq!(Domain::new().try_generate(|n| if n < 0 { Err(n) } else { Ok(()) }), Err(-1));
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_bounds_over_union() {
+        let domain = Domain::Union(vec![
+            Domain::between(0, 10),
+            Domain::between(20, 30),
+        ]);
+        assert_eq!(domain.gt(Value::Secluded(5)).repr(), "(5;10]⋃[20;30]".to_string());
+        assert!(!domain.gt(Value::Secluded(25)).contains(10));
+        assert!(!domain.lt(Value::Secluded(5)).contains(20));
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_generate() {
 
-        fn rec(n: i32, c: &()) {
+        fn rec(n: i32, _c: &()) {
             assert!(n > 5);
             assert!(n < 10);
         }
 
-        let mut domain = Domain::new()
+        let domain = Domain::new()
             .gt(Value::Secluded(5))
             .lt(Value::Secluded(10));
 
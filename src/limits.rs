@@ -0,0 +1,78 @@
+//! Regression tests at the `i32` limits: the ±1 step of a secluded border
+//! used to overflow, panicking in debug builds or looping in release ones
+
+use std::cell::RefCell;
+
+use crate::{Domain, Value};
+
+const MIN: i32 = i32::MIN;
+const MAX: i32 = i32::MAX;
+
+#[allow(deprecated)]
+fn generated(domain: &Domain<i32>) -> Vec<i32> {
+    fn rec(n: i32, c: &RefCell<Vec<i32>>) {
+        c.borrow_mut().push(n);
+    }
+
+    let seen = RefCell::new(Vec::new());
+    domain.generate(rec, &seen);
+    seen.into_inner()
+}
+
+#[test]
+fn test_gt_at_limits() {
+    assert_eq!(Domain::new().gt(Value::Included(MIN)), Domain::new());
+    assert_eq!(Domain::new().gt(Value::Secluded(MIN)).iter().next(), Some(MIN + 1));
+    assert_eq!(Domain::new().gt(Value::Included(MAX)).iter().collect::<Vec<_>>(), vec![MAX]);
+    assert!(Domain::new().gt(Value::Secluded(MAX)).is_empty());
+    assert!(Domain::between(MIN, MIN).gt(Value::Secluded(MIN)).is_empty());
+}
+
+#[test]
+fn test_lt_at_limits() {
+    assert_eq!(Domain::new().lt(Value::Included(MAX)), Domain::new());
+    assert_eq!(Domain::new().lt(Value::Secluded(MAX)).iter().next_back(), Some(MAX - 1));
+    assert_eq!(Domain::new().lt(Value::Included(MIN)).iter().collect::<Vec<_>>(), vec![MIN]);
+    assert!(Domain::new().lt(Value::Secluded(MIN)).is_empty());
+    assert!(Domain::between(MAX, MAX).lt(Value::Secluded(MAX)).is_empty());
+}
+
+#[test]
+fn test_generate_at_limits() {
+    assert_eq!(generated(&Domain::between(MAX - 1, MAX)), vec![MAX - 1, MAX]);
+    assert_eq!(generated(&Domain::between(MIN, MIN + 1)), vec![MIN, MIN + 1]);
+    assert_eq!(generated(&Domain::Domain(Value::Secluded(MAX - 1), Value::Infinite)), vec![MAX]);
+    assert_eq!(generated(&Domain::Domain(Value::Infinite, Value::Secluded(MIN + 1))), vec![MIN]);
+    assert!(generated(&Domain::Domain(Value::Secluded(MAX), Value::Infinite)).is_empty());
+    assert!(generated(&Domain::Domain(Value::Infinite, Value::Secluded(MIN))).is_empty());
+    assert!(generated(&Domain::Domain(Value::Secluded(MAX), Value::Secluded(MIN))).is_empty());
+}
+
+#[test]
+fn test_cardinality_at_limits() {
+    assert_eq!(Domain::between(MIN, MAX).cardinality(), Some(1 << 32));
+    assert_eq!(Domain::Domain(Value::Secluded(MIN), Value::Secluded(MAX)).cardinality(), Some((1 << 32) - 2));
+    assert_eq!(Domain::Domain(Value::Secluded(MAX), Value::Infinite).cardinality(), Some(0));
+    assert_eq!(Domain::Domain(Value::Infinite, Value::Secluded(MIN)).normalize().cardinality(), Some(0));
+    assert_eq!(Domain::at_least(MAX).cardinality(), None);
+}
+
+#[test]
+fn test_clamp_at_limits() {
+    assert_eq!(Domain::between(MAX, MAX).clamp(MIN), Some(MAX));
+    assert_eq!(Domain::between(MIN, MIN).clamp(MAX), Some(MIN));
+    assert_eq!(Domain::Domain(Value::Secluded(MAX - 1), Value::Infinite).clamp(0), Some(MAX));
+    assert_eq!(Domain::Domain(Value::Infinite, Value::Secluded(MIN + 1)).clamp(0), Some(MIN));
+    assert_eq!(Domain::Domain(Value::Secluded(MAX), Value::Infinite).clamp(0), None);
+    assert_eq!(Domain::new().clamp(MIN), Some(MIN));
+}
+
+#[test]
+fn test_nth_at_limits() {
+    assert_eq!(Domain::new().nth(0), Some(MIN));
+    assert_eq!(Domain::new().nth(u32::MAX as u64), Some(MAX));
+    assert_eq!(Domain::new().nth(1 << 32), None);
+    assert_eq!(Domain::Domain(Value::Secluded(MAX - 1), Value::Infinite).nth(0), Some(MAX));
+    assert_eq!(Domain::Domain(Value::Infinite, Value::Secluded(MIN)).nth(0), None);
+    assert_eq!(Domain::new().normalize().nth(u64::MAX), None);
+}
@@ -0,0 +1,224 @@
+//! Checking of `domain!` literals while compiling.
+//!
+//! A member like `(5; 10]` does not balance its delimiters, so it can not
+//! be written as macro tokens and the literal is a string instead. The
+//! string is validated by a `const fn` at compile time, which makes
+//! malformed syntax, integers out of `i32` and reversed borders compile
+//! errors, and parsed by `FromStr` at run time
+//! ```compile_fail
+//! dynamic_domain::domain!("[5; 3]");
+//! ```
+//! ```compile_fail
+//! dynamic_domain::domain!("[1; 2] U ([3; 4] U [5; 6])");
+//! ```
+//! ```compile_fail
+//! dynamic_domain::domain!("[-inf; 5]");
+//! ```
+//! ```compile_fail
+//! dynamic_domain::domain!("[0; 2147483648]");
+//! ```
+//! ```compile_fail
+//! dynamic_domain::domain!(5);
+//! ```
+
+/// Domain literal in the notation of `repr`, with the ASCII spellings
+/// `FromStr` accepts: `inf` for `∞`, `U` for `⋃` and `{}` for `∅`.
+/// Members must not be nested and a finite left border must not be
+/// greater than the right one, both checked at compile time
+/// ```
+/// use dynamic_domain::{domain, Domain, Value};
+/// assert_eq!(domain!("(5; 10]"), Domain::Domain(Value::Secluded(5), Value::Included(10)));
+/// assert_eq!(domain!("[1;3] U (7; inf)").repr(), "[1;3]⋃(7;∞)");
+/// assert_eq!(domain!("{}"), Domain::None);
+/// assert_eq!(domain!("(-inf; inf)"), Domain::new());
+/// ```
+#[macro_export]
+macro_rules! domain {
+    ($literal:literal) => {{
+        const _: () = $crate::literal::validate($literal);
+        $literal
+            .parse::<$crate::Domain<i32>>()
+            .expect("domain literal validated at compile time")
+    }};
+}
+
+const INFINITY: &[u8] = "∞".as_bytes();
+const EMPTY: &[u8] = "∅".as_bytes();
+const UNIONS: [&[u8]; 4] = ["⋃".as_bytes(), "∪".as_bytes(), b"U", b"u"];
+
+/// Border read by `border`: a finite point or one of the infinities
+#[derive(Clone, Copy)]
+enum Border {
+    Int(i64),
+    NegInfinite,
+    PosInfinite,
+}
+
+const fn starts_with(bytes: &[u8], at: usize, prefix: &[u8]) -> bool {
+    if at + prefix.len() > bytes.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if bytes[at + i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn skip_whitespace(bytes: &[u8], mut at: usize) -> usize {
+    while at < bytes.len() && bytes[at].is_ascii_whitespace() {
+        at += 1;
+    }
+    at
+}
+
+const fn expect(bytes: &[u8], at: usize, expected: u8) -> usize {
+    let at = skip_whitespace(bytes, at);
+    if at >= bytes.len() || bytes[at] != expected {
+        panic!("malformed domain literal");
+    }
+    at + 1
+}
+
+const fn border(bytes: &[u8], at: usize) -> (Border, usize) {
+    let mut at = skip_whitespace(bytes, at);
+    let negative = at < bytes.len() && bytes[at] == b'-';
+    if at < bytes.len() && (bytes[at] == b'-' || bytes[at] == b'+') {
+        at += 1;
+    }
+
+    let infinity = if starts_with(bytes, at, INFINITY) {
+        INFINITY.len()
+    } else if starts_with(bytes, at, b"inf") {
+        3
+    } else {
+        0
+    };
+    if infinity > 0 {
+        let border = if negative { Border::NegInfinite } else { Border::PosInfinite };
+        return (border, at + infinity);
+    }
+
+    let start = at;
+    let limit = if negative { i32::MAX as i64 + 1 } else { i32::MAX as i64 };
+    let mut n: i64 = 0;
+    while at < bytes.len() && bytes[at].is_ascii_digit() {
+        n = n * 10 + (bytes[at] - b'0') as i64;
+        if n > limit {
+            panic!("integer out of i32 in domain literal");
+        }
+        at += 1;
+    }
+    if at == start {
+        panic!("malformed domain literal");
+    }
+    (Border::Int(if negative { -n } else { n }), at)
+}
+
+/// Index just past the member starting at `at`
+const fn member(bytes: &[u8], at: usize) -> usize {
+    let at = skip_whitespace(bytes, at);
+    if starts_with(bytes, at, EMPTY) {
+        return at + EMPTY.len();
+    }
+    if starts_with(bytes, at, b"{") {
+        return expect(bytes, at + 1, b'}');
+    }
+    if !starts_with(bytes, at, b"[") && !starts_with(bytes, at, b"(") {
+        panic!("malformed domain literal");
+    }
+    let left_included = bytes[at] == b'[';
+
+    let (left, at) = border(bytes, at + 1);
+    let at = expect(bytes, at, b';');
+    let (right, at) = border(bytes, at);
+    let at = skip_whitespace(bytes, at);
+    if at >= bytes.len() || (bytes[at] != b']' && bytes[at] != b')') {
+        panic!("malformed domain literal");
+    }
+    let right_included = bytes[at] == b']';
+
+    if let (Border::Int(l), Border::Int(r)) = (left, right) {
+        if l > r {
+            panic!("reversed borders in domain literal");
+        }
+    }
+    let infinities_valid = match (left, right) {
+        (Border::PosInfinite, _) | (_, Border::NegInfinite) => false,
+        (Border::NegInfinite, _) if left_included => false,
+        (_, Border::PosInfinite) if right_included => false,
+        _ => true,
+    };
+    if !infinities_valid {
+        panic!("misplaced infinity in domain literal");
+    }
+    at + 1
+}
+
+/// Panics, which is a compile error in a constant, unless `literal` is a
+/// valid `domain!` literal
+#[doc(hidden)]
+pub const fn validate(literal: &str) {
+    let bytes = literal.as_bytes();
+    let mut at = member(bytes, 0);
+    loop {
+        at = skip_whitespace(bytes, at);
+        if at == bytes.len() {
+            return;
+        }
+        let mut union = 0;
+        let mut i = 0;
+        while i < UNIONS.len() {
+            if starts_with(bytes, at, UNIONS[i]) {
+                union = UNIONS[i].len();
+            }
+            i += 1;
+        }
+        if union == 0 {
+            panic!("malformed domain literal");
+        }
+        at = member(bytes, at + union);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use crate::{Domain, Value};
+
+    fn validates(literal: &str) -> bool {
+        panic::catch_unwind(|| super::validate(literal)).is_ok()
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(domain!("[-5;-1]"), Domain::between(-5, -1));
+        assert_eq!(domain!("( -2147483648 ; 2147483647 ]").repr(), "(-2147483648;2147483647]");
+        assert_eq!(domain!("(-∞;5)⋃{ }∪[8;8]").repr(), "(-∞;5)⋃∅⋃[8;8]");
+        assert_eq!(domain!("∅"), Domain::None);
+        assert_eq!(domain!("\n\t[1;3]\nu\n(7;+inf)\n").repr(), "[1;3]⋃(7;∞)");
+        assert_eq!(domain!("(5;5)"), Domain::Domain(Value::Secluded(5), Value::Secluded(5)));
+        const _: () = super::validate("[1;2]U[3;4]");
+    }
+
+    #[test]
+    fn test_validate_matches_parse() {
+        let cases = [
+            "[1;2]", "(-inf;inf)", "[-2147483648;0)", "{}", "∅ U ∅", "[3;3]⋃(4;∞)",
+            "", "[1;2", "[1,2]", "[1;2] [3;4]", "[1;2]U", "([1;2])", "[[1;2]]",
+            "[-∞;2]", "(∞;2]", "(1;-∞)", "(1;inf]", "[2147483648;0]", "[-2147483649;0]",
+            "[5;3]", "(1;int)", "[;2]", "[-;2]",
+        ];
+        for literal in cases.iter() {
+            if validates(literal) {
+                assert!(literal.parse::<Domain<i32>>().is_ok(), "{}", literal);
+            }
+        }
+        let valid: Vec<&str> = cases.iter().copied().filter(|literal| validates(literal)).collect();
+        assert_eq!(valid, ["[1;2]", "(-inf;inf)", "[-2147483648;0)", "{}", "∅ U ∅", "[3;3]⋃(4;∞)"]);
+    }
+}
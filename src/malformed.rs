@@ -0,0 +1,172 @@
+//! Negative tests: the looping operations on domains no constructor
+//! builds, nested, reversed, empty or repeated, either repair them by
+//! normalizing or find nothing, and finish within a watchdog's count of
+//! steps rather than walking the same values over and over
+
+use std::collections::HashSet;
+
+use crate::{BoundedUniverse, Domain, IterOrder, Value};
+
+const MIN: i32 = i32::MIN;
+const MAX: i32 = i32::MAX;
+
+/// Steps any operation may take on one entry of the catalogue
+const WATCHDOG: usize = 100_000;
+
+/// Malformed domains with a domain of the same values as constructors
+/// build it
+fn catalogue() -> Vec<(&'static str, Domain<i32>, Domain<i32>)> {
+    let nested = (0..64).fold(Domain::between(0, 9), |domain, _| Domain::Union(vec![domain]));
+    vec![
+        ("empty union", Domain::Union(vec![]), Domain::None),
+        (
+            "unions of empty unions",
+            Domain::Union(vec![Domain::Union(vec![]), Domain::Union(vec![Domain::None, Domain::Union(vec![])])]),
+            Domain::None,
+        ),
+        ("reversed", Domain::Domain(Value::Included(10), Value::Included(1)), Domain::None),
+        ("reversed across the range", Domain::between(MAX, MIN), Domain::None),
+        ("secluded on both sides of one value", Domain::Domain(Value::Secluded(5), Value::Secluded(5)), Domain::None),
+        ("secluded past the upper limit", Domain::Domain(Value::Secluded(MAX), Value::Infinite), Domain::None),
+        ("secluded past the lower limit", Domain::Domain(Value::Infinite, Value::Secluded(MIN)), Domain::None),
+        ("nested 64 deep", nested, Domain::between(0, 9)),
+        (
+            "nested overlapping",
+            Domain::Union(vec![
+                Domain::Union(vec![Domain::between(0, 5), Domain::Union(vec![Domain::between(3, 9)])]),
+                Domain::between(7, 12),
+            ]),
+            Domain::between(0, 12),
+        ),
+        (
+            "reversed among others",
+            Domain::Union(vec![
+                Domain::between(9, 0),
+                Domain::between(20, 29),
+                Domain::Domain(Value::Secluded(3), Value::Secluded(4)),
+            ]),
+            Domain::between(20, 29),
+        ),
+        (
+            "descending members",
+            Domain::Union(vec![Domain::between(20, 29), Domain::at_most(-5)]),
+            Domain::Union(vec![Domain::at_most(-5), Domain::between(20, 29)]),
+        ),
+        ("repeated", Domain::Union(vec![Domain::between(0, 99_999); 64]), Domain::between(0, 99_999)),
+        ("repeated at the limit", Domain::Union(vec![Domain::at_least(MAX - 9); 8]), Domain::at_least(MAX - 9)),
+        ("repeated infinite", Domain::Union(vec![Domain::new(); 16]), Domain::new()),
+        (
+            "infinite inside a nested union",
+            Domain::Union(vec![Domain::Union(vec![Domain::Domain(Value::Infinite, Value::Infinite)]), Domain::between(0, 5)]),
+            Domain::new(),
+        ),
+    ]
+}
+
+/// Values `try_generate` hands out before the watchdog stops it, and
+/// whether it finished first
+fn watched_generate(domain: &Domain<i32>) -> (Vec<i32>, bool) {
+    let mut seen = Vec::new();
+    let finished = domain
+        .try_generate(|n| {
+            if seen.len() == WATCHDOG {
+                return Err(());
+            }
+            seen.push(n);
+            Ok(())
+        })
+        .is_ok();
+    (seen, finished)
+}
+
+/// `i32` values of `expected`, infinite borders ending at the limits
+fn values(expected: &Domain<i32>) -> u64 {
+    expected.runs().map(|run| (run.end() - run.start() + 1) as u64).sum()
+}
+
+#[test]
+fn test_generate_repairs() {
+    for (name, domain, expected) in catalogue() {
+        let (seen, finished) = watched_generate(&domain);
+        let distinct: HashSet<i32> = seen.iter().copied().collect();
+        assert_eq!(distinct.len(), seen.len(), "{} hands out a value twice", name);
+        assert!(seen.iter().all(|n| expected.contains(*n)), "{}", name);
+        assert_eq!(finished, values(&expected) <= WATCHDOG as u64, "{}", name);
+        if finished {
+            assert_eq!(seen.len() as u64, values(&expected), "{}", name);
+        }
+
+        assert_eq!(domain.generate_capped(WATCHDOG as u64, |_| ()).is_ok(), finished, "{}", name);
+        if finished {
+            let mut each = 0;
+            domain.for_each(|_| each += 1);
+            assert_eq!(each, seen.len(), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_iterators_repair() {
+    for (name, domain, expected) in catalogue() {
+        let values: Vec<i32> = domain.iter().take(WATCHDOG).collect();
+        assert_eq!(values, expected.iter().take(WATCHDOG).collect::<Vec<_>>(), "{}", name);
+        let values: Vec<i32> = domain.iter().rev().take(WATCHDOG).collect();
+        assert_eq!(values, expected.iter().rev().take(WATCHDOG).collect::<Vec<_>>(), "{}", name);
+        let values: Vec<i32> = domain.clone().into_iter().take(WATCHDOG).collect();
+        assert_eq!(values, expected.iter().take(WATCHDOG).collect::<Vec<_>>(), "{}", name);
+        assert_eq!(domain.iter_in(-50, 50).collect::<Vec<_>>(), expected.iter_in(-50, 50).collect::<Vec<_>>(), "{}", name);
+        assert_eq!(domain.iter_in(MAX - 20, MAX).collect::<Vec<_>>(), expected.iter_in(MAX - 20, MAX).collect::<Vec<_>>(), "{}", name);
+
+        for order in [IterOrder::Ascending, IterOrder::Descending, IterOrder::FromMidpoint, IterOrder::FromValue(MAX)].iter().copied() {
+            let values = domain.iter_ordered(order).map(|iter| iter.take(WATCHDOG).collect::<Vec<_>>());
+            let wanted = expected.iter_ordered(order).map(|iter| iter.take(WATCHDOG).collect::<Vec<_>>());
+            assert_eq!(values.ok(), wanted.ok(), "{} {:?}", name, order);
+        }
+
+        let view = domain.view_below(50);
+        assert_eq!(view.iter().take(WATCHDOG).collect::<Vec<_>>(), expected.view_below(50).iter().take(WATCHDOG).collect::<Vec<_>>(), "{}", name);
+        assert_eq!(view.cardinality(), expected.view_below(50).cardinality(), "{}", name);
+        let view = domain.view_above(MAX - 5);
+        assert_eq!(view.iter().collect::<Vec<_>>(), expected.view_above(MAX - 5).iter().collect::<Vec<_>>(), "{}", name);
+    }
+}
+
+#[test]
+fn test_queries_repair() {
+    let universe = BoundedUniverse::new(&Domain::between(-100, 100));
+    for (name, domain, expected) in catalogue() {
+        assert_eq!(domain.runs().take(WATCHDOG).collect::<Vec<_>>(), expected.runs().collect::<Vec<_>>(), "{}", name);
+        assert_eq!(
+            domain.boundary_events().take(WATCHDOG).collect::<Vec<_>>(),
+            expected.boundary_events().collect::<Vec<_>>(),
+            "{}",
+            name,
+        );
+        assert_eq!(domain.cardinality(), expected.cardinality(), "{}", name);
+        for n in [0, 1, WATCHDOG as u64, u32::MAX as u64, u64::MAX].iter().copied() {
+            assert_eq!(domain.nth(n), expected.nth(n), "{} nth {}", name, n);
+        }
+        for value in [MIN, -1, 0, 11, MAX].iter().copied() {
+            assert_eq!(domain.rank(value), expected.rank(value), "{} rank {}", name, value);
+        }
+        for random in [0, u64::MAX / 2, u64::MAX].iter().copied() {
+            assert_eq!(universe.sample(&domain, random), universe.sample(&expected, random), "{}", name);
+        }
+        assert_eq!(domain.chunks(7), expected.chunks(7), "{}", name);
+        let labeled = domain.enumerate_labeled(|n| n.to_string(), WATCHDOG);
+        assert_eq!(labeled.ok(), expected.enumerate_labeled(|n| n.to_string(), WATCHDOG).ok(), "{}", name);
+    }
+}
+
+#[test]
+fn test_deferred_union_repairs() {
+    let mut deferred = Domain::None;
+    let mut expected = Domain::None;
+    for (_, domain, normalized) in catalogue() {
+        deferred.union_assign_deferred(domain);
+        expected = expected.union(&normalized).into_inner();
+    }
+    deferred.flush();
+    assert_eq!(deferred, expected);
+    assert_eq!(deferred, Domain::new());
+}
@@ -0,0 +1,323 @@
+use crate::error::KeyOverlap;
+use crate::util;
+use crate::{Domain, Value};
+
+/// Values mapped from disjoint domains of keys, for piecewise settings
+/// like tax brackets or rate tiers. Keys are kept normalized, in the
+/// order of their least value, and looked up by binary search over their
+/// runs. Infinite borders stop at the `i32` limits like `Domain::iter`
+/// ```
+/// use dynamic_domain::{Domain, DomainMap, Value};
+/// let mut rates = DomainMap::new();
+/// rates.insert(Domain::Domain(Value::Included(0), Value::Secluded(10_000)), 0).unwrap();
+/// rates.insert(Domain::Domain(Value::Included(10_000), Value::Secluded(40_000)), 20).unwrap();
+/// rates.insert(Domain::at_least(40_000), 40).unwrap();
+/// assert_eq!(rates.get(9_999), Some(&0));
+/// assert_eq!(rates.get(10_000), Some(&20));
+/// assert_eq!(rates.get(-1), None);
+/// assert!(rates.insert(Domain::between(35_000, 45_000), 30).is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct DomainMap<V> {
+    /// Entries ascending by the least value of their key
+    entries: Vec<(Domain<i32>, V)>,
+    /// Runs of every key ascending, with the position of their entry
+    runs: Vec<(i64, i64, usize)>,
+}
+
+impl<V> Default for DomainMap<V> {
+    fn default() -> Self {
+        DomainMap { entries: Vec::new(), runs: Vec::new() }
+    }
+}
+
+impl<V> DomainMap<V> {
+
+    /// Map without entries
+    /// ```
+    /// use dynamic_domain::DomainMap;
+    /// assert!(DomainMap::<u8>::new().is_empty());
+    /// ```
+    pub fn new() -> Self {
+        DomainMap::default()
+    }
+
+    /// Number of entries, a key split by `insert_replacing` stays one entry
+    /// ```
+    /// use dynamic_domain::{Domain, DomainMap};
+    /// let mut map = DomainMap::new();
+    /// map.insert(Domain::Union(vec![Domain::between(0, 1), Domain::between(5, 6)]), 'a').unwrap();
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no entries
+    /// ```
+    /// use dynamic_domain::{Domain, DomainMap};
+    /// let mut map = DomainMap::new();
+    /// map.insert(Domain::between(0, 1), 'a').unwrap();
+    /// assert!(!map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Maps every value of `key` to `value`, refusing a key which shares
+    /// values with a key already mapped: nothing changes and the error
+    /// holds the shared values. An empty key maps no value, it is accepted
+    /// and not kept. Takes time linear in the size of the map
+    /// ```
+    /// use dynamic_domain::{Domain, DomainMap, Value};
+    /// let mut map = DomainMap::new();
+    /// map.insert(Domain::between(0, 9), "low").unwrap();
+    /// // Adjacent keys share no value
+    /// map.insert(Domain::between(10, 19), "mid").unwrap();
+    /// let error = map.insert(Domain::between(15, 30), "high").unwrap_err();
+    /// assert_eq!(error.overlap().clone().repr(), "[15;19]");
+    /// assert!(map.insert(Domain::Domain(Value::Secluded(19), Value::Included(30)), "high").is_ok());
+    /// ```
+    pub fn insert(&mut self, key: Domain<i32>, value: V) -> Result<(), KeyOverlap> {
+        let runs = util::i32_runs(&key);
+        let mut overlap = Vec::new();
+        self.meet(&runs, |lo, hi, _| overlap.push((Value::Included(lo as i32), Value::Included(hi as i32))));
+        if !overlap.is_empty() {
+            return Err(KeyOverlap::new(util::from_members(overlap).normalize().into_inner()));
+        }
+        if !runs.is_empty() {
+            self.entries.push((key.normalize().into_inner(), value));
+            self.reindex();
+        }
+        Ok(())
+    }
+
+    /// Maps every value of `key` to `value`, taking those values away
+    /// from the keys already mapped: an entry keeps the rest of its key,
+    /// possibly split in two around `key`, and is dropped when nothing is
+    /// left of it
+    /// ```
+    /// use dynamic_domain::{Domain, DomainMap};
+    /// let mut map = DomainMap::new();
+    /// map.insert(Domain::between(0, 99), "standard").unwrap();
+    /// map.insert_replacing(Domain::between(40, 59), "discount");
+    /// assert_eq!(map.get(39), Some(&"standard"));
+    /// assert_eq!(map.get(40), Some(&"discount"));
+    /// assert_eq!(map.get(60), Some(&"standard"));
+    /// let keys: Vec<String> = map.iter().map(|(key, _)| key.clone().repr()).collect();
+    /// assert_eq!(keys, vec!["[0;40)⋃(59;99]", "[40;59]"]);
+    /// ```
+    pub fn insert_replacing(&mut self, key: Domain<i32>, value: V) {
+        let runs = util::i32_runs(&key);
+        let mut hit = vec![false; self.entries.len()];
+        self.meet(&runs, |_, _, entry| hit[entry] = true);
+
+        let mut hit = hit.into_iter();
+        self.entries.retain_mut(|(existing, _)| {
+            if hit.next() == Some(true) {
+                *existing = existing.difference(&key).into_inner();
+            }
+            !existing.is_empty()
+        });
+        if !runs.is_empty() {
+            self.entries.push((key.normalize().into_inner(), value));
+        }
+        self.reindex();
+    }
+
+    /// Value mapped from `value`
+    /// ```
+    /// use dynamic_domain::{Domain, DomainMap, Value};
+    /// let mut map = DomainMap::new();
+    /// map.insert(Domain::Domain(Value::Secluded(0), Value::Included(10)), 'a').unwrap();
+    /// assert_eq!(map.get(0), None);
+    /// assert_eq!(map.get(10), Some(&'a'));
+    /// ```
+    pub fn get(&self, value: i32) -> Option<&V> {
+        let value = value as i64;
+        let run = self.runs.partition_point(|(lo, _, _)| *lo <= value).checked_sub(1)?;
+        let (_, hi, entry) = self.runs[run];
+        match value <= hi {
+            true => Some(&self.entries[entry].1),
+            false => None,
+        }
+    }
+
+    /// Entries whose key shares a value with `domain`, in key order, each
+    /// with its whole key
+    /// ```
+    /// use dynamic_domain::{Domain, DomainMap};
+    /// let mut map = DomainMap::new();
+    /// map.insert(Domain::between(0, 9), 'a').unwrap();
+    /// map.insert(Domain::between(10, 19), 'b').unwrap();
+    /// map.insert(Domain::between(20, 29), 'c').unwrap();
+    /// let found: Vec<char> = map.range_query(&Domain::between(5, 10)).into_iter().map(|(_, value)| *value).collect();
+    /// assert_eq!(found, vec!['a', 'b']);
+    /// ```
+    pub fn range_query(&self, domain: &Domain<i32>) -> Vec<(&Domain<i32>, &V)> {
+        let mut hit = vec![false; self.entries.len()];
+        self.meet(&util::i32_runs(domain), |_, _, entry| hit[entry] = true);
+        self.entries
+            .iter()
+            .zip(hit)
+            .filter(|(_, hit)| *hit)
+            .map(|((key, value), _)| (key, value))
+            .collect()
+    }
+
+    /// Entries in key order, ascending by the least value of their key
+    /// ```
+    /// use dynamic_domain::{Domain, DomainMap};
+    /// let mut map = DomainMap::new();
+    /// map.insert(Domain::between(20, 29), 'b').unwrap();
+    /// map.insert(Domain::Union(vec![Domain::between(0, 9), Domain::between(40, 49)]), 'a').unwrap();
+    /// let values: Vec<char> = map.iter().map(|(_, value)| *value).collect();
+    /// assert_eq!(values, vec!['a', 'b']);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&Domain<i32>, &V)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    /// Calls `f` with every piece where `runs`, ascending and disjoint,
+    /// meet the runs of the keys, and the position of the entry of that key
+    fn meet(&self, runs: &[(i64, i64)], mut f: impl FnMut(i64, i64, usize)) {
+        let (mut i, mut j) = (0, 0);
+        while i < self.runs.len() && j < runs.len() {
+            let (lo, hi, entry) = self.runs[i];
+            let (other_lo, other_hi) = runs[j];
+            if lo.max(other_lo) <= hi.min(other_hi) {
+                f(lo.max(other_lo), hi.min(other_hi), entry);
+            }
+            if hi < other_hi {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+
+    /// Sorts the entries by key and lays out the runs of their keys again
+    fn reindex(&mut self) {
+        self.entries.sort_by_cached_key(|(key, _)| util::i32_runs(key).first().map(|(lo, _)| *lo));
+        self.runs = self
+            .entries
+            .iter()
+            .enumerate()
+            .flat_map(|(entry, (key, _))| util::i32_runs(key).into_iter().map(move |(lo, hi)| (lo, hi, entry)))
+            .collect();
+        self.runs.sort_unstable_by_key(|(lo, _, _)| *lo);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::{border, union_of};
+    use crate::{Domain, DomainMap, Value};
+
+    fn probe() -> impl Strategy<Value = i32> {
+        prop_oneof![8 => -35..35, 1 => Just(i32::MIN), 1 => Just(i32::MAX)]
+    }
+
+    /// Checks the map answers the same as a list of `(key, value)` pairs
+    /// searched one by one
+    fn check(map: &DomainMap<usize>, plain: &[(Domain<i32>, usize)], probes: &[i32]) -> Result<(), TestCaseError> {
+        for probe in probes {
+            let expected = plain.iter().find(|(key, _)| key.contains(*probe)).map(|(_, value)| value);
+            prop_assert_eq!(map.get(*probe), expected, "at {}", probe);
+        }
+        let keys: Vec<&Domain<i32>> = map.iter().map(|(key, _)| key).collect();
+        prop_assert_eq!(keys.len(), map.len());
+        for pair in keys.windows(2) {
+            let least = |key: &Domain<i32>| key.runs().next().map(|run| *run.start());
+            prop_assert!(least(pair[0]) < least(pair[1]));
+            prop_assert!(pair[0].intersect(pair[1]).is_empty());
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_insert_rejects_overlap(keys in prop::collection::vec(union_of(border(30), 0..3), 0..8), probes in prop::collection::vec(probe(), 20)) {
+            let mut map = DomainMap::new();
+            let mut plain: Vec<(Domain<i32>, usize)> = Vec::new();
+            for (value, key) in keys.into_iter().enumerate() {
+                let overlap = plain.iter().fold(Domain::None, |overlap, (other, _)| overlap.union(&key.intersect(other)).into_inner());
+                match map.insert(key.clone(), value) {
+                    Ok(()) => {
+                        prop_assert!(overlap.is_empty());
+                        plain.push((key, value));
+                    },
+                    Err(error) => prop_assert_eq!(error.overlap(), &overlap),
+                }
+                check(&map, &plain, &probes)?;
+            }
+        }
+
+        #[test]
+        fn test_insert_replacing(keys in prop::collection::vec(union_of(border(30), 0..3), 0..8), probes in prop::collection::vec(probe(), 20), query in union_of(border(30), 0..3)) {
+            let mut map = DomainMap::new();
+            let mut plain: Vec<(Domain<i32>, usize)> = Vec::new();
+            for (value, key) in keys.into_iter().enumerate() {
+                map.insert_replacing(key.clone(), value);
+                // Last inserted first, so it wins
+                plain.insert(0, (key, value));
+                check(&map, &plain, &probes)?;
+            }
+
+            let found: Vec<usize> = map.range_query(&query).into_iter().map(|(_, value)| *value).collect();
+            let expected: Vec<usize> = map.iter().filter(|(key, _)| !key.intersect(&query).is_empty()).map(|(_, value)| *value).collect();
+            prop_assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn test_brackets() {
+        let bracket = |lo: i32, hi: Value<i32>| Domain::Domain(Value::Included(lo), hi);
+        let mut tax = DomainMap::new();
+        tax.insert(bracket(0, Value::Secluded(11_000)), 10).unwrap();
+        tax.insert(bracket(11_000, Value::Secluded(44_725)), 12).unwrap();
+        tax.insert(bracket(44_725, Value::Included(95_375)), 22).unwrap();
+        tax.insert(Domain::Domain(Value::Secluded(95_375), Value::Infinite), 24).unwrap();
+
+        let rates: Vec<Option<&i32>> = [-1, 0, 10_999, 11_000, 44_724, 44_725, 95_375, 95_376, i32::MAX].iter().map(|income| tax.get(*income)).collect();
+        assert_eq!(rates, vec![None, Some(&10), Some(&10), Some(&12), Some(&12), Some(&22), Some(&22), Some(&24), Some(&24)]);
+
+        // Open edges meeting on a value leave it out of both
+        let mut gap = DomainMap::new();
+        gap.insert(Domain::Domain(Value::Infinite, Value::Secluded(0)), "negative").unwrap();
+        gap.insert(Domain::Domain(Value::Secluded(0), Value::Infinite), "positive").unwrap();
+        assert_eq!((gap.get(-1), gap.get(0), gap.get(1)), (Some(&"negative"), None, Some(&"positive")));
+        assert_eq!(gap.get(i32::MIN), Some(&"negative"));
+        assert!(gap.insert(Domain::between(0, 0), "zero").is_ok());
+        assert_eq!(gap.insert(Domain::between(-1, 1), "small").unwrap_err().overlap().clone().repr(), "[-1;1]");
+        assert_eq!(gap.len(), 3);
+    }
+
+    #[test]
+    fn test_replacing_splits() {
+        let mut tiers = DomainMap::new();
+        tiers.insert(Domain::between(0, 9), 'a').unwrap();
+        tiers.insert(Domain::between(10, 19), 'b').unwrap();
+        tiers.insert(Domain::between(20, 29), 'c').unwrap();
+
+        // Swallows `b` whole and cuts into both neighbours
+        tiers.insert_replacing(Domain::Domain(Value::Secluded(5), Value::Secluded(25)), 'x');
+        let entries: Vec<(String, char)> = tiers.iter().map(|(key, value)| (key.clone().repr(), *value)).collect();
+        assert_eq!(entries, vec![("[0;5]".to_string(), 'a'), ("(5;25)".to_string(), 'x'), ("[25;29]".to_string(), 'c')]);
+        let found: Vec<char> = tiers.range_query(&Domain::between(5, 6)).into_iter().map(|(_, value)| *value).collect();
+        assert_eq!(found, vec!['a', 'x']);
+
+        // Splits one key in two, which stay a single entry
+        tiers.insert_replacing(Domain::between(12, 13), 'y');
+        assert_eq!(tiers.len(), 4);
+        assert_eq!((tiers.get(11), tiers.get(12), tiers.get(14)), (Some(&'x'), Some(&'y'), Some(&'x')));
+
+        tiers.insert_replacing(Domain::new(), 'z');
+        assert_eq!(tiers.iter().map(|(_, value)| *value).collect::<Vec<_>>(), vec!['z']);
+        tiers.insert_replacing(Domain::None, 'n');
+        assert_eq!((tiers.len(), tiers.get(i32::MIN), tiers.get(i32::MAX)), (1, Some(&'z'), Some(&'z')));
+        assert!(tiers.range_query(&Domain::None).is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+use crate::util::{self, Member};
+use crate::{Domain, DomainError, Value};
+
+/// Behaviour of a function at an infinite border of its argument,
+/// used by `Domain::apply_monotonic`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    /// The function grows without bound
+    Infinite,
+
+    /// The function settles at the value. A monotone integer function
+    /// bounded on one side reaches its limit, so the value is admitted
+    Asymptote(i32),
+}
+
+impl Limit {
+    fn border(self) -> Value<i32> {
+        match self {
+            Limit::Infinite => Value::Infinite,
+            Limit::Asymptote(i) => Value::Included(i),
+        }
+    }
+}
+
+impl Domain<i32> {
+
+    /// Interval hull of the image of every member under the monotone `f`.
+    /// Borders are mapped as written, so open borders stay open, which
+    /// expects `f` to be strictly monotone. Decreasing functions swap the
+    /// borders. `at_neg_inf` and `at_pos_inf` state what `f` tends to
+    /// when its argument does
+    /// ```
+    /// use dynamic_domain::{Domain, Limit, Value};
+    /// let domain = Domain::Domain(Value::Secluded(1), Value::Included(4));
+    /// let image = domain.apply_monotonic(|x| 2 * x, true, Limit::Infinite, Limit::Infinite);
+    /// assert_eq!(image.repr(), "(2;8]");
+    ///
+    /// let negated = Domain::at_least(3).apply_monotonic(|x| -x, false, Limit::Infinite, Limit::Infinite);
+    /// assert_eq!(negated.repr(), "(-∞;-3]");
+    /// ```
+    pub fn apply_monotonic(
+        &self,
+        f: impl Fn(i32) -> i32,
+        increasing: bool,
+        at_neg_inf: Limit,
+        at_pos_inf: Limit,
+    ) -> Self {
+        let map = |value: &Value<i32>, limit: Limit| match value {
+            Value::Included(i) => Value::Included(f(*i)),
+            Value::Secluded(i) => Value::Secluded(f(*i)),
+            Value::Infinite => limit.border(),
+        };
+
+        let members: Vec<Member> = util::normalized(self)
+            .iter()
+            .map(|(l, r)| {
+                let (l, r) = (map(l, at_neg_inf), map(r, at_pos_inf));
+                if increasing { (l, r) } else { (r, l) }
+            })
+            .collect();
+
+        util::from_members(util::normalize_members(members))
+    }
+
+    /// `apply_monotonic` for functions whose monotonicity flips at each of
+    /// the ascending `breakpoints`, `increasing` describes the piece left
+    /// of the first breakpoint. The images of the pieces are united.
+    /// Errors with `DomainError::Reversed` if `breakpoints` are not
+    /// strictly ascending
+    /// ```
+    /// use dynamic_domain::{Domain, DomainError, Limit};
+    /// let square = Domain::between(-3, 2)
+    ///     .apply_piecewise_monotonic(|x| x * x, &[0], false, Limit::Infinite, Limit::Infinite);
+    /// assert_eq!(square.unwrap().repr(), "[0;9]");
+    ///
+    /// let unsorted = Domain::new().apply_piecewise_monotonic(|x| x, &[3, 1], true, Limit::Infinite, Limit::Infinite);
+    /// assert_eq!(unsorted, Err(DomainError::Reversed));
+    /// ```
+    pub fn apply_piecewise_monotonic(
+        &self,
+        f: impl Fn(i32) -> i32,
+        breakpoints: &[i32],
+        increasing: bool,
+        at_neg_inf: Limit,
+        at_pos_inf: Limit,
+    ) -> Result<Self, DomainError> {
+        if breakpoints.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(DomainError::Reversed);
+        }
+
+        let mut starts = vec![Value::Infinite];
+        starts.extend(breakpoints.iter().map(|b| Value::Included(*b)));
+        let mut ends: Vec<Value<i32>> = breakpoints.iter().map(|b| Value::Included(*b)).collect();
+        ends.push(Value::Infinite);
+
+        let mut image = Domain::None;
+        for (i, (start, end)) in starts.into_iter().zip(ends).enumerate() {
+            let piece = self.intersect(&Domain::Domain(start, end));
+            let piece_increasing = increasing == (i % 2 == 0);
+            image = image.union(
+                &piece.apply_monotonic(&f, piece_increasing, at_neg_inf, at_pos_inf)
+            ).into_inner();
+        }
+
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Domain, DomainError, Limit, Value};
+
+    #[test]
+    fn test_apply_increasing() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Infinite, Value::Secluded(0)),
+            Domain::between(3, 5),
+        ]);
+        let cubed = domain.apply_monotonic(|x| x * x * x, true, Limit::Infinite, Limit::Infinite);
+        assert_eq!(cubed.repr(), "(-∞;0)⋃[27;125]");
+
+        let pow2 = Domain::at_most(4).apply_monotonic(
+            |x| if x < 0 { 0 } else { 1 << x },
+            true,
+            Limit::Asymptote(0),
+            Limit::Infinite,
+        );
+        assert_eq!(pow2.repr(), "[0;16]");
+    }
+
+    #[test]
+    fn test_apply_decreasing() {
+        let domain = Domain::Domain(Value::Included(-2), Value::Secluded(10));
+        let flipped = domain.apply_monotonic(|x| 100 - x, false, Limit::Infinite, Limit::Infinite);
+        assert_eq!(flipped.repr(), "(90;102]");
+
+        let reciprocal = Domain::at_least(1).apply_monotonic(
+            |x| 60 / x,
+            false,
+            Limit::Infinite,
+            Limit::Asymptote(0),
+        );
+        assert_eq!(reciprocal.repr(), "[0;60]");
+        assert_eq!(Domain::None.apply_monotonic(|x| x, false, Limit::Infinite, Limit::Infinite), Domain::None);
+    }
+
+    #[test]
+    fn test_apply_merges_images() {
+        let domain = Domain::Union(vec![Domain::between(0, 2), Domain::between(4, 5)]);
+        let clamped = domain.apply_monotonic(|x| x.min(3), true, Limit::Infinite, Limit::Asymptote(3));
+        assert_eq!(clamped.repr(), "[0;3]");
+    }
+
+    #[test]
+    fn test_square_straddling_zero() {
+        let square = |x: i32| x * x;
+
+        let naive = Domain::between(-3, 2).apply_monotonic(square, true, Limit::Infinite, Limit::Infinite);
+        assert_eq!(naive, Domain::None);
+
+        let domain = Domain::Domain(Value::Secluded(-5), Value::Included(2));
+        let image = domain.apply_piecewise_monotonic(square, &[0], false, Limit::Infinite, Limit::Infinite);
+        assert_eq!(image.unwrap().repr(), "[0;25)");
+
+        let positive = Domain::between(2, 3).apply_piecewise_monotonic(square, &[0], false, Limit::Infinite, Limit::Infinite);
+        assert_eq!(positive.unwrap().repr(), "[4;9]");
+
+        let everything = Domain::new().apply_piecewise_monotonic(square, &[0], false, Limit::Infinite, Limit::Infinite);
+        assert_eq!(everything.unwrap().repr(), "[0;∞)");
+    }
+
+    #[test]
+    fn test_piecewise_several_breakpoints() {
+        // Zigzag rising on (-∞;0], falling on [0;10], rising on [10;∞)
+        let zigzag = |x: i32| if x <= 0 { x } else if x <= 10 { -x } else { x - 20 };
+        let image = Domain::between(-4, 12)
+            .apply_piecewise_monotonic(zigzag, &[0, 10], true, Limit::Infinite, Limit::Infinite);
+        assert_eq!(image.unwrap().repr(), "[-10;0]");
+    }
+
+    #[test]
+    fn test_piecewise_unsorted_breakpoints() {
+        for breakpoints in [&[3, 1][..], &[1, 1], &[0, 5, 5, 9]].iter() {
+            let image = Domain::new().apply_piecewise_monotonic(|x| x, breakpoints, true, Limit::Infinite, Limit::Infinite);
+            assert_eq!(image, Err(DomainError::Reversed));
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use std::convert::TryFrom;
+use std::ops::Deref;
+use std::slice;
+
+use crate::util;
+use crate::Domain;
+
+/// Closed run of a normalized member, which is always a plain interval
+fn run(domain: &Domain<i32>) -> (i64, i64) {
+    match domain {
+        Domain::Domain(l, r) => util::clipped_run(l, r),
+        _ => (1, 0),
+    }
+}
+
+/// Domain known to be normalized: `Domain::None` when empty, a single
+/// `Domain::Domain` or a `Domain::Union` of at least two plain intervals
+/// sorted ascending, each admitting some `i32`, with gaps between them.
+/// Returned by `normalize` and the set operations, derefs to the domain
+/// for read-only use, the query methods below skip the normalization
+/// the general ones have to do
+/// ```
+/// use dynamic_domain::Domain;
+/// let domain = Domain::Union(vec![Domain::between(5, 9), Domain::between(1, 6)]).normalize();
+/// assert_eq!(domain.members().len(), 1);
+/// assert!(domain.contains(3));
+/// assert_eq!(domain.into_inner(), Domain::between(1, 9));
+/// ```
+#[derive(Clone, Debug)]
+pub struct NormalizedDomain<T>(Domain<T>);
+
+impl<T> NormalizedDomain<T> {
+
+    /// The wrapped domain
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain: Domain<i32> = Domain::between(1, 2).normalize().into_inner();
+    /// assert_eq!(domain.repr(), "[1;2]");
+    /// ```
+    pub fn into_inner(self) -> Domain<T> {
+        self.0
+    }
+
+    /// Members as plain intervals in ascending order, empty for `Domain::None`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(20, 29), Domain::between(0, 9)]);
+    /// assert_eq!(domain.normalize().members()[0].clone().repr(), "[0;9]");
+    /// ```
+    pub fn members(&self) -> &[Domain<T>] {
+        match &self.0 {
+            Domain::Union(domains) => domains,
+            Domain::Domain(..) => slice::from_ref(&self.0),
+            Domain::None => &[],
+        }
+    }
+}
+
+impl NormalizedDomain<i32> {
+
+    /// Wraps a domain already built from normalized members
+    pub(crate) fn new_unchecked(domain: Domain<i32>) -> Self {
+        debug_assert!(Self::is_normalized(&domain), "{:?} is not normalized", domain);
+        NormalizedDomain(domain)
+    }
+
+    /// Wraps the normalized form of `members`
+    pub(crate) fn from_members(members: Vec<util::Member>) -> Self {
+        Self::new_unchecked(util::from_members(util::normalize_members(members)))
+    }
+
+    /// Performing value representation, same as `Domain::repr`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::between(1, 2).normalize().repr(), "[1;2]");
+    /// ```
+    pub fn repr(self) -> String {
+        self.0.repr()
+    }
+
+    fn is_normalized(domain: &Domain<i32>) -> bool {
+        match domain {
+            Domain::Union(domains) => domains.len() > 1 && util::is_normalized(domains),
+            Domain::Domain(..) => util::is_normalized(slice::from_ref(domain)),
+            Domain::None => true,
+        }
+    }
+
+    fn runs(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.members().iter().map(run)
+    }
+
+    /// Checks whether `value` is admitted with a binary search
+    /// over the members
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(0, 9), Domain::between(20, 29)]).normalize();
+    /// assert!(domain.contains(25));
+    /// assert!(!domain.contains(15));
+    /// ```
+    pub fn contains(&self, value: i32) -> bool {
+        let value = value as i64;
+        let members = self.members();
+        let next = members.partition_point(|domain| run(domain).1 < value);
+        members.get(next).is_some_and(|domain| run(domain).0 <= value)
+    }
+
+    /// Number of admitted values, `None` if the domain is unbounded. Only
+    /// the outer members can have infinite borders
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(0, 9), Domain::between(5, 14)]).normalize();
+    /// assert_eq!(domain.cardinality(), Some(15));
+    /// assert_eq!(Domain::new().normalize().cardinality(), None);
+    /// ```
+    pub fn cardinality(&self) -> Option<u64> {
+        let members = self.members();
+        let unbounded = |domain: Option<&Domain<i32>>| {
+            matches!(domain, Some(Domain::Domain(l, r)) if l.is_infinite() || r.is_infinite())
+        };
+        if unbounded(members.first()) || unbounded(members.last()) {
+            return None;
+        }
+
+        Some(self.runs().map(|(lo, hi)| (hi - lo + 1) as u64).sum())
+    }
+
+    /// Number of admitted values below `value`, same as `Domain::rank`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(0, 4), Domain::between(10, 14)]).normalize();
+    /// assert_eq!(domain.rank(12), 7);
+    /// ```
+    pub fn rank(&self, value: i32) -> u64 {
+        let value = value as i64;
+        self.runs()
+            .take_while(|(lo, _)| *lo < value)
+            .map(|(lo, hi)| (hi.min(value - 1) - lo + 1) as u64)
+            .sum()
+    }
+
+    /// Admitted value at position `n` in ascending order, same as `Domain::nth`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(0, 4), Domain::between(10, 14)]).normalize();
+    /// assert_eq!(domain.nth(6), Some(11));
+    /// assert_eq!(domain.nth(10), None);
+    /// ```
+    pub fn nth(&self, n: u64) -> Option<i32> {
+        util::nth_of_runs(self.runs(), n)
+    }
+}
+
+impl Deref for NormalizedDomain<i32> {
+    type Target = Domain<i32>;
+
+    fn deref(&self) -> &Domain<i32> {
+        &self.0
+    }
+}
+
+impl From<NormalizedDomain<i32>> for Domain<i32> {
+    fn from(domain: NormalizedDomain<i32>) -> Self {
+        domain.0
+    }
+}
+
+/// Accepts the domain only when it already holds the invariants,
+/// hands it back otherwise. `Domain::normalize` always succeeds
+/// ```
+/// use std::convert::TryFrom;
+/// use dynamic_domain::{Domain, NormalizedDomain};
+/// assert!(NormalizedDomain::try_from(Domain::between(1, 5)).is_ok());
+/// assert!(NormalizedDomain::try_from(Domain::between(5, 1)).is_err());
+/// ```
+impl TryFrom<Domain<i32>> for NormalizedDomain<i32> {
+    type Error = Domain<i32>;
+
+    fn try_from(domain: Domain<i32>) -> Result<Self, Domain<i32>> {
+        if Self::is_normalized(&domain) {
+            Ok(NormalizedDomain(domain))
+        } else {
+            Err(domain)
+        }
+    }
+}
+
+impl PartialEq for NormalizedDomain<i32> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for NormalizedDomain<i32> {}
+
+impl PartialEq<Domain<i32>> for NormalizedDomain<i32> {
+    fn eq(&self, other: &Domain<i32>) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<NormalizedDomain<i32>> for Domain<i32> {
+    fn eq(&self, other: &NormalizedDomain<i32>) -> bool {
+        *self == other.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::{Domain, NormalizedDomain, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            1 => Just(Value::Infinite),
+            2 => (-100..100).prop_map(Value::Included),
+            2 => (-100..100).prop_map(Value::Secluded),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..6)
+    }
+
+    proptest! {
+        #[test]
+        fn test_queries_match_general(domain in domain(), value in -120..120, n in 0..250u64) {
+            let normalized = domain.normalize();
+            prop_assert!(NormalizedDomain::try_from(normalized.clone().into_inner()).is_ok());
+            prop_assert_eq!(normalized.contains(value), domain.contains(value));
+            prop_assert_eq!(normalized.cardinality(), domain.cardinality());
+            prop_assert_eq!(normalized.rank(value), domain.rank(value));
+            prop_assert_eq!(normalized.nth(n), domain.nth(n));
+        }
+    }
+
+    #[test]
+    fn test_invariant_checks() {
+        let rejected = vec![
+            Domain::Union(vec![Domain::between(5, 9), Domain::between(0, 2)]),
+            Domain::Union(vec![Domain::between(0, 5), Domain::between(3, 9)]),
+            Domain::Union(vec![Domain::between(0, 5), Domain::between(6, 9)]),
+            Domain::Union(vec![Domain::between(0, 5), Domain::between(9, 7)]),
+            Domain::Union(vec![Domain::between(0, 5), Domain::Union(vec![Domain::between(7, 9)])]),
+            Domain::Union(vec![Domain::between(0, 5), Domain::None]),
+            Domain::Union(vec![Domain::between(0, 5)]),
+            Domain::Union(vec![]),
+            Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite),
+            Domain::Domain(Value::Secluded(5), Value::Secluded(6)),
+        ];
+        for domain in rejected {
+            assert!(NormalizedDomain::try_from(domain.clone()).is_err(), "{}", domain.repr());
+        }
+
+        let accepted = vec![
+            Domain::None,
+            Domain::new(),
+            Domain::between(i32::MAX, i32::MAX),
+            Domain::Union(vec![Domain::at_most(0), Domain::between(2, 3), Domain::at_least(5)]),
+        ];
+        for domain in accepted {
+            assert!(NormalizedDomain::try_from(domain.clone()).is_ok(), "{}", domain.repr());
+        }
+    }
+
+    #[test]
+    fn test_conversions() {
+        let domain = Domain::Union(vec![Domain::between(3, 4), Domain::between(0, 1)]);
+        let normalized = domain.normalize();
+        assert_eq!(normalized, domain);
+        assert_eq!(domain, normalized);
+        assert_eq!(normalized.members().len(), 2);
+        assert_eq!(Domain::from(normalized.clone()).repr(), "[0;1]⋃[3;4]");
+        assert_eq!(normalized.iter().count(), 4);
+        assert!(Domain::None.normalize().members().is_empty());
+    }
+
+    #[test]
+    fn test_set_operations_normalize() {
+        let a = Domain::Union(vec![Domain::between(10, 20), Domain::between(0, 12)]);
+        let b = Domain::Union(vec![Domain::between(40, 50), Domain::between(5, 45)]);
+        let results = vec![
+            a.intersect(&b),
+            a.union(&b),
+            a.complement(),
+            a.difference(&b),
+            a.remove_value(7),
+            Domain::at_most(i32::MIN).remove_value(i32::MIN),
+        ];
+        for result in results {
+            let inner = result.into_inner();
+            assert!(NormalizedDomain::try_from(inner.clone()).is_ok(), "{}", inner.repr());
+        }
+    }
+
+    #[test]
+    fn test_queries_at_limits() {
+        let full = Domain::new().normalize();
+        assert!(full.contains(i32::MIN) && full.contains(i32::MAX));
+        assert_eq!(full.rank(i32::MAX), u32::MAX as u64);
+        assert_eq!(full.nth(0), Some(i32::MIN));
+        assert_eq!(full.nth(u32::MAX as u64), Some(i32::MAX));
+        assert_eq!(full.nth(1 << 32), None);
+
+        let edges = Domain::Union(vec![
+            Domain::between(i32::MAX, i32::MAX),
+            Domain::between(i32::MIN, i32::MIN),
+        ]).normalize();
+        assert_eq!(edges.cardinality(), Some(2));
+        assert_eq!(edges.rank(0), 1);
+        assert_eq!(edges.nth(1), Some(i32::MAX));
+    }
+}
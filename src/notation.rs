@@ -0,0 +1,101 @@
+/// Symbols `repr_with` writes and `parse_with` reads, for embedding the
+/// notation into another grammar. The tokens should differ from each
+/// other, from digits and signs, and the separator should not start
+/// with a digit, or the output will not read back
+/// ```
+/// use dynamic_domain::{Domain, Notation};
+/// let dsl = Notation { separator: "..", union: " or ", empty: "empty", ..Notation::ASCII };
+/// let domain = Domain::Union(vec![Domain::between(1, 5), Domain::at_least(10)]);
+/// assert_eq!(domain.repr_with(&dsl), "[1..5] or [10..inf)");
+/// assert_eq!(Domain::parse_with("[1..5] or [10..inf)", &dsl), Ok(domain));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Notation {
+    /// Between the two borders of an interval
+    pub separator: &'static str,
+    /// Between the members of a union
+    pub union: &'static str,
+    /// Infinite border, written after a `-` as a starting border
+    pub infinity: &'static str,
+    /// Empty domain
+    pub empty: &'static str,
+    /// Opening and closing bracket of an included border
+    pub included: (char, char),
+    /// Opening and closing bracket of a secluded border
+    pub secluded: (char, char),
+}
+
+impl Notation {
+    /// The notation of `repr`: `[1;5)⋃(7;∞)` and `∅`
+    pub const DEFAULT: Notation = Notation {
+        separator: ";",
+        union: "⋃",
+        infinity: "∞",
+        empty: "∅",
+        included: ('[', ']'),
+        secluded: ('(', ')'),
+    };
+
+    /// The ASCII spellings `FromStr` accepts: `[1;5)U(7;inf)` and `{}`
+    pub const ASCII: Notation = Notation {
+        union: "U",
+        infinity: "inf",
+        empty: "{}",
+        ..Notation::DEFAULT
+    };
+
+    /// Whether a point written with `c` is quoted: whitespace, quotes,
+    /// backslashes, the brackets and every character of a token
+    pub(crate) fn reserves(&self, c: char) -> bool {
+        c.is_whitespace()
+            || c == '"'
+            || c == '\\'
+            || [self.included.0, self.included.1, self.secluded.0, self.secluded.1].contains(&c)
+            || [self.separator, self.union, self.infinity, self.empty].iter().any(|token| token.contains(c))
+    }
+
+    /// The ASCII characters `reserves`, bit `c` set for the character `c`,
+    /// for checking a point without going through the tokens every time
+    pub(crate) const fn reserved_ascii(&self) -> u128 {
+        let mut mask = ascii_mask(0, "\t\n\x0b\x0c\r \"\\");
+        let brackets = [self.included.0, self.included.1, self.secluded.0, self.secluded.1];
+        let mut i = 0;
+        while i < brackets.len() {
+            if brackets[i].is_ascii() {
+                mask |= 1 << brackets[i] as u32;
+            }
+            i += 1;
+        }
+        mask = ascii_mask(mask, self.separator);
+        mask = ascii_mask(mask, self.union);
+        mask = ascii_mask(mask, self.infinity);
+        ascii_mask(mask, self.empty)
+    }
+}
+
+/// `Notation::reserved_ascii` of `Notation::DEFAULT`, the notation of `repr`
+pub(crate) const DEFAULT_RESERVED: u128 = Notation::DEFAULT.reserved_ascii();
+
+/// `mask` with the bits of the ASCII characters of `token` set
+const fn ascii_mask(mut mask: u128, token: &str) -> u128 {
+    let bytes = token.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii() {
+            mask |= 1 << bytes[i];
+        }
+        i += 1;
+    }
+    mask
+}
+
+/// `Notation::DEFAULT`
+/// ```
+/// use dynamic_domain::Notation;
+/// assert_eq!(Notation::default(), Notation::DEFAULT);
+/// ```
+impl Default for Notation {
+    fn default() -> Self {
+        Notation::DEFAULT
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{Domain, OutOfDomain, Value};
+
+/// Outcome of checking a value against a hidden threshold, the
+/// observation `Domain::refine_monotone` narrows the candidates with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MonotoneOutcome {
+    /// The value is below the threshold, which is greater than it
+    TooLow,
+    /// The value is above the threshold, which is less than it
+    TooHigh,
+    /// The value is the threshold, or acceptable, nothing is learnt
+    Ok,
+}
+
+impl Domain<i32> {
+
+    /// The domain after observing whether `value` is admitted. A value
+    /// seen as not admitted is removed, a value seen as admitted changes
+    /// nothing, also when the domain did not admit it, see
+    /// `try_refine_with_observation` to catch that inconsistency
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let learnt = Domain::between(0, 20)
+    ///     .refine_with_observation(7, true)
+    ///     .refine_with_observation(15, false);
+    /// assert_eq!(learnt.clone().repr(), "[0;15)⋃(15;20]");
+    /// assert_eq!(learnt.refine_with_observation(15, true), learnt);
+    /// ```
+    pub fn refine_with_observation(&self, value: i32, contained: bool) -> Domain<i32> {
+        if contained {
+            self.clone()
+        } else {
+            self.remove_value(value).into_inner()
+        }
+    }
+
+    /// `refine_with_observation` refusing an admitted observation of a
+    /// value the domain does not admit, with the error of
+    /// `validate_value`
+    /// ```
+    /// use dynamic_domain::{Domain, Violation};
+    /// let learnt = Domain::between(0, 20).try_refine_with_observation(15, false).unwrap();
+    /// let error = learnt.try_refine_with_observation(15, true).unwrap_err();
+    /// assert_eq!((error.value(), error.violation()), (15, Violation::InGap));
+    /// assert!(learnt.try_refine_with_observation(7, true).is_ok());
+    /// ```
+    pub fn try_refine_with_observation(&self, value: i32, contained: bool) -> Result<Domain<i32>, OutOfDomain> {
+        if contained {
+            self.validate_value(value)?;
+        }
+        Ok(self.refine_with_observation(value, contained))
+    }
+
+    /// The candidates for a threshold after checking `value` against it,
+    /// as in a binary search: `TooLow` keeps the values greater than
+    /// `value`, `TooHigh` the values less than it and `Ok` all of them
+    /// ```
+    /// use dynamic_domain::{Domain, MonotoneOutcome};
+    /// let candidates = Domain::between(0, 100)
+    ///     .refine_monotone(50, MonotoneOutcome::TooLow)
+    ///     .refine_monotone(75, MonotoneOutcome::TooHigh);
+    /// assert_eq!(candidates.clone().repr(), "(50;75)");
+    /// assert_eq!(candidates.refine_monotone(60, MonotoneOutcome::Ok), candidates);
+    /// ```
+    pub fn refine_monotone(&self, value: i32, outcome: MonotoneOutcome) -> Domain<i32> {
+        match outcome {
+            MonotoneOutcome::TooLow => self.gt(Value::Secluded(value)),
+            MonotoneOutcome::TooHigh => self.lt(Value::Secluded(value)),
+            MonotoneOutcome::Ok => self.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{Domain, MonotoneOutcome, Value, Violation};
+
+    /// Checks of `value` against the hidden `threshold`
+    fn check(value: i32, threshold: i32) -> MonotoneOutcome {
+        match value.cmp(&threshold) {
+            std::cmp::Ordering::Less => MonotoneOutcome::TooLow,
+            std::cmp::Ordering::Greater => MonotoneOutcome::TooHigh,
+            std::cmp::Ordering::Equal => MonotoneOutcome::Ok,
+        }
+    }
+
+    /// Observations until only `threshold` is left, probing the median
+    /// candidate every time
+    fn calibrate(mut candidates: Domain<i32>, threshold: i32) -> (Domain<i32>, u32) {
+        let mut observations = 0;
+        loop {
+            let count = candidates.cardinality().unwrap();
+            if count <= 1 {
+                return (candidates, observations);
+            }
+            let probe = candidates.nth(count / 2).unwrap();
+            observations += 1;
+            match check(probe, threshold) {
+                MonotoneOutcome::Ok => return (Domain::between(probe, probe), observations),
+                outcome => candidates = candidates.refine_monotone(probe, outcome),
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_calibration_converges(threshold in any::<i32>()) {
+            let (found, observations) = calibrate(Domain::between(i32::MIN, i32::MAX), threshold);
+            prop_assert_eq!(found, Domain::between(threshold, threshold));
+            // One observation per halving of 2^32 candidates
+            prop_assert!(observations <= 33, "{} observations", observations);
+        }
+
+        #[test]
+        fn test_observations_keep_the_rest(value in -20..20, other in -25..25, contained in any::<bool>()) {
+            let domain = Domain::Union(vec![Domain::between(-10, -1), Domain::Domain(Value::Secluded(3), Value::Infinite)]);
+            let learnt = domain.refine_with_observation(value, contained);
+            prop_assert_eq!(learnt.contains(other), domain.contains(other) && (contained || other != value));
+            prop_assert_eq!(domain.try_refine_with_observation(value, contained).is_ok(), !contained || domain.contains(value));
+        }
+    }
+
+    #[test]
+    fn test_calibration_limits() {
+        for threshold in [i32::MIN, i32::MIN + 1, -1, 0, i32::MAX - 1, i32::MAX].iter().copied() {
+            let (found, _) = calibrate(Domain::between(i32::MIN, i32::MAX), threshold);
+            assert_eq!(found, Domain::between(threshold, threshold));
+        }
+        let (found, observations) = calibrate(Domain::between(0, 1023), 700);
+        assert_eq!((found, observations <= 11), (Domain::between(700, 700), true));
+    }
+
+    #[test]
+    fn test_monotone_at_limits() {
+        let full = Domain::new();
+        assert!(full.refine_monotone(i32::MAX, MonotoneOutcome::TooLow).is_empty());
+        assert!(full.refine_monotone(i32::MIN, MonotoneOutcome::TooHigh).is_empty());
+        assert_eq!(full.refine_monotone(i32::MAX - 1, MonotoneOutcome::TooLow), Domain::between(i32::MAX, i32::MAX));
+        assert_eq!(Domain::None.refine_monotone(0, MonotoneOutcome::Ok), Domain::None);
+
+        let error = Domain::at_least(i32::MAX).refine_with_observation(i32::MAX, false).try_refine_with_observation(i32::MAX, true).unwrap_err();
+        assert_eq!(error.violation(), Violation::Empty);
+        assert_eq!(Domain::None.try_refine_with_observation(0, false), Ok(Domain::None));
+    }
+}
@@ -0,0 +1,686 @@
+use crate::interval;
+use crate::sweep;
+use crate::util::{self, first_admitted, last_admitted};
+use crate::{Domain, NormalizedDomain, TooComplex, Value};
+
+impl<T: PartialOrd> Domain<T> {
+
+    /// Checks whether `value` is admitted by the domain. Only compares
+    /// `value` with the borders, so it works for any ordered points.
+    /// A value not comparable with itself, like `f64::NAN`, is never admitted
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Included(5), Value::Secluded(10));
+    /// assert!(domain.contains(5));
+    /// assert!(!domain.contains(10));
+    ///
+    /// let domain = Domain::Domain(Value::Secluded(0.5), Value::Infinite);
+    /// assert!(domain.contains(0.75));
+    /// assert!(!Domain::<f64>::Domain(Value::Infinite, Value::Infinite).contains(f64::NAN));
+    /// ```
+    pub fn contains(&self, value: T) -> bool {
+        value.partial_cmp(&value).is_some() && self.admits(&value)
+    }
+
+    fn admits(&self, value: &T) -> bool {
+        match self {
+            Domain::Union(domains) => domains.iter().any(|domain| domain.admits(value)),
+            Domain::Domain(l, r) => interval::admits(l, r, value),
+            Domain::None => false,
+        }
+    }
+}
+
+impl Domain<i32> {
+
+    /// Sorts the members, drops empty ones and merges
+    /// overlapping or adjacent ones
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Union(vec![
+    ///     Domain::Domain(Value::Included(5), Value::Included(9)),
+    ///     Domain::Domain(Value::Included(1), Value::Included(6)),
+    /// ]);
+    /// assert_eq!(domain.normalize().repr(), "[1;9]");
+    /// ```
+    pub fn normalize(&self) -> NormalizedDomain<i32> {
+        NormalizedDomain::new_unchecked(util::from_members(util::normalized(self)))
+    }
+
+    /// `normalize` which also glues members separated by at most `max_gap`
+    /// missing values, in one sweep over the sorted members. Chains of
+    /// small gaps merge transitively and the glued members keep their
+    /// outer borders, so `coalesce(0)` is exactly `normalize`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let samples = Domain::Union(vec![
+    ///     Domain::between(0, 3),
+    ///     Domain::between(5, 8),
+    ///     Domain::between(11, 12),
+    ///     Domain::between(20, 25),
+    /// ]);
+    /// assert_eq!(samples.coalesce(2).repr(), "[0;12]⋃[20;25]");
+    /// assert_eq!(samples.coalesce(0), samples.normalize());
+    /// ```
+    pub fn coalesce(&self, max_gap: u32) -> NormalizedDomain<i32> {
+        let mut members: Vec<util::Member> = Vec::new();
+        for (l, r) in util::normalized(self) {
+            if let Some((_, last)) = members.last_mut() {
+                let missing = first_admitted(&l) - last_admitted(last) - 1;
+                if missing <= max_gap as i64 {
+                    *last = r;
+                    continue;
+                }
+            }
+            members.push((l, r));
+        }
+        NormalizedDomain::new_unchecked(util::from_members(members))
+    }
+
+    /// Checks whether the hulls of the two domains overlap or are adjacent,
+    /// so their union has no gap between them. `false` when either admits
+    /// no `i32`
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// assert!(Domain::between(0, 4).touches(&Domain::between(5, 9)));
+    /// assert!(Domain::between(0, 4).touches(&Domain::at_least(2)));
+    /// assert!(!Domain::between(0, 4).touches(&Domain::Domain(Value::Secluded(5), Value::Included(9))));
+    /// assert!(!Domain::new().touches(&Domain::None));
+    /// ```
+    pub fn touches(&self, other: &Domain<i32>) -> bool {
+        let hull = |domain: &Domain<i32>| {
+            let runs = util::i32_runs(domain);
+            Some((runs.first()?.0, runs.last()?.1))
+        };
+        match (hull(self), hull(other)) {
+            (Some((a, b)), Some((c, d))) => a <= d + 1 && c <= b + 1,
+            _ => false,
+        }
+    }
+
+    /// Checks whether the domain admits no `i32` at all: reversed borders,
+    /// open borders on the same or adjacent points and `Domain::None`
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// assert!(Domain::between(10, 5).is_empty());
+    /// assert!(Domain::Domain(Value::Secluded(5), Value::Secluded(6)).is_empty());
+    /// assert!(!Domain::between(5, 5).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        util::normalized(self).is_empty()
+    }
+
+    /// Checks whether every admitted value is at least some finite
+    /// border, `true` for an empty domain. Works on the normalized
+    /// members, so members admitting nothing do not count, and goes by
+    /// the borders as written: `[-2147483648;0]` is bounded below while
+    /// the equal `(-∞;0]` is not
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// assert!(Domain::Union(vec![Domain::between(0, 5), Domain::at_least(9)]).is_bounded_below());
+    /// assert!(!Domain::at_most(3).is_bounded_below());
+    /// let unused = Domain::Domain(Value::Infinite, Value::Secluded(i32::MIN));
+    /// assert!(Domain::Union(vec![unused, Domain::between(1, 2)]).is_bounded_below());
+    /// ```
+    pub fn is_bounded_below(&self) -> bool {
+        util::normalized(self).iter().all(|(l, _)| *l != Value::Infinite)
+    }
+
+    /// Checks whether every admitted value is at most some finite
+    /// border, `true` for an empty domain, counterpart of
+    /// `is_bounded_below`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert!(Domain::at_most(3).is_bounded_above());
+    /// assert!(!Domain::Union(vec![Domain::between(0, 5), Domain::at_least(9)]).is_bounded_above());
+    /// assert!(Domain::Union(vec![Domain::at_least(9), Domain::between(5, 0)]).is_bounded_below());
+    /// ```
+    pub fn is_bounded_above(&self) -> bool {
+        util::normalized(self).iter().all(|(_, r)| *r != Value::Infinite)
+    }
+
+    /// Checks whether the domain is bounded on both sides, which is when
+    /// `cardinality` is known and iteration ends. `true` for an empty
+    /// domain and `false` for the full one
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert!(Domain::between(0, 9).is_bounded());
+    /// assert!(Domain::None.is_bounded());
+    /// assert!(!Domain::new().is_bounded());
+    /// assert!(!Domain::at_least(0).is_bounded());
+    /// ```
+    pub fn is_bounded(&self) -> bool {
+        let members = util::normalized(self);
+        !util::is_unbounded(&members)
+    }
+
+    /// Smallest `[lo; hi]` window holding every admitted value, the closed
+    /// hull of the normalized members, for sizing a dense representation
+    /// like a bitset. `None` when the domain is unbounded, and when it is
+    /// empty and needs no window at all
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Union(vec![Domain::between(20, 30), Domain::Domain(Value::Secluded(4), Value::Included(9))]);
+    /// assert_eq!(domain.active_window(), Some((5, 30)));
+    /// // The complement spreads to the full range, an intersection shrinks it back
+    /// let complement = domain.complement();
+    /// assert_eq!(complement.active_window(), None);
+    /// assert_eq!(complement.intersect(&Domain::between(10, 100)).active_window(), Some((10, 100)));
+    /// assert_eq!(Domain::None.active_window(), None);
+    /// ```
+    pub fn active_window(&self) -> Option<(i32, i32)> {
+        let members = util::normalized(self);
+        if util::is_unbounded(&members) {
+            return None;
+        }
+        let runs = util::runs_of(&members);
+        Some((runs.first()?.0 as i32, runs.last()?.1 as i32))
+    }
+
+    /// Admitted value closest to `value`, the smaller one on a tie.
+    /// `None` when the domain admits no `i32` at all
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Included(5), Value::Secluded(10));
+    /// assert_eq!(domain.clamp(7), Some(7));
+    /// assert_eq!(domain.clamp(100), Some(9));
+    /// assert_eq!(Domain::None.clamp(100), None);
+    /// ```
+    pub fn clamp(&self, value: i32) -> Option<i32> {
+        let target = value as i64;
+        let in_range = |n: i64| (i32::MIN as i64..=i32::MAX as i64).contains(&n);
+
+        util::normalized(self)
+            .iter()
+            .filter_map(|(l, r)| {
+                let first = first_admitted(l).max(i32::MIN as i64);
+                let last = last_admitted(r).min(i32::MAX as i64);
+                if !in_range(first) || !in_range(last) || first > last {
+                    return None;
+                }
+                Some(target.max(first).min(last))
+            })
+            .min_by_key(|candidate| ((candidate - target).abs(), *candidate))
+            .map(|candidate| candidate as i32)
+    }
+
+    /// Set of values admitted by both domains, the pieces of `merge_join`
+    /// both admit. Both are normalized and then swept together once, so
+    /// the cost is linear in the total number
+    /// of members plus the sort of members given out of order. Normalized
+    /// inputs, like results of the set operations, are already sorted
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let a = Domain::new().lt(Value::Secluded(10));
+    /// let b = Domain::new().gt(Value::Secluded(5));
+    /// assert_eq!(a.intersect(&b).repr(), "(5;10)");
+    /// ```
+    pub fn intersect(&self, other: &Domain<i32>) -> NormalizedDomain<i32> {
+        let members = intersect_members(self, other, usize::MAX).unwrap_or_default();
+        NormalizedDomain::new_unchecked(util::from_members(members))
+    }
+
+    /// `intersect` giving up with `TooComplex` as soon as the result
+    /// has more than `max_output_members` members, which bounds the
+    /// memory spent on interleaved unions from untrusted input
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let evens = Domain::Union((0..100).map(|i| Domain::between(i * 4, i * 4 + 1)).collect());
+    /// let odds = Domain::Union((0..100).map(|i| Domain::between(i * 4 + 1, i * 4 + 2)).collect());
+    /// assert_eq!(evens.intersect_limited(&odds, 100).unwrap().normalize().members().len(), 100);
+    /// let error = evens.intersect_limited(&odds, 99).unwrap_err();
+    /// assert_eq!(error.max_output_members(), 99);
+    /// ```
+    pub fn intersect_limited(&self, other: &Domain<i32>, max_output_members: usize) -> Result<Domain<i32>, TooComplex> {
+        let members = intersect_members(self, other, max_output_members)?;
+        Ok(util::from_members(members))
+    }
+
+    /// Set of values admitted by any of the domains, the pieces of
+    /// `merge_join` either admits with the neighbouring ones joined.
+    /// Linear after normalizing both, which only checks the order of
+    /// normalized inputs
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let a = Domain::new().lt(Value::Secluded(5));
+    /// let b = Domain::Domain(Value::Included(8), Value::Included(100));
+    /// assert_eq!(a.union(&b).repr(), "(-∞;5)⋃[8;100]");
+    /// ```
+    pub fn union(&self, other: &Domain<i32>) -> NormalizedDomain<i32> {
+        let mut members: Vec<util::Member> = Vec::new();
+        let mut joined = false;
+        self.merge_join(other, |segment| {
+            let kept = segment.in_left || segment.in_right;
+            if kept {
+                let (l, r) = segment.range.into_borders();
+                match members.last_mut() {
+                    // The piece before was kept too, so this one goes on
+                    Some(last) if joined => last.1 = r,
+                    _ => members.push((l, r)),
+                }
+            }
+            joined = kept;
+        });
+        NormalizedDomain::new_unchecked(util::from_members(members))
+    }
+
+    /// Set of values not admitted by the domain,
+    /// the complement of `Domain::None` is the full domain
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Included(5), Value::Secluded(10));
+    /// assert_eq!(domain.complement().repr(), "(-∞;5)⋃[10;∞)");
+    /// assert_eq!(Domain::None.complement().repr(), "(-∞;∞)");
+    /// ```
+    pub fn complement(&self) -> NormalizedDomain<i32> {
+        // A starting border turned into the ending border of the gap
+        // before it and the other way round
+        let flip = |value: &Value<i32>| match value {
+            Value::Included(i) => Value::Secluded(*i),
+            Value::Secluded(i) => Value::Included(*i),
+            Value::Infinite => Value::Infinite,
+        };
+
+        let mut members = Vec::new();
+        let mut start = Value::Infinite;
+        let mut open = true;
+
+        for (l, r) in util::normalized(self) {
+            if l != Value::Infinite {
+                members.push((start, flip(&l)));
+            }
+            open = r != Value::Infinite;
+            start = flip(&r);
+        }
+
+        if open {
+            members.push((start, Value::Infinite));
+        }
+
+        // Gaps beyond the i32 limits, like (2147483647;∞), admit nothing
+        NormalizedDomain::from_members(members)
+    }
+
+    /// Set of values admitted by the domain but not by `other`, the
+    /// pieces of `merge_join` only the domain admits. Just as linear as
+    /// `intersect`
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Included(0), Value::Included(10));
+    /// let hole = Domain::Domain(Value::Included(3), Value::Secluded(5));
+    /// assert_eq!(domain.difference(&hole).repr(), "[0;3)⋃[5;10]");
+    /// ```
+    pub fn difference(&self, other: &Domain<i32>) -> NormalizedDomain<i32> {
+        let mut members = Vec::new();
+        self.merge_join(other, |segment| {
+            if segment.in_left && !segment.in_right {
+                members.push(segment.range.into_borders());
+            }
+        });
+        NormalizedDomain::new_unchecked(util::from_members(members))
+    }
+
+    /// Checks that every admitted value is admitted by one of `covers`,
+    /// otherwise hands out the normalized part no cover admits
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let requested = Domain::Domain(Value::Included(10), Value::Secluded(20));
+    /// let windows = [Domain::between(0, 12), Domain::between(11, 15)];
+    /// let uncovered = requested.covered_by(&windows).unwrap_err();
+    /// assert_eq!(uncovered.repr(), "(15;20)");
+    /// ```
+    pub fn covered_by(&self, covers: &[Domain<i32>]) -> Result<(), Domain<i32>> {
+        let allowed = covers
+            .iter()
+            .fold(Domain::None, |allowed, cover| allowed.union(cover).into_inner());
+
+        match self.difference(&allowed).into_inner() {
+            Domain::None => Ok(()),
+            uncovered => Err(uncovered),
+        }
+    }
+
+    /// Punches a hole at `value`, splitting the member
+    /// which contains it
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Domain(Value::Included(1), Value::Included(9))
+    ///     .remove_value(5);
+    /// assert_eq!(domain.repr(), "[1;5)⋃(5;9]");
+    /// ```
+    pub fn remove_value(&self, value: i32) -> NormalizedDomain<i32> {
+        let point = value as i64;
+        let mut members = Vec::new();
+
+        for (l, r) in util::normalized(self) {
+            if first_admitted(&l) <= point && point <= last_admitted(&r) {
+                let parts = vec![
+                    (l, Value::Secluded(value)),
+                    (Value::Secluded(value), r),
+                ];
+                members.extend(
+                    parts
+                        .into_iter()
+                        .filter(|(l, r)| {
+                            let (first, last) = util::clipped_run(l, r);
+                            first <= last
+                        })
+                );
+            } else {
+                members.push((l, r));
+            }
+        }
+
+        NormalizedDomain::new_unchecked(util::from_members(members))
+    }
+}
+
+/// Sweep of `intersect` over the normalized members of both domains,
+/// stopping once the result would have more than `limit` members
+fn intersect_members(a: &Domain<i32>, b: &Domain<i32>, limit: usize) -> Result<Vec<util::Member>, TooComplex> {
+    let mut members = Vec::new();
+    sweep::try_merge_join(a, b, |segment| {
+        if segment.in_left && segment.in_right {
+            if members.len() == limit {
+                return Err(TooComplex::new(limit));
+            }
+            members.push(segment.range.into_borders());
+        }
+        Ok(())
+    })?;
+    Ok(members)
+}
+
+/// Domains are equal when they admit the same `i32` values, no matter
+/// how the borders are written. Infinite borders count as the `i32`
+/// limits, so `(-∞;∞)` equals `[-2147483648;2147483647]`. Use
+/// `structurally_equal` to compare the representation instead
+/// ```
+/// use dynamic_domain::{Domain, Value};
+/// let a = Domain::Domain(Value::Secluded(4), Value::Secluded(10));
+/// let b = Domain::Domain(Value::Included(5), Value::Included(9));
+/// assert_eq!(a, b);
+/// ```
+impl PartialEq for Domain<i32> {
+    fn eq(&self, other: &Self) -> bool {
+        util::i32_runs(self) == util::i32_runs(other)
+    }
+}
+
+impl Eq for Domain<i32> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Domain, Value};
+
+    #[test]
+    fn test_normalize() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Included(10), Value::Infinite),
+            Domain::Domain(Value::Secluded(3), Value::Secluded(3)),
+            Domain::Domain(Value::Infinite, Value::Included(1)),
+            Domain::Domain(Value::Included(2), Value::Secluded(5)),
+        ]);
+        assert_eq!(domain.normalize().repr(), "(-∞;5)⋃[10;∞)");
+    }
+
+    #[test]
+    fn test_normalize_drops_beyond_limits() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite),
+            Domain::Domain(Value::Infinite, Value::Secluded(i32::MIN)),
+            Domain::Domain(Value::Included(1), Value::Included(2)),
+        ]);
+        assert_eq!(domain.normalize().repr(), "[1;2]");
+        assert_eq!(Domain::new().gt(Value::Secluded(i32::MAX)).normalize().repr(), "∅");
+    }
+
+    #[test]
+    fn test_intersect() {
+        let a = Domain::Union(vec![
+            Domain::Domain(Value::Included(0), Value::Included(10)),
+            Domain::Domain(Value::Included(20), Value::Included(30)),
+        ]);
+        let b = Domain::Domain(Value::Secluded(5), Value::Secluded(25));
+        assert_eq!(a.intersect(&b).repr(), "(5;10]⋃[20;25)");
+        assert_eq!(a.intersect(&Domain::None).repr(), "∅");
+    }
+
+    #[test]
+    fn test_union() {
+        let a = Domain::Domain(Value::Included(0), Value::Included(5));
+        let b = Domain::Domain(Value::Included(6), Value::Secluded(9));
+        assert_eq!(a.union(&b).repr(), "[0;9)");
+    }
+
+    #[test]
+    fn test_remove_value() {
+        let domain = Domain::new().remove_value(0);
+        assert_eq!(domain.repr(), "(-∞;0)⋃(0;∞)");
+
+        let point = Domain::Domain(Value::Included(3), Value::Included(3));
+        assert_eq!(point.remove_value(3), Domain::None);
+        assert_eq!(point.remove_value(4), point);
+    }
+
+    #[test]
+    fn test_complement() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Infinite, Value::Secluded(0)),
+            Domain::Domain(Value::Secluded(3), Value::Included(5)),
+            Domain::Domain(Value::Included(4), Value::Included(8)),
+        ]);
+        assert_eq!(domain.complement().repr(), "[0;3]⋃(8;∞)");
+        assert_eq!(domain.complement().complement(), domain);
+        assert_eq!(Domain::new().complement(), Domain::None);
+        assert_eq!(Domain::at_least(1).complement().repr(), "(-∞;1)");
+        assert_eq!(Domain::between(i32::MIN, i32::MAX).complement().repr(), "∅");
+        assert_eq!(Domain::between(0, i32::MAX).complement().repr(), "(-∞;0)");
+    }
+
+    #[test]
+    fn test_difference() {
+        let domain = Domain::new().difference(&Domain::between(-1, 1));
+        assert_eq!(domain.repr(), "(-∞;-1)⋃(1;∞)");
+        assert_eq!(Domain::between(0, 5).difference(&Domain::new()), Domain::None);
+        assert_eq!(Domain::between(0, 5).difference(&Domain::None).repr(), "[0;5]");
+    }
+
+    #[test]
+    fn test_covered_jointly() {
+        let requested = Domain::between(0, 100);
+        let covers = [
+            Domain::Domain(Value::Included(40), Value::Infinite),
+            Domain::Domain(Value::Secluded(-5), Value::Included(20)),
+            Domain::Domain(Value::Secluded(20), Value::Secluded(41)),
+        ];
+        assert_eq!(requested.covered_by(&covers), Ok(()));
+
+        for skipped in 0..covers.len() {
+            let partial: Vec<Domain<i32>> = covers
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != skipped)
+                .map(|(_, cover)| cover.clone())
+                .collect();
+            assert!(requested.covered_by(&partial).is_err());
+        }
+    }
+
+    #[test]
+    fn test_covered_witness() {
+        let requested = Domain::Union(vec![Domain::between(0, 10), Domain::between(20, 30)]);
+        let covers = [Domain::between(0, 4), Domain::between(6, 25)];
+        let uncovered = requested.covered_by(&covers).unwrap_err();
+        assert_eq!(uncovered.repr(), "(4;6)⋃(25;30]");
+
+        assert_eq!(Domain::at_least(0).covered_by(&[]), Err(Domain::at_least(0)));
+        assert_eq!(Domain::at_least(0).covered_by(&[Domain::between(0, i32::MAX)]), Ok(()));
+        assert_eq!(Domain::new().covered_by(&[Domain::between(i32::MIN, i32::MAX)]), Ok(()));
+        assert_eq!(Domain::None.covered_by(&[]), Ok(()));
+    }
+
+    #[test]
+    fn test_clamp() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Infinite, Value::Secluded(0)),
+            Domain::Domain(Value::Included(10), Value::Included(20)),
+        ]);
+        assert_eq!(domain.clamp(3), Some(-1));
+        assert_eq!(domain.clamp(5), Some(10));
+        assert_eq!(domain.clamp(4), Some(-1));
+        assert_eq!(domain.clamp(i32::MAX), Some(20));
+
+        let beyond = Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite);
+        assert_eq!(beyond.clamp(0), None);
+    }
+
+    #[test]
+    fn test_set_equality() {
+        let a = Domain::Union(vec![
+            Domain::Domain(Value::Included(1), Value::Included(3)),
+            Domain::Domain(Value::Included(4), Value::Included(6)),
+        ]);
+        let b = Domain::Domain(Value::Secluded(0), Value::Secluded(7));
+        assert_eq!(a, b);
+        assert_ne!(a, Domain::new());
+        assert_eq!(Domain::Domain(Value::Secluded(5), Value::Secluded(6)), Domain::None);
+    }
+
+    #[test]
+    fn test_set_equality_at_limits() {
+        assert_eq!(Domain::new().gt(Value::Secluded(i32::MAX)), Domain::None);
+        assert_eq!(Domain::new().lt(Value::Secluded(i32::MIN)), Domain::None);
+        assert_eq!(Domain::new(), Domain::between(i32::MIN, i32::MAX));
+        assert_eq!(Domain::at_least(0), Domain::between(0, i32::MAX));
+        assert_ne!(Domain::at_least(0), Domain::between(0, i32::MAX - 1));
+    }
+
+    #[test]
+    fn test_coalesce_chains() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Secluded(30), Value::Infinite),
+            Domain::between(10, 12),
+            Domain::Domain(Value::Infinite, Value::Secluded(0)),
+            Domain::between(14, 15),
+            Domain::between(2, 8),
+            Domain::between(18, 27),
+        ]);
+        assert_eq!(domain.coalesce(0).repr(), "(-∞;0)⋃[2;8]⋃[10;12]⋃[14;15]⋃[18;27]⋃(30;∞)");
+        assert_eq!(domain.coalesce(1).repr(), "(-∞;0)⋃[2;15]⋃[18;27]⋃(30;∞)");
+        assert_eq!(domain.coalesce(2).repr(), "(-∞;27]⋃(30;∞)");
+        assert_eq!(domain.coalesce(3).repr(), "(-∞;∞)");
+        assert_eq!(domain.coalesce(u32::MAX), Domain::new());
+    }
+
+    #[test]
+    fn test_coalesce_matches_normalize() {
+        let domains = vec![
+            Domain::None,
+            Domain::between(10, 5),
+            Domain::Union(vec![Domain::between(3, 4), Domain::Domain(Value::Secluded(4), Value::Included(9)), Domain::between(0, 1)]),
+            Domain::Union(vec![Domain::between(i32::MIN, i32::MIN), Domain::between(i32::MAX, i32::MAX)]),
+        ];
+        for domain in domains {
+            assert_eq!(domain.coalesce(0).repr(), domain.normalize().repr());
+        }
+        let edges = Domain::Union(vec![Domain::between(i32::MIN, i32::MIN), Domain::between(i32::MAX, i32::MAX)]);
+        assert_eq!(edges.coalesce(u32::MAX - 2).repr(), "[-2147483648;-2147483648]⋃[2147483647;2147483647]");
+        assert_eq!(edges.coalesce(u32::MAX - 1), Domain::new());
+    }
+
+    #[test]
+    fn test_touches() {
+        let spread = Domain::Union(vec![Domain::between(0, 2), Domain::between(8, 10)]);
+        assert!(spread.touches(&Domain::between(4, 5)));
+        assert!(spread.touches(&Domain::between(11, 20)));
+        assert!(Domain::between(11, 20).touches(&spread));
+        assert!(!spread.touches(&Domain::between(12, 20)));
+        assert!(!spread.touches(&Domain::between(-10, -2)));
+        assert!(Domain::at_most(i32::MIN).touches(&Domain::at_least(i32::MIN + 1)));
+        assert!(!Domain::between(5, 1).touches(&Domain::new()));
+    }
+
+    #[test]
+    fn test_intersect_limited() {
+        let comb = |offset: i32| Domain::Union((0..1000).rev().map(|i| Domain::between(i * 10 + offset, i * 10 + offset + 5)).collect());
+        let (a, b) = (comb(0), comb(3));
+        let full = a.intersect(&b);
+        assert_eq!(full.members().len(), 1000);
+        assert_eq!(a.intersect_limited(&b, 1000).unwrap(), full.into_inner());
+        assert_eq!(a.intersect_limited(&b, 999).unwrap_err().to_string(), "result has more than 999 members");
+
+        assert_eq!(a.intersect_limited(&Domain::between(10_000, 20_000), 0), Ok(Domain::None));
+        assert!(a.intersect_limited(&Domain::new(), 0).is_err());
+        assert_eq!(Domain::new().intersect_limited(&Domain::new(), 1), Ok(Domain::new()));
+        assert_eq!(Domain::at_least(i32::MAX).intersect_limited(&Domain::at_most(i32::MAX), 1).unwrap().repr(), "[2147483647;2147483647]");
+    }
+
+    #[test]
+    fn test_bounded() {
+        let cases = [
+            (Domain::between(0, 9), (true, true)),
+            (Domain::at_least(0), (true, false)),
+            (Domain::at_most(0), (false, true)),
+            (Domain::new(), (false, false)),
+            (Domain::None, (true, true)),
+            (Domain::Union(vec![]), (true, true)),
+            (Domain::Union(vec![Domain::at_most(0), Domain::at_least(9)]), (false, false)),
+            (Domain::Union(vec![Domain::between(0, 1), Domain::at_least(9)]), (true, false)),
+            (Domain::between(i32::MIN, i32::MAX), (true, true)),
+        ];
+        for (domain, (below, above)) in cases.iter() {
+            assert_eq!((domain.is_bounded_below(), domain.is_bounded_above()), (*below, *above), "{:?}", domain);
+            assert_eq!(domain.is_bounded(), *below && *above, "{:?}", domain);
+            assert_eq!(domain.is_bounded(), domain.cardinality().is_some(), "{:?}", domain);
+        }
+    }
+
+    #[test]
+    fn test_bounded_malformed() {
+        // Members admitting no i32 are dropped before the borders are looked at
+        let below_min = Domain::Domain(Value::Infinite, Value::Secluded(i32::MIN));
+        let above_max = Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite);
+        assert!(below_min.is_bounded() && above_max.is_bounded());
+        let padded = Domain::Union(vec![below_min, Domain::between(3, 4), above_max]);
+        assert!(padded.is_bounded());
+
+        // Reversed members admit nothing either, nested unions are flattened
+        let reversed = Domain::Union(vec![Domain::between(9, 2), Domain::at_least(5)]);
+        assert!(reversed.is_bounded_below() && !reversed.is_bounded_above());
+        let nested = Domain::Union(vec![Domain::Union(vec![Domain::between(0, 1), Domain::at_most(-5)]), Domain::None]);
+        assert!(!nested.is_bounded_below() && nested.is_bounded_above());
+
+        // A member of the full domain swallows the bounded ones it overlaps
+        assert!(!Domain::Union(vec![Domain::between(0, 1), Domain::new()]).is_bounded_above());
+    }
+
+    #[test]
+    fn test_active_window() {
+        let domain = Domain::Union(vec![
+            Domain::between(40, 50),
+            Domain::Domain(Value::Secluded(-3), Value::Secluded(0)),
+            Domain::between(9, 1),
+        ]);
+        assert_eq!(domain.active_window(), Some((-2, 50)));
+        assert_eq!(Domain::between(7, 7).active_window(), Some((7, 7)));
+        assert_eq!(Domain::between(i32::MIN, i32::MAX).active_window(), Some((i32::MIN, i32::MAX)));
+        assert_eq!(Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite).active_window(), None);
+        assert_eq!(Domain::at_least(0).active_window(), None);
+        assert_eq!(Domain::between(3, 1).active_window(), None);
+
+        // Complements spread up to the limits, intersections bring the window back
+        let complement = domain.complement();
+        assert_eq!(complement.active_window(), None);
+        assert_eq!(complement.intersect(&Domain::between(-10, 45)).active_window(), Some((-10, 39)));
+        assert_eq!(complement.intersect(&Domain::between(0, 45)).active_window(), Some((0, 39)));
+        assert_eq!(complement.intersect(&domain).active_window(), None);
+
+        // Within a universe the complement keeps to the window of the universe
+        let universe = Domain::between(-100, 100);
+        let within = domain.complement_within(&universe);
+        assert_eq!(within.active_window(), Some((-100, 100)));
+        assert_eq!(Domain::between(-100, 0).complement_within(&universe).active_window(), Some((1, 100)));
+        assert_eq!(universe.complement_within(&universe).active_window(), None);
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+use crate::{interval, DiscreteStep, Domain, Notation, Value};
+
+/// How `Domain::repr_with_options` writes a domain
+/// ```
+/// use dynamic_domain::{Domain, Notation, ReprOptions, Value};
+/// let domain = Domain::Domain(Value::Secluded(5), Value::Secluded(10));
+/// let closed = ReprOptions { canonical_closed: true, ..ReprOptions::DEFAULT };
+/// assert_eq!(domain.repr_with_options(&closed), "[6;9]");
+/// let ascii = ReprOptions { notation: Notation::ASCII, ..closed };
+/// assert_eq!(Domain::at_least(1).repr_with_options(&ascii), "[1;inf)");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReprOptions {
+    /// Brackets, separators and symbols
+    pub notation: Notation,
+    /// Writes a finite secluded border of discrete points as the adjacent
+    /// included point, `(5;10)` as `[6;9]`. A border with no adjacent
+    /// point, like `(-∞;-2147483648)`, stays open. Has no effect on
+    /// continuous points
+    pub canonical_closed: bool,
+}
+
+impl ReprOptions {
+    /// The options of `repr`: `Notation::DEFAULT` and borders as written
+    pub const DEFAULT: ReprOptions = ReprOptions {
+        notation: Notation::DEFAULT,
+        canonical_closed: false,
+    };
+}
+
+/// `ReprOptions::DEFAULT`
+/// ```
+/// use dynamic_domain::ReprOptions;
+/// assert_eq!(ReprOptions::default(), ReprOptions::DEFAULT);
+/// ```
+impl Default for ReprOptions {
+    fn default() -> Self {
+        ReprOptions::DEFAULT
+    }
+}
+
+/// `domain` with its finite secluded borders closed wherever the adjacent
+/// point exists
+fn closed<T: DiscreteStep>(domain: &Domain<T>) -> Domain<T> {
+    let close = |border: &Value<T>, step: fn(&T) -> Option<T>| match border {
+        Value::Secluded(point) => step(point).map_or_else(|| border.clone(), Value::Included),
+        border => border.clone(),
+    };
+    match domain {
+        Domain::Union(domains) => Domain::Union(domains.iter().map(closed).collect()),
+        Domain::Domain(l, r) => Domain::Domain(close(l, T::successor), close(r, T::predecessor)),
+        Domain::None => Domain::None,
+    }
+}
+
+/// `repr` of `domain` in `notation`, every point written by `Display`
+fn write<T: fmt::Display>(domain: &Domain<T>, notation: &Notation) -> String {
+    let mut out = String::new();
+    domain.write_points(&mut out, &|out, point| write!(out, "{}", point), notation).expect(interval::WRITE_FAILED);
+    out
+}
+
+impl<T: DiscreteStep + fmt::Display> Domain<T> {
+
+    /// `repr` as configured by `options`, the domain itself is untouched.
+    /// Members are written as they are, normalize first to merge them and
+    /// drop empty ones
+    /// ```
+    /// use dynamic_domain::{Domain, ReprOptions, Value};
+    /// let closed = ReprOptions { canonical_closed: true, ..ReprOptions::default() };
+    /// let domain = Domain::Union(vec![
+    ///     Domain::Domain(Value::Infinite, Value::Secluded(0)),
+    ///     Domain::Domain(Value::Secluded(5), Value::Included(10)),
+    /// ]);
+    /// assert_eq!(domain.repr_with_options(&ReprOptions::DEFAULT), "(-∞;0)⋃(5;10]");
+    /// assert_eq!(domain.repr_with_options(&closed), "(-∞;-1]⋃[6;10]");
+    /// ```
+    pub fn repr_with_options(&self, options: &ReprOptions) -> String {
+        if options.canonical_closed {
+            write(&closed(self), &options.notation)
+        } else {
+            write(self, &options.notation)
+        }
+    }
+}
+
+macro_rules! continuous_options {
+    ($($float:ty),*) => {$(
+        impl Domain<$float> {
+
+            /// `repr` as configured by `options`, where `canonical_closed`
+            /// has no effect: a continuous point has no adjacent one
+            /// ```
+            /// use dynamic_domain::{Domain, ReprOptions, Value};
+            /// let closed = ReprOptions { canonical_closed: true, ..ReprOptions::DEFAULT };
+            #[doc = concat!("let domain = Domain::Domain(Value::Secluded(0.5_", stringify!($float), "), Value::Infinite);")]
+            /// assert_eq!(domain.repr_with_options(&closed), "(0.5;∞)");
+            /// ```
+            pub fn repr_with_options(&self, options: &ReprOptions) -> String {
+                write(self, &options.notation)
+            }
+        }
+    )*};
+}
+
+continuous_options!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Domain, Notation, ReprOptions, Value};
+
+    const CLOSED: ReprOptions = ReprOptions { canonical_closed: true, ..ReprOptions::DEFAULT };
+
+    #[test]
+    fn test_display_modes() {
+        let cases = vec![
+            (Domain::Domain(Value::Secluded(5), Value::Secluded(10)), "(5;10)", "[6;9]"),
+            (Domain::between(5, 10), "[5;10]", "[5;10]"),
+            (Domain::Domain(Value::Infinite, Value::Secluded(3)), "(-∞;3)", "(-∞;2]"),
+            (Domain::new(), "(-∞;∞)", "(-∞;∞)"),
+            (Domain::None, "∅", "∅"),
+            (
+                Domain::Union(vec![Domain::Domain(Value::Secluded(-3), Value::Included(0)), Domain::Domain(Value::Secluded(7), Value::Infinite)]),
+                "(-3;0]⋃(7;∞)",
+                "[-2;0]⋃[8;∞)",
+            ),
+        ];
+        for (domain, open, closed) in cases {
+            let before = domain.clone();
+            assert_eq!(domain.repr_with_options(&ReprOptions::DEFAULT), open);
+            assert_eq!(domain.repr_with_options(&CLOSED), closed);
+            assert_eq!(domain.clone().repr(), open);
+            // Only the display changes
+            assert_eq!(format!("{:?}", domain), format!("{:?}", before));
+        }
+
+        let merged = Domain::Union(vec![Domain::Domain(Value::Secluded(5), Value::Secluded(8)), Domain::between(8, 9), Domain::Domain(Value::Secluded(20), Value::Secluded(21))]);
+        assert_eq!(merged.normalize().repr_with_options(&CLOSED), "[6;9]");
+    }
+
+    #[test]
+    fn test_limits_stay_open() {
+        let cases = vec![
+            (Domain::Domain(Value::Infinite, Value::Secluded(i32::MIN)), "(-∞;-2147483648)"),
+            (Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite), "(2147483647;∞)"),
+            (Domain::Domain(Value::Secluded(i32::MIN), Value::Secluded(i32::MAX)), "[-2147483647;2147483646]"),
+            (Domain::Domain(Value::Secluded(i32::MAX), Value::Secluded(i32::MIN)), "(2147483647;-2147483648)"),
+        ];
+        for (domain, closed) in cases {
+            assert_eq!(domain.repr_with_options(&CLOSED), closed);
+        }
+        let bytes = Domain::Domain(Value::Secluded(u8::MIN), Value::Secluded(u8::MAX));
+        assert_eq!(bytes.repr_with_options(&CLOSED), "[1;254]");
+    }
+
+    #[test]
+    fn test_options_notation() {
+        let domain = Domain::Union(vec![Domain::Domain(Value::Infinite, Value::Secluded(0)), Domain::None]);
+        let ascii = ReprOptions { notation: Notation::ASCII, ..CLOSED };
+        assert_eq!(domain.repr_with_options(&ascii), "(-inf;-1]U{}");
+        assert_eq!(domain.repr_with_options(&ReprOptions { notation: Notation::ASCII, ..ReprOptions::DEFAULT }), domain.repr_with(&Notation::ASCII));
+
+        let floats = Domain::Domain(Value::Secluded(0.5_f64), Value::Secluded(1.5));
+        assert_eq!(floats.repr_with_options(&CLOSED), floats.repr_with_options(&ReprOptions::DEFAULT));
+        assert_eq!(floats.repr_with_options(&CLOSED), "(0.5;1.5)");
+    }
+}
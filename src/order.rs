@@ -0,0 +1,175 @@
+use std::cmp::Ordering;
+
+use crate::util;
+use crate::{Domain, Value};
+
+impl Domain<i32> {
+
+    /// Position of the domain relative to `other` on the number line:
+    /// `Less` when every value is below every value of `other`, `Greater`
+    /// for the converse, `Equal` for equal sets and `None` when they
+    /// interleave. An empty domain is `Equal` to another empty one and
+    /// not comparable with anything else
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use dynamic_domain::{Domain, Value};
+    /// let low = Domain::Union(vec![Domain::between(0, 2), Domain::between(5, 6)]);
+    /// assert_eq!(low.partial_cmp_position(&Domain::at_least(7)), Some(Ordering::Less));
+    /// assert_eq!(Domain::at_least(7).partial_cmp_position(&low), Some(Ordering::Greater));
+    /// assert_eq!(low.partial_cmp_position(&Domain::between(3, 4)), None);
+    /// let same = Domain::Domain(Value::Secluded(-1), Value::Secluded(3));
+    /// assert_eq!(same.partial_cmp_position(&Domain::between(0, 2)), Some(Ordering::Equal));
+    /// ```
+    pub fn partial_cmp_position(&self, other: &Domain<i32>) -> Option<Ordering> {
+        let (a, b) = (util::i32_runs(self), util::i32_runs(other));
+        if a == b {
+            return Some(Ordering::Equal);
+        }
+
+        let hull = |runs: &[(i64, i64)]| Some((runs.first()?.0, runs.last()?.1));
+        let ((a_first, a_last), (b_first, b_last)) = (hull(&a)?, hull(&b)?);
+        if a_last < b_first {
+            Some(Ordering::Less)
+        } else if b_last < a_first {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+
+    /// Total order for sorting: the normalized members are compared in
+    /// turn by starting border, then by ending border, and a domain
+    /// running out of members first is the smaller one, so `Domain::None`
+    /// comes first. Borders are compared closed, which keeps the order
+    /// consistent with `==`: equal sets are `Equal` however they are written
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use dynamic_domain::{Domain, Value};
+    /// let mut domains = vec![
+    ///     Domain::between(3, 9),
+    ///     Domain::Union(vec![Domain::between(0, 1), Domain::between(4, 5)]),
+    ///     Domain::between(0, 1),
+    ///     Domain::None,
+    /// ];
+    /// domains.sort_by(|a, b| a.cmp_canonical(b));
+    /// let sorted: Vec<String> = domains.into_iter().map(|domain| domain.repr()).collect();
+    /// assert_eq!(sorted, vec!["∅", "[0;1]", "[0;1]⋃[4;5]", "[3;9]"]);
+    ///
+    /// let open = Domain::Domain(Value::Secluded(2), Value::Secluded(10));
+    /// assert_eq!(open.cmp_canonical(&Domain::between(3, 9)), Ordering::Equal);
+    /// ```
+    pub fn cmp_canonical(&self, other: &Domain<i32>) -> Ordering {
+        let closed = |domain: &Domain<i32>| -> Vec<(Value<i64>, Value<i64>)> {
+            util::i32_runs(domain)
+                .into_iter()
+                .map(|(first, last)| (Value::Included(first), Value::Included(last)))
+                .collect()
+        };
+        let (a, b) = (closed(self), closed(other));
+
+        for ((a_lower, a_upper), (b_lower, b_upper)) in a.iter().zip(&b) {
+            let order = a_lower.cmp_as_lower(b_lower).then_with(|| a_upper.cmp_as_upper(b_upper));
+            if order != Ordering::Equal {
+                return order;
+            }
+        }
+        a.len().cmp(&b.len())
+    }
+}
+
+/// `partial_cmp_position`: `a < b` when `a` lies entirely below `b`
+/// ```
+/// use dynamic_domain::Domain;
+/// assert!(Domain::between(0, 4) < Domain::between(5, 9));
+/// assert!(!(Domain::between(0, 5) < Domain::between(5, 9)));
+/// assert!(Domain::between(0, 5) <= Domain::between(0, 5));
+/// ```
+impl PartialOrd for Domain<i32> {
+    fn partial_cmp(&self, other: &Domain<i32>) -> Option<Ordering> {
+        self.partial_cmp_position(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::{Domain, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            1 => Just(Value::Infinite),
+            3 => (-20..20).prop_map(Value::Included),
+            3 => (-20..20).prop_map(Value::Secluded),
+            1 => Just(Value::Included(i32::MIN)),
+            1 => Just(Value::Included(i32::MAX)),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..4)
+    }
+
+    proptest! {
+        #[test]
+        fn test_canonical_is_total_order(a in domain(), b in domain(), c in domain()) {
+            prop_assert_eq!(a.cmp_canonical(&b), b.cmp_canonical(&a).reverse());
+            prop_assert_eq!(a.cmp_canonical(&b) == Ordering::Equal, a == b);
+            if a.cmp_canonical(&b) != Ordering::Greater && b.cmp_canonical(&c) != Ordering::Greater {
+                prop_assert_ne!(a.cmp_canonical(&c), Ordering::Greater);
+            }
+        }
+
+        #[test]
+        fn test_position_matches_values(a in domain(), b in domain()) {
+            let (xs, ys): (Vec<i32>, Vec<i32>) = ((-25..25).filter(|x| a.contains(*x)).collect(), (-25..25).filter(|y| b.contains(*y)).collect());
+            match a.partial_cmp_position(&b) {
+                Some(Ordering::Less) => prop_assert!(xs.iter().all(|x| ys.iter().all(|y| x < y))),
+                Some(Ordering::Greater) => prop_assert!(xs.iter().all(|x| ys.iter().all(|y| x > y))),
+                Some(Ordering::Equal) => prop_assert!(a == b),
+                None => prop_assert!(a != b),
+            }
+            prop_assert_eq!(a.partial_cmp_position(&b).map(Ordering::reverse), b.partial_cmp_position(&a));
+        }
+    }
+
+    #[test]
+    fn test_position_pairs() {
+        let disjoint = (Domain::between(0, 4), Domain::between(5, 9));
+        assert_eq!(disjoint.0.partial_cmp_position(&disjoint.1), Some(Ordering::Less));
+
+        let overlapping = (Domain::between(0, 5), Domain::between(5, 9));
+        assert_eq!(overlapping.0.partial_cmp_position(&overlapping.1), None);
+
+        let nested = (Domain::between(0, 9), Domain::between(3, 4));
+        assert_eq!(nested.0.partial_cmp_position(&nested.1), None);
+        assert_eq!(nested.1.partial_cmp_position(&nested.0), None);
+
+        let interleaved = Domain::Union(vec![Domain::between(0, 1), Domain::between(8, 9)]);
+        assert_eq!(interleaved.partial_cmp_position(&Domain::between(4, 5)), None);
+
+        assert_eq!(Domain::None.partial_cmp_position(&Domain::between(10, 5)), Some(Ordering::Equal));
+        assert_eq!(Domain::None.partial_cmp_position(&Domain::new()), None);
+        assert_eq!(Domain::at_most(i32::MIN).partial_cmp_position(&Domain::at_least(i32::MIN + 1)), Some(Ordering::Less));
+        assert_eq!(Domain::new().partial_cmp_position(&Domain::between(i32::MIN, i32::MAX)), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn test_canonical_pairs() {
+        let nested = (Domain::between(0, 9), Domain::between(3, 4));
+        assert_eq!(nested.0.cmp_canonical(&nested.1), Ordering::Less);
+
+        let same_start = (Domain::between(0, 4), Domain::between(0, 9));
+        assert_eq!(same_start.0.cmp_canonical(&same_start.1), Ordering::Less);
+
+        let prefix = (Domain::between(0, 4), Domain::Union(vec![Domain::between(0, 4), Domain::between(8, 9)]));
+        assert_eq!(prefix.0.cmp_canonical(&prefix.1), Ordering::Less);
+
+        assert_eq!(Domain::new().cmp_canonical(&Domain::between(i32::MIN, i32::MAX)), Ordering::Equal);
+        assert_eq!(Domain::None.cmp_canonical(&Domain::between(i32::MIN, i32::MIN)), Ordering::Less);
+        assert_eq!(Domain::at_least(i32::MAX).cmp_canonical(&Domain::at_most(i32::MIN)), Ordering::Greater);
+    }
+}
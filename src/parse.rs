@@ -0,0 +1,555 @@
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::{Domain, Notation, Value};
+
+/// Alternative union symbols accepted by `FromStr` next to `⋃`
+const UNION_ALIASES: [&str; 3] = ["∪", "U", "u"];
+
+enum Border<T> {
+    Point(T),
+    NegInfinite,
+    PosInfinite,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    notation: &'a Notation,
+    /// Whether the ASCII spellings of `Notation::ASCII` and the union
+    /// aliases are accepted too, as `FromStr` does
+    aliases: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str, notation: &'a Notation, aliases: bool) -> Self {
+        Parser { input, chars: input.char_indices().peekable(), notation, aliases }
+    }
+
+    fn position(&mut self) -> usize {
+        match self.chars.peek() {
+            Some((position, _)) => *position,
+            None => self.input.len(),
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.chars.next();
+        }
+    }
+
+    /// Consumes `token` if the input continues with it. Whitespace around
+    /// the token is skipped by the callers, so it is trimmed here
+    fn eat(&mut self, token: &str) -> bool {
+        let token = token.trim();
+        let position = self.position();
+        if token.is_empty() || !self.input[position..].starts_with(token) {
+            return false;
+        }
+        while self.position() < position + token.len() {
+            self.chars.next();
+        }
+        true
+    }
+
+    /// Consumes the first of the `tokens` the input continues with
+    fn eat_any(&mut self, tokens: &[&str]) -> bool {
+        tokens.iter().any(|token| self.eat(token))
+    }
+
+    fn error(&mut self) -> ParseError {
+        let position = self.position();
+        match self.peek() {
+            Some(c) => ParseError::new(position, ParseErrorKind::UnexpectedChar(c)),
+            None => ParseError::new(position, ParseErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.eat(expected) {
+            return Ok(());
+        }
+        Err(self.error())
+    }
+
+    /// Domain with every border read by `border`
+    fn domain<T>(&mut self, border: fn(&mut Self) -> Result<Border<T>, ParseError>) -> Result<Domain<T>, ParseError> {
+        let mut members = vec![self.member(border)?];
+
+        loop {
+            self.skip_whitespace();
+            if self.peek().is_none() {
+                break;
+            }
+            let aliases: &[&str] = if self.aliases { &UNION_ALIASES } else { &[] };
+            if !self.eat(self.notation.union) && !self.eat_any(aliases) {
+                return Err(self.error());
+            }
+            members.push(self.member(border)?);
+        }
+
+        if members.len() == 1 {
+            return Ok(members.remove(0));
+        }
+        Ok(Domain::Union(members))
+    }
+
+    fn member<T>(&mut self, border: fn(&mut Self) -> Result<Border<T>, ParseError>) -> Result<Domain<T>, ParseError> {
+        self.skip_whitespace();
+        let start = self.position();
+
+        if self.eat(self.notation.empty) {
+            return Ok(Domain::None);
+        }
+        if self.aliases && self.peek() == Some('{') {
+            self.chars.next();
+            self.expect("}")?;
+            return Ok(Domain::None);
+        }
+
+        let (included, secluded) = (self.notation.included, self.notation.secluded);
+        let left_included = match self.peek() {
+            Some(c) if c == included.0 => true,
+            Some(c) if c == secluded.0 => false,
+            _ => return Err(self.error()),
+        };
+        self.chars.next();
+
+        let left = border(self)?;
+        self.expect(self.notation.separator)?;
+        let right = border(self)?;
+
+        self.skip_whitespace();
+        let right_included = match self.peek() {
+            Some(c) if c == included.1 => true,
+            Some(c) if c == secluded.1 => false,
+            _ => return Err(self.error()),
+        };
+        let end = self.position();
+        self.chars.next();
+
+        let left = match (left, left_included) {
+            (Border::Point(i), true) => Value::Included(i),
+            (Border::Point(i), false) => Value::Secluded(i),
+            (Border::NegInfinite, false) => Value::Infinite,
+            _ => return Err(ParseError::new(start, ParseErrorKind::InvalidInfinity)),
+        };
+
+        let right = match (right, right_included) {
+            (Border::Point(i), true) => Value::Included(i),
+            (Border::Point(i), false) => Value::Secluded(i),
+            (Border::PosInfinite, false) => Value::Infinite,
+            _ => return Err(ParseError::new(end, ParseErrorKind::InvalidInfinity)),
+        };
+
+        Ok(Domain::Domain(left, right))
+    }
+
+    /// Integer border, also one quoted by `repr_with` in a notation
+    /// reserving a character of it
+    fn border(&mut self) -> Result<Border<i32>, ParseError> {
+        self.skip_whitespace();
+        let start = self.position();
+        if self.peek() == Some('"') {
+            return self.quoted().and_then(|text| point(start, &text));
+        }
+
+        let negative = match self.peek() {
+            Some('-') => {
+                self.chars.next();
+                true
+            },
+            Some('+') => {
+                self.chars.next();
+                false
+            },
+            _ => false,
+        };
+
+        if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return self.integer(start, negative);
+        }
+        let aliases: &[&str] = if self.aliases { &[Notation::ASCII.infinity] } else { &[] };
+        if !self.eat(self.notation.infinity) && !self.eat_any(aliases) {
+            return Err(self.error());
+        }
+
+        Ok(if negative { Border::NegInfinite } else { Border::PosInfinite })
+    }
+
+    /// Border of any point type, a quoted point or the longest run of
+    /// characters `notation` does not reserve, read by `FromStr`
+    fn point<T: FromStr>(&mut self) -> Result<Border<T>, ParseError> {
+        self.skip_whitespace();
+        let start = self.position();
+        if self.peek() == Some('"') {
+            return self.quoted().and_then(|text| point(start, &text));
+        }
+
+        let infinity = self.notation.infinity.trim();
+        let rest = &self.input[start..];
+        if rest.strip_prefix('-').is_some_and(|rest| !infinity.is_empty() && rest.starts_with(infinity)) {
+            self.chars.next();
+            self.eat(infinity);
+            return Ok(Border::NegInfinite);
+        }
+        if self.eat(infinity) {
+            return Ok(Border::PosInfinite);
+        }
+
+        while self.peek().is_some_and(|c| !self.notation.reserves(c)) {
+            self.chars.next();
+        }
+        let end = self.position();
+        if end == start {
+            return Err(self.error());
+        }
+        point(start, &self.input[start..end])
+    }
+
+    /// Text of a quoted point, in which `\"` and `\\` stand for `"` and `\`
+    fn quoted(&mut self) -> Result<String, ParseError> {
+        self.chars.next();
+        let mut text = String::new();
+        loop {
+            let position = self.position();
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(text),
+                Some((_, '\\')) => match self.chars.next() {
+                    Some((_, c @ ('"' | '\\'))) => text.push(c),
+                    Some(_) => return Err(ParseError::new(position, ParseErrorKind::InvalidEscape)),
+                    None => return Err(self.error()),
+                },
+                Some((_, c)) => text.push(c),
+                None => return Err(self.error()),
+            }
+        }
+    }
+
+    fn integer(&mut self, start: usize, negative: bool) -> Result<Border<i32>, ParseError> {
+        let limit = if negative { i32::MAX as i64 + 1 } else { i32::MAX as i64 };
+        let mut n: i64 = 0;
+
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+            n = n * 10 + digit as i64;
+            if n > limit {
+                return Err(ParseError::new(start, ParseErrorKind::IntegerOverflow));
+            }
+            self.chars.next();
+        }
+
+        Ok(Border::Point(if negative { -n } else { n } as i32))
+    }
+}
+
+/// Point written as `text` at `start`, read by `FromStr`
+fn point<T: FromStr>(start: usize, text: &str) -> Result<Border<T>, ParseError> {
+    text.parse()
+        .map(Border::Point)
+        .map_err(|_| ParseError::new(start, ParseErrorKind::InvalidPoint(text.to_string())))
+}
+
+impl Domain<i32> {
+
+    /// Parses the output of `repr_with` in `notation`, its spellings only.
+    /// Whitespace between the tokens is skipped as by `FromStr`, also
+    /// whitespace the tokens of `notation` start or end with
+    /// ```
+    /// use dynamic_domain::{Domain, Notation, Value};
+    /// let dsl = Notation { separator: "..", union: "|", ..Notation::DEFAULT };
+    /// let domain = Domain::parse_with("(-∞..5) | [8..100]", &dsl).unwrap();
+    /// assert_eq!(domain.repr(), "(-∞;5)⋃[8;100]");
+    /// assert!(Domain::parse_with("(-∞;5)", &dsl).is_err());
+    /// ```
+    pub fn parse_with(input: &str, notation: &Notation) -> Result<Domain<i32>, ParseError> {
+        Parser::new(input, notation, false).domain(Parser::border)
+    }
+}
+
+impl<T: FromStr> Domain<T> {
+
+    /// Parses the output of `repr_with` in `notation` for any point type,
+    /// every point read back by `FromStr`. A point is quoted when it is
+    /// empty or has whitespace, a `"`, a `\\`, a bracket or a character of
+    /// a token of `notation`, with `\"` and `\\` escaping a quote or a
+    /// backslash inside. Any other point runs up to the next such character
+    /// ```
+    /// use dynamic_domain::{Domain, Notation, Value};
+    /// let domain = Domain::Domain(Value::Included(-0.5), Value::Infinite);
+    /// assert_eq!(Domain::<f64>::parse_points(&domain.repr_with(&Notation::DEFAULT), &Notation::DEFAULT).unwrap().repr(), "[-0.5;∞)");
+    ///
+    /// let names = Domain::Domain(Value::Included("a;b".to_string()), Value::Secluded("say \"hi\"".to_string()));
+    /// let written = names.repr_with(&Notation::DEFAULT);
+    /// assert_eq!(written, r#"["a;b";"say \"hi\"")"#);
+    /// let read = Domain::<String>::parse_points(&written, &Notation::DEFAULT).unwrap();
+    /// assert_eq!(read.repr_with(&Notation::DEFAULT), written);
+    /// ```
+    pub fn parse_points(input: &str, notation: &Notation) -> Result<Domain<T>, ParseError> {
+        Parser::new(input, notation, false).domain(Parser::point)
+    }
+}
+
+/// Parses the notation produced by `repr`. ASCII spellings are
+/// accepted too: `inf` for `∞`, `U` or `∪` for `⋃` and `{}` for `∅`.
+/// Integers which do not fit into `i32` are rejected rather than clamped
+/// ```
+/// use dynamic_domain::{Domain, Value};
+/// let domain: Domain<i32> = "(-∞;5)⋃[8;100]".parse().unwrap();
+/// assert_eq!(domain.repr(), "(-∞;5)⋃[8;100]");
+///
+/// let domain: Domain<i32> = "(-inf; 5) U [8; 100]".parse().unwrap();
+/// assert_eq!(domain.repr(), "(-∞;5)⋃[8;100]");
+/// ```
+impl FromStr for Domain<i32> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::new(s, &Notation::DEFAULT, true).domain(Parser::border)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+    use std::str::FromStr;
+
+    use proptest::prelude::*;
+
+    use crate::error::ParseErrorKind;
+    use crate::{Domain, Notation, Value};
+
+    /// Point whose `Display` wraps its text in reserved characters,
+    /// `";)` before and `⋃` after
+    #[derive(Clone, Debug)]
+    struct Hostile(String);
+
+    impl fmt::Display for Hostile {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "\";){}⋃", self.0)
+        }
+    }
+
+    impl FromStr for Hostile {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, ()> {
+            let text = s.strip_prefix("\";)").and_then(|s| s.strip_suffix('⋃')).ok_or(())?;
+            Ok(Hostile(text.to_string()))
+        }
+    }
+
+    /// Point whose `Display` panics
+    struct Panicking;
+
+    impl fmt::Display for Panicking {
+        fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+            panic!("point can not be written")
+        }
+    }
+
+    const DSL: Notation = Notation { separator: "..", union: "u", empty: "empty", ..Notation::ASCII };
+
+    /// French notation, `]1;5[` for an open interval
+    const FRENCH: Notation = Notation { secluded: (']', '['), ..Notation::DEFAULT };
+
+    fn parse(s: &str) -> Result<Domain<i32>, crate::ParseError> {
+        s.parse()
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        let domain = parse("[5;10)").unwrap();
+        assert_eq!(domain.repr(), "[5;10)");
+
+        let domain = parse("  ( -2147483648 ; +inf )  ").unwrap();
+        assert_eq!(domain.repr(), "(-2147483648;∞)");
+    }
+
+    #[test]
+    fn test_parse_union() {
+        let domain = parse("(-∞;5)⋃∅∪[8;100]").unwrap();
+        assert_eq!(domain.repr(), "(-∞;5)⋃∅⋃[8;100]");
+        assert_eq!(parse("{}").unwrap(), Domain::None);
+        assert_eq!(parse("∅").unwrap().repr(), "∅");
+    }
+
+    #[test]
+    fn test_parse_keeps_reversed_borders() {
+        let domain = parse("[10;5]").unwrap();
+        match domain {
+            Domain::Domain(Value::Included(10), Value::Included(5)) => (),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = vec![
+            ("", 0, ParseErrorKind::UnexpectedEnd),
+            ("[1;2", 4, ParseErrorKind::UnexpectedEnd),
+            ("[1,2]", 2, ParseErrorKind::UnexpectedChar(',')),
+            ("[1;2]⋃", 8, ParseErrorKind::UnexpectedEnd),
+            ("[1;2] [3;4]", 6, ParseErrorKind::UnexpectedChar('[')),
+            ("[-∞;2]", 0, ParseErrorKind::InvalidInfinity),
+            ("(∞;2]", 0, ParseErrorKind::InvalidInfinity),
+            ("(1;-∞)", 7, ParseErrorKind::InvalidInfinity),
+            ("(1;int)", 3, ParseErrorKind::UnexpectedChar('i')),
+            ("[2147483648;0]", 1, ParseErrorKind::IntegerOverflow),
+            ("[-99999999999999999999;0]", 1, ParseErrorKind::IntegerOverflow),
+        ];
+
+        for (input, position, kind) in cases {
+            let error = parse(input).unwrap_err();
+            assert_eq!((error.position(), error.kind()), (position, &kind), "{}", input);
+        }
+    }
+
+    fn value() -> impl Strategy<Value = Value<i32>> {
+        prop_oneof![
+            1 => Just(Value::Infinite),
+            3 => any::<i32>().prop_map(Value::Included),
+            3 => (-20..20).prop_map(Value::Secluded),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        let member = prop_oneof![
+            6 => (value(), value()).prop_map(|(l, r)| Domain::Domain(l, r)),
+            1 => Just(Domain::None),
+        ];
+        prop::collection::vec(member, 1..5).prop_map(|mut members| {
+            if members.len() == 1 { members.remove(0) } else { Domain::Union(members) }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_notation_roundtrip(domain in domain()) {
+            for notation in [Notation::DEFAULT, Notation::ASCII, DSL, FRENCH].iter() {
+                let written = domain.repr_with(notation);
+                let read = Domain::parse_with(&written, notation).unwrap();
+                prop_assert_eq!(read.repr_with(notation), written);
+            }
+            prop_assert_eq!(domain.repr_with(&Notation::DEFAULT), domain.clone().repr());
+            prop_assert_eq!(domain.repr_with(&Notation::ASCII).parse::<Domain<i32>>().unwrap().repr(), domain.repr());
+        }
+
+        #[test]
+        fn test_quoted_roundtrip(points in prop::collection::vec(("\\PC{0,6}", any::<bool>()), 1..5)) {
+            let border = |(text, secluded): &(String, bool)| match secluded {
+                true => Value::Secluded(Hostile(text.clone())),
+                false => Value::Included(Hostile(text.clone())),
+            };
+            let members = points.chunks(2).map(|pair| match pair {
+                [l, r] => Domain::Domain(border(l), border(r)),
+                [l] => Domain::Domain(border(l), Value::Infinite),
+                _ => Domain::None,
+            });
+            let mut members: Vec<_> = members.collect();
+            let domain = if members.len() == 1 { members.remove(0) } else { Domain::Union(members) };
+            for notation in [Notation::DEFAULT, Notation::ASCII, DSL, FRENCH].iter() {
+                let written = domain.repr_with(notation);
+                let read = Domain::<Hostile>::parse_points(&written, notation).unwrap();
+                prop_assert_eq!(format!("{:?}", read), format!("{:?}", domain), "{}", written);
+            }
+        }
+
+        #[test]
+        fn test_points_read_integers(domain in domain()) {
+            for notation in [Notation::DEFAULT, Notation::ASCII, DSL, FRENCH].iter() {
+                let written = domain.repr_with(notation);
+                let read = Domain::<i32>::parse_points(&written, notation).unwrap();
+                prop_assert_eq!(read.repr_with(notation), written);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quoted_points() {
+        let domain = Domain::Domain(Value::Included(Hostile(String::new())), Value::Secluded(Hostile("a\\b".to_string())));
+        let written = domain.repr_with(&Notation::DEFAULT);
+        assert_eq!(written, r#"["\";)⋃";"\";)a\\b⋃")"#);
+        let read = Domain::<Hostile>::parse_points(&written, &Notation::DEFAULT).unwrap();
+        assert_eq!(format!("{:?}", read), format!("{:?}", domain));
+
+        // Points longer than the ones held back while checking them
+        let long = Domain::Domain(Value::Included(Hostile("x".repeat(100))), Value::Included(Hostile("y".repeat(100))));
+        let read = Domain::<Hostile>::parse_points(&long.repr_with(&Notation::ASCII), &Notation::ASCII).unwrap();
+        assert_eq!(format!("{:?}", read), format!("{:?}", long));
+        let plain = Domain::Domain(Value::Included("z".repeat(100)), Value::Infinite);
+        assert_eq!(plain.repr_with(&Notation::DEFAULT), format!("[{};∞)", "z".repeat(100)));
+
+        // Only points which need it are quoted
+        let words = Domain::Union(vec![
+            Domain::Domain(Value::Included("".to_string()), Value::Included("b c".to_string())),
+            Domain::Domain(Value::Secluded("-x".to_string()), Value::Infinite),
+        ]);
+        assert_eq!(words.repr_with(&Notation::DEFAULT), r#"["";"b c"]⋃(-x;∞)"#);
+        let floats = Domain::Domain(Value::Included(f64::NEG_INFINITY), Value::Included(f64::NAN));
+        assert_eq!(floats.repr_with(&Notation::DEFAULT), "[-inf;NaN]");
+        assert_eq!(floats.repr_with(&Notation::ASCII), r#"["-inf";NaN]"#);
+        for notation in [Notation::DEFAULT, Notation::ASCII].iter() {
+            let read = Domain::<f64>::parse_points(&floats.repr_with(notation), notation).unwrap();
+            assert_eq!(read.repr_with(notation), floats.repr_with(notation));
+        }
+        assert_eq!(Domain::<i32>::parse_with(r#"["-5";3]"#, &Notation::DEFAULT).unwrap(), Domain::between(-5, 3));
+    }
+
+    #[test]
+    fn test_point_errors() {
+        let cases = vec![
+            (r#"["a;b]"#, 6, ParseErrorKind::UnexpectedEnd),
+            (r#"["a\"#, 4, ParseErrorKind::UnexpectedEnd),
+            (r#"["\x";1]"#, 2, ParseErrorKind::InvalidEscape),
+            ("[x;1]", 1, ParseErrorKind::InvalidPoint("x".to_string())),
+            (r#"[1;"2 "]"#, 3, ParseErrorKind::InvalidPoint("2 ".to_string())),
+            ("[;1]", 1, ParseErrorKind::UnexpectedChar(';')),
+            ("[1.5 2;3]", 5, ParseErrorKind::UnexpectedChar('2')),
+            ("[-∞;1]", 0, ParseErrorKind::InvalidInfinity),
+        ];
+        for (input, position, kind) in cases {
+            let error = Domain::<f64>::parse_points(input, &Notation::DEFAULT).unwrap_err();
+            assert_eq!((error.position(), error.kind()), (position, &kind), "{}", input);
+        }
+        let error = Domain::<i32>::parse_with(r#"["x";1]"#, &Notation::DEFAULT).unwrap_err();
+        assert_eq!((error.position(), error.kind()), (1, &ParseErrorKind::InvalidPoint("x".to_string())));
+    }
+
+    #[test]
+    #[should_panic(expected = "point can not be written")]
+    fn test_point_panics_propagate() {
+        Domain::Domain(Value::Included(Panicking), Value::Infinite).repr();
+    }
+
+    #[test]
+    fn test_custom_notation() {
+        let domain = Domain::Union(vec![Domain::at_most(-1), Domain::None, Domain::Domain(Value::Secluded(3), Value::Infinite)]);
+        assert_eq!(domain.repr_with(&DSL), "(-inf..-1]uemptyu(3..inf)");
+        assert_eq!(Domain::parse_with(" ( -inf .. -1 ] u empty u (3..inf) ", &DSL).unwrap(), domain);
+        assert_eq!(Domain::parse_with("empty", &DSL), Ok(Domain::None));
+
+        assert_eq!(Domain::Domain(Value::Secluded(1), Value::Secluded(5)).repr_with(&FRENCH), "]1;5[");
+        assert_eq!(Domain::parse_with("]-∞;0]⋃[2;5[", &FRENCH).unwrap().repr(), "(-∞;0]⋃[2;5)");
+
+        // Only the spellings of the notation, no aliases
+        let cases = vec![
+            ("[1;2]", 2, ParseErrorKind::UnexpectedChar(';')),
+            ("[1..2]U[3..4]", 6, ParseErrorKind::UnexpectedChar('U')),
+            ("{}", 0, ParseErrorKind::UnexpectedChar('{')),
+            ("(-∞..2]", 2, ParseErrorKind::UnexpectedChar('∞')),
+            ("[1..2", 5, ParseErrorKind::UnexpectedEnd),
+            ("(1..inf]", 7, ParseErrorKind::InvalidInfinity),
+        ];
+        for (input, position, kind) in cases {
+            let error = Domain::parse_with(input, &DSL).unwrap_err();
+            assert_eq!((error.position(), error.kind()), (position, &kind), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::DomainError;
+
+/// Points borders are built on. Before building an interval every finite
+/// point goes through `check`, once it passes the points order totally so
+/// the comparisons of `PartialOrd` always give an answer
+/// ```
+/// use dynamic_domain::{Domain, DomainError, Point, Value};
+/// #[derive(PartialEq, PartialOrd)]
+/// struct Version(u32, u32);
+/// impl Point for Version {}
+///
+/// assert!(Domain::interval(Value::Included(Version(1, 0)), Value::Secluded(Version(2, 0))).is_ok());
+/// assert_eq!(f64::NAN.check(), Err(DomainError::NotComparable));
+/// ```
+pub trait Point: PartialOrd {
+    /// Accepts every point comparable with itself
+    /// ```
+    /// use dynamic_domain::Point;
+    /// assert!(5.check().is_ok());
+    /// assert!('a'.check().is_ok());
+    /// ```
+    fn check(&self) -> Result<(), DomainError> {
+        match self.partial_cmp(self) {
+            Some(_) => Ok(()),
+            None => Err(DomainError::NotComparable),
+        }
+    }
+}
+
+macro_rules! points {
+    ($($point:ty),*) => {
+        $(impl Point for $point {})*
+    };
+}
+
+points!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, char);
+
+macro_rules! float_points {
+    ($($float:ty),*) => {
+        $(
+            /// Refuses NaN, which compares with nothing, and the infinities:
+            /// an unbounded side is written as `Value::Infinite`
+            impl Point for $float {
+                fn check(&self) -> Result<(), DomainError> {
+                    if self.is_nan() {
+                        Err(DomainError::NotComparable)
+                    } else if self.is_infinite() {
+                        Err(DomainError::InfinitePoint)
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+        )*
+    };
+}
+
+float_points!(f32, f64);
@@ -0,0 +1,131 @@
+//! Python bindings, enabled by the `python` feature.
+//! Build the extension with `maturin build --features python,pyo3/extension-module`
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::util;
+use crate::{Domain, DomainIter};
+
+/// Values `to_list` returns at most unless told otherwise
+const DEFAULT_LIST_CAP: usize = 1_000_000;
+
+/// Admitted values in ascending order, refusing unbounded
+/// domains and domains larger than `cap`
+fn checked_list(domain: &Domain<i32>, cap: usize) -> Result<Vec<i32>, String> {
+    if util::is_unbounded(&util::normalized(domain)) {
+        return Err(format!("{} is unbounded", domain.clone().repr()));
+    }
+
+    let values: Vec<i32> = domain.iter().take(cap.saturating_add(1)).collect();
+    if values.len() > cap {
+        return Err(format!("{} has more than {} values", domain.clone().repr(), cap));
+    }
+    Ok(values)
+}
+
+#[pyclass(name = "Domain", module = "dynamic_domain")]
+#[derive(Clone)]
+pub struct PyDomain {
+    inner: Domain<i32>,
+}
+
+#[pymethods]
+impl PyDomain {
+    /// Parses interval notation, `Domain("[0;10)⋃[20;30]")`
+    #[new]
+    fn new(repr: &str) -> PyResult<Self> {
+        let inner = repr
+            .parse::<Domain<i32>>()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyDomain { inner })
+    }
+
+    #[staticmethod]
+    fn between(lo: i32, hi: i32) -> Self {
+        PyDomain { inner: Domain::between(lo, hi) }
+    }
+
+    #[staticmethod]
+    fn at_least(lo: i32) -> Self {
+        PyDomain { inner: Domain::at_least(lo) }
+    }
+
+    fn __contains__(&self, value: i32) -> bool {
+        self.inner.contains(value)
+    }
+
+    fn __and__(&self, other: &PyDomain) -> PyDomain {
+        PyDomain { inner: self.inner.intersect(&other.inner).into_inner() }
+    }
+
+    fn __or__(&self, other: &PyDomain) -> PyDomain {
+        PyDomain { inner: self.inner.union(&other.inner).into_inner() }
+    }
+
+    fn __repr__(&self) -> String {
+        self.inner.clone().repr()
+    }
+
+    /// Admitted values, ValueError for unbounded domains
+    /// or ones larger than `cap`
+    #[pyo3(signature = (cap = DEFAULT_LIST_CAP))]
+    fn to_list(&self, cap: usize) -> PyResult<Vec<i32>> {
+        checked_list(&self.inner, cap).map_err(PyValueError::new_err)
+    }
+
+    fn __iter__(&self) -> PyDomainIter {
+        PyDomainIter { inner: self.inner.iter() }
+    }
+}
+
+#[pyclass(name = "DomainIter", module = "dynamic_domain")]
+pub struct PyDomainIter {
+    inner: DomainIter,
+}
+
+#[pymethods]
+impl PyDomainIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<i32> {
+        self.inner.next()
+    }
+}
+
+#[pymodule]
+fn dynamic_domain(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyDomain>()?;
+    m.add_class::<PyDomainIter>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checked_list, PyDomain};
+    use crate::{Domain, Value};
+
+    #[test]
+    fn test_checked_list() {
+        let domain = Domain::Union(vec![Domain::between(1, 3), Domain::between(7, 8)]);
+        assert_eq!(checked_list(&domain, 5), Ok(vec![1, 2, 3, 7, 8]));
+        assert_eq!(checked_list(&domain, 4), Err("[1;3]⋃[7;8] has more than 4 values".to_string()));
+        assert_eq!(checked_list(&Domain::None, 0), Ok(vec![]));
+
+        let unbounded = Domain::Domain(Value::Included(1), Value::Infinite);
+        assert_eq!(checked_list(&unbounded, 5), Err("[1;∞) is unbounded".to_string()));
+    }
+
+    #[test]
+    fn test_operators() {
+        let a = PyDomain::between(0, 10);
+        let b = PyDomain::at_least(5);
+        assert_eq!((a.__and__(&b)).__repr__(), "[5;10]");
+        assert_eq!((a.__or__(&b)).__repr__(), "[0;∞)");
+        assert!(a.__contains__(10));
+        assert!(PyDomain::new("[1;").is_err());
+        assert_eq!(PyDomain::new("(1;4)").unwrap().__iter__().inner.collect::<Vec<i32>>(), vec![2, 3]);
+    }
+}
@@ -0,0 +1,254 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::util;
+use crate::Domain;
+
+/// Parser of comma separated integers and `a-b` ranges
+struct RangeList<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> RangeList<'a> {
+    fn new(input: &'a str) -> Self {
+        RangeList { input, chars: input.char_indices().peekable() }
+    }
+
+    fn position(&mut self) -> usize {
+        match self.chars.peek() {
+            Some((position, _)) => *position,
+            None => self.input.len(),
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.chars.next();
+        }
+    }
+
+    fn error(&mut self) -> ParseError {
+        let position = self.position();
+        match self.peek() {
+            Some(c) => ParseError::new(position, ParseErrorKind::UnexpectedChar(c)),
+            None => ParseError::new(position, ParseErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn domain(&mut self) -> Result<Domain<i32>, ParseError> {
+        let mut members = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek().is_none() {
+            return Ok(Domain::None);
+        }
+
+        loop {
+            members.push(self.item()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.chars.next();
+                },
+                None => break,
+                _ => return Err(self.error()),
+            }
+        }
+
+        Ok(Domain::union_of(members))
+    }
+
+    /// Single integer or an inclusive range. The first `-` after the
+    /// start of a range is the separator, a `-` right in front of the
+    /// digits of an integer is its sign
+    fn item(&mut self) -> Result<Domain<i32>, ParseError> {
+        self.skip_whitespace();
+        let start = self.position();
+        let lo = self.integer()?;
+
+        self.skip_whitespace();
+        if self.peek() != Some('-') {
+            return Ok(Domain::between(lo, lo));
+        }
+        self.chars.next();
+
+        self.skip_whitespace();
+        let hi = self.integer()?;
+        if lo > hi {
+            return Err(ParseError::new(start, ParseErrorKind::InvalidRange));
+        }
+        Ok(Domain::between(lo, hi))
+    }
+
+    fn integer(&mut self) -> Result<i32, ParseError> {
+        let start = self.position();
+        let negative = match self.peek() {
+            Some(sign @ ('-' | '+')) => {
+                self.chars.next();
+                sign == '-'
+            },
+            _ => false,
+        };
+
+        if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.error());
+        }
+
+        let limit = if negative { i32::MAX as i64 + 1 } else { i32::MAX as i64 };
+        let mut n: i64 = 0;
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+            n = n * 10 + digit as i64;
+            if n > limit {
+                return Err(ParseError::new(start, ParseErrorKind::IntegerOverflow));
+            }
+            self.chars.next();
+        }
+
+        Ok(if negative { -n } else { n } as i32)
+    }
+}
+
+impl Domain<i32> {
+
+    /// Parses a range list such as `1-5,8,10-12`: comma separated integers
+    /// and inclusive `a-b` ranges, whitespace around items and separators is
+    /// ignored and overlapping items are merged. Within a range the first `-`
+    /// after the start separates the ends, so `-5--2` is `[-5;-2]`.
+    /// An empty list is the empty domain
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::from_range_list("1-5, 8, 10-12, 4").unwrap();
+    /// assert_eq!(domain.repr(), "[1;5]⋃[8;8]⋃[10;12]");
+    /// assert_eq!(Domain::from_range_list("-5--2").unwrap(), Domain::between(-5, -2));
+    /// assert_eq!(Domain::from_range_list("5-1").unwrap_err().position(), 0);
+    /// ```
+    pub fn from_range_list(s: &str) -> Result<Domain<i32>, ParseError> {
+        RangeList::new(s).domain()
+    }
+
+    /// Shortest range list admitting the same values, `None` when the
+    /// domain is unbounded. The empty domain gives the empty list
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Union(vec![
+    ///     Domain::Domain(Value::Secluded(0), Value::Included(5)),
+    ///     Domain::between(8, 8),
+    ///     Domain::between(-3, -2),
+    /// ]);
+    /// assert_eq!(domain.to_range_list(), Some("-3--2,1-5,8".to_string()));
+    /// assert_eq!(Domain::at_least(0).to_range_list(), None);
+    /// ```
+    pub fn to_range_list(&self) -> Option<String> {
+        let members = util::normalized(self);
+        if util::is_unbounded(&members) {
+            return None;
+        }
+
+        let items: Vec<String> = util::runs_of(&members)
+            .into_iter()
+            .map(|(lo, hi)| match lo == hi {
+                true => lo.to_string(),
+                false => format!("{}-{}", lo, hi),
+            })
+            .collect();
+        Some(items.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::error::ParseErrorKind;
+    use crate::{Domain, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            (-1000..1000).prop_map(Value::Included),
+            (-1000..1000).prop_map(Value::Secluded),
+            Just(Value::Included(i32::MIN)),
+            Just(Value::Included(i32::MAX)),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..8)
+    }
+
+    proptest! {
+        #[test]
+        fn test_roundtrip(domain in domain()) {
+            let list = domain.to_range_list().unwrap();
+            let parsed = Domain::from_range_list(&list).unwrap();
+            prop_assert_eq!(&parsed, &domain, "{}", list);
+            prop_assert_eq!(parsed.to_range_list().unwrap(), list);
+        }
+    }
+
+    #[test]
+    fn test_from_range_list() {
+        let cases = vec![
+            ("", "∅"),
+            ("  ", "∅"),
+            ("7", "[7;7]"),
+            (" 1 - 3 ,\t5 ", "[1;3]⋃[5;5]"),
+            ("-5--2", "[-5;-2]"),
+            ("-5 - -2", "[-5;-2]"),
+            ("+1-+2", "[1;2]"),
+            ("1-3,4,6-8,2-5", "[1;8]"),
+            ("1-3,7,5", "[1;3]⋃[5;5]⋃[7;7]"),
+            ("-2147483648-2147483647", "[-2147483648;2147483647]"),
+            ("3-3", "[3;3]"),
+        ];
+
+        for (input, repr) in cases {
+            let domain = Domain::from_range_list(input).unwrap();
+            assert_eq!(domain.repr(), repr, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_range_list_errors() {
+        let cases = vec![
+            ("1,", 2, ParseErrorKind::UnexpectedEnd),
+            (",1", 0, ParseErrorKind::UnexpectedChar(',')),
+            ("1,,2", 2, ParseErrorKind::UnexpectedChar(',')),
+            ("1-", 2, ParseErrorKind::UnexpectedEnd),
+            ("1 2", 2, ParseErrorKind::UnexpectedChar('2')),
+            ("1-2-3", 3, ParseErrorKind::UnexpectedChar('-')),
+            ("1--2", 0, ParseErrorKind::InvalidRange),
+            ("4, 9-8", 3, ParseErrorKind::InvalidRange),
+            ("- 5", 1, ParseErrorKind::UnexpectedChar(' ')),
+            ("1;2", 1, ParseErrorKind::UnexpectedChar(';')),
+            ("2147483648", 0, ParseErrorKind::IntegerOverflow),
+            ("0--2147483649", 2, ParseErrorKind::IntegerOverflow),
+            ("1-∞", 2, ParseErrorKind::UnexpectedChar('∞')),
+        ];
+
+        for (input, position, kind) in cases {
+            let error = Domain::from_range_list(input).unwrap_err();
+            assert_eq!((error.position(), error.kind()), (position, &kind), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_to_range_list() {
+        assert_eq!(Domain::None.to_range_list(), Some(String::new()));
+        assert_eq!(Domain::new().to_range_list(), None);
+        assert_eq!(
+            Domain::between(i32::MIN, i32::MAX).to_range_list(),
+            Some("-2147483648-2147483647".to_string())
+        );
+        let adjacent = Domain::Union(vec![Domain::between(1, 2), Domain::between(3, 4)]);
+        assert_eq!(adjacent.to_range_list(), Some("1-4".to_string()));
+        let beyond = Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite);
+        assert_eq!(beyond.to_range_list(), Some(String::new()));
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+
+use crate::Domain;
+
+/// Members written out for an operand or a previous domain, longer
+/// unions are abbreviated
+const SHOWN_MEMBERS: usize = 8;
+
+/// One step of the explanation of an empty `TrackedDomain`: the
+/// operation which gave the empty domain, its operand, and the domain it
+/// emptied unless that was empty already. The steps after the first one
+/// record how emptiness propagated through further operations
+/// ```
+/// use dynamic_domain::{Domain, TrackedDomain};
+/// let mut tracked = TrackedDomain::new(Domain::between(0, 3));
+/// tracked.intersect(&Domain::between(5, 9));
+/// let reason = &tracked.empty_reasons()[0];
+/// assert_eq!((reason.operation(), reason.operand(), reason.previous()), ("intersect", "[5;9]", Some("[0;3]")));
+/// assert_eq!(reason.to_string(), "intersect [5;9] emptied [0;3]");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmptyReason {
+    operation: &'static str,
+    operand: String,
+    previous: Option<String>,
+}
+
+impl EmptyReason {
+    /// Reason for `operation` with `operand` giving an empty result,
+    /// recording `previous` unless it was empty already
+    pub(crate) fn new(operation: &'static str, operand: String, previous: &Domain<i32>) -> Self {
+        let previous = if previous.is_empty() { None } else { Some(describe(previous)) };
+        EmptyReason { operation, operand, previous }
+    }
+
+    /// Name of the method which gave the empty domain
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 3));
+    /// tracked.gt(10);
+    /// assert_eq!(tracked.empty_reasons()[0].operation(), "gt");
+    /// ```
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// The operand of the operation, a domain in `repr` notation or a
+    /// number
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain, Value};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 3));
+    /// tracked.gt(Value::Secluded(10));
+    /// assert_eq!(tracked.empty_reasons()[0].operand(), "(10;∞)");
+    /// ```
+    pub fn operand(&self) -> &str {
+        &self.operand
+    }
+
+    /// `repr` of the domain the operation emptied, `None` when it was
+    /// empty already
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::None);
+    /// assert_eq!(tracked.empty_reasons()[0].previous(), None);
+    /// ```
+    pub fn previous(&self) -> Option<&str> {
+        self.previous.as_deref()
+    }
+}
+
+/// `repr` of `domain` for a reason, unions past `SHOWN_MEMBERS` members
+/// abbreviated
+pub(crate) fn describe(domain: &Domain<i32>) -> String {
+    domain.repr_abbreviated(SHOWN_MEMBERS)
+}
+
+impl fmt::Display for EmptyReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.operation, self.operand)?;
+        if let Some(previous) = &self.previous {
+            write!(f, " emptied {}", previous)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! `repr` and `FromStr` must agree: parsing the representation of
+//! any domain gives back a domain admitting the same values
+
+use crate::{Domain, Value};
+
+/// Small xorshift generator, good enough to spread test cases
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn int(&mut self) -> i32 {
+        match self.below(8) {
+            0 => i32::MIN,
+            1 => i32::MAX,
+            2 => self.next() as i32,
+            _ => self.below(41) as i32 - 20,
+        }
+    }
+
+    fn value(&mut self) -> Value<i32> {
+        match self.below(5) {
+            0 => Value::Infinite,
+            1 | 2 => Value::Included(self.int()),
+            _ => Value::Secluded(self.int()),
+        }
+    }
+
+    fn domain(&mut self, depth: u32) -> Domain<i32> {
+        match self.below(if depth == 0 { 6 } else { 5 }) {
+            0 => Domain::None,
+            5 => {
+                let len = self.below(5);
+                Domain::Union((0..len).map(|_| self.domain(depth + 1)).collect())
+            },
+            _ => Domain::Domain(self.value(), self.value()),
+        }
+    }
+}
+
+#[test]
+fn test_repr_roundtrip() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+
+    for _ in 0..10_000 {
+        let domain = rng.domain(0);
+        let repr = domain.clone().repr();
+        let parsed: Domain<i32> = repr
+            .parse()
+            .unwrap_or_else(|e| panic!("{} failed to parse: {}", repr, e));
+        assert_eq!(parsed, domain, "{}", repr);
+    }
+}
+
+#[test]
+fn test_parse_arbitrary_input() {
+    let alphabet: Vec<char> = "[]();,-+0123456789 ∞∅⋃∪Uu{}inf\u{0}é".chars().collect();
+    let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+
+    for _ in 0..10_000 {
+        let len = rng.below(24) as usize;
+        let input: String = (0..len)
+            .map(|_| alphabet[rng.below(alphabet.len() as u64) as usize])
+            .collect();
+
+        if let Err(error) = input.parse::<Domain<i32>>() {
+            assert!(error.position() <= input.len());
+            assert!(input.is_char_boundary(error.position()));
+        }
+    }
+}
+
+#[test]
+fn test_parse_long_union() {
+    let repr = vec!["[1;2]"; 100_000].join("⋃");
+    let domain: Domain<i32> = repr.parse().unwrap();
+    assert_eq!(domain, Domain::Domain(Value::Included(1), Value::Included(2)));
+}
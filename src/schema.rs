@@ -0,0 +1,186 @@
+use std::convert::TryFrom;
+
+use crate::util;
+use crate::{Domain, DomainError, Value};
+
+/// Numeric bounds of a JSON Schema, the `minimum`, `maximum`,
+/// `exclusiveMinimum` and `exclusiveMaximum` keywords, absent ones `None`
+/// ```
+/// use dynamic_domain::{Domain, SchemaBounds};
+/// let bounds = SchemaBounds { minimum: Some(0), exclusive_maximum: Some(100), ..SchemaBounds::default() };
+/// assert_eq!(Domain::from_schema_bounds(bounds.minimum, None, None, bounds.exclusive_maximum).unwrap().repr(), "[0;100)");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SchemaBounds {
+    /// Values must be greater than or equal to it
+    pub minimum: Option<i64>,
+    /// Values must be less than or equal to it
+    pub maximum: Option<i64>,
+    /// Values must be greater than it
+    pub exclusive_minimum: Option<i64>,
+    /// Values must be less than it
+    pub exclusive_maximum: Option<i64>,
+}
+
+fn checked(bound: Option<i64>) -> Result<Option<i32>, DomainError> {
+    bound.map(|value| i32::try_from(value).map_err(|_| DomainError::OutOfRange(value))).transpose()
+}
+
+/// The tighter of an inclusive and an exclusive bound, `tighter` picks
+/// between two present ones
+fn border(
+    inclusive: Option<i32>,
+    exclusive: Option<i32>,
+    tighter: impl Fn(Value<i32>, Value<i32>) -> Value<i32>,
+) -> Value<i32> {
+    match (inclusive, exclusive) {
+        (Some(i), Some(e)) => tighter(Value::Included(i), Value::Secluded(e)),
+        (Some(i), None) => Value::Included(i),
+        (None, Some(e)) => Value::Secluded(e),
+        (None, None) => Value::Infinite,
+    }
+}
+
+impl Domain<i32> {
+
+    /// Domain of the integers valid against the numeric keywords of a
+    /// JSON Schema, with the numeric `exclusiveMinimum` and
+    /// `exclusiveMaximum` of draft 6 and later. A bound and its exclusive
+    /// variant both apply, so the tighter one wins, and unsatisfiable
+    /// bounds give `Domain::None`. Bounds out of `i32` are
+    /// `DomainError::OutOfRange`
+    /// ```
+    /// use dynamic_domain::{Domain, DomainError};
+    /// let domain = Domain::from_schema_bounds(Some(0), None, None, Some(100)).unwrap();
+    /// assert_eq!(domain.repr(), "[0;100)");
+    /// let domain = Domain::from_schema_bounds(Some(5), None, Some(5), None).unwrap();
+    /// assert_eq!(domain.repr(), "(5;∞)");
+    /// assert_eq!(Domain::from_schema_bounds(Some(3), Some(2), None, None), Ok(Domain::None));
+    /// assert_eq!(Domain::from_schema_bounds(None, Some(1 << 40), None, None), Err(DomainError::OutOfRange(1 << 40)));
+    /// ```
+    pub fn from_schema_bounds(
+        min: Option<i64>,
+        max: Option<i64>,
+        excl_min: Option<i64>,
+        excl_max: Option<i64>,
+    ) -> Result<Domain<i32>, DomainError> {
+        let (min, max, excl_min, excl_max) = (checked(min)?, checked(max)?, checked(excl_min)?, checked(excl_max)?);
+        let lower = border(min, excl_min, Value::max_lower);
+        let upper = border(max, excl_max, Value::min_upper);
+        Ok(util::from_members(util::normalize_members(vec![(lower, upper)])))
+    }
+
+    /// Numeric JSON Schema keywords admitting exactly the values of a
+    /// domain with a single interval, `None` for an empty domain or one
+    /// with a gap. Borders map to keywords as written: an included one
+    /// to `minimum` or `maximum`, a secluded one to the exclusive variant
+    /// and an infinite one to no keyword
+    /// ```
+    /// use dynamic_domain::{Domain, SchemaBounds, Value};
+    /// let domain = Domain::Domain(Value::Secluded(0), Value::Included(10));
+    /// let bounds = domain.to_schema_bounds().unwrap();
+    /// assert_eq!(bounds, SchemaBounds { exclusive_minimum: Some(0), maximum: Some(10), ..SchemaBounds::default() });
+    /// assert_eq!(Domain::at_least(3).to_schema_bounds().unwrap().maximum, None);
+    /// assert_eq!(Domain::Union(vec![Domain::between(0, 1), Domain::between(5, 6)]).to_schema_bounds(), None);
+    /// assert_eq!(Domain::None.to_schema_bounds(), None);
+    /// ```
+    pub fn to_schema_bounds(&self) -> Option<SchemaBounds> {
+        let members = util::normalized(self);
+        let (lower, upper) = match members.as_slice() {
+            [member] => member,
+            _ => return None,
+        };
+
+        let mut bounds = SchemaBounds::default();
+        match lower {
+            Value::Included(i) => bounds.minimum = Some(*i as i64),
+            Value::Secluded(i) => bounds.exclusive_minimum = Some(*i as i64),
+            Value::Infinite => (),
+        }
+        match upper {
+            Value::Included(i) => bounds.maximum = Some(*i as i64),
+            Value::Secluded(i) => bounds.exclusive_maximum = Some(*i as i64),
+            Value::Infinite => (),
+        }
+        Some(bounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{Domain, DomainError, SchemaBounds, Value};
+
+    fn bound() -> impl Strategy<Value = Option<i64>> {
+        prop_oneof![
+            2 => Just(None),
+            4 => (-20i64..20).prop_map(Some),
+            1 => Just(Some(i32::MIN as i64)),
+            1 => Just(Some(i32::MAX as i64)),
+        ]
+    }
+
+    fn admitted(value: i64, min: Option<i64>, max: Option<i64>, excl_min: Option<i64>, excl_max: Option<i64>) -> bool {
+        min.is_none_or(|m| value >= m)
+            && max.is_none_or(|m| value <= m)
+            && excl_min.is_none_or(|m| value > m)
+            && excl_max.is_none_or(|m| value < m)
+    }
+
+    proptest! {
+        #[test]
+        fn test_schema_semantics(min in bound(), max in bound(), excl_min in bound(), excl_max in bound()) {
+            let domain = Domain::from_schema_bounds(min, max, excl_min, excl_max).unwrap();
+            for value in (-25..25).chain(vec![i32::MIN, i32::MIN + 1, i32::MAX - 1, i32::MAX]) {
+                prop_assert_eq!(domain.contains(value), admitted(value as i64, min, max, excl_min, excl_max), "{}", value);
+            }
+
+            match domain.to_schema_bounds() {
+                Some(bounds) => {
+                    let back = Domain::from_schema_bounds(bounds.minimum, bounds.maximum, bounds.exclusive_minimum, bounds.exclusive_maximum);
+                    prop_assert_eq!(back, Ok(domain));
+                },
+                None => prop_assert_eq!(domain, Domain::None),
+            }
+        }
+    }
+
+    #[test]
+    fn test_spec_examples() {
+        // "minimum": 0, "exclusiveMaximum": 100
+        let range = Domain::from_schema_bounds(Some(0), None, None, Some(100)).unwrap();
+        let valid: Vec<bool> = [-1, 0, 10, 99, 100, 101].iter().map(|x| range.contains(*x)).collect();
+        assert_eq!(valid, [false, true, true, true, false, false]);
+
+        // "minimum" and "exclusiveMinimum" both present, the tighter applies
+        let both = Domain::from_schema_bounds(Some(5), None, Some(5), None).unwrap();
+        assert!(!both.contains(5) && both.contains(6));
+        let looser_exclusive = Domain::from_schema_bounds(Some(5), None, Some(3), None).unwrap();
+        assert_eq!(looser_exclusive.repr(), "[5;∞)");
+        let tighter_exclusive = Domain::from_schema_bounds(None, Some(10), None, Some(8)).unwrap();
+        assert_eq!(tighter_exclusive.repr(), "(-∞;8)");
+
+        // "exclusiveMinimum": 5, "exclusiveMaximum": 6 admits no integer
+        assert_eq!(Domain::from_schema_bounds(None, None, Some(5), Some(6)), Ok(Domain::None));
+        assert_eq!(Domain::from_schema_bounds(None, None, None, None), Ok(Domain::new()));
+    }
+
+    #[test]
+    fn test_range_checks() {
+        let past = i32::MAX as i64 + 1;
+        assert_eq!(Domain::from_schema_bounds(Some(past), None, None, None), Err(DomainError::OutOfRange(past)));
+        assert_eq!(Domain::from_schema_bounds(None, None, None, Some(past)), Err(DomainError::OutOfRange(past)));
+        let below = i32::MIN as i64 - 1;
+        assert_eq!(Domain::from_schema_bounds(None, Some(0), Some(below), None).unwrap_err().to_string(), "bound -2147483649 is out of i32");
+
+        let full = Domain::from_schema_bounds(Some(i32::MIN as i64), Some(i32::MAX as i64), None, None).unwrap();
+        assert_eq!(full, Domain::new());
+        assert_eq!(
+            full.to_schema_bounds(),
+            Some(SchemaBounds { minimum: Some(i32::MIN as i64), maximum: Some(i32::MAX as i64), ..SchemaBounds::default() })
+        );
+        assert_eq!(Domain::new().to_schema_bounds(), Some(SchemaBounds::default()));
+        assert_eq!(Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite).to_schema_bounds(), None);
+    }
+}
@@ -0,0 +1,290 @@
+//! Adapters for `#[serde(with = "...")]`, enabled by the `serde` feature.
+//! All of them reject nested unions and reversed borders in both directions
+
+use std::fmt;
+
+use crate::util::Member;
+use crate::{Domain, Value};
+
+/// Single intervals of `domain`, checked to be representable
+fn checked_members(domain: &Domain<i32>) -> Result<Vec<Member>, String> {
+    let mut members = Vec::new();
+
+    match domain {
+        Domain::None => (),
+        Domain::Domain(l, r) => members.push((l.clone(), r.clone())),
+        Domain::Union(domains) => {
+            for domain in domains {
+                match domain {
+                    Domain::None => (),
+                    Domain::Domain(l, r) => members.push((l.clone(), r.clone())),
+                    Domain::Union(_) => return Err("nested unions are not supported".to_string()),
+                }
+            }
+        },
+    }
+
+    for (l, r) in &members {
+        check_member(l, r)?;
+    }
+
+    Ok(members)
+}
+
+fn check_member(l: &Value<i32>, r: &Value<i32>) -> Result<(), String> {
+    match (l, r) {
+        (Value::Included(a), Value::Included(b))
+        | (Value::Included(a), Value::Secluded(b))
+        | (Value::Secluded(a), Value::Included(b))
+        | (Value::Secluded(a), Value::Secluded(b)) if a > b => {
+            Err(format!("reversed borders: {} is greater than {}", a, b))
+        },
+        _ => Ok(()),
+    }
+}
+
+fn from_bound(bound: Option<i32>, inclusive: bool) -> Value<i32> {
+    match bound {
+        None => Value::Infinite,
+        Some(i) if inclusive => Value::Included(i),
+        Some(i) => Value::Secluded(i),
+    }
+}
+
+fn to_bound(value: &Value<i32>) -> (Option<i32>, bool) {
+    match value {
+        Value::Included(i) => (Some(*i), true),
+        Value::Secluded(i) => (Some(*i), false),
+        Value::Infinite => (None, false),
+    }
+}
+
+fn error<E: serde::de::Error>(message: impl fmt::Display) -> E {
+    E::custom(message)
+}
+
+/// Single interval as `{"min": 5, "max": 10, "min_inclusive": true, "max_inclusive": false}`.
+/// Infinite borders are `null`, missing inclusivity flags default to `true`.
+/// Empty domains and unions of several intervals can not be represented
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// use dynamic_domain::Domain;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "dynamic_domain::serde_object")]
+///     limit: Domain<i32>,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{"limit": {"min": 5, "max": 10}}"#).unwrap();
+/// assert_eq!(config.limit.repr(), "[5;10]");
+/// ```
+pub mod object {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{checked_members, error, from_bound, to_bound};
+    use crate::Domain;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Object {
+        #[serde(default)]
+        min: Option<i32>,
+        #[serde(default)]
+        max: Option<i32>,
+        #[serde(default = "inclusive")]
+        min_inclusive: bool,
+        #[serde(default = "inclusive")]
+        max_inclusive: bool,
+    }
+
+    fn inclusive() -> bool {
+        true
+    }
+
+    pub fn serialize<S: Serializer>(domain: &Domain<i32>, serializer: S) -> Result<S::Ok, S::Error> {
+        let members = checked_members(domain).map_err(serde::ser::Error::custom)?;
+        if members.len() != 1 {
+            return Err(serde::ser::Error::custom("only a single interval can be represented as an object"));
+        }
+
+        let (min, min_inclusive) = to_bound(&members[0].0);
+        let (max, max_inclusive) = to_bound(&members[0].1);
+        Object { min, max, min_inclusive, max_inclusive }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Domain<i32>, D::Error> {
+        let object = Object::deserialize(deserializer)?;
+        let domain = Domain::Domain(
+            from_bound(object.min, object.min_inclusive),
+            from_bound(object.max, object.max_inclusive),
+        );
+        checked_members(&domain).map_err(error)?;
+        Ok(domain)
+    }
+}
+
+/// Domain as its `repr` string, e.g. `"(5;10]"`
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// use dynamic_domain::Domain;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "dynamic_domain::serde_string")]
+///     limit: Domain<i32>,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{"limit": "(5;10]⋃[20;∞)"}"#).unwrap();
+/// assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"limit":"(5;10]⋃[20;∞)"}"#);
+/// ```
+pub mod string {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::{checked_members, error};
+    use crate::Domain;
+
+    pub fn serialize<S: Serializer>(domain: &Domain<i32>, serializer: S) -> Result<S::Ok, S::Error> {
+        checked_members(domain).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&domain.clone().repr())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Domain<i32>, D::Error> {
+        let repr = String::deserialize(deserializer)?;
+        let domain: Domain<i32> = repr.parse().map_err(error)?;
+        checked_members(&domain).map_err(error)?;
+        Ok(domain)
+    }
+}
+
+/// Domain as an array of `[lo, hi, lo_open, hi_open]` tuples,
+/// one per interval, with `null` for infinite borders.
+/// The empty domain is `[]`
+/// ```
+/// # use serde::{Deserialize, Serialize};
+/// use dynamic_domain::Domain;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(with = "dynamic_domain::serde_intervals")]
+///     limit: Domain<i32>,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{"limit": [[null, 5, true, true], [8, 100, false, false]]}"#).unwrap();
+/// assert_eq!(config.limit.repr(), "(-∞;5)⋃[8;100]");
+/// ```
+pub mod intervals {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::ser::SerializeSeq;
+
+    use super::{checked_members, error, from_bound, to_bound};
+    use crate::util;
+    use crate::Domain;
+
+    type Tuple = (Option<i32>, Option<i32>, bool, bool);
+
+    pub fn serialize<S: Serializer>(domain: &Domain<i32>, serializer: S) -> Result<S::Ok, S::Error> {
+        let members = checked_members(domain).map_err(serde::ser::Error::custom)?;
+        let mut seq = serializer.serialize_seq(Some(members.len()))?;
+
+        for (l, r) in &members {
+            let (lo, lo_inclusive) = to_bound(l);
+            let (hi, hi_inclusive) = to_bound(r);
+            let tuple: Tuple = (lo, hi, !lo_inclusive, !hi_inclusive);
+            seq.serialize_element(&tuple)?;
+        }
+
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Domain<i32>, D::Error> {
+        let tuples = Vec::<Tuple>::deserialize(deserializer)?;
+        let mut members = Vec::with_capacity(tuples.len());
+
+        for (lo, hi, lo_open, hi_open) in tuples {
+            let member = (from_bound(lo, !lo_open), from_bound(hi, !hi_open));
+            super::check_member(&member.0, &member.1).map_err(error)?;
+            members.push(member);
+        }
+
+        Ok(util::from_members(members))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{Domain, Value};
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Limits {
+        #[serde(with = "crate::serde_object")]
+        object: Domain<i32>,
+        #[serde(with = "crate::serde_string")]
+        string: Domain<i32>,
+        #[serde(with = "crate::serde_intervals")]
+        intervals: Domain<i32>,
+    }
+
+    fn union() -> Domain<i32> {
+        Domain::Union(vec![
+            Domain::Domain(Value::Infinite, Value::Secluded(0)),
+            Domain::Domain(Value::Secluded(5), Value::Included(10)),
+        ])
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let limits = Limits {
+            object: Domain::Domain(Value::Secluded(5), Value::Infinite),
+            string: union(),
+            intervals: union(),
+        };
+
+        let json = serde_json::to_string(&limits).unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"object":{"min":5,"max":null,"min_inclusive":false,"max_inclusive":false},"#,
+                r#""string":"(-∞;0)⋃(5;10]","#,
+                r#""intervals":[[null,0,true,true],[5,10,true,false]]}"#,
+            )
+        );
+
+        let parsed: Limits = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.object.repr(), "(5;∞)");
+        assert_eq!(parsed.string.repr(), "(-∞;0)⋃(5;10]");
+        assert_eq!(parsed.intervals.repr(), "(-∞;0)⋃(5;10]");
+    }
+
+    #[test]
+    fn test_empty() {
+        let json = r#"{"object":{"min":1,"max":1},"string":"∅","intervals":[]}"#;
+        let parsed: Limits = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.string, Domain::None);
+        assert_eq!(parsed.intervals.repr(), "∅");
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        let nested = Domain::Union(vec![union(), Domain::new()]);
+        let limits = Limits { object: Domain::new(), string: nested.clone(), intervals: Domain::None };
+        let error = serde_json::to_string(&limits).unwrap_err();
+        assert!(error.to_string().contains("nested unions"));
+
+        let limits = Limits { object: union(), string: Domain::None, intervals: Domain::None };
+        assert!(serde_json::to_string(&limits).is_err());
+
+        let invalid = vec![
+            r#"{"object":{"min":10,"max":5},"string":"∅","intervals":[]}"#,
+            r#"{"object":{"min":1,"max":5,"step":1},"string":"∅","intervals":[]}"#,
+            r#"{"object":{},"string":"[10;5]","intervals":[]}"#,
+            r#"{"object":{},"string":"[1;","intervals":[]}"#,
+            r#"{"object":{},"string":"∅","intervals":[[3,2,false,false]]}"#,
+        ];
+        for json in invalid {
+            assert!(serde_json::from_str::<Limits>(json).is_err(), "{}", json);
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::util;
+use crate::{Domain, Value};
+
+/// Index of a slice as written, with its position for errors
+type Index = Option<(usize, i32)>;
+
+/// Parser of Python slices such as `5:10`, `-3:` or `::1`
+struct Slice<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Slice<'a> {
+    fn new(input: &'a str) -> Self {
+        Slice { input, chars: input.char_indices().peekable() }
+    }
+
+    fn position(&mut self) -> usize {
+        match self.chars.peek() {
+            Some((position, _)) => *position,
+            None => self.input.len(),
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.chars.next();
+        }
+    }
+
+    fn error(&mut self) -> ParseError {
+        let position = self.position();
+        match self.peek() {
+            Some(c) => ParseError::new(position, ParseErrorKind::UnexpectedChar(c)),
+            None => ParseError::new(position, ParseErrorKind::UnexpectedEnd),
+        }
+    }
+
+    /// Start, stop and step, each of them may be left out. The first
+    /// colon is required, the second one only in front of a step
+    fn slice(&mut self) -> Result<(Index, Index, Index), ParseError> {
+        let start = self.index()?;
+        if self.peek() != Some(':') {
+            return Err(self.error());
+        }
+        self.chars.next();
+
+        let stop = self.index()?;
+        let step = match self.peek() {
+            Some(':') => {
+                self.chars.next();
+                self.index()?
+            },
+            _ => None,
+        };
+        if self.peek().is_some() {
+            return Err(self.error());
+        }
+        Ok((start, stop, step))
+    }
+
+    /// Integer between whitespace, `None` when there are no digits
+    fn index(&mut self) -> Result<Index, ParseError> {
+        self.skip_whitespace();
+        let start = self.position();
+        let negative = match self.peek() {
+            Some(sign @ ('-' | '+')) => {
+                self.chars.next();
+                sign == '-'
+            },
+            _ if self.peek().is_some_and(|c| c.is_ascii_digit()) => false,
+            _ => return Ok(None),
+        };
+
+        if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.error());
+        }
+
+        let limit = if negative { i32::MAX as i64 + 1 } else { i32::MAX as i64 };
+        let mut n: i64 = 0;
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+            n = n * 10 + digit as i64;
+            if n > limit {
+                return Err(ParseError::new(start, ParseErrorKind::IntegerOverflow));
+            }
+            self.chars.next();
+        }
+
+        self.skip_whitespace();
+        Ok(Some((start, if negative { -n } else { n } as i32)))
+    }
+}
+
+impl Domain<i32> {
+
+    /// Parses a Python slice such as `5:10` into the indices it selects,
+    /// the half-open `[5;10)`. Start, stop and the step after a second
+    /// colon may be left out, whitespace around them is ignored. The step
+    /// must be 1 when given, other strides are refused with
+    /// `ParseErrorKind::UnsupportedStep`.
+    ///
+    /// With a `len_hint` the indices are resolved like Python resolves them
+    /// against a sequence of that length: a left out start is 0 and a left
+    /// out stop is the length, a negative index counts from the end, then
+    /// both are clamped into `[0;len]`. Without one a left out border is
+    /// infinite, so `:` is the full domain, and a negative index is refused
+    /// with `ParseErrorKind::NegativeIndex`. A stop not past the start
+    /// selects nothing, the empty domain
+    /// ```
+    /// use dynamic_domain::{Domain, ParseErrorKind};
+    /// assert_eq!(Domain::from_slice_notation("5:10", None).unwrap().repr(), "[5;10)");
+    /// assert_eq!(Domain::from_slice_notation("5:", None).unwrap().repr(), "[5;∞)");
+    /// assert_eq!(Domain::from_slice_notation(":", None).unwrap(), Domain::new());
+    /// assert_eq!(Domain::from_slice_notation("-3:", Some(10)).unwrap().repr(), "[7;10)");
+    /// assert_eq!(Domain::from_slice_notation(":-3", Some(10)).unwrap().repr(), "[0;7)");
+    /// assert_eq!(Domain::from_slice_notation("-3:", None).unwrap_err().kind(), &ParseErrorKind::NegativeIndex);
+    /// assert_eq!(Domain::from_slice_notation("::2", None).unwrap_err().kind(), &ParseErrorKind::UnsupportedStep);
+    /// ```
+    pub fn from_slice_notation(s: &str, len_hint: Option<i32>) -> Result<Domain<i32>, ParseError> {
+        let (start, stop, step) = Slice::new(s).slice()?;
+        if let Some((position, step)) = step {
+            if step != 1 {
+                return Err(ParseError::new(position, ParseErrorKind::UnsupportedStep));
+            }
+        }
+        if len_hint.is_some_and(|len| len < 0) {
+            return Err(ParseError::new(0, ParseErrorKind::InvalidLength));
+        }
+
+        let resolve = |index: Index, omitted: Option<i64>| match (index, len_hint) {
+            (None, _) => Ok(omitted),
+            (Some((position, i)), None) if i < 0 => Err(ParseError::new(position, ParseErrorKind::NegativeIndex)),
+            (Some((_, i)), None) => Ok(Some(i as i64)),
+            (Some((_, i)), Some(len)) => {
+                let i = if i < 0 { i as i64 + len as i64 } else { i as i64 };
+                Ok(Some(i.clamp(0, len as i64)))
+            },
+        };
+        let start = resolve(start, len_hint.map(|_| 0))?;
+        let stop = resolve(stop, len_hint.map(|len| len as i64))?;
+
+        Ok(match (start, stop) {
+            (Some(start), Some(stop)) if start >= stop => Domain::None,
+            (start, stop) => Domain::Domain(
+                start.map_or(Value::Infinite, |start| Value::Included(start as i32)),
+                stop.map_or(Value::Infinite, |stop| Value::Secluded(stop as i32)),
+            ),
+        })
+    }
+
+    /// The Python slice selecting the admitted values as indices, `None`
+    /// unless they form a single run, `0:0` for the empty domain. A border
+    /// at the `i32` limit, like an infinite one, is left out, so `[5;∞)`
+    /// is `5:`. A negative start or stop means counting from the end to
+    /// Python, so a run which needs one is `None` as well
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// assert_eq!(Domain::between(5, 9).to_slice_notation(), Some("5:10".to_string()));
+    /// assert_eq!(Domain::Domain(Value::Infinite, Value::Included(4)).to_slice_notation(), Some(":5".to_string()));
+    /// assert_eq!(Domain::at_least(0).to_slice_notation(), Some("0:".to_string()));
+    /// assert_eq!(Domain::new().to_slice_notation(), Some(":".to_string()));
+    /// assert_eq!(Domain::between(-5, 5).to_slice_notation(), None);
+    /// let gapped = Domain::Union(vec![Domain::between(0, 1), Domain::between(3, 4)]);
+    /// assert_eq!(gapped.to_slice_notation(), None);
+    /// ```
+    pub fn to_slice_notation(&self) -> Option<String> {
+        let (lo, hi) = match util::i32_runs(self).as_slice() {
+            [] => return Some("0:0".to_string()),
+            [run] => *run,
+            _ => return None,
+        };
+        let start = match lo {
+            lo if lo == i32::MIN as i64 => String::new(),
+            lo if lo < 0 => return None,
+            lo => lo.to_string(),
+        };
+        let stop = match hi {
+            hi if hi == i32::MAX as i64 => String::new(),
+            hi if hi < -1 => return None,
+            hi => (hi + 1).to_string(),
+        };
+        Some(format!("{}:{}", start, stop))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::error::ParseErrorKind;
+    use crate::{Domain, Value};
+
+    /// Indices `seq[start:stop]` selects from a sequence of `len` items,
+    /// as resolved by CPython's `PySlice_AdjustIndices` for a step of 1
+    fn python_indices(start: Option<i64>, stop: Option<i64>, len: i64) -> Vec<i32> {
+        let adjust = |index: Option<i64>, omitted: i64| match index {
+            None => omitted,
+            Some(i) if i < 0 => (i + len).max(0),
+            Some(i) => i.min(len),
+        };
+        (adjust(start, 0)..adjust(stop, len)).map(|i| i as i32).collect()
+    }
+
+    fn written(index: Option<i64>) -> String {
+        index.map_or(String::new(), |i| i.to_string())
+    }
+
+    proptest! {
+        #[test]
+        fn test_python_resolution(
+            start in prop::option::of(-25i64..25),
+            stop in prop::option::of(-25i64..25),
+            len in 0i64..20,
+        ) {
+            let slice = format!("{}:{}", written(start), written(stop));
+            let domain = Domain::from_slice_notation(&slice, Some(len as i32)).unwrap();
+            let values: Vec<i32> = domain.iter().collect();
+            prop_assert_eq!(values, python_indices(start, stop, len), "{}", slice);
+        }
+
+        #[test]
+        fn test_roundtrip(lo in -5..50, hi in -5..50, infinite in any::<(bool, bool)>()) {
+            let l = if infinite.0 { Value::Infinite } else { Value::Included(lo) };
+            let r = if infinite.1 { Value::Infinite } else { Value::Included(hi) };
+            let domain = Domain::Domain(l, r);
+            // Only a finite start below 0 or stop below 0 has no slice
+            let empty = !infinite.0 && !infinite.1 && lo > hi;
+            let negative = (!infinite.0 && lo < 0) || (!infinite.1 && hi < -1);
+            match domain.to_slice_notation() {
+                Some(slice) => {
+                    prop_assert!(empty || !negative, "{}", slice);
+                    prop_assert_eq!(Domain::from_slice_notation(&slice, None).unwrap(), domain, "{}", slice);
+                },
+                None => prop_assert!(!empty && negative),
+            }
+        }
+    }
+
+    #[test]
+    fn test_negative_indices() {
+        // Results of `list(range(10))[slice]` in Python
+        let cases = vec![
+            ("-3:", "[7;10)"),
+            (":-3", "[0;7)"),
+            ("-3:-1", "[7;9)"),
+            ("-1:", "[9;10)"),
+            ("-10:", "[0;10)"),
+            ("-11:", "[0;10)"),
+            ("-20:5", "[0;5)"),
+            ("5:20", "[5;10)"),
+            ("10:", "∅"),
+            (":-10", "∅"),
+            ("-1:-3", "∅"),
+            ("8:2", "∅"),
+            ("3:-3", "[3;7)"),
+            (":", "[0;10)"),
+            ("::", "[0;10)"),
+            ("::1", "[0;10)"),
+            (" -2 : ", "[8;10)"),
+        ];
+        for (slice, expected) in cases {
+            assert_eq!(Domain::from_slice_notation(slice, Some(10)).unwrap().repr(), expected, "{}", slice);
+        }
+        assert_eq!(Domain::from_slice_notation(":", Some(0)).unwrap(), Domain::None);
+        assert_eq!(Domain::from_slice_notation("-1:", Some(0)).unwrap(), Domain::None);
+        assert_eq!(Domain::from_slice_notation("-2147483648:", Some(i32::MAX)).unwrap().repr(), "[0;2147483647)");
+        assert_eq!(Domain::from_slice_notation("-1:", Some(i32::MAX)).unwrap(), Domain::between(i32::MAX - 1, i32::MAX - 1));
+    }
+
+    #[test]
+    fn test_without_length() {
+        let cases = vec![
+            ("5:10", "[5;10)"),
+            ("5:", "[5;∞)"),
+            (":10", "(-∞;10)"),
+            (":", "(-∞;∞)"),
+            ("7:7", "∅"),
+            ("9:3", "∅"),
+            ("+1:+2", "[1;2)"),
+            ("0:2147483647", "[0;2147483647)"),
+            ("5:10:1", "[5;10)"),
+        ];
+        for (slice, expected) in cases {
+            assert_eq!(Domain::from_slice_notation(slice, None).unwrap().repr(), expected, "{}", slice);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases = vec![
+            ("", None, 0, ParseErrorKind::UnexpectedEnd),
+            ("5", None, 1, ParseErrorKind::UnexpectedEnd),
+            ("5;10", None, 1, ParseErrorKind::UnexpectedChar(';')),
+            ("1:2:3:4", None, 5, ParseErrorKind::UnexpectedChar(':')),
+            ("a:", None, 0, ParseErrorKind::UnexpectedChar('a')),
+            ("- 1:", None, 1, ParseErrorKind::UnexpectedChar(' ')),
+            ("1 2:", None, 2, ParseErrorKind::UnexpectedChar('2')),
+            ("2147483648:", None, 0, ParseErrorKind::IntegerOverflow),
+            (":-2147483649", Some(5), 1, ParseErrorKind::IntegerOverflow),
+            ("-3:", None, 0, ParseErrorKind::NegativeIndex),
+            ("2: -1", None, 3, ParseErrorKind::NegativeIndex),
+            (":", Some(-1), 0, ParseErrorKind::InvalidLength),
+            ("::2", None, 2, ParseErrorKind::UnsupportedStep),
+            ("::0", Some(10), 2, ParseErrorKind::UnsupportedStep),
+            ("1:5:-1", Some(10), 4, ParseErrorKind::UnsupportedStep),
+        ];
+        for (slice, len, position, kind) in cases {
+            let error = Domain::from_slice_notation(slice, len).unwrap_err();
+            assert_eq!((error.position(), error.kind()), (position, &kind), "{}", slice);
+        }
+    }
+
+    #[test]
+    fn test_to_slice_notation() {
+        let cases = vec![
+            (Domain::None, Some("0:0")),
+            (Domain::between(5, 1), Some("0:0")),
+            (Domain::between(0, 0), Some("0:1")),
+            (Domain::Domain(Value::Secluded(4), Value::Secluded(8)), Some("5:8")),
+            (Domain::between(3, i32::MAX), Some("3:")),
+            (Domain::between(i32::MIN, -1), Some(":0")),
+            (Domain::between(i32::MIN, -2), None),
+            (Domain::between(-1, 3), None),
+            (Domain::between(i32::MIN, i32::MAX), Some(":")),
+            (Domain::between(0, i32::MAX - 1), Some("0:2147483647")),
+            (Domain::Union(vec![Domain::between(0, 4), Domain::between(5, 9)]), Some("0:10")),
+        ];
+        for (domain, expected) in cases {
+            assert_eq!(domain.to_slice_notation().as_deref(), expected, "{}", domain.clone().repr());
+        }
+    }
+}
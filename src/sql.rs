@@ -0,0 +1,162 @@
+use crate::error::IdentifierError;
+use crate::util::{self, first_admitted, last_admitted};
+use crate::{Domain, Value};
+
+/// Accepts `name` and `schema.table.name` where every part matches
+/// `[A-Za-z_][A-Za-z0-9_]*`, so the column can be spliced as is
+fn checked_column(column: &str) -> Result<&str, IdentifierError> {
+    let valid_part = |part: &str| {
+        let mut chars = part.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+
+    if column.split('.').all(valid_part) {
+        Ok(column)
+    } else {
+        Err(IdentifierError::new(column))
+    }
+}
+
+/// Builds the predicate, `literal` renders each bound value
+fn predicate(
+    domain: &Domain<i32>,
+    column: &str,
+    mut literal: impl FnMut(i32) -> String,
+) -> Result<String, IdentifierError> {
+    let column = checked_column(column)?;
+    let members = util::normalized(domain);
+
+    if members.is_empty() {
+        return Ok("1=0".to_string());
+    }
+
+    let mut clauses = Vec::with_capacity(members.len());
+    for (l, r) in &members {
+        let clause = match (l, r) {
+            (Value::Infinite, Value::Infinite) => return Ok("1=1".to_string()),
+            _ if first_admitted(l) == last_admitted(r) => {
+                format!("{} = {}", column, literal(first_admitted(l) as i32))
+            },
+            (Value::Included(lo), Value::Included(hi)) => {
+                format!("{} BETWEEN {} AND {}", column, literal(*lo), literal(*hi))
+            },
+            _ => {
+                let lower = match l {
+                    Value::Included(i) => Some(format!("{} >= {}", column, literal(*i))),
+                    Value::Secluded(i) => Some(format!("{} > {}", column, literal(*i))),
+                    Value::Infinite => None,
+                };
+                let upper = match r {
+                    Value::Included(i) => Some(format!("{} <= {}", column, literal(*i))),
+                    Value::Secluded(i) => Some(format!("{} < {}", column, literal(*i))),
+                    Value::Infinite => None,
+                };
+                match (lower, upper) {
+                    (Some(lower), Some(upper)) => format!("({} AND {})", lower, upper),
+                    (Some(side), None) | (None, Some(side)) => side,
+                    (None, None) => unreachable!(),
+                }
+            },
+        };
+        clauses.push(clause);
+    }
+
+    Ok(clauses.join(" OR "))
+}
+
+impl Domain<i32> {
+
+    /// SQL `WHERE` condition admitting the same values of `column`:
+    /// `BETWEEN` for closed members, `=` for single values and comparison
+    /// pairs otherwise. `1=0` stands for the empty domain and `1=1` for the
+    /// full one. `column` must be a plain identifier, dotted parts allowed,
+    /// quoted identifiers are rejected
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::Union(vec![
+    ///     Domain::Domain(Value::Included(5), Value::Secluded(10)),
+    ///     Domain::between(20, 30),
+    /// ]);
+    /// assert_eq!(
+    ///     domain.to_sql_predicate("price").unwrap(),
+    ///     "(price >= 5 AND price < 10) OR price BETWEEN 20 AND 30"
+    /// );
+    /// assert!(domain.to_sql_predicate("price; DROP TABLE t").is_err());
+    /// ```
+    pub fn to_sql_predicate(&self, column: &str) -> Result<String, IdentifierError> {
+        predicate(self, column, |value| value.to_string())
+    }
+
+    /// Same as `to_sql_predicate` with `?` placeholders in place of the
+    /// values, which are returned in placeholder order for binding
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let (sql, binds) = Domain::at_least(18).to_sql_predicate_params("users.age").unwrap();
+    /// assert_eq!(sql, "users.age >= ?");
+    /// assert_eq!(binds, vec![18]);
+    /// ```
+    pub fn to_sql_predicate_params(&self, column: &str) -> Result<(String, Vec<i32>), IdentifierError> {
+        let mut binds = Vec::new();
+        let sql = predicate(self, column, |value| {
+            binds.push(value);
+            "?".to_string()
+        })?;
+        Ok((sql, binds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Domain, Value};
+
+    #[test]
+    fn test_clauses() {
+        let domain = Domain::Union(vec![
+            Domain::Domain(Value::Infinite, Value::Secluded(-10)),
+            Domain::Domain(Value::Secluded(0), Value::Secluded(2)),
+            Domain::Domain(Value::Secluded(5), Value::Included(9)),
+            Domain::Domain(Value::Included(50), Value::Infinite),
+        ]);
+        assert_eq!(
+            domain.to_sql_predicate("x").unwrap(),
+            "x < -10 OR x = 1 OR (x > 5 AND x <= 9) OR x >= 50"
+        );
+    }
+
+    #[test]
+    fn test_empty_and_full() {
+        assert_eq!(Domain::None.to_sql_predicate("x").unwrap(), "1=0");
+        assert_eq!(Domain::between(3, 2).to_sql_predicate("x").unwrap(), "1=0");
+        assert_eq!(Domain::new().to_sql_predicate("x").unwrap(), "1=1");
+
+        let full = Domain::Union(vec![Domain::at_most(0), Domain::at_least(1)]);
+        assert_eq!(full.to_sql_predicate("x").unwrap(), "1=1");
+        assert_eq!(full.to_sql_predicate_params("x").unwrap(), ("1=1".to_string(), vec![]));
+    }
+
+    #[test]
+    fn test_identifiers() {
+        let domain = Domain::between(1, 2);
+        assert!(domain.to_sql_predicate("_col1").is_ok());
+        assert!(domain.to_sql_predicate("db.orders.total").is_ok());
+
+        for column in &["", "1col", "a b", "a.", ".a", "\"quoted\"", "a-b", "x OR 1=1", "prïce"] {
+            let error = domain.to_sql_predicate(column).unwrap_err();
+            assert_eq!(error.identifier(), *column);
+        }
+        assert!(Domain::None.to_sql_predicate_params("--").is_err());
+    }
+
+    #[test]
+    fn test_params() {
+        let domain = Domain::Union(vec![
+            Domain::between(20, 30),
+            Domain::Domain(Value::Included(5), Value::Secluded(10)),
+            Domain::between(40, 40),
+        ]);
+        let (sql, binds) = domain.to_sql_predicate_params("price").unwrap();
+        assert_eq!(sql, "(price >= ? AND price < ?) OR price BETWEEN ? AND ? OR price = ?");
+        assert_eq!(binds, vec![5, 10, 20, 30, 40]);
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use crate::{Constraint, Domain};
+
+/// Checks whether `name` matches `pattern`, where `*` stands for any
+/// run of characters, possibly empty, and every other character for
+/// itself
+fn matches(pattern: &str, name: &str) -> bool {
+    let (pattern, name): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    // Position in the pattern after the last `*` and the name position it
+    // was matched up to, for backtracking
+    let (mut p, mut n, mut star) = (0, 0, None);
+
+    while n < name.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p + 1, n));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == name[n] {
+            p += 1;
+            n += 1;
+        } else if let Some((after, matched)) = star {
+            p = after;
+            n = matched + 1;
+            star = Some((after, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Domains of named variables, kept in insertion order so every
+/// iteration, and so every solver run over the store, is reproducible
+/// ```
+/// use dynamic_domain::{Constraint, Domain, DomainStore};
+/// let mut store = DomainStore::new();
+/// store.insert("x1", Domain::between(0, 9));
+/// store.insert("x2", Domain::between(5, 6));
+/// store.insert("y", Domain::between(0, 1));
+/// assert_eq!(store.intersect_all_with("x*", &Constraint::Ne(5)), 2);
+/// assert_eq!(store.get("x2").unwrap().clone().repr(), "(5;6]");
+/// assert_eq!(store.total_search_space(), Some(9 * 1 * 2));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DomainStore {
+    variables: Vec<(String, Domain<i32>)>,
+    positions: HashMap<String, usize>,
+}
+
+impl DomainStore {
+
+    /// Store without variables
+    /// ```
+    /// use dynamic_domain::DomainStore;
+    /// assert!(DomainStore::new().is_empty());
+    /// ```
+    pub fn new() -> Self {
+        DomainStore { variables: Vec::new(), positions: HashMap::new() }
+    }
+
+    /// Sets the domain of `name`, returning the previous one. A variable
+    /// set again keeps its place in the insertion order
+    /// ```
+    /// use dynamic_domain::{Domain, DomainStore};
+    /// let mut store = DomainStore::new();
+    /// assert_eq!(store.insert("x", Domain::between(0, 9)), None);
+    /// assert_eq!(store.insert("x", Domain::between(0, 1)), Some(Domain::between(0, 9)));
+    /// assert_eq!(store.len(), 1);
+    /// ```
+    pub fn insert(&mut self, name: impl Into<String>, domain: Domain<i32>) -> Option<Domain<i32>> {
+        let name = name.into();
+        match self.positions.get(&name) {
+            Some(position) => Some(std::mem::replace(&mut self.variables[*position].1, domain)),
+            None => {
+                self.positions.insert(name.clone(), self.variables.len());
+                self.variables.push((name, domain));
+                None
+            },
+        }
+    }
+
+    /// Domain of `name`, `None` for an unknown variable
+    /// ```
+    /// use dynamic_domain::{Domain, DomainStore};
+    /// let mut store = DomainStore::new();
+    /// store.insert("x", Domain::between(0, 9));
+    /// assert!(store.get("x").is_some() && store.get("y").is_none());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&Domain<i32>> {
+        self.positions.get(name).map(|position| &self.variables[*position].1)
+    }
+
+    /// Number of variables
+    /// ```
+    /// use dynamic_domain::{Domain, DomainStore};
+    /// let mut store = DomainStore::new();
+    /// store.insert("x", Domain::new());
+    /// assert_eq!(store.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.variables.len()
+    }
+
+    /// Checks whether the store has no variables
+    /// ```
+    /// use dynamic_domain::DomainStore;
+    /// assert!(DomainStore::default().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+
+    /// Variables with their domains in insertion order
+    /// ```
+    /// use dynamic_domain::{Domain, DomainStore};
+    /// let mut store = DomainStore::new();
+    /// store.insert("b", Domain::between(0, 1));
+    /// store.insert("a", Domain::between(2, 3));
+    /// let names: Vec<&str> = store.iter().map(|(name, _)| name).collect();
+    /// assert_eq!(names, vec!["b", "a"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Domain<i32>)> + '_ {
+        self.variables.iter().map(|(name, domain)| (name.as_str(), domain))
+    }
+
+    /// Applies `constraint` to every variable whose name matches
+    /// `name_pattern`, where `*` stands for any run of characters.
+    /// Returns the number of matching variables
+    /// ```
+    /// use dynamic_domain::{Constraint, Domain, DomainStore, Value};
+    /// let mut store = DomainStore::new();
+    /// store.insert("row1.col1", Domain::between(1, 9));
+    /// store.insert("row1.col2", Domain::between(1, 9));
+    /// store.insert("row2.col1", Domain::between(1, 9));
+    /// assert_eq!(store.intersect_all_with("*.col1", &Constraint::Lt(Value::Included(3))), 2);
+    /// assert_eq!(store.get("row1.col2"), Some(&Domain::between(1, 9)));
+    /// ```
+    pub fn intersect_all_with(&mut self, name_pattern: &str, constraint: &Constraint) -> usize {
+        let mut matched = 0;
+        for (name, domain) in self.variables.iter_mut() {
+            if matches(name_pattern, name) {
+                *domain = constraint.apply(domain);
+                matched += 1;
+            }
+        }
+        matched
+    }
+
+    /// Variables left without a value, in insertion order
+    /// ```
+    /// use dynamic_domain::{Domain, DomainStore};
+    /// let mut store = DomainStore::new();
+    /// store.insert("x", Domain::between(5, 1));
+    /// store.insert("y", Domain::between(1, 5));
+    /// store.insert("z", Domain::None);
+    /// assert_eq!(store.failed_vars(), vec!["x", "z"]);
+    /// ```
+    pub fn failed_vars(&self) -> Vec<&str> {
+        self.iter().filter(|(_, domain)| domain.is_empty()).map(|(name, _)| name).collect()
+    }
+
+    /// Number of assignments of a value to every variable, the product
+    /// of the cardinalities. 0 as soon as a variable failed, otherwise
+    /// `None` when a domain is unbounded or the product overflows `u128`.
+    /// An empty store has the single empty assignment
+    /// ```
+    /// use dynamic_domain::{Domain, DomainStore};
+    /// let mut store = DomainStore::new();
+    /// assert_eq!(store.total_search_space(), Some(1));
+    /// store.insert("x", Domain::between(1, 3));
+    /// store.insert("y", Domain::between(1, 4));
+    /// assert_eq!(store.total_search_space(), Some(12));
+    /// store.insert("z", Domain::at_least(0));
+    /// assert_eq!(store.total_search_space(), None);
+    /// store.insert("w", Domain::None);
+    /// assert_eq!(store.total_search_space(), Some(0));
+    /// ```
+    pub fn total_search_space(&self) -> Option<u128> {
+        if self.variables.iter().any(|(_, domain)| domain.is_empty()) {
+            return Some(0);
+        }
+        self.variables
+            .iter()
+            .try_fold(1u128, |total, (_, domain)| total.checked_mul(domain.cardinality()? as u128))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Constraint, Domain, DomainStore, Value};
+
+    fn dump(store: &DomainStore) -> String {
+        store
+            .iter()
+            .map(|(name, domain)| format!("{}={}", name, domain.clone().repr()))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Same sequence of calls on a store of `n` variables
+    fn run(n: i32) -> (String, Vec<String>, Option<u128>) {
+        let mut store = DomainStore::new();
+        for i in (0..n).rev() {
+            store.insert(format!("v{}", i), Domain::between(0, i));
+        }
+        store.intersect_all_with("v1*", &Constraint::Gt(Value::Secluded(0)));
+        store.intersect_all_with("*5", &Constraint::Ne(3));
+        store.intersect_all_with("v*", &Constraint::OutsideOf(Domain::between(4, 6)));
+        store.intersect_all_with("v1", &Constraint::Ne(1));
+        store.insert("v3", Domain::between(7, 9));
+        let failed = store.failed_vars().into_iter().map(String::from).collect();
+        (dump(&store), failed, store.total_search_space())
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let first = run(60);
+        assert_eq!(first, run(60));
+        assert!(first.0.starts_with("v59=[0;4)⋃(6;59] v58="));
+        assert_eq!(first.1, vec!["v1"]);
+        assert_eq!(first.2, Some(0));
+        assert!(first.0.ends_with(" v4=[0;4) v3=[7;9] v2=[0;2] v1=∅ v0=[0;0]"), "{}", first.0);
+
+        // A new variable goes last, so the dump depends on the call order only
+        assert_eq!(run(1), ("v0=[0;0] v3=[7;9]".to_string(), vec![], Some(3)));
+    }
+
+    #[test]
+    fn test_matches() {
+        let cases = [
+            ("*", "", true),
+            ("", "", true),
+            ("", "x", false),
+            ("x*", "x", true),
+            ("x*", "yx", false),
+            ("*x", "yx", true),
+            ("a*b*c", "aXbYbZc", true),
+            ("a*b*c", "aXbYbZ", false),
+            ("**", "anything", true),
+            ("a?", "a?", true),
+            ("a?", "ab", false),
+            ("*ab", "aab", true),
+        ];
+        for (pattern, name, expected) in cases.iter() {
+            assert_eq!(super::matches(pattern, name), *expected, "{} {}", pattern, name);
+        }
+    }
+
+    #[test]
+    fn test_search_space_limits() {
+        let mut store = DomainStore::new();
+        for i in 0..3 {
+            store.insert(format!("full{}", i), Domain::between(i32::MIN, i32::MAX));
+        }
+        assert_eq!(store.total_search_space(), Some(1 << 96));
+        store.insert("full3", Domain::between(i32::MIN, i32::MAX));
+        assert_eq!(store.total_search_space(), None);
+        store.insert("unbounded", Domain::at_most(0));
+        store.insert("failed", Domain::between(1, 0));
+        assert_eq!(store.total_search_space(), Some(0));
+        assert_eq!(store.failed_vars(), vec!["failed"]);
+    }
+}
@@ -0,0 +1,162 @@
+//! Structural comparison, telling domains apart by how they are written
+//! rather than by the values they admit. `==` stays set equality, these
+//! are for callers keying caches on the exact representation.
+//!
+//! `Clone`, the parser and the builders moving borders in place (`gt`,
+//! `lt`, `tighten`) keep the structure they are given. `normalize` and the
+//! set operations returning `NormalizedDomain` flatten, sort and merge
+//! members, so two spellings of the same set usually agree structurally
+//! once normalized. Border notation is kept, though: `[5;9]` and `[5;10)`
+//! stay structurally different through `normalize`
+
+use std::hash::{Hash, Hasher};
+
+use crate::{Domain, Value};
+
+impl<T: PartialEq> Domain<T> {
+
+    /// Whether both domains have the same variants, members in the same
+    /// order and borders written the same way. Unlike `==` it tells
+    /// `[5;9]` and `[5;10)` apart, as well as a union of one member and
+    /// the member itself
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let closed = Domain::between(5, 9);
+    /// let half_open = Domain::Domain(Value::Included(5), Value::Secluded(10));
+    /// assert_eq!(closed, half_open);
+    /// assert!(!closed.structurally_equal(&half_open));
+    /// assert!(closed.structurally_equal(&closed.clone()));
+    /// ```
+    pub fn structurally_equal(&self, other: &Domain<T>) -> bool {
+        match (self, other) {
+            (Domain::Union(a), Domain::Union(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.structurally_equal(b))
+            },
+            (Domain::Domain(al, ar), Domain::Domain(bl, br)) => al == bl && ar == br,
+            (Domain::None, Domain::None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Hash> Domain<T> {
+
+    /// Feeds the exact representation into `state`, consistent with
+    /// `structurally_equal`: structurally equal domains hash the same.
+    /// Domains equal under `==` only may not
+    /// ```
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    /// use dynamic_domain::Domain;
+    /// let hash = |domain: &Domain<i32>| {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     domain.structural_hash(&mut hasher);
+    ///     hasher.finish()
+    /// };
+    /// let domain = Domain::Union(vec![Domain::between(0, 3), Domain::at_least(10)]);
+    /// assert_eq!(hash(&domain), hash(&domain.clone()));
+    /// ```
+    pub fn structural_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Domain::Union(domains) => {
+                state.write_u8(0);
+                state.write_usize(domains.len());
+                for domain in domains {
+                    domain.structural_hash(state);
+                }
+            },
+            Domain::Domain(l, r) => {
+                state.write_u8(1);
+                hash_value(l, state);
+                hash_value(r, state);
+            },
+            Domain::None => state.write_u8(2),
+        }
+    }
+}
+
+fn hash_value<T: Hash, H: Hasher>(value: &Value<T>, state: &mut H) {
+    match value {
+        Value::Included(point) => {
+            state.write_u8(0);
+            point.hash(state);
+        },
+        Value::Secluded(point) => {
+            state.write_u8(1);
+            point.hash(state);
+        },
+        Value::Infinite => state.write_u8(2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+
+    use proptest::prelude::*;
+
+    use crate::algebra::border_at;
+    use crate::{Domain, Value};
+
+    fn hash(domain: &Domain<i32>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        domain.structural_hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn member() -> impl Strategy<Value = Domain<i32>> {
+        (border_at(-5..5), border_at(-5..5)).prop_map(|(l, r)| Domain::Domain(l, r))
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        prop_oneof![
+            1 => Just(Domain::None),
+            2 => member(),
+            3 => prop::collection::vec(member(), 0..4).prop_map(Domain::Union),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_structure_implies_equality(a in domain(), b in domain()) {
+            prop_assert!(a.structurally_equal(&a.clone()));
+            prop_assert_eq!(hash(&a), hash(&a.clone()));
+            if a.structurally_equal(&b) {
+                prop_assert_eq!(&a, &b);
+                prop_assert_eq!(hash(&a), hash(&b));
+            }
+            prop_assert!(a.normalize().structurally_equal(&a.normalize().into_inner().normalize()));
+        }
+    }
+
+    #[test]
+    fn test_notation_disagrees() {
+        let closed = Domain::between(5, 9);
+        let half_open = Domain::Domain(Value::Included(5), Value::Secluded(10));
+        assert_eq!(closed, half_open);
+        assert!(!closed.structurally_equal(&half_open));
+        assert_ne!(hash(&closed), hash(&half_open));
+
+        // normalize keeps the borders as written
+        assert!(!closed.normalize().structurally_equal(&half_open.normalize()));
+    }
+
+    #[test]
+    fn test_normalize_agrees() {
+        let scattered = Domain::Union(vec![
+            Domain::between(20, 29),
+            Domain::None,
+            Domain::Union(vec![Domain::between(0, 4)]),
+            Domain::between(5, 9),
+        ]);
+        let tidy = Domain::Union(vec![Domain::between(0, 9), Domain::between(20, 29)]);
+        assert_eq!(scattered, tidy);
+        assert!(!scattered.structurally_equal(&tidy));
+        assert!(scattered.normalize().structurally_equal(&tidy));
+        assert_eq!(hash(&scattered.normalize()), hash(&tidy));
+
+        assert!(!Domain::Union(vec![]).structurally_equal(&Domain::<i32>::None));
+        assert!(Domain::Union(vec![]).normalize().structurally_equal(&Domain::None));
+    }
+}
@@ -0,0 +1,414 @@
+use std::convert::Infallible;
+
+use crate::util::{self, first_admitted, last_admitted, NEG_INF, POS_INF};
+use crate::{Domain, Interval, NormalizedDomain, Value};
+
+/// Interval of one of the swept domains, borrowed from it
+type Border<'a> = (&'a Value<i32>, &'a Value<i32>, usize);
+
+/// Bits of an event holding the index of its member, the rest holds the
+/// point and whether it starts or ends the member
+const INDEX_BITS: u32 = 30;
+
+/// Borrows the members of `domain` admitting some `i32`, tagged with `source`
+fn borrow_members<'a>(domain: &'a Domain<i32>, source: usize, members: &mut Vec<Border<'a>>) {
+    match domain {
+        Domain::Union(domains) => {
+            for domain in domains {
+                borrow_members(domain, source, members);
+            }
+        },
+        Domain::Domain(l, r) => {
+            let (first, last) = util::clipped_run(l, r);
+            if first <= last {
+                members.push((l, r, source));
+            }
+        },
+        Domain::None => (),
+    }
+}
+
+/// Sweeps the borders of all `domains` at once and keeps the values
+/// admitted by at least `threshold` of them. Members of the same domain
+/// may overlap, a domain counts once however many of them cover a value
+fn covered(domains: &[&Domain<i32>], threshold: usize) -> NormalizedDomain<i32> {
+    let mut members = Vec::new();
+    for (source, domain) in domains.iter().enumerate() {
+        borrow_members(domain, source, &mut members);
+    }
+    if members.len() >= 1 << INDEX_BITS {
+        // Too many members to index them in an event, fold pairwise instead
+        return if threshold == 1 {
+            domains.iter().fold(Domain::None.normalize(), |all, domain| all.union(domain))
+        } else {
+            domains.iter().fold(Domain::new().normalize(), |all, domain| all.intersect(domain))
+        };
+    }
+
+    // Each event packs the offset of the clipped point from `i32::MIN`,
+    // an end flag and the index of the member. Ends are inclusive, so a
+    // start sorts before an end on the same point
+    let offset = |point: i64| ((point - i32::MIN as i64) as u64) << (INDEX_BITS + 1);
+    let mut events = Vec::with_capacity(members.len() * 2);
+    for (i, (l, r, _)) in members.iter().enumerate() {
+        let (first, last) = util::clipped_run(l, r);
+        events.push(offset(first) | i as u64);
+        events.push(offset(last) | 1 << INDEX_BITS | i as u64);
+    }
+    events.sort_unstable();
+
+    let mut depths = vec![0usize; domains.len()];
+    let mut depth = 0;
+    let mut start = None;
+    let mut result = Vec::new();
+
+    for event in events {
+        let (l, r, source) = members[(event & ((1 << INDEX_BITS) - 1)) as usize];
+        if event & 1 << INDEX_BITS == 0 {
+            depths[source] += 1;
+            if depths[source] == 1 {
+                depth += 1;
+                if depth == threshold {
+                    start = Some(l);
+                }
+            }
+        } else {
+            if depths[source] == 1 {
+                if depth == threshold {
+                    if let Some(start) = start.take() {
+                        result.push((start.clone(), r.clone()));
+                    }
+                }
+                depth -= 1;
+            }
+            depths[source] -= 1;
+        }
+    }
+
+    // Pieces closing and opening on neighbouring points get merged here
+    NormalizedDomain::from_members(result)
+}
+
+/// Piece of the `i32` line between two neighbouring borders of the
+/// domains `Domain::merge_join` sweeps, with the domains admitting it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Segment {
+    /// Values of the piece, at least one
+    pub range: Interval<i32>,
+    /// Whether the domain `merge_join` is called on admits them
+    pub in_left: bool,
+    /// Whether the other domain admits them
+    pub in_right: bool,
+}
+
+/// Place where a normalized domain starts or stops admitting values: the
+/// first value after it in the closed `i64` form, the ending border of the
+/// piece before and the starting border of the piece after, `None` on the
+/// far side of an infinite border
+type Cut = (i64, Option<Value<i32>>, Option<Value<i32>>);
+
+/// The cuts of the normalized `domain`, ascending
+fn cuts(domain: &Domain<i32>) -> Vec<Cut> {
+    // The border of the neighbouring piece on the same cut
+    let flip = |value: &Value<i32>| match value {
+        Value::Included(i) => Value::Secluded(*i),
+        Value::Secluded(i) => Value::Included(*i),
+        Value::Infinite => Value::Infinite,
+    };
+
+    let mut cuts = Vec::new();
+    for (l, r) in util::normalized(domain) {
+        cuts.push(match l {
+            Value::Infinite => (NEG_INF, None, Some(l)),
+            _ => (first_admitted(&l), Some(flip(&l)), Some(l)),
+        });
+        cuts.push(match r {
+            Value::Infinite => (POS_INF, Some(r), None),
+            _ => (last_admitted(&r) + 1, Some(r.clone()), Some(flip(&r))),
+        });
+    }
+    cuts
+}
+
+/// Border of a cut both domains have, the included spelling if either
+/// has it
+fn shared(a: Option<Value<i32>>, b: Option<Value<i32>>) -> Option<Value<i32>> {
+    match (a, b) {
+        (Some(Value::Secluded(_)), Some(b @ Value::Included(_))) => Some(b),
+        (a, b) => a.or(b),
+    }
+}
+
+/// `Domain::merge_join` stopping at the first error of `f`
+pub(crate) fn try_merge_join<E>(a: &Domain<i32>, b: &Domain<i32>, mut f: impl FnMut(Segment) -> Result<(), E>) -> Result<(), E> {
+    let (a, b) = (cuts(a), cuts(b));
+    let (mut i, mut j) = (0, 0);
+    let (mut in_left, mut in_right) = (false, false);
+    let mut lower = Some(Value::Infinite);
+
+    let mut emit = |lower: Option<Value<i32>>, upper: Option<Value<i32>>, in_left: bool, in_right: bool| {
+        if let (Some(lo), Some(hi)) = (lower, upper) {
+            // Only pieces past the i32 limits, like (2147483647;∞), admit nothing
+            let (first, last) = util::clipped_run(&lo, &hi);
+            if first <= last {
+                return f(Segment { range: Interval::new(lo, hi), in_left, in_right });
+            }
+        }
+        Ok(())
+    };
+
+    while let Some(at) = a.get(i).into_iter().chain(b.get(j)).map(|cut| cut.0).min() {
+        let (mut upper, mut next) = (None, None);
+        let (was_left, was_right) = (in_left, in_right);
+        if a.get(i).map(|cut| cut.0) == Some(at) {
+            upper = a[i].1.clone();
+            next = a[i].2.clone();
+            in_left = !in_left;
+            i += 1;
+        }
+        if b.get(j).map(|cut| cut.0) == Some(at) {
+            upper = shared(upper, b[j].1.clone());
+            next = shared(next, b[j].2.clone());
+            in_right = !in_right;
+            j += 1;
+        }
+        emit(lower, upper, was_left, was_right)?;
+        lower = next;
+    }
+    emit(lower, Some(Value::Infinite), in_left, in_right)
+}
+
+impl Domain<i32> {
+
+    /// Hands `f` the pieces the borders of both normalized domains cut the
+    /// `i32` line into, in ascending order, each with the domains
+    /// admitting it. The pieces cover every `i32` once, the ones neither
+    /// domain admits included, and neighbouring pieces differ in at least
+    /// one flag. Every border is spelled as in the domain it comes from,
+    /// with the piece on the other side of it getting the flipped border:
+    /// `[5` ends the piece before it with `5)`. Where both domains have a
+    /// border between the same two neighbouring values, like `(4` and
+    /// `[5`, the included spelling wins on both pieces, `4]` and `[5`.
+    /// `intersect`, `union` and `difference` keep the pieces of one kind
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let a = Domain::between(0, 9);
+    /// let b = Domain::Domain(Value::Secluded(4), Value::Infinite);
+    /// let mut pieces = Vec::new();
+    /// a.merge_join(&b, |segment| pieces.push((segment.range.repr(), segment.in_left, segment.in_right)));
+    /// assert_eq!(pieces, vec![
+    ///     ("(-∞;0)".to_string(), false, false),
+    ///     ("[0;4]".to_string(), true, false),
+    ///     ("(4;9]".to_string(), true, true),
+    ///     ("(9;∞)".to_string(), false, true),
+    /// ]);
+    /// ```
+    pub fn merge_join(&self, other: &Domain<i32>, mut f: impl FnMut(Segment)) {
+        try_merge_join(self, other, |segment| {
+            f(segment);
+            Ok::<(), Infallible>(())
+        })
+        .unwrap_or_else(|never| match never {})
+    }
+
+    /// Set of values admitted by every one of `domains` in a single sweep
+    /// over all borders instead of folding `intersect`. Nothing to intersect
+    /// leaves the full domain, the identity of intersection
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domains = vec![Domain::between(0, 10), Domain::between(5, 20), Domain::at_most(8)];
+    /// assert_eq!(Domain::intersect_many(&domains).repr(), "[5;8]");
+    /// assert_eq!(Domain::intersect_many(&[]), Domain::new());
+    /// ```
+    pub fn intersect_many<'a>(domains: impl IntoIterator<Item = &'a Domain<i32>>) -> NormalizedDomain<i32> {
+        let domains: Vec<&Domain<i32>> = domains.into_iter().collect();
+        if domains.is_empty() {
+            return Domain::new().normalize();
+        }
+        covered(&domains, domains.len())
+    }
+
+    /// Set of values admitted by any of `domains` in a single sweep over
+    /// all borders instead of folding `union`. Nothing to unite leaves the
+    /// empty domain, the identity of union
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domains = vec![Domain::between(0, 3), Domain::between(10, 20), Domain::between(4, 5)];
+    /// assert_eq!(Domain::union_many(&domains).repr(), "[0;5]⋃[10;20]");
+    /// assert_eq!(Domain::union_many(&[]), Domain::None);
+    /// ```
+    pub fn union_many<'a>(domains: impl IntoIterator<Item = &'a Domain<i32>>) -> NormalizedDomain<i32> {
+        let domains: Vec<&Domain<i32>> = domains.into_iter().collect();
+        covered(&domains, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::util;
+    use crate::{Domain, NormalizedDomain, Segment, Value};
+
+    /// The pieces `merge_join` hands out, in order
+    fn segments(a: &Domain<i32>, b: &Domain<i32>) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        a.merge_join(b, |segment| segments.push(segment));
+        segments
+    }
+
+    /// Pieces written as `repr` and the two flags as `L`, `R` or `-`
+    fn pieces(a: &Domain<i32>, b: &Domain<i32>) -> Vec<String> {
+        let flag = |on: bool, name: &str| if on { name.to_string() } else { "-".to_string() };
+        segments(a, b)
+            .iter()
+            .map(|segment| format!("{}{}{}", segment.range.repr(), flag(segment.in_left, "L"), flag(segment.in_right, "R")))
+            .collect()
+    }
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            1 => Just(Value::Infinite),
+            3 => (-100..100).prop_map(Value::Included),
+            3 => (-100..100).prop_map(Value::Secluded),
+            1 => Just(Value::Secluded(i32::MAX)),
+            1 => Just(Value::Secluded(i32::MIN)),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..5)
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_fold(domains in prop::collection::vec(domain(), 0..6)) {
+            let intersection = Domain::intersect_many(&domains);
+            let folded = domains
+                .iter()
+                .fold(Domain::new(), |all, domain| all.intersect(domain).into_inner());
+            prop_assert_eq!(&intersection, &folded);
+            prop_assert!(NormalizedDomain::try_from(intersection.into_inner()).is_ok());
+
+            let union = Domain::union_many(&domains);
+            let folded = domains
+                .iter()
+                .fold(Domain::None, |all, domain| all.union(domain).into_inner());
+            prop_assert_eq!(&union, &folded);
+            prop_assert!(NormalizedDomain::try_from(union.into_inner()).is_ok());
+        }
+
+        #[test]
+        fn test_segments_partition(a in domain(), b in domain()) {
+            let joined = segments(&a, &b);
+            let runs: Vec<(i64, i64)> = joined
+                .iter()
+                .map(|segment| util::clipped_run(segment.range.lo(), segment.range.hi()))
+                .collect();
+
+            // Cover every i32 once, in ascending order
+            prop_assert_eq!(runs[0].0, i32::MIN as i64);
+            prop_assert_eq!(runs[runs.len() - 1].1, i32::MAX as i64);
+            for pair in runs.windows(2) {
+                prop_assert_eq!(pair[0].1 + 1, pair[1].0);
+            }
+            for pair in joined.windows(2) {
+                prop_assert!((pair[0].in_left, pair[0].in_right) != (pair[1].in_left, pair[1].in_right));
+            }
+            for (segment, (first, last)) in joined.iter().zip(runs) {
+                prop_assert!(first <= last);
+                for point in [first, last] {
+                    prop_assert_eq!(a.contains(point as i32), segment.in_left);
+                    prop_assert_eq!(b.contains(point as i32), segment.in_right);
+                }
+            }
+
+            // Swapping the domains swaps the flags only
+            let swapped: Vec<(bool, bool)> = segments(&b, &a).iter().map(|segment| (segment.in_right, segment.in_left)).collect();
+            let flags: Vec<(bool, bool)> = joined.iter().map(|segment| (segment.in_left, segment.in_right)).collect();
+            prop_assert_eq!(swapped, flags);
+        }
+
+        #[test]
+        fn test_set_operations_agree(a in domain(), b in domain()) {
+            let mut xor = Vec::new();
+            a.merge_join(&b, |segment| if segment.in_left != segment.in_right {
+                xor.push(Domain::from(segment.range));
+            });
+            let xor = Domain::Union(xor);
+            prop_assert_eq!(&xor, &a.union(&b).difference(&a.intersect(&b)).into_inner());
+            prop_assert_eq!(&xor, &Domain::Union(vec![a.difference(&b).into_inner(), b.difference(&a).into_inner()]));
+            for result in [a.union(&b), a.intersect(&b), a.difference(&b)] {
+                prop_assert!(NormalizedDomain::try_from(result.into_inner()).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_coincident_borders() {
+        // (4 and [5 start both domains between 4 and 5, 9] and 10) end both
+        let a = Domain::Domain(Value::Secluded(4), Value::Included(9));
+        let b = Domain::Domain(Value::Included(5), Value::Secluded(10));
+        assert_eq!(pieces(&a, &b), vec!["(-∞;4]--", "[5;9]LR", "[10;∞)--"]);
+        assert_eq!(pieces(&b, &a), vec!["(-∞;4]--", "[5;9]LR", "[10;∞)--"]);
+        assert_eq!(a.union(&b).repr(), "[5;9]");
+        assert_eq!(b.intersect(&a).repr(), "[5;9]");
+
+        // One ends where the other starts, the same spelling on both sides
+        let low = Domain::Domain(Value::Infinite, Value::Secluded(5));
+        let high = Domain::Domain(Value::Secluded(4), Value::Infinite);
+        assert_eq!(pieces(&low, &high), vec!["(-∞;4]L-", "[5;∞)-R"]);
+        assert_eq!(low.union(&high).repr(), "(-∞;∞)");
+        assert!(low.intersect(&high).is_empty());
+        assert_eq!(low.difference(&high).repr(), "(-∞;4]");
+
+        // Borders of one domain only keep their spelling
+        let open = Domain::Domain(Value::Secluded(0), Value::Secluded(3));
+        let closed = Domain::between(3, 6);
+        assert_eq!(pieces(&open, &closed), vec!["(-∞;0]--", "(0;3)L-", "[3;6]-R", "(6;∞)--"]);
+        assert_eq!(open.union(&closed).repr(), "(0;6]");
+    }
+
+    #[test]
+    fn test_segment_limits() {
+        assert_eq!(pieces(&Domain::None, &Domain::Union(vec![])), vec!["(-∞;∞)--"]);
+        assert_eq!(pieces(&Domain::new(), &Domain::between(i32::MIN, i32::MAX)), vec!["[-2147483648;2147483647]LR"]);
+
+        // (-∞;-2147483648) and (2147483647;∞) admit nothing and are skipped
+        let edges = Domain::Union(vec![Domain::between(i32::MIN, i32::MIN), Domain::at_least(i32::MAX)]);
+        assert_eq!(pieces(&edges, &Domain::at_most(i32::MIN)), vec![
+            "[-2147483648;-2147483648]LR",
+            "(-2147483648;2147483647)--",
+            "[2147483647;∞)L-",
+        ]);
+        let beyond = Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite);
+        assert_eq!(pieces(&beyond, &Domain::new()), vec!["(-∞;∞)-R"]);
+    }
+
+    #[test]
+    fn test_keeps_notation() {
+        let domains = vec![
+            Domain::Domain(Value::Secluded(0), Value::Infinite),
+            Domain::Domain(Value::Infinite, Value::Secluded(10)),
+        ];
+        assert_eq!(Domain::intersect_many(&domains).repr(), "(0;10)");
+        assert_eq!(Domain::union_many(&domains).repr(), "(-∞;∞)");
+    }
+
+    #[test]
+    fn test_limits() {
+        let domains = vec![Domain::new(), Domain::at_least(i32::MAX)];
+        assert_eq!(Domain::intersect_many(&domains).repr(), "[2147483647;∞)");
+
+        let edges = vec![Domain::between(i32::MIN, i32::MIN), Domain::between(i32::MAX, i32::MAX)];
+        assert_eq!(Domain::intersect_many(&edges), Domain::None);
+        assert_eq!(Domain::union_many(&edges).cardinality(), Some(2));
+
+        let beyond = vec![Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite)];
+        assert_eq!(Domain::union_many(&beyond), Domain::None);
+        assert_eq!(Domain::intersect_many(&beyond), Domain::None);
+    }
+}
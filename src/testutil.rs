@@ -0,0 +1,190 @@
+//! Assertions for tests of code working with domains, behind the
+//! `test-util` feature. Failures print the reprs of both sides and the
+//! values only one of them admits, rather than two `Debug` trees
+//! ```
+//! use dynamic_domain::{assert_contains_exactly, assert_domain_eq, Domain};
+//! let domain = Domain::Union(vec![Domain::between(1, 3), Domain::between(4, 5)]);
+//! assert_domain_eq!(domain, Domain::between(1, 5));
+//! assert_contains_exactly!(domain, [1, 2, 3, 4, 5]);
+//! ```
+
+use crate::Domain;
+
+/// The first `cap` admitted values, in ascending order and each once,
+/// all of them for a domain of at most `cap` values
+/// ```
+/// use dynamic_domain::testutil::collect_ordered;
+/// use dynamic_domain::Domain;
+/// let domain = Domain::Union(vec![Domain::between(7, 8), Domain::between(0, 2), Domain::between(1, 3)]);
+/// assert_eq!(collect_ordered(&domain, 100), vec![0, 1, 2, 3, 7, 8]);
+/// assert_eq!(collect_ordered(&Domain::at_least(0), 3), vec![0, 1, 2]);
+/// ```
+pub fn collect_ordered(domain: &Domain<i32>, cap: usize) -> Vec<i32> {
+    domain.iter().take(cap).collect()
+}
+
+/// The failure message of `assert_domain_eq!`, `None` when both admit
+/// the same values
+/// ```
+/// use dynamic_domain::testutil::domain_mismatch;
+/// use dynamic_domain::Domain;
+/// assert_eq!(domain_mismatch(&Domain::between(0, 3), &Domain::between(0, 3)), None);
+/// assert_eq!(
+///     domain_mismatch(&Domain::between(0, 5), &Domain::between(3, 9)).unwrap(),
+///     "domains differ\n      left: [0;5]\n     right: [3;9]\n only left: [0;3)\nonly right: (5;9]"
+/// );
+/// ```
+pub fn domain_mismatch(left: &Domain<i32>, right: &Domain<i32>) -> Option<String> {
+    let only_left = left.difference(right);
+    let only_right = right.difference(left);
+    if only_left.is_empty() && only_right.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "domains differ\n      left: {}\n     right: {}\n only left: {}\nonly right: {}",
+        left.clone().repr(),
+        right.clone().repr(),
+        only_left.repr(),
+        only_right.repr(),
+    ))
+}
+
+/// The failure message of `assert_contains_exactly!`, `None` when
+/// `domain` admits `values` and nothing else. Every value of the hull of
+/// both is checked, as a set difference rather than one by one, so it is
+/// as quick for `[i32::MIN, i32::MAX]` as for `[1, 2]`
+/// ```
+/// use dynamic_domain::testutil::exact_mismatch;
+/// use dynamic_domain::Domain;
+/// assert_eq!(exact_mismatch(&Domain::between(1, 2), &[2, 1, 2]), None);
+/// assert_eq!(
+///     exact_mismatch(&Domain::between(1, 4), &[1, 2, 7]).unwrap(),
+///     "domain does not admit exactly the values\n       domain: [1;4]\n  not listed: (2;4]\nnot admitted: [7;7]"
+/// );
+/// ```
+pub fn exact_mismatch(domain: &Domain<i32>, values: &[i32]) -> Option<String> {
+    let expected: Domain<i32> = values.iter().copied().collect();
+    let not_listed = domain.difference(&expected);
+    let not_admitted = expected.difference(domain);
+    if not_listed.is_empty() && not_admitted.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "domain does not admit exactly the values\n       domain: {}\n  not listed: {}\nnot admitted: {}",
+        domain.clone().repr(),
+        not_listed.repr(),
+        not_admitted.repr(),
+    ))
+}
+
+/// Asserts two `Domain<i32>` admit the same values, whatever their
+/// members. On failure prints both reprs and the values only one of
+/// them admits, then the optional message like `assert_eq!`
+/// ```
+/// use dynamic_domain::{assert_domain_eq, Domain, Value};
+/// let open = Domain::Domain(Value::Secluded(0), Value::Secluded(4));
+/// assert_domain_eq!(open, Domain::between(1, 3));
+/// assert_domain_eq!(Domain::between(5, 1), Domain::None, "empty for {}", "reversed borders");
+/// ```
+/// ```should_panic
+/// use dynamic_domain::{assert_domain_eq, Domain};
+/// assert_domain_eq!(Domain::between(0, 5), Domain::between(0, 4));
+/// ```
+#[macro_export]
+macro_rules! assert_domain_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        if let Some(mismatch) = $crate::testutil::domain_mismatch(&$left, &$right) {
+            panic!("{}", mismatch);
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        if let Some(mismatch) = $crate::testutil::domain_mismatch(&$left, &$right) {
+            panic!("{}\n{}", mismatch, format_args!($($arg)+));
+        }
+    };
+}
+
+/// Asserts a `Domain<i32>` admits the listed values and no other one,
+/// in any order and with repeats. On failure prints the repr of the
+/// domain, the values it admits which are not listed and the listed ones
+/// it does not admit, then the optional message like `assert_eq!`
+/// ```
+/// use dynamic_domain::{assert_contains_exactly, Domain, Value};
+/// let domain = Domain::Union(vec![Domain::between(-1, 0), Domain::Domain(Value::Secluded(8), Value::Included(9))]);
+/// assert_contains_exactly!(domain, [9, -1, 0]);
+/// assert_contains_exactly!(Domain::None, []);
+/// ```
+/// ```should_panic
+/// use dynamic_domain::{assert_contains_exactly, Domain};
+/// assert_contains_exactly!(Domain::at_least(i32::MAX - 1), [i32::MAX]);
+/// ```
+#[macro_export]
+macro_rules! assert_contains_exactly {
+    ($domain:expr, [$($value:expr),* $(,)?] $(,)?) => {
+        if let Some(mismatch) = $crate::testutil::exact_mismatch(&$domain, &[$($value),*]) {
+            panic!("{}", mismatch);
+        }
+    };
+    ($domain:expr, [$($value:expr),* $(,)?], $($arg:tt)+) => {
+        if let Some(mismatch) = $crate::testutil::exact_mismatch(&$domain, &[$($value),*]) {
+            panic!("{}\n{}", mismatch, format_args!($($arg)+));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use proptest::prelude::*;
+
+    use crate::algebra::flat_domain;
+    use crate::testutil::{collect_ordered, domain_mismatch, exact_mismatch};
+    use crate::{Domain, Value};
+
+    /// Message of the panic `f` raises
+    fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+        let payload = panic::catch_unwind(f).unwrap_err();
+        payload.downcast_ref::<String>().cloned().unwrap_or_default()
+    }
+
+    proptest! {
+        #[test]
+        fn test_mismatch_is_inequality(a in flat_domain(), b in flat_domain()) {
+            prop_assert_eq!(domain_mismatch(&a, &b).is_none(), a == b);
+            let values = collect_ordered(&a, 64);
+            let listed = exact_mismatch(&a, &values).is_none();
+            prop_assert_eq!(listed, collect_ordered(&a, 65).len() <= 64);
+        }
+    }
+
+    #[test]
+    fn test_collect_ordered() {
+        let domain = Domain::Union(vec![Domain::at_least(i32::MAX - 1), Domain::at_most(i32::MIN)]);
+        assert_eq!(collect_ordered(&domain, 10), vec![i32::MIN, i32::MAX - 1, i32::MAX]);
+        assert_eq!(collect_ordered(&domain, 1), vec![i32::MIN]);
+        assert!(collect_ordered(&Domain::new(), 0).is_empty());
+        assert!(collect_ordered(&Domain::between(3, 1), 5).is_empty());
+    }
+
+    #[test]
+    fn test_messages() {
+        let message = panic_message(|| assert_domain_eq!(Domain::between(0, 9), Domain::at_least(5), "step {}", 3));
+        assert_eq!(message, "domains differ\n      left: [0;9]\n     right: [5;∞)\n only left: [0;5)\nonly right: (9;∞)\nstep 3");
+
+        let message = panic_message(|| assert_contains_exactly!(Domain::new(), [0]));
+        assert_eq!(message, "domain does not admit exactly the values\n       domain: (-∞;∞)\n  not listed: (-∞;0)⋃(0;∞)\nnot admitted: ∅");
+
+        let message = panic_message(|| assert_contains_exactly!(Domain::None, [i32::MIN, i32::MAX], "for {:?}", "limits"));
+        assert!(message.ends_with("not admitted: [-2147483648;-2147483648]⋃[2147483647;2147483647]\nfor \"limits\""));
+    }
+
+    #[test]
+    fn test_assertions_pass() {
+        assert_domain_eq!(Domain::at_least(i32::MAX), Domain::between(i32::MAX, i32::MAX));
+        assert_domain_eq!(Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite), Domain::None);
+        assert_domain_eq!(Domain::Union(vec![Domain::between(0, 1), Domain::between(2, 3)]), Domain::between(0, 3),);
+        assert_contains_exactly!(Domain::between(i32::MIN, i32::MIN + 1), [i32::MIN + 1, i32::MIN]);
+        assert_contains_exactly!(Domain::Domain(Value::Secluded(4), Value::Secluded(6)), [5, 5,]);
+    }
+}
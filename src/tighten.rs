@@ -0,0 +1,346 @@
+use crate::util::{self, first_admitted, last_admitted, Member};
+use crate::{Domain, Value};
+
+/// Single restriction applied by `Domain::tighten_all`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Constraint {
+    /// Keeps the values admitted by the border used as a starting border,
+    /// `Gt(Value::Secluded(5))` is `x > 5` and `Gt(Value::Included(5))` is `x >= 5`.
+    /// `Gt(Value::Infinite)` keeps nothing, as `Domain::gt` does
+    Gt(Value<i32>),
+    /// Keeps the values admitted by the border used as an ending border,
+    /// `Lt(Value::Infinite)` keeps nothing like `Domain::lt`
+    Lt(Value<i32>),
+    /// Removes a single value
+    Ne(i32),
+    /// Keeps the values admitted by the domain
+    InsideOf(Domain<i32>),
+    /// Removes the values admitted by the domain
+    OutsideOf(Domain<i32>),
+}
+
+impl Constraint {
+    /// Applies the constraint on its own, `tighten_all` gives the same
+    /// result as applying every constraint in turn
+    /// ```
+    /// use dynamic_domain::{Constraint, Domain, Value};
+    /// let domain = Constraint::Gt(Value::Secluded(5)).apply(&Domain::between(0, 10));
+    /// assert_eq!(domain.repr(), "(5;10]");
+    /// assert_eq!(Constraint::Lt(Value::Infinite).apply(&Domain::between(0, 10)), Domain::None);
+    /// ```
+    pub fn apply(&self, domain: &Domain<i32>) -> Domain<i32> {
+        match self {
+            Constraint::Gt(Value::Infinite) | Constraint::Lt(Value::Infinite) => return Domain::None,
+            Constraint::Gt(value) => domain.intersect(&Domain::Domain(value.clone(), Value::Infinite)),
+            Constraint::Lt(value) => domain.intersect(&Domain::Domain(Value::Infinite, value.clone())),
+            Constraint::Ne(n) => domain.remove_value(*n),
+            Constraint::InsideOf(other) => domain.intersect(other),
+            Constraint::OutsideOf(other) => domain.difference(other),
+        }
+        .into_inner()
+    }
+}
+
+/// Punches a hole at every value of the ascending `holes`
+/// into the normalized `members` in a single pass
+fn punch(members: Vec<Member>, holes: &[i32]) -> Vec<Member> {
+    let mut result = Vec::with_capacity(members.len() + holes.len());
+    let mut next = 0;
+
+    for (l, r) in members {
+        let (first, last) = (first_admitted(&l), last_admitted(&r));
+        while next < holes.len() && (holes[next] as i64) < first {
+            next += 1;
+        }
+
+        let mut start = l;
+        while next < holes.len() && holes[next] as i64 <= last {
+            let hole = holes[next];
+            if first_admitted(&start) < hole as i64 {
+                result.push((start, Value::Secluded(hole)));
+            }
+            start = Value::Secluded(hole);
+            next += 1;
+        }
+
+        if first_admitted(&start) <= last {
+            result.push((start, r));
+        }
+    }
+
+    result
+}
+
+impl Domain<i32> {
+
+    /// Applies all `constraints` at once: the bounds are folded into a single
+    /// pair, the removed values are collected and sorted and the holes are
+    /// punched in one sweep. Same result as applying them one by one
+    /// ```
+    /// use dynamic_domain::{Constraint, Domain, Value};
+    /// let domain = Domain::new().tighten_all(&[
+    ///     Constraint::Gt(Value::Included(0)),
+    ///     Constraint::Lt(Value::Secluded(10)),
+    ///     Constraint::Gt(Value::Secluded(2)),
+    ///     Constraint::Ne(5),
+    ///     Constraint::OutsideOf(Domain::between(7, 8)),
+    /// ]);
+    /// assert_eq!(domain.repr(), "(2;5)⋃(5;7)⋃(8;10)");
+    /// ```
+    pub fn tighten_all(&self, constraints: &[Constraint]) -> Self {
+        let mut lower = Value::Infinite;
+        let mut upper = Value::Infinite;
+        let mut holes = Vec::new();
+        let mut inside = Vec::new();
+        let mut outside = Vec::new();
+
+        for constraint in constraints {
+            match constraint {
+                // Keeps nothing, whatever comes after it
+                Constraint::Gt(Value::Infinite) | Constraint::Lt(Value::Infinite) => return Domain::None,
+                Constraint::Gt(value) => lower = lower.max_lower(value.clone()),
+                Constraint::Lt(value) => upper = upper.min_upper(value.clone()),
+                Constraint::Ne(n) => holes.push(*n),
+                Constraint::InsideOf(domain) => inside.push(domain),
+                Constraint::OutsideOf(domain) => outside.extend(util::members(domain)),
+            }
+        }
+
+        let mut domain = self.intersect(&Domain::Domain(lower, upper)).into_inner();
+        for other in inside {
+            domain = domain.intersect(other).into_inner();
+        }
+        if !outside.is_empty() {
+            domain = domain.difference(&util::from_members(outside)).into_inner();
+        }
+
+        holes.sort_unstable();
+        holes.dedup();
+        util::from_members(punch(util::into_members(domain), &holes))
+    }
+
+    /// Keeps the values less than some value of `other`, or less than or
+    /// equal to one when not `strict`: `lt` with the greatest value of
+    /// `other`. A secluded ending border of `other` admits only the values
+    /// below it, so `(-∞;5)` keeps the values up to 4, or up to 3 when
+    /// `strict`. With nothing in `other` to compare with nothing is kept,
+    /// an `other` without an ending border keeps everything
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let x = Domain::between(0, 10);
+    /// let y = Domain::Domain(Value::Included(2), Value::Secluded(6));
+    /// assert_eq!(x.lt_domain(&y, false).repr(), "[0;6)");
+    /// assert_eq!(x.lt_domain(&y, true).repr(), "[0;5)");
+    /// assert_eq!(x.lt_domain(&Domain::at_least(3), true), x);
+    /// assert_eq!(x.lt_domain(&Domain::None, false), Domain::None);
+    /// ```
+    pub fn lt_domain(&self, other: &Domain<i32>, strict: bool) -> Domain<i32> {
+        let upper = match util::normalized(other).pop() {
+            None => return Domain::None,
+            Some((_, Value::Infinite)) => return self.clone(),
+            Some((_, r)) if strict => Value::Secluded(last_admitted(&r) as i32),
+            Some((_, r)) => r,
+        };
+        self.lt(upper)
+    }
+
+    /// Keeps the values greater than some value of `other`, or greater
+    /// than or equal to one when not `strict`, the mirror of `lt_domain`
+    /// on the least value of `other`
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let x = Domain::between(0, 10);
+    /// let y = Domain::Domain(Value::Secluded(2), Value::Included(6));
+    /// assert_eq!(x.gt_domain(&y, false).repr(), "(2;10]");
+    /// assert_eq!(x.gt_domain(&y, true).repr(), "(3;10]");
+    /// assert_eq!(x.gt_domain(&Domain::at_most(3), true), x);
+    /// assert_eq!(x.gt_domain(&Domain::None, true), Domain::None);
+    /// ```
+    pub fn gt_domain(&self, other: &Domain<i32>, strict: bool) -> Domain<i32> {
+        let lower = match util::normalized(other).first() {
+            None => return Domain::None,
+            Some((Value::Infinite, _)) => return self.clone(),
+            Some((l, _)) if strict => Value::Secluded(first_admitted(l) as i32),
+            Some((l, _)) => l.clone(),
+        };
+        self.gt(lower)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::union_of;
+    use crate::{Constraint, Domain, Value};
+
+    fn value() -> impl Strategy<Value = Value<i32>> + Clone {
+        prop_oneof![
+            1 => Just(Value::Infinite),
+            3 => (-50..50).prop_map(Value::Included),
+            3 => (-50..50).prop_map(Value::Secluded),
+        ]
+    }
+
+    fn domain() -> impl Strategy<Value = Domain<i32>> {
+        union_of(value(), 0..4)
+    }
+
+    fn constraint() -> impl Strategy<Value = Constraint> {
+        prop_oneof![
+            value().prop_map(Constraint::Gt),
+            value().prop_map(Constraint::Lt),
+            (-50..50).prop_map(Constraint::Ne),
+            domain().prop_map(Constraint::InsideOf),
+            domain().prop_map(Constraint::OutsideOf),
+        ]
+    }
+
+    /// The operation of `Domain` each constraint stands for
+    fn sequential(domain: Domain<i32>, constraint: &Constraint) -> Domain<i32> {
+        match constraint {
+            Constraint::Gt(value) => domain.gt(value.clone()),
+            Constraint::Lt(value) => domain.lt(value.clone()),
+            Constraint::Ne(n) => domain.remove_value(*n).into_inner(),
+            Constraint::InsideOf(other) => domain.intersect(other).into_inner(),
+            Constraint::OutsideOf(other) => domain.difference(other).into_inner(),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_sequential(start in domain(), constraints in prop::collection::vec(constraint(), 0..30)) {
+            let sequential = constraints.iter().fold(start.clone(), sequential);
+            let applied = constraints
+                .iter()
+                .fold(start.clone(), |domain, constraint| constraint.apply(&domain));
+            let batched = start.tighten_all(&constraints);
+            prop_assert_eq!(batched.clone(), sequential.clone(), "{} vs {}", batched.repr(), sequential.clone().repr());
+            prop_assert_eq!(applied, sequential);
+        }
+    }
+
+    /// Values of `-12..12` admitted by `domain`
+    fn admitted(domain: &Domain<i32>) -> Vec<i32> {
+        (-12..12).filter(|x| domain.contains(*x)).collect()
+    }
+
+    fn small() -> impl Strategy<Value = Domain<i32>> {
+        let border = prop_oneof![
+            1 => Just(Value::Infinite),
+            3 => (-10..10).prop_map(Value::Included),
+            3 => (-10..10).prop_map(Value::Secluded),
+        ];
+        union_of(border, 0..4)
+    }
+
+    proptest! {
+        #[test]
+        fn test_compare_with_domain(x in small(), y in small(), strict in any::<bool>()) {
+            // Finite borders of `y` lie within -10..10, so its values there decide
+            let values = admitted(&y);
+            let unbounded = |side: fn(&Domain<i32>) -> bool| !y.is_empty() && !side(&y);
+            let expected = |keep: &dyn Fn(i32) -> bool| admitted(&x).into_iter().filter(|v| keep(*v)).collect::<Vec<_>>();
+
+            let below = match unbounded(Domain::is_bounded_above) {
+                true => admitted(&x),
+                false => expected(&|v| values.iter().any(|w| if strict { v < *w } else { v <= *w })),
+            };
+            prop_assert_eq!(admitted(&x.lt_domain(&y, strict)), below);
+            let above = match unbounded(Domain::is_bounded_below) {
+                true => admitted(&x),
+                false => expected(&|v| values.iter().any(|w| if strict { v > *w } else { v >= *w })),
+            };
+            prop_assert_eq!(admitted(&x.gt_domain(&y, strict)), above);
+        }
+    }
+
+    #[test]
+    fn test_compare_openness() {
+        let open = |lo, hi| Domain::Domain(Value::Secluded(lo), Value::Secluded(hi));
+        let closed = |lo, hi| Domain::between(lo, hi);
+        // Every openness of both sides meeting at 5, and at 0 for gt_domain
+        let cases = vec![
+            (closed(0, 5), closed(0, 5), false, vec![0, 1, 2, 3, 4, 5], vec![0, 1, 2, 3, 4, 5]),
+            (closed(0, 5), closed(0, 5), true, vec![0, 1, 2, 3, 4], vec![1, 2, 3, 4, 5]),
+            (closed(0, 5), open(0, 5), false, vec![0, 1, 2, 3, 4], vec![1, 2, 3, 4, 5]),
+            (closed(0, 5), open(0, 5), true, vec![0, 1, 2, 3], vec![2, 3, 4, 5]),
+            (open(0, 5), closed(0, 5), false, vec![1, 2, 3, 4], vec![1, 2, 3, 4]),
+            (open(0, 5), closed(0, 5), true, vec![1, 2, 3, 4], vec![1, 2, 3, 4]),
+            (open(0, 5), open(0, 5), false, vec![1, 2, 3, 4], vec![1, 2, 3, 4]),
+            (open(0, 5), open(0, 5), true, vec![1, 2, 3], vec![2, 3, 4]),
+        ];
+        for (x, y, strict, below, above) in cases {
+            let context = format!("{} against {}, strict {}", x.clone().repr(), y.clone().repr(), strict);
+            assert_eq!(admitted(&x.lt_domain(&y, strict)), below, "{}", context);
+            assert_eq!(admitted(&x.gt_domain(&y, strict)), above, "{}", context);
+        }
+    }
+
+    #[test]
+    fn test_compare_edges() {
+        let x = Domain::between(i32::MIN, i32::MAX);
+        assert_eq!(x.lt_domain(&Domain::between(0, i32::MAX), true), Domain::between(i32::MIN, i32::MAX - 1));
+        assert_eq!(x.gt_domain(&Domain::between(i32::MIN, 0), true), Domain::between(i32::MIN + 1, i32::MAX));
+        assert_eq!(x.lt_domain(&Domain::between(i32::MIN, i32::MIN), true), Domain::None);
+        assert_eq!(x.gt_domain(&Domain::between(i32::MAX, i32::MAX), true), Domain::None);
+        assert_eq!(x.lt_domain(&Domain::at_least(i32::MAX), true), x);
+
+        // Empty in spite of its members, and every value is greater than a value of a union reaching -∞
+        let empty = Domain::Union(vec![Domain::between(5, 1), Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite)]);
+        assert_eq!(x.lt_domain(&empty, false), Domain::None);
+        let y = Domain::Union(vec![Domain::between(7, 9), Domain::at_most(-3)]);
+        assert_eq!(x.gt_domain(&y, true), x);
+        assert_eq!(x.lt_domain(&y, true).repr(), "[-2147483648;9)");
+        assert_eq!(Domain::None.lt_domain(&y, false), Domain::None);
+    }
+
+    #[test]
+    fn test_holes() {
+        let domain = Domain::between(0, 10).tighten_all(&[
+            Constraint::Ne(10),
+            Constraint::Ne(3),
+            Constraint::Ne(-4),
+            Constraint::Ne(0),
+            Constraint::Ne(4),
+            Constraint::Ne(3),
+        ]);
+        assert_eq!(domain.repr(), "(0;3)⋃(4;10)");
+
+        let point = Domain::between(5, 5).tighten_all(&[Constraint::Ne(5)]);
+        assert_eq!(point, Domain::None);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds = [
+            Constraint::Lt(Value::Included(20)),
+            Constraint::Lt(Value::Secluded(15)),
+            Constraint::Gt(Value::Included(i32::MIN)),
+        ];
+        assert_eq!(Domain::new().tighten_all(&bounds).repr(), "[-2147483648;15)");
+        for infinite in [Constraint::Gt(Value::Infinite), Constraint::Lt(Value::Infinite)].iter() {
+            let mut constraints = bounds.to_vec();
+            constraints.insert(1, infinite.clone());
+            let expected = constraints.iter().fold(Domain::new(), sequential);
+            assert_eq!(expected, Domain::None);
+            assert_eq!(Domain::new().tighten_all(&constraints), expected, "{:?}", infinite);
+            assert_eq!(infinite.apply(&Domain::between(0, 9)), Domain::between(0, 9).gt(Value::Infinite));
+        }
+        assert_eq!(Domain::new().tighten_all(&[]), Domain::new());
+        assert_eq!(
+            Domain::new().tighten_all(&[Constraint::Gt(Value::Secluded(i32::MAX))]),
+            Domain::None
+        );
+    }
+
+    #[test]
+    fn test_inside_and_outside() {
+        let domain = Domain::new().tighten_all(&[
+            Constraint::InsideOf(Domain::between(0, 100)),
+            Constraint::OutsideOf(Domain::between(10, 89)),
+            Constraint::InsideOf(Domain::at_least(5)),
+        ]);
+        assert_eq!(domain.repr(), "[5;10)⋃(89;100]");
+    }
+}
@@ -0,0 +1,427 @@
+use crate::reason::{self, EmptyReason};
+use crate::{Constraint, Domain, IntoBound};
+
+/// Domain with a generation counter, bumped by every mutator which
+/// changes the admitted set and left alone by the ones which do not.
+/// Equal generations read from the same wrapper mean an equal set, so
+/// a cached computation can be checked for staleness without comparing
+/// domains. An empty domain carries the `EmptyReason`s explaining how it
+/// got empty, cleared once a mutator brings values back
+/// ```
+/// use dynamic_domain::{Domain, TrackedDomain, Value};
+/// let mut tracked = TrackedDomain::new(Domain::between(0, 100));
+/// let seen = tracked.generation();
+/// tracked.lt(Value::Included(200));
+/// assert_eq!(tracked.generation(), seen);
+/// tracked.lt(Value::Included(50));
+/// assert_ne!(tracked.generation(), seen);
+/// assert_eq!(tracked.domain().clone().repr(), "[0;50]");
+/// ```
+#[derive(Clone, Debug)]
+pub struct TrackedDomain {
+    domain: Domain<i32>,
+    generation: u64,
+    reasons: Vec<EmptyReason>,
+}
+
+impl TrackedDomain {
+
+    /// Wraps `domain` at generation 0
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// assert_eq!(TrackedDomain::new(Domain::new()).generation(), 0);
+    /// ```
+    pub fn new(domain: Domain<i32>) -> Self {
+        let mut reasons = Vec::new();
+        if domain.is_empty() {
+            reasons.push(EmptyReason::new("new", reason::describe(&domain), &Domain::None));
+        }
+        TrackedDomain { domain, generation: 0, reasons }
+    }
+
+    /// Number of changes of the admitted set so far
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+    /// tracked.remove_value(3);
+    /// tracked.remove_value(3);
+    /// assert_eq!(tracked.generation(), 1);
+    /// ```
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The wrapped domain
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// assert_eq!(TrackedDomain::new(Domain::between(1, 2)).domain(), &Domain::between(1, 2));
+    /// ```
+    pub fn domain(&self) -> &Domain<i32> {
+        &self.domain
+    }
+
+    /// How the domain got empty, the operation which emptied it first and
+    /// the ones which kept it empty after, none while it admits values
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+    /// tracked.difference(&Domain::between(0, 9));
+    /// tracked.gt(5);
+    /// let operations: Vec<&str> = tracked.empty_reasons().iter().map(|reason| reason.operation()).collect();
+    /// assert_eq!(operations, vec!["difference", "gt"]);
+    /// tracked.union(&Domain::between(1, 1));
+    /// assert!(tracked.empty_reasons().is_empty());
+    /// ```
+    pub fn empty_reasons(&self) -> &[EmptyReason] {
+        &self.reasons
+    }
+
+    /// The `empty_reasons` written from the first one on, `None` while
+    /// the domain admits values. `repr` of the domain stays `∅`
+    /// ```
+    /// use dynamic_domain::{Constraint, Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 3));
+    /// assert_eq!(tracked.explain_empty(), None);
+    /// tracked.tighten(&Constraint::InsideOf(Domain::between(5, 9)));
+    /// tracked.remove_value(4);
+    /// assert_eq!(tracked.explain_empty().unwrap(), "tighten [5;9] emptied [0;3], then remove_value 4");
+    /// assert_eq!(tracked.domain().clone().repr(), "∅");
+    /// ```
+    pub fn explain_empty(&self) -> Option<String> {
+        if self.reasons.is_empty() {
+            return None;
+        }
+        Some(self.reasons.iter().map(EmptyReason::to_string).collect::<Vec<String>>().join(", then "))
+    }
+
+    /// The wrapped domain, dropping the generation
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// assert_eq!(TrackedDomain::new(Domain::None).into_inner(), Domain::None);
+    /// ```
+    pub fn into_inner(self) -> Domain<i32> {
+        self.domain
+    }
+
+    /// Replaces the domain with `next`, the result of `operation` with
+    /// the operand `operand` writes, bumping the generation unless it
+    /// admits the same values. Returns whether the set changed
+    fn update(&mut self, next: Domain<i32>, operation: &'static str, operand: impl FnOnce() -> String) -> bool {
+        if next.is_empty() {
+            self.reasons.push(EmptyReason::new(operation, operand(), &self.domain));
+        } else {
+            self.reasons.clear();
+        }
+        let changed = next != self.domain;
+        if changed {
+            self.generation += 1;
+        }
+        self.domain = next;
+        changed
+    }
+
+    /// Replaces the domain, a different spelling of the same set keeps
+    /// the generation. Returns whether the set changed
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain, Value};
+    /// let mut tracked = TrackedDomain::new(Domain::between(1, 9));
+    /// assert!(!tracked.set(Domain::Domain(Value::Secluded(0), Value::Secluded(10))));
+    /// assert!(tracked.set(Domain::between(1, 8)));
+    /// ```
+    pub fn set(&mut self, domain: Domain<i32>) -> bool {
+        let operand = reason::describe(&domain);
+        self.update(domain, "set", || operand)
+    }
+
+    /// `Domain::gt` in place. Returns whether the set changed
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain, Value};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+    /// assert!(tracked.gt(Value::Secluded(4)));
+    /// assert_eq!(tracked.domain().clone().repr(), "(4;9]");
+    /// ```
+    pub fn gt(&mut self, value: impl IntoBound) -> bool {
+        let value = value.into_bound();
+        let next = self.domain.gt(value.clone());
+        self.update(next, "gt", || reason::describe(&Domain::new().gt(value)))
+    }
+
+    /// `Domain::lt` in place. Returns whether the set changed
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain, Value};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+    /// assert!(!tracked.lt(Value::Secluded(10)));
+    /// ```
+    pub fn lt(&mut self, value: impl IntoBound) -> bool {
+        let value = value.into_bound();
+        let next = self.domain.lt(value.clone());
+        self.update(next, "lt", || reason::describe(&Domain::new().lt(value)))
+    }
+
+    /// Applies one constraint in place. Returns whether the set changed
+    /// ```
+    /// use dynamic_domain::{Constraint, Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+    /// assert!(!tracked.tighten(&Constraint::OutsideOf(Domain::between(20, 29))));
+    /// assert!(tracked.tighten(&Constraint::Ne(0)));
+    /// ```
+    pub fn tighten(&mut self, constraint: &Constraint) -> bool {
+        let next = constraint.apply(&self.domain);
+        self.update(next, "tighten", || reason::describe(&constraint.apply(&Domain::new())))
+    }
+
+    /// `Domain::tighten_all` in place, a single generation step for all
+    /// the constraints. Returns whether the set changed
+    /// ```
+    /// use dynamic_domain::{Constraint, Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+    /// assert!(tracked.tighten_all(&[Constraint::Ne(1), Constraint::Ne(2)]));
+    /// assert_eq!(tracked.generation(), 1);
+    /// ```
+    pub fn tighten_all(&mut self, constraints: &[Constraint]) -> bool {
+        let next = self.domain.tighten_all(constraints);
+        self.update(next, "tighten_all", || reason::describe(&Domain::new().tighten_all(constraints)))
+    }
+
+    /// `Domain::intersect` in place. Returns whether the set changed
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+    /// assert!(!tracked.intersect(&Domain::new()));
+    /// ```
+    pub fn intersect(&mut self, other: &Domain<i32>) -> bool {
+        let next = self.domain.intersect(other).into_inner();
+        self.update(next, "intersect", || reason::describe(other))
+    }
+
+    /// `Domain::union` in place. Returns whether the set changed
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+    /// assert!(!tracked.union(&Domain::between(3, 4)));
+    /// assert!(tracked.union(&Domain::between(10, 10)));
+    /// ```
+    pub fn union(&mut self, other: &Domain<i32>) -> bool {
+        let next = self.domain.union(other).into_inner();
+        self.update(next, "union", || reason::describe(other))
+    }
+
+    /// `Domain::difference` in place. Returns whether the set changed
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+    /// assert!(tracked.difference(&Domain::at_least(5)));
+    /// assert_eq!(tracked.domain().clone().repr(), "[0;5)");
+    /// ```
+    pub fn difference(&mut self, other: &Domain<i32>) -> bool {
+        let next = self.domain.difference(other).into_inner();
+        self.update(next, "difference", || reason::describe(other))
+    }
+
+    /// `Domain::remove_value` in place. Returns whether the set changed
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+    /// assert!(!tracked.remove_value(10));
+    /// ```
+    pub fn remove_value(&mut self, value: i32) -> bool {
+        let next = self.domain.remove_value(value).into_inner();
+        self.update(next, "remove_value", || value.to_string())
+    }
+
+    /// `Domain::coalesce` in place. Returns whether the set changed
+    /// ```
+    /// use dynamic_domain::{Domain, TrackedDomain};
+    /// let mut tracked = TrackedDomain::new(Domain::Union(vec![Domain::between(0, 4), Domain::between(7, 9)]));
+    /// assert!(!tracked.coalesce(1));
+    /// assert!(tracked.coalesce(2));
+    /// ```
+    pub fn coalesce(&mut self, max_gap: u32) -> bool {
+        let next = self.domain.coalesce(max_gap).into_inner();
+        self.update(next, "coalesce", || max_gap.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{Constraint, Domain, TrackedDomain, Value};
+
+    #[derive(Clone, Debug)]
+    enum Call {
+        Gt(i32),
+        Lt(i32),
+        Tighten(Constraint),
+        Intersect(i32, i32),
+        Union(i32, i32),
+        Difference(i32, i32),
+        RemoveValue(i32),
+        Coalesce(u32),
+    }
+
+    fn call() -> impl Strategy<Value = Call> {
+        let point = -20..20;
+        prop_oneof![
+            point.clone().prop_map(Call::Gt),
+            point.clone().prop_map(Call::Lt),
+            point.clone().prop_map(|n| Call::Tighten(Constraint::Ne(n))),
+            (point.clone(), point.clone()).prop_map(|(lo, hi)| Call::Intersect(lo, hi)),
+            (point.clone(), point.clone()).prop_map(|(lo, hi)| Call::Union(lo, hi)),
+            (point.clone(), point.clone()).prop_map(|(lo, hi)| Call::Difference(lo, hi)),
+            point.prop_map(Call::RemoveValue),
+            (0..4u32).prop_map(Call::Coalesce),
+        ]
+    }
+
+    fn apply(tracked: &mut TrackedDomain, call: &Call) -> bool {
+        match call {
+            Call::Gt(n) => tracked.gt(Value::Included(*n)),
+            Call::Lt(n) => tracked.lt(Value::Included(*n)),
+            Call::Tighten(constraint) => tracked.tighten(constraint),
+            Call::Intersect(lo, hi) => tracked.intersect(&Domain::between(*lo, *hi)),
+            Call::Union(lo, hi) => tracked.union(&Domain::between(*lo, *hi)),
+            Call::Difference(lo, hi) => tracked.difference(&Domain::between(*lo, *hi)),
+            Call::RemoveValue(n) => tracked.remove_value(*n),
+            Call::Coalesce(max_gap) => tracked.coalesce(*max_gap),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_generation_tracks_changes(calls in prop::collection::vec(call(), 0..16)) {
+            let mut tracked = TrackedDomain::new(Domain::between(-10, 10));
+            let mut seen = vec![(tracked.generation(), tracked.domain().clone())];
+
+            for call in &calls {
+                let before = tracked.domain().clone();
+                let changed = apply(&mut tracked, call);
+                prop_assert_eq!(changed, &before != tracked.domain());
+                prop_assert_eq!(tracked.generation(), seen.last().unwrap().0 + changed as u64);
+                seen.push((tracked.generation(), tracked.domain().clone()));
+            }
+
+            for (generation, domain) in &seen {
+                for (other_generation, other) in &seen {
+                    if generation == other_generation {
+                        prop_assert_eq!(domain, other);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The plain `Domain` result of `call`
+    fn replay(domain: &Domain<i32>, call: &Call) -> Domain<i32> {
+        match call {
+            Call::Gt(n) => domain.gt(Value::Included(*n)),
+            Call::Lt(n) => domain.lt(Value::Included(*n)),
+            Call::Tighten(constraint) => constraint.apply(domain),
+            Call::Intersect(lo, hi) => domain.intersect(&Domain::between(*lo, *hi)).into_inner(),
+            Call::Union(lo, hi) => domain.union(&Domain::between(*lo, *hi)).into_inner(),
+            Call::Difference(lo, hi) => domain.difference(&Domain::between(*lo, *hi)).into_inner(),
+            Call::RemoveValue(n) => domain.remove_value(*n).into_inner(),
+            Call::Coalesce(max_gap) => domain.coalesce(*max_gap).into_inner(),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_reasons_follow_emptiness(start in -3..3, calls in prop::collection::vec(call(), 0..16)) {
+            let mut tracked = TrackedDomain::new(Domain::between(start, 2));
+            let mut plain = Domain::between(start, 2);
+            let mut empty_for = plain.is_empty() as usize;
+
+            for call in &calls {
+                apply(&mut tracked, call);
+                plain = replay(&plain, call);
+                empty_for = if plain.is_empty() { empty_for + 1 } else { 0 };
+
+                prop_assert_eq!(tracked.domain(), &plain);
+                prop_assert_eq!(tracked.empty_reasons().len(), empty_for);
+                prop_assert_eq!(tracked.explain_empty().is_some(), plain.is_empty());
+                if let Some(first) = tracked.empty_reasons().first() {
+                    prop_assert!(first.previous().is_some() || first.operation() == "new");
+                    prop_assert!(tracked.empty_reasons()[1..].iter().all(|reason| reason.previous().is_none()));
+
+                    // The annotated domain is a plain empty one to every set operation
+                    let other = Domain::between(-5, 5);
+                    let annotated = tracked.domain();
+                    prop_assert_eq!(annotated.intersect(&other).into_inner(), Domain::None);
+                    prop_assert_eq!(annotated.union(&other).into_inner(), Domain::None.union(&other).into_inner());
+                    prop_assert_eq!(other.difference(annotated).into_inner(), other.clone());
+                    prop_assert_eq!(annotated.complement().into_inner(), Domain::new());
+                    prop_assert_eq!(annotated.clone().normalize().repr(), "∅");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_explain_empty() {
+        let mut tracked = TrackedDomain::new(Domain::between(1, 0));
+        assert_eq!(tracked.explain_empty().unwrap(), "new ∅");
+        assert!(!tracked.gt(Value::Infinite));
+        assert_eq!(tracked.explain_empty().unwrap(), "new ∅, then gt ∅");
+
+        let mut tracked = TrackedDomain::new(Domain::Union((0..20).map(|i| Domain::between(i * 3, i * 3)).collect()));
+        tracked.tighten_all(&[Constraint::Gt(Value::Included(100)), Constraint::Lt(Value::Included(200))]);
+        assert_eq!(
+            tracked.explain_empty().unwrap(),
+            "tighten_all [100;200] emptied [0;0]⋃[3;3]⋃[6;6]⋃[9;9]⋃…(12 more)…⋃[48;48]⋃[51;51]⋃[54;54]⋃[57;57] (hull [0;57])"
+        );
+        tracked.set(Domain::between(i32::MAX, i32::MAX));
+        assert!(tracked.remove_value(i32::MAX));
+        assert_eq!(tracked.explain_empty().unwrap(), "remove_value 2147483647 emptied [2147483647;2147483647]");
+        tracked.coalesce(0);
+        tracked.set(Domain::None);
+        assert_eq!(
+            tracked.explain_empty().unwrap(),
+            "remove_value 2147483647 emptied [2147483647;2147483647], then coalesce 0, then set ∅"
+        );
+    }
+
+    #[test]
+    fn test_noop_tighten_keeps_generation() {
+        let mut tracked = TrackedDomain::new(Domain::between(0, 9));
+        let noops = [
+            Constraint::Gt(Value::Included(0)),
+            Constraint::Gt(Value::Secluded(-1)),
+            Constraint::Lt(Value::Secluded(10)),
+            Constraint::Ne(10),
+            Constraint::InsideOf(Domain::new()),
+            Constraint::OutsideOf(Domain::None),
+            Constraint::OutsideOf(Domain::between(10, 5)),
+        ];
+        for constraint in noops.iter() {
+            assert!(!tracked.tighten(constraint), "{:?}", constraint);
+        }
+        assert!(!tracked.tighten_all(&noops));
+        assert_eq!(tracked.generation(), 0);
+
+        assert!(tracked.tighten(&Constraint::Ne(0)));
+        assert!(!tracked.tighten(&Constraint::Ne(0)));
+        assert!(!tracked.tighten(&Constraint::Gt(Value::Secluded(0))));
+        assert_eq!(tracked.generation(), 1);
+        assert_eq!(tracked.domain().clone().repr(), "(0;9]");
+
+        // An infinite ending border keeps nothing, as `lt` does
+        let expected = tracked.domain().lt(Value::Infinite);
+        assert!(tracked.tighten(&Constraint::Lt(Value::Infinite)));
+        assert_eq!((tracked.generation(), tracked.domain()), (2, &expected));
+    }
+
+    #[test]
+    fn test_edges() {
+        let mut tracked = TrackedDomain::new(Domain::new());
+        assert!(!tracked.set(Domain::between(i32::MIN, i32::MAX)));
+        assert!(!tracked.gt(Value::Included(i32::MIN)));
+        assert!(!tracked.lt(Value::Included(i32::MAX)));
+        assert!(tracked.remove_value(i32::MAX));
+        assert!(!tracked.union(&Domain::None));
+        assert!(tracked.intersect(&Domain::None));
+        assert!(!tracked.difference(&Domain::new()));
+        assert!(!tracked.set(Domain::between(1, 0)));
+        assert_eq!((tracked.generation(), tracked.into_inner()), (2, Domain::None));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::util;
+use crate::{Domain, NormalizedDomain};
+
+impl Domain<i32> {
+
+    /// Values of `universe` the domain does not admit, `universe \ self`.
+    /// Admitted values outside of `universe` are ignored, and for the full
+    /// domain as `universe` it is the plain `complement`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let ids = Domain::between(0, 999_999);
+    /// let taken = Domain::Union(vec![Domain::between(-10, 99), Domain::between(500_000, 1_200_000)]);
+    /// assert_eq!(taken.complement_within(&ids).repr(), "(99;500000)");
+    /// assert_eq!(taken.complement_within(&Domain::new()), taken.complement());
+    /// ```
+    pub fn complement_within(&self, universe: &Domain<i32>) -> NormalizedDomain<i32> {
+        universe.difference(self)
+    }
+}
+
+/// Carrier of the domains of a problem, like `[0;999999]` for ids, which
+/// the complement and the other universe-aware operations stay inside
+/// ```
+/// use dynamic_domain::{BoundedUniverse, Domain};
+/// let ids = BoundedUniverse::new(&Domain::between(0, 999_999));
+/// let free = ids.complement(&Domain::at_least(1_000));
+/// assert_eq!(free.repr(), "[0;1000)");
+/// assert!(ids.is_full(&Domain::at_least(-5)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundedUniverse {
+    universe: NormalizedDomain<i32>,
+}
+
+impl BoundedUniverse {
+
+    /// Universe of the values `universe` admits
+    /// ```
+    /// use dynamic_domain::{BoundedUniverse, Domain};
+    /// let universe = BoundedUniverse::new(&Domain::Union(vec![Domain::between(5, 9), Domain::between(0, 4)]));
+    /// assert_eq!(universe.universe().clone().repr(), "[0;9]");
+    /// ```
+    pub fn new(universe: &Domain<i32>) -> Self {
+        BoundedUniverse { universe: universe.normalize() }
+    }
+
+    /// The normalized carrier
+    /// ```
+    /// use dynamic_domain::{BoundedUniverse, Domain};
+    /// assert!(BoundedUniverse::new(&Domain::None).universe().is_empty());
+    /// ```
+    pub fn universe(&self) -> &NormalizedDomain<i32> {
+        &self.universe
+    }
+
+    /// `domain.complement_within(universe)`
+    /// ```
+    /// use dynamic_domain::{BoundedUniverse, Domain};
+    /// let digits = BoundedUniverse::new(&Domain::between(0, 9));
+    /// assert_eq!(digits.complement(&Domain::between(3, 20)).repr(), "[0;3)");
+    /// ```
+    pub fn complement(&self, domain: &Domain<i32>) -> NormalizedDomain<i32> {
+        domain.complement_within(&self.universe)
+    }
+
+    /// Whether `domain` admits every value of the universe, whatever it
+    /// admits outside of it
+    /// ```
+    /// use dynamic_domain::{BoundedUniverse, Domain};
+    /// let digits = BoundedUniverse::new(&Domain::between(0, 9));
+    /// assert!(digits.is_full(&Domain::between(-1, 10)));
+    /// assert!(!digits.is_full(&Domain::between(1, 10)));
+    /// ```
+    pub fn is_full(&self, domain: &Domain<i32>) -> bool {
+        self.complement(domain).is_empty()
+    }
+
+    /// Value of `domain` inside the universe picked by `random`, uniform
+    /// over those values when `random` is a uniform `u64` of any
+    /// generator. `None` when `domain` admits nothing inside the universe
+    /// ```
+    /// use dynamic_domain::{BoundedUniverse, Domain};
+    /// let digits = BoundedUniverse::new(&Domain::between(0, 9));
+    /// let odd = Domain::Union((0..20).map(|i| Domain::between(i * 2 + 1, i * 2 + 1)).collect());
+    /// assert_eq!(digits.sample(&odd, 0), Some(1));
+    /// assert_eq!(digits.sample(&odd, u64::MAX), Some(9));
+    /// assert_eq!(digits.sample(&Domain::at_least(10), 0), None);
+    /// ```
+    pub fn sample(&self, domain: &Domain<i32>, random: u64) -> Option<i32> {
+        let runs = util::i32_runs(&self.universe.intersect(domain));
+        let count: u64 = runs.iter().map(|(lo, hi)| (hi - lo + 1) as u64).sum();
+        // Scales `random` down to the count without the bias of a modulo
+        let n = ((random as u128 * count as u128) >> 64) as u64;
+        util::nth_of_runs(runs.into_iter(), n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::flat_domain;
+    use crate::{BoundedUniverse, Domain};
+
+    proptest! {
+        #[test]
+        fn test_full_universe(domain in flat_domain()) {
+            prop_assert_eq!(domain.complement_within(&Domain::new()), domain.complement());
+            prop_assert_eq!(domain.complement_within(&Domain::between(i32::MIN, i32::MAX)), domain.complement());
+        }
+
+        #[test]
+        fn test_stays_inside(domain in flat_domain(), universe in flat_domain(), random in any::<u64>()) {
+            let complement = domain.complement_within(&universe);
+            prop_assert!(complement.difference(&universe).is_empty());
+            prop_assert!(complement.intersect(&domain).is_empty());
+            prop_assert_eq!(Domain::Union(vec![complement.into_inner(), domain.clone()]).intersect(&universe), universe.normalize());
+
+            let universe = BoundedUniverse::new(&universe);
+            match universe.sample(&domain, random) {
+                Some(value) => prop_assert!(domain.contains(value) && universe.universe().contains(value)),
+                None => prop_assert!(universe.universe().intersect(&domain).is_empty()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_partially_outside() {
+        let ids = BoundedUniverse::new(&Domain::between(0, 999_999));
+        let taken = Domain::Union(vec![Domain::at_most(10), Domain::between(20, 29), Domain::at_least(999_990)]);
+        assert_eq!(ids.complement(&taken).repr(), "(10;20)⋃(29;999990)");
+        assert!(!ids.is_full(&taken));
+        assert!(ids.is_full(&Domain::new()));
+        assert!(ids.complement(&Domain::None).structurally_equal(ids.universe()));
+        assert!(ids.complement(&Domain::at_most(-1)).structurally_equal(ids.universe()));
+
+        let empty = BoundedUniverse::new(&Domain::None);
+        assert!(empty.is_full(&Domain::None));
+        assert_eq!(empty.sample(&Domain::new(), 42), None);
+    }
+
+    #[test]
+    fn test_sample_limits() {
+        let full = BoundedUniverse::new(&Domain::new());
+        assert_eq!(full.sample(&Domain::new(), 0), Some(i32::MIN));
+        assert_eq!(full.sample(&Domain::new(), u64::MAX), Some(i32::MAX));
+        assert_eq!(full.sample(&Domain::new(), 1 << 63), Some(0));
+
+        let edges = Domain::Union(vec![Domain::at_most(i32::MIN), Domain::at_least(i32::MAX)]);
+        assert_eq!(full.sample(&edges, 0), Some(i32::MIN));
+        assert_eq!(full.sample(&edges, u64::MAX), Some(i32::MAX));
+
+        // Every one of four values gets a quarter of the random range
+        let four = BoundedUniverse::new(&Domain::between(0, 3));
+        let picks: Vec<_> = (0..4u64).map(|i| four.sample(&Domain::new(), i << 62).unwrap()).collect();
+        assert_eq!(picks, vec![0, 1, 2, 3]);
+    }
+}
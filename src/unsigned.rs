@@ -0,0 +1,231 @@
+//! Domains within the points of a `Bounded` type, where infinite borders
+//! stop at the limits of the type. The unsigned integers have a natural
+//! lower edge, `Bounded::MIN_VALUE` is `Some(0)`, so `Domain::full` starts
+//! at `[0` and nothing below zero is admitted or suggested
+
+use std::convert::TryFrom;
+
+use crate::{Bounded, Domain, Value};
+
+/// Starting border of `Domain::full`
+fn lowest_border<T: Bounded>() -> Value<T> {
+    T::MIN_VALUE.map_or(Value::Infinite, Value::Included)
+}
+
+/// First and last admitted point of every member, sorted and with
+/// overlapping and adjacent ones merged
+fn runs<T: Bounded>(domain: &Domain<T>) -> Vec<(T, T)> {
+    let mut members = Vec::new();
+    collect_runs(domain, &mut members);
+    members.sort();
+
+    let mut runs: Vec<(T, T)> = Vec::with_capacity(members.len());
+    for (first, last) in members {
+        match runs.last_mut() {
+            Some((_, end)) if end.successor().is_none_or(|next| first <= next) => {
+                if last > *end {
+                    *end = last;
+                }
+            },
+            _ => runs.push((first, last)),
+        }
+    }
+    runs
+}
+
+fn collect_runs<T: Bounded>(domain: &Domain<T>, runs: &mut Vec<(T, T)>) {
+    match domain {
+        Domain::Union(domains) => domains.iter().for_each(|domain| collect_runs(domain, runs)),
+        Domain::Domain(l, r) => {
+            let first = match l {
+                Value::Included(p) => Some(p.clone()),
+                Value::Secluded(p) => p.successor(),
+                Value::Infinite => Some(T::LOWEST),
+            };
+            let last = match r {
+                Value::Included(p) => Some(p.clone()),
+                Value::Secluded(p) => p.predecessor(),
+                Value::Infinite => Some(T::HIGHEST),
+            };
+            if let (Some(first), Some(last)) = (first, last) {
+                if first <= last {
+                    runs.push((first, last));
+                }
+            }
+        },
+        Domain::None => (),
+    }
+}
+
+/// The members as a domain, a plain interval or `Domain::None` when
+/// there are fewer than two
+fn from_members<T>(mut members: Vec<Domain<T>>) -> Domain<T> {
+    match members.len() {
+        0 => Domain::None,
+        1 => members.remove(0),
+        _ => Domain::Union(members),
+    }
+}
+
+/// Gaps between the runs, each written with the borders of the runs
+/// around it flipped, and from `lowest_border` before the first run
+fn complement<T: Bounded>(domain: &Domain<T>) -> Domain<T> {
+    let mut members = Vec::new();
+    let mut end: Option<T> = None;
+    for (first, last) in runs(domain) {
+        if first != T::LOWEST {
+            let start = end.map_or_else(lowest_border, Value::Secluded);
+            members.push(Domain::Domain(start, Value::Secluded(first)));
+        }
+        end = Some(last);
+    }
+    if end.as_ref() != Some(&T::HIGHEST) {
+        let start = end.map_or_else(lowest_border, Value::Secluded);
+        members.push(Domain::Domain(start, Value::Infinite));
+    }
+    from_members(members)
+}
+
+impl<T: Bounded> Domain<T> {
+
+    /// Every point of `T`. The starting border is `Bounded::MIN_VALUE`
+    /// where the type has a natural lower edge, so `[0;∞)` for unsigned
+    /// integers, and -∞ otherwise, like `Domain::new`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::<u32>::full().repr(), "[0;∞)");
+    /// assert_eq!(Domain::<i64>::full().repr(), "(-∞;∞)");
+    /// assert_eq!(Domain::<i32>::full(), Domain::new());
+    /// ```
+    pub fn full() -> Domain<T> {
+        Domain::Domain(lowest_border(), Value::Infinite)
+    }
+
+    /// Set of points of `T` not admitted by the domain, starting from
+    /// `Domain::full`, so from the included 0 of unsigned integers rather
+    /// than -∞. The complement of `Domain::None` is `Domain::full`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::from(5u32..=10);
+    /// assert_eq!(domain.complement_within_type().repr(), "[0;5)⋃(10;∞)");
+    /// assert_eq!(Domain::<u8>::None.complement_within_type().repr(), "[0;∞)");
+    /// assert_eq!(Domain::from(5i64..=10).complement_within_type().repr(), "(-∞;5)⋃(10;∞)");
+    /// ```
+    pub fn complement_within_type(&self) -> Domain<T> {
+        complement(self)
+    }
+}
+
+impl<T: Bounded + Into<i128>> Domain<T> {
+
+    /// Admitted point of `T` closest to `value`, the smaller one on a
+    /// tie. An infinite border stops at the limit of `T`, so nothing
+    /// below zero is suggested for unsigned integers. `None` when the
+    /// domain admits nothing
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::from(..=3u8), Domain::from(10..=20)]);
+    /// assert_eq!(domain.clamp_within_type(0), Some(0));
+    /// assert_eq!(domain.clamp_within_type(6), Some(3));
+    /// assert_eq!(domain.clamp_within_type(7), Some(10));
+    /// assert_eq!(domain.clamp_within_type(50), Some(20));
+    /// assert_eq!(Domain::<u8>::None.clamp_within_type(5), None);
+    /// ```
+    pub fn clamp_within_type(&self, value: T) -> Option<T> {
+        let target: i128 = value.clone().into();
+        runs(self)
+            .into_iter()
+            .map(|(first, last)| value.clone().max(first).min(last))
+            .min_by_key(|candidate| {
+                let point: i128 = candidate.clone().into();
+                ((point - target).abs(), point)
+            })
+    }
+
+    /// Number of admitted points of `T`, counting infinite borders up to
+    /// the limits of `T`. `None` only when the count does not fit a
+    /// `u64`, like every `u64`
+    /// ```
+    /// use dynamic_domain::{Domain, Value};
+    /// let domain = Domain::<u32>::Domain(Value::Secluded(5), Value::Included(10));
+    /// assert_eq!(domain.cardinality_within_type(), Some(5));
+    /// assert_eq!(Domain::<u8>::full().cardinality_within_type(), Some(256));
+    /// assert_eq!(Domain::<u64>::full().cardinality_within_type(), None);
+    /// ```
+    pub fn cardinality_within_type(&self) -> Option<u64> {
+        let count: i128 = runs(self)
+            .into_iter()
+            .map(|(first, last)| last.into() - first.into() + 1)
+            .sum();
+        u64::try_from(count).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::{border_at, union_of};
+    use crate::{Domain, Value};
+
+    fn domain() -> impl Strategy<Value = Domain<u8>> {
+        let point = prop_oneof![4 => 0u8..20, 1 => Just(0u8), 1 => Just(u8::MAX), 1 => 250u8..=255];
+        union_of(border_at(point), 0..5)
+    }
+
+    proptest! {
+        #[test]
+        fn test_complement_partitions_u8(domain in domain()) {
+            // Every u8 is in exactly one of the two
+            let complement = domain.complement_within_type();
+            for value in 0..=u8::MAX {
+                prop_assert_ne!(domain.contains(value), complement.contains(value), "{}", value);
+            }
+            let (inside, outside) = (domain.cardinality_within_type().unwrap(), complement.cardinality_within_type().unwrap());
+            prop_assert_eq!(inside + outside, 256);
+            prop_assert_eq!(inside, (0..=u8::MAX).filter(|value| domain.contains(*value)).count() as u64);
+        }
+
+        #[test]
+        fn test_clamp_is_nearest_u8(domain in domain(), value in any::<u8>()) {
+            let nearest = (0..=u8::MAX)
+                .filter(|candidate| domain.contains(*candidate))
+                .min_by_key(|candidate| ((*candidate as i16 - value as i16).abs(), *candidate));
+            prop_assert_eq!(domain.clamp_within_type(value), nearest);
+        }
+    }
+
+    #[test]
+    fn test_u8_edges() {
+        let full = Domain::<u8>::full();
+        assert_eq!(full.clone().repr(), "[0;∞)");
+        assert_eq!(full.cardinality_within_type(), Some(256));
+        assert_eq!(Domain::<u8>::from(..).cardinality_within_type(), Some(256));
+        assert_eq!(full.complement_within_type().repr(), "∅");
+        assert_eq!(Domain::<u8>::None.complement_within_type().repr(), "[0;∞)");
+
+        assert_eq!(Domain::from(0u8..=0).complement_within_type().repr(), "(0;∞)");
+        assert_eq!(Domain::from(255u8..=255).complement_within_type().repr(), "[0;255)");
+        assert_eq!(Domain::from(255u8..).complement_within_type().repr(), "[0;255)");
+        assert_eq!(Domain::<u8>::Domain(Value::Secluded(255), Value::Infinite).complement_within_type().repr(), "[0;∞)");
+        assert_eq!(Domain::<u8>::Domain(Value::Infinite, Value::Secluded(0)).cardinality_within_type(), Some(0));
+
+        let ends = Domain::Union(vec![Domain::from(0u8..=0), Domain::from(255..=255)]);
+        assert_eq!(ends.cardinality_within_type(), Some(2));
+        assert_eq!(ends.complement_within_type().repr(), "(0;255)");
+        assert_eq!(ends.clamp_within_type(127), Some(0));
+        assert_eq!(ends.clamp_within_type(128), Some(255));
+        assert_eq!(Domain::from(..=3u8).clamp_within_type(0), Some(0));
+    }
+
+    #[test]
+    fn test_wide_unsigned() {
+        assert_eq!(Domain::from(5u32..=10).complement_within_type().repr(), "[0;5)⋃(10;∞)");
+        assert_eq!(Domain::<u32>::full().cardinality_within_type(), Some(u32::MAX as u64 + 1));
+        assert_eq!(Domain::<u64>::full().cardinality_within_type(), None);
+        assert_eq!(Domain::from(1..=u64::MAX).cardinality_within_type(), Some(u64::MAX));
+        assert_eq!(Domain::from(1..=u64::MAX).complement_within_type().repr(), "[0;1)");
+        assert_eq!(Domain::<u16>::from(..).clamp_within_type(0), Some(0));
+        assert_eq!(Domain::<u64>::Domain(Value::Secluded(u64::MAX - 1), Value::Infinite).clamp_within_type(0), Some(u64::MAX));
+    }
+}
@@ -1,19 +1,237 @@
-use crate::{Domain, Value};
-
-pub fn convert_to_secluded(value: Value<i32>, gt: bool) -> Option<i32> {
-    Some(
-        match value {
-            Value::Included(i) => {
-                if gt {
-                    i - 1
-                } else {
-                    i + 1
+//! Border arithmetic works on the closed `i64` form of the borders, where
+//! stepping past a secluded `i32` point can not overflow. Values are cast
+//! back to `i32` only once clipped to the `i32` range
+
+use crate::interval;
+use crate::{Domain, Interval, Value};
+
+/// Closed integer form of the -∞ border
+pub const NEG_INF: i64 = i64::MIN;
+
+/// Closed integer form of the ∞ border
+pub const POS_INF: i64 = i64::MAX;
+
+/// Single interval as a pair of starting and ending borders
+pub type Member = (Value<i32>, Value<i32>);
+
+/// First integer admitted by `value` used as a starting border
+pub fn first_admitted(value: &Value<i32>) -> i64 {
+    match value {
+        Value::Included(i) => *i as i64,
+        Value::Secluded(i) => *i as i64 + 1,
+        Value::Infinite => NEG_INF,
+    }
+}
+
+/// Last integer admitted by `value` used as an ending border
+pub fn last_admitted(value: &Value<i32>) -> i64 {
+    match value {
+        Value::Included(i) => *i as i64,
+        Value::Secluded(i) => *i as i64 - 1,
+        Value::Infinite => POS_INF,
+    }
+}
+
+/// Same domain with `f` applied to the borders of every member, unions
+/// keep their shape. The route for every builder moving borders in
+/// place, like `gt` and `lt`: `f` is never called for `Domain::None`, so
+/// an empty domain can not come back with borders, and nested unions are
+/// walked here rather than in each builder. The transforms working on
+/// `normalized` members get the same from the empty member list
+pub fn map_intervals(domain: &Domain<i32>, f: &dyn Fn(Value<i32>, Value<i32>) -> Member) -> Domain<i32> {
+    match domain {
+        Domain::Union(domains) => Domain::Union(
+            domains.iter().map(|domain| map_intervals(domain, f)).collect()
+        ),
+        Domain::Domain(l, r) => {
+            let (l, r) = f(l.clone(), r.clone());
+            Domain::Domain(l, r)
+        },
+        Domain::None => Domain::None,
+    }
+}
+
+/// Same domain without `value`: every member admitting it is split into
+/// the parts below and above it, the ones admitting some `i32` taking its
+/// place in the union it belongs to. Unions keep their shape otherwise,
+/// like `map_intervals`, and members without `value` stay as written
+pub fn punch_hole(domain: &Domain<i32>, value: i32) -> Domain<i32> {
+    match domain {
+        Domain::Union(domains) => {
+            let mut split = Vec::with_capacity(domains.len() + 1);
+            for domain in domains {
+                match (domain, punch_hole(domain, value)) {
+                    (Domain::Domain(..), Domain::Union(parts)) => split.extend(parts),
+                    (_, domain) => split.push(domain),
                 }
-            },
-            Value::Secluded(i) => i,
-            Value::Infinite => {
-                return None;
-            },
+            }
+            Domain::Union(split)
+        },
+        Domain::Domain(l, r) if interval::admits(l, r, &value) => {
+            let parts = vec![(l.clone(), Value::Secluded(value)), (Value::Secluded(value), r.clone())];
+            from_members(parts.into_iter().filter(|(l, r)| {
+                let (first, last) = clipped_run(l, r);
+                first <= last
+            }).collect())
+        },
+        other => other.clone(),
+    }
+}
+
+/// Single intervals of the domain in their original order,
+/// nested unions are flattened
+pub fn members(domain: &Domain<i32>) -> Vec<Member> {
+    let mut members = Vec::new();
+    collect_members(domain, &mut members);
+    members
+}
+
+fn collect_members(domain: &Domain<i32>, members: &mut Vec<Member>) {
+    match domain {
+        Domain::Union(domains) => {
+            for domain in domains {
+                collect_members(domain, members);
+            }
+        },
+        Domain::Domain(l, r) => members.push((l.clone(), r.clone())),
+        Domain::None => (),
+    }
+}
+
+/// Same as `members`, moving the intervals out of the domain
+pub fn into_members(domain: Domain<i32>) -> Vec<Member> {
+    match domain {
+        Domain::Union(domains) => {
+            let mut members = Vec::with_capacity(domains.len());
+            for domain in domains {
+                match domain {
+                    Domain::Domain(l, r) => members.push((l, r)),
+                    nested => members.extend(into_members(nested)),
+                }
+            }
+            members
+        },
+        Domain::Domain(l, r) => vec![(l, r)],
+        Domain::None => Vec::new(),
+    }
+}
+
+/// Members sorted by starting border with the ones admitting no `i32`
+/// dropped and overlapping or adjacent ones merged. Borders keep the
+/// notation they were written with
+pub fn normalized(domain: &Domain<i32>) -> Vec<Member> {
+    normalize_members(members(domain))
+}
+
+/// `normalized` over already collected members
+pub fn normalize_members(mut members: Vec<Member>) -> Vec<Member> {
+    members.retain(|(l, r)| {
+        let (first, last) = clipped_run(l, r);
+        first <= last
+    });
+    members.sort_by_key(|(l, _)| first_admitted(l));
+
+    let mut merged: Vec<Member> = Vec::with_capacity(members.len());
+
+    for (l, r) in members {
+        let next = Interval::new(l, r);
+        if let Some(last) = merged.last_mut() {
+            // Sorted, so keeps the starting border of `last` as it is
+            if let Some(interval) = Interval::new(last.0.clone(), last.1.clone()).merge_with(&next) {
+                *last = interval.into_borders();
+                continue;
+            }
         }
+        merged.push(next.into_borders());
+    }
+
+    merged
+}
+
+/// Builds the smallest domain variant holding `members`
+pub fn from_members(mut members: Vec<Member>) -> Domain<i32> {
+    match members.len() {
+        0 => Domain::None,
+        1 => {
+            let (l, r) = members.remove(0);
+            Domain::Domain(l, r)
+        },
+        _ => Domain::Union(
+            members
+                .into_iter()
+                .map(|(l, r)| Domain::Domain(l, r))
+                .collect()
+        ),
+    }
+}
+
+/// Normalized members as closed `i64` runs clipped to the `i32` range,
+/// runs admitting no `i32` are dropped
+pub fn i32_runs(domain: &Domain<i32>) -> Vec<(i64, i64)> {
+    runs_of(&normalized(domain))
+}
+
+/// `i32_runs` over already normalized members
+pub fn runs_of(members: &[Member]) -> Vec<(i64, i64)> {
+    members
+        .iter()
+        .map(|(l, r)| clipped_run(l, r))
+        .filter(|(first, last)| first <= last)
+        .collect()
+}
+
+/// First and last `i32` admitted between the borders as a closed `i64`
+/// run, reversed when there is none
+pub fn clipped_run(l: &Value<i32>, r: &Value<i32>) -> (i64, i64) {
+    (
+        first_admitted(l).max(i32::MIN as i64),
+        last_admitted(r).min(i32::MAX as i64),
     )
-}
\ No newline at end of file
+}
+
+/// Value at position `n` of the ascending closed `runs`
+pub fn nth_of_runs(runs: impl Iterator<Item = (i64, i64)>, mut n: u64) -> Option<i32> {
+    for (lo, hi) in runs {
+        let len = (hi - lo + 1) as u64;
+        if n < len {
+            return Some((lo + n as i64) as i32);
+        }
+        n -= len;
+    }
+    None
+}
+
+/// Whether the flat `domains` are already normalized: plain intervals,
+/// each admitting some `i32`, ascending with gaps between them
+pub fn is_normalized(domains: &[Domain<i32>]) -> bool {
+    let mut previous: Option<i64> = None;
+
+    for domain in domains {
+        let (first, last) = match domain {
+            Domain::Domain(l, r) => clipped_run(l, r),
+            _ => return false,
+        };
+        if first > last || previous.is_some_and(|previous| previous + 1 >= first) {
+            return false;
+        }
+        previous = Some(last);
+    }
+
+    true
+}
+
+/// Whether two of the `members` admit a common `i32`, in any order
+pub fn overlapping(members: &[Member]) -> bool {
+    let mut runs: Vec<(i64, i64)> = members.iter().map(|(l, r)| clipped_run(l, r)).collect();
+    runs.retain(|(first, last)| first <= last);
+    runs.sort_unstable();
+    // Sorted by first value, an overlap shows between neighbours
+    runs.windows(2).any(|pair| pair[1].0 <= pair[0].1)
+}
+
+/// Whether any of the members has an infinite border
+pub fn is_unbounded(members: &[Member]) -> bool {
+    members
+        .iter()
+        .any(|(l, r)| *l == Value::Infinite || *r == Value::Infinite)
+}
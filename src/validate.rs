@@ -0,0 +1,96 @@
+use crate::util;
+use crate::{Domain, OutOfDomain, Violation};
+
+impl Domain<i32> {
+
+    /// Hands `value` back when the domain admits it, otherwise an error
+    /// telling which way it misses and the nearest admitted value
+    /// ```
+    /// use dynamic_domain::{Domain, Value, Violation};
+    /// let domain = Domain::Union(vec![
+    ///     Domain::Domain(Value::Secluded(0), Value::Included(10)),
+    ///     Domain::between(20, 30),
+    /// ]);
+    /// assert_eq!(domain.validate_value(5), Ok(5));
+    ///
+    /// let error = domain.validate_value(42).unwrap_err();
+    /// assert_eq!(error.violation(), Violation::AboveMax);
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "42 is not allowed: must be in (0;10]⋃[20;30], nearest allowed value is 30"
+    /// );
+    /// ```
+    pub fn validate_value(&self, value: i32) -> Result<i32, OutOfDomain> {
+        let normalized = self.normalize();
+        if normalized.contains(value) {
+            return Ok(value);
+        }
+
+        let runs = util::i32_runs(&normalized);
+        let violation = match (runs.first(), runs.last()) {
+            (Some((first, _)), _) if (value as i64) < *first => Violation::BelowMin,
+            (_, Some((_, last))) if (value as i64) > *last => Violation::AboveMax,
+            (Some(_), Some(_)) => Violation::InGap,
+            _ => Violation::Empty,
+        };
+
+        let nearest = normalized.clamp(value);
+        Err(OutOfDomain::new(value, normalized.repr(), nearest, violation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Domain, Value, Violation};
+
+    fn gappy() -> Domain<i32> {
+        Domain::Union(vec![
+            Domain::between(20, 30),
+            Domain::Domain(Value::Secluded(0), Value::Included(10)),
+        ])
+    }
+
+    #[test]
+    fn test_admitted() {
+        assert_eq!(gappy().validate_value(1), Ok(1));
+        assert_eq!(gappy().validate_value(30), Ok(30));
+        assert_eq!(Domain::new().validate_value(i32::MIN), Ok(i32::MIN));
+    }
+
+    #[test]
+    fn test_below_min() {
+        let error = gappy().validate_value(0).unwrap_err();
+        assert_eq!(error.value(), 0);
+        assert_eq!(error.violation(), Violation::BelowMin);
+        assert_eq!(error.nearest(), Some(1));
+        assert_eq!(error.to_string(), "0 is not allowed: must be in (0;10]⋃[20;30], nearest allowed value is 1");
+    }
+
+    #[test]
+    fn test_above_max() {
+        let error = Domain::at_most(5).validate_value(i32::MAX).unwrap_err();
+        assert_eq!(error.violation(), Violation::AboveMax);
+        assert_eq!(error.nearest(), Some(5));
+        assert_eq!(error.domain(), "(-∞;5]");
+    }
+
+    #[test]
+    fn test_in_gap() {
+        let error = gappy().validate_value(15).unwrap_err();
+        assert_eq!(error.violation(), Violation::InGap);
+        assert_eq!(error.nearest(), Some(10));
+
+        let error = gappy().remove_value(25).validate_value(25).unwrap_err();
+        assert_eq!(error.violation(), Violation::InGap);
+        assert_eq!(error.nearest(), Some(24));
+    }
+
+    #[test]
+    fn test_empty() {
+        let empty = Domain::Domain(Value::Secluded(i32::MAX), Value::Infinite);
+        let error = empty.validate_value(7).unwrap_err();
+        assert_eq!(error.violation(), Violation::Empty);
+        assert_eq!(error.nearest(), None);
+        assert_eq!(error.to_string(), "7 is not allowed: must be in ∅, which admits no value");
+    }
+}
@@ -0,0 +1,400 @@
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::Value;
+
+/// Which border of an interval a value stands for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The starting border
+    Lower,
+    /// The ending border
+    Upper,
+}
+
+impl<T> Value<T> {
+
+    /// Border as a `std::ops::Bound`: `Secluded` is `Excluded` and
+    /// `Infinite` is `Unbounded`
+    /// ```
+    /// use std::ops::Bound;
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Secluded(3).as_bound(), Bound::Excluded(&3));
+    /// assert_eq!(Value::<i32>::Infinite.as_bound(), Bound::Unbounded);
+    /// ```
+    pub fn as_bound(&self) -> Bound<&T> {
+        match self {
+            Value::Included(i) => Bound::Included(i),
+            Value::Secluded(i) => Bound::Excluded(i),
+            Value::Infinite => Bound::Unbounded,
+        }
+    }
+
+    /// Border from a `std::ops::Bound` used on the given `side`. Both
+    /// sides give the same `Value`, as `Infinite` stands for either end
+    /// ```
+    /// use std::ops::Bound;
+    /// use dynamic_domain::{Side, Value};
+    /// assert_eq!(Value::from_bound(Bound::Excluded(3), Side::Lower), Value::Secluded(3));
+    /// assert_eq!(Value::<i32>::from_bound(Bound::Unbounded, Side::Upper), Value::Infinite);
+    /// ```
+    pub fn from_bound(bound: Bound<T>, side: Side) -> Value<T> {
+        match (bound, side) {
+            (Bound::Included(i), _) => Value::Included(i),
+            (Bound::Excluded(i), _) => Value::Secluded(i),
+            (Bound::Unbounded, Side::Lower) | (Bound::Unbounded, Side::Upper) => Value::Infinite,
+        }
+    }
+
+    /// Applies `f` to the point keeping the kind of the border
+    /// ```
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Secluded(2).map(|n| n * 10), Value::Secluded(20));
+    /// assert_eq!(Value::<i32>::Infinite.map(|n| n * 10), Value::Infinite);
+    /// ```
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Value<U> {
+        match self {
+            Value::Included(i) => Value::Included(f(i)),
+            Value::Secluded(i) => Value::Secluded(f(i)),
+            Value::Infinite => Value::Infinite,
+        }
+    }
+
+    /// Point of the border, `None` for an infinite one
+    /// ```
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Included(3).as_inner(), Some(&3));
+    /// assert_eq!(Value::<i32>::Infinite.as_inner(), None);
+    /// ```
+    pub fn as_inner(&self) -> Option<&T> {
+        match self {
+            Value::Included(i) | Value::Secluded(i) => Some(i),
+            Value::Infinite => None,
+        }
+    }
+
+    /// Checks whether the border is infinite
+    /// ```
+    /// use dynamic_domain::Value;
+    /// assert!(Value::<i32>::Infinite.is_infinite());
+    /// assert!(!Value::Secluded(0).is_infinite());
+    /// ```
+    pub fn is_infinite(&self) -> bool {
+        matches!(self, Value::Infinite)
+    }
+
+    /// Checks whether the border admits its own point
+    /// ```
+    /// use dynamic_domain::Value;
+    /// assert!(Value::Included(0).is_included());
+    /// assert!(!Value::Secluded(0).is_included());
+    /// ```
+    pub fn is_included(&self) -> bool {
+        matches!(self, Value::Included(_))
+    }
+}
+
+impl Value<i32> {
+
+    /// Moves the point by `n`, `Infinite` stays `Infinite`.
+    /// `None` on overflow
+    /// ```
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Included(1).checked_add(2), Some(Value::Included(3)));
+    /// assert_eq!(Value::Included(i32::MAX).checked_add(1), None);
+    /// assert_eq!(Value::Infinite.checked_add(1), Some(Value::Infinite));
+    /// ```
+    pub fn checked_add(&self, n: i32) -> Option<Value<i32>> {
+        match self {
+            Value::Included(i) => i.checked_add(n).map(Value::Included),
+            Value::Secluded(i) => i.checked_add(n).map(Value::Secluded),
+            Value::Infinite => Some(Value::Infinite),
+        }
+    }
+
+    /// Moves the point by `-n`, `Infinite` stays `Infinite`.
+    /// `None` on overflow
+    /// ```
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Secluded(1).checked_sub(2), Some(Value::Secluded(-1)));
+    /// assert_eq!(Value::Secluded(i32::MIN).checked_sub(1), None);
+    /// ```
+    pub fn checked_sub(&self, n: i32) -> Option<Value<i32>> {
+        match self {
+            Value::Included(i) => i.checked_sub(n).map(Value::Included),
+            Value::Secluded(i) => i.checked_sub(n).map(Value::Secluded),
+            Value::Infinite => Some(Value::Infinite),
+        }
+    }
+}
+
+impl<T: Ord> Value<T> {
+
+    /// Orders borders used as starting borders from the loosest to the
+    /// tightest: `Infinite` (-∞) first, then by point, `Included(x)`
+    /// before `Secluded(x)` at the same point
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Included(5).cmp_as_lower(&Value::Secluded(5)), Ordering::Less);
+    /// assert_eq!(Value::Infinite.cmp_as_lower(&Value::Included(i32::MIN)), Ordering::Less);
+    /// ```
+    pub fn cmp_as_lower(&self, other: &Value<T>) -> Ordering {
+        let rank = |value: &Value<T>| match value {
+            Value::Secluded(_) => 1,
+            _ => 0,
+        };
+
+        match (self, other) {
+            (Value::Infinite, Value::Infinite) => Ordering::Equal,
+            (Value::Infinite, _) => Ordering::Less,
+            (_, Value::Infinite) => Ordering::Greater,
+            (Value::Included(a), Value::Included(b))
+            | (Value::Included(a), Value::Secluded(b))
+            | (Value::Secluded(a), Value::Included(b))
+            | (Value::Secluded(a), Value::Secluded(b)) => {
+                a.cmp(b).then(rank(self).cmp(&rank(other)))
+            },
+        }
+    }
+
+    /// Orders borders used as ending borders from the tightest to the
+    /// loosest: by point, `Secluded(x)` before `Included(x)` at the same
+    /// point, `Infinite` (∞) last
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Secluded(5).cmp_as_upper(&Value::Included(5)), Ordering::Less);
+    /// assert_eq!(Value::Infinite.cmp_as_upper(&Value::Included(i32::MAX)), Ordering::Greater);
+    /// ```
+    pub fn cmp_as_upper(&self, other: &Value<T>) -> Ordering {
+        let rank = |value: &Value<T>| match value {
+            Value::Included(_) => 1,
+            _ => 0,
+        };
+
+        match (self, other) {
+            (Value::Infinite, Value::Infinite) => Ordering::Equal,
+            (Value::Infinite, _) => Ordering::Greater,
+            (_, Value::Infinite) => Ordering::Less,
+            (Value::Included(a), Value::Included(b))
+            | (Value::Included(a), Value::Secluded(b))
+            | (Value::Secluded(a), Value::Included(b))
+            | (Value::Secluded(a), Value::Secluded(b)) => {
+                a.cmp(b).then(rank(self).cmp(&rank(other)))
+            },
+        }
+    }
+
+    /// Looser of two starting borders, the one of the union
+    /// ```
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Secluded(3).min_lower(Value::Included(3)), Value::Included(3));
+    /// ```
+    pub fn min_lower(self, other: Value<T>) -> Value<T> {
+        if self.cmp_as_lower(&other) == Ordering::Greater { other } else { self }
+    }
+
+    /// Tighter of two starting borders, the one of the intersection
+    /// ```
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Secluded(3).max_lower(Value::Included(3)), Value::Secluded(3));
+    /// ```
+    pub fn max_lower(self, other: Value<T>) -> Value<T> {
+        if self.cmp_as_lower(&other) == Ordering::Less { other } else { self }
+    }
+
+    /// Tighter of two ending borders, the one of the intersection
+    /// ```
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Infinite.min_upper(Value::Included(3)), Value::Included(3));
+    /// ```
+    pub fn min_upper(self, other: Value<T>) -> Value<T> {
+        if self.cmp_as_upper(&other) == Ordering::Greater { other } else { self }
+    }
+
+    /// Looser of two ending borders, the one of the union
+    /// ```
+    /// use dynamic_domain::Value;
+    /// assert_eq!(Value::Secluded(3).max_upper(Value::Included(3)), Value::Included(3));
+    /// ```
+    pub fn max_upper(self, other: Value<T>) -> Value<T> {
+        if self.cmp_as_upper(&other) == Ordering::Less { other } else { self }
+    }
+}
+
+/// A bare point is an included border
+/// ```
+/// use dynamic_domain::Value;
+/// assert_eq!(Value::from(5), Value::Included(5));
+/// ```
+impl<T> From<T> for Value<T> {
+    fn from(point: T) -> Self {
+        Value::Included(point)
+    }
+}
+
+/// Same as `Value::from_bound`, either side gives the same `Value`
+/// ```
+/// use std::ops::Bound;
+/// use dynamic_domain::Value;
+/// assert_eq!(Value::from(Bound::Excluded(5)), Value::Secluded(5));
+/// assert_eq!(Value::<i32>::from(Bound::Unbounded), Value::Infinite);
+/// ```
+impl<T> From<Bound<T>> for Value<T> {
+    fn from(bound: Bound<T>) -> Self {
+        Value::from_bound(bound, Side::Lower)
+    }
+}
+
+mod sealed {
+    use std::ops::Bound;
+
+    use crate::Value;
+
+    pub trait Sealed {}
+
+    impl Sealed for i32 {}
+    impl Sealed for Value<i32> {}
+    impl Sealed for Bound<i32> {}
+}
+
+/// Anything `Domain::gt` and `Domain::lt` take as a border: an `i32`,
+/// which is an included border, a `Value<i32>` or a `std::ops::Bound<i32>`,
+/// converted by the `From` impls of `Value`. Sealed, the accepted forms
+/// are fixed
+/// ```
+/// use std::ops::Bound;
+/// use dynamic_domain::{Domain, Value};
+/// let domain = Domain::new().gt(5).lt(Value::Secluded(10)).lt(Bound::Excluded(8));
+/// assert_eq!(domain.repr(), "[5;8)");
+/// ```
+pub trait IntoBound: sealed::Sealed {
+    /// The border as a `Value`
+    fn into_bound(self) -> Value<i32>;
+}
+
+impl IntoBound for i32 {
+    fn into_bound(self) -> Value<i32> {
+        Value::from(self)
+    }
+}
+
+impl IntoBound for Value<i32> {
+    fn into_bound(self) -> Value<i32> {
+        self
+    }
+}
+
+impl IntoBound for Bound<i32> {
+    fn into_bound(self) -> Value<i32> {
+        Value::from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use std::ops::Bound;
+
+    use crate::{Side, Value};
+
+    /// Borders around 0 and 1 in ascending order as starting borders
+    fn lowers() -> Vec<Value<i32>> {
+        vec![
+            Value::Infinite,
+            Value::Included(0),
+            Value::Secluded(0),
+            Value::Included(1),
+            Value::Secluded(1),
+        ]
+    }
+
+    /// Borders around 0 and 1 in ascending order as ending borders
+    fn uppers() -> Vec<Value<i32>> {
+        vec![
+            Value::Secluded(0),
+            Value::Included(0),
+            Value::Secluded(1),
+            Value::Included(1),
+            Value::Infinite,
+        ]
+    }
+
+    #[test]
+    fn test_cmp_as_lower() {
+        let lowers = lowers();
+        for (i, a) in lowers.iter().enumerate() {
+            for (j, b) in lowers.iter().enumerate() {
+                assert_eq!(a.cmp_as_lower(b), i.cmp(&j), "{:?} vs {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cmp_as_upper() {
+        let uppers = uppers();
+        for (i, a) in uppers.iter().enumerate() {
+            for (j, b) in uppers.iter().enumerate() {
+                assert_eq!(a.cmp_as_upper(b), i.cmp(&j), "{:?} vs {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_max() {
+        let lowers = lowers();
+        let uppers = uppers();
+        for (i, a) in lowers.iter().enumerate() {
+            for (j, b) in lowers.iter().enumerate() {
+                assert_eq!(a.clone().min_lower(b.clone()), lowers[i.min(j)]);
+                assert_eq!(a.clone().max_lower(b.clone()), lowers[i.max(j)]);
+            }
+        }
+        for (i, a) in uppers.iter().enumerate() {
+            for (j, b) in uppers.iter().enumerate() {
+                assert_eq!(a.clone().min_upper(b.clone()), uppers[i.min(j)]);
+                assert_eq!(a.clone().max_upper(b.clone()), uppers[i.max(j)]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_accessors() {
+        assert_eq!(Value::Included(i32::MIN).map(i64::from), Value::Included(i32::MIN as i64));
+        assert_eq!(Value::Secluded(i32::MAX).as_inner(), Some(&i32::MAX));
+        assert!(!Value::<i32>::Infinite.is_included());
+        assert!(!Value::Included(i32::MAX).is_infinite());
+    }
+
+    #[test]
+    fn test_checked_at_limits() {
+        assert_eq!(Value::Included(i32::MAX).checked_add(1), None);
+        assert_eq!(Value::Secluded(i32::MAX).checked_add(1), None);
+        assert_eq!(Value::Included(i32::MAX).checked_add(-1), Some(Value::Included(i32::MAX - 1)));
+        assert_eq!(Value::Included(i32::MIN).checked_sub(1), None);
+        assert_eq!(Value::Secluded(i32::MIN).checked_sub(1), None);
+        assert_eq!(Value::Secluded(i32::MIN).checked_add(1), Some(Value::Secluded(i32::MIN + 1)));
+        assert_eq!(Value::Included(0).checked_sub(i32::MIN), None);
+        assert_eq!(Value::Infinite.checked_add(i32::MAX), Some(Value::Infinite));
+        assert_eq!(Value::Infinite.checked_sub(i32::MIN), Some(Value::Infinite));
+    }
+
+    #[test]
+    fn test_bound_roundtrip() {
+        let values = vec![Value::Included(i32::MIN), Value::Secluded(i32::MAX), Value::Infinite];
+        for value in values {
+            for side in [Side::Lower, Side::Upper] {
+                assert_eq!(Value::from_bound(value.as_bound().cloned(), side), value);
+            }
+        }
+        assert_eq!(Value::from_bound(Bound::Included('a'), Side::Upper).as_bound(), Bound::Included(&'a'));
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(Value::Included(i32::MAX).cmp_as_lower(&Value::Secluded(i32::MIN)), Ordering::Greater);
+        assert_eq!(Value::Secluded(i32::MAX).cmp_as_upper(&Value::Infinite), Ordering::Less);
+        assert_eq!(Value::Included('a').cmp_as_lower(&Value::Secluded('a')), Ordering::Less);
+    }
+}
@@ -0,0 +1,304 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::slice;
+
+use crate::util::{self, NEG_INF, POS_INF};
+use crate::{Domain, DomainIter, Value};
+
+/// Read-only part of a domain below or above pivots, clipped on the fly
+/// rather than built as new intervals. Made by `Domain::view_below` and
+/// `Domain::view_above`, and by the same methods of a view for the part
+/// of the part. A normalized domain is borrowed as it is, so no view of
+/// it allocates. Any other one is normalized when the first view is
+/// made, and the views of that view copy the members they keep
+/// ```
+/// use dynamic_domain::Domain;
+/// let domain = Domain::Union(vec![Domain::between(0, 9), Domain::between(20, 29)]);
+/// let middle = domain.view_above(5).view_below(25);
+/// assert!(middle.contains(5) && !middle.contains(25));
+/// assert_eq!(middle.cardinality(), Some(10));
+/// assert_eq!(middle.to_domain().repr(), "[5;9]⋃[20;24]");
+/// ```
+#[derive(Clone, Debug)]
+pub struct DomainView<'a> {
+    /// Normalized members reaching into the window
+    members: Cow<'a, [Domain<i32>]>,
+    /// First value the view lets through, `NEG_INF` when not clipped
+    lo: i64,
+    /// Last value the view lets through, `POS_INF` when not clipped
+    hi: i64,
+}
+
+/// Closed run of a normalized member
+fn run(member: &Domain<i32>) -> (i64, i64) {
+    match member {
+        Domain::Domain(l, r) => util::clipped_run(l, r),
+        // Normalized members are plain intervals
+        _ => (POS_INF, NEG_INF),
+    }
+}
+
+/// Positions of the members reaching into `[lo;hi]`
+fn reaching(members: &[Domain<i32>], lo: i64, hi: i64) -> Range<usize> {
+    let start = members.partition_point(|member| run(member).1 < lo);
+    let end = members.partition_point(|member| run(member).0 <= hi).max(start);
+    start..end
+}
+
+impl<'a> DomainView<'a> {
+
+    fn new(domain: &'a Domain<i32>, lo: i64, hi: i64) -> Self {
+        let flat: &[Domain<i32>] = match domain {
+            Domain::Union(domains) => domains,
+            Domain::Domain(..) => slice::from_ref(domain),
+            Domain::None => &[],
+        };
+        let members = if util::is_normalized(flat) {
+            Cow::Borrowed(&flat[reaching(flat, lo, hi)])
+        } else {
+            let mut members: Vec<Domain<i32>> = util::normalized(domain).into_iter().map(|(l, r)| Domain::Domain(l, r)).collect();
+            let reached = reaching(&members, lo, hi);
+            members.truncate(reached.end);
+            members.drain(..reached.start);
+            Cow::Owned(members)
+        };
+        DomainView { members, lo, hi }
+    }
+
+    /// View of the same domain through the narrower window `[lo;hi]`.
+    /// Borrows what this view borrows, an owned view copies the members
+    /// left
+    fn narrowed(&self, lo: i64, hi: i64) -> DomainView<'a> {
+        let reached = reaching(&self.members, lo, hi);
+        let members = match &self.members {
+            Cow::Borrowed(members) => Cow::Borrowed(&members[reached]),
+            Cow::Owned(members) => Cow::Owned(members[reached].to_vec()),
+        };
+        DomainView { members, lo, hi }
+    }
+
+    /// Runs of the members clipped to the window
+    fn runs(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.members
+            .iter()
+            .map(move |member| {
+                let (first, last) = run(member);
+                (first.max(self.lo), last.min(self.hi))
+            })
+            .filter(|(first, last)| first <= last)
+    }
+
+    /// The part of this view below `pivot`, a view of the same domain
+    /// which outlives this one
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::between(0, 99);
+    /// let view = domain.view_above(10);
+    /// assert_eq!(view.view_below(20).cardinality(), Some(10));
+    /// assert_eq!(view.view_below(5).cardinality(), Some(0));
+    /// ```
+    pub fn view_below(&self, pivot: i32) -> DomainView<'a> {
+        self.narrowed(self.lo, self.hi.min(pivot as i64 - 1))
+    }
+
+    /// The part of this view from `pivot` up, a view of the same domain
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::between(0, 99);
+    /// let view = domain.view_below(50);
+    /// assert_eq!(view.view_above(40).cardinality(), Some(10));
+    /// ```
+    pub fn view_above(&self, pivot: i32) -> DomainView<'a> {
+        self.narrowed(self.lo.max(pivot as i64), self.hi)
+    }
+
+    /// Checks whether `value` is admitted by the domain and let through
+    /// by the view, a binary search over the members
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(0, 4), Domain::between(8, 9)]);
+    /// let view = domain.view_below(9);
+    /// assert!(view.contains(8) && !view.contains(9) && !view.contains(6));
+    /// ```
+    pub fn contains(&self, value: i32) -> bool {
+        let value = value as i64;
+        let at = self.members.partition_point(|member| run(member).1 < value);
+        self.lo <= value && value <= self.hi && self.members.get(at).is_some_and(|member| run(member).0 <= value)
+    }
+
+    /// Checks whether the view lets no value through
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert!(Domain::between(0, 9).view_above(10).is_empty());
+    /// assert!(!Domain::between(0, 9).view_above(9).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.runs().next().is_none()
+    }
+
+    /// Number of values let through, `None` when an infinite border of
+    /// the domain is not clipped by the view, like `Domain::cardinality`
+    /// of the owned part
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// assert_eq!(Domain::at_least(0).view_below(10).cardinality(), Some(10));
+    /// assert_eq!(Domain::at_least(0).view_above(10).cardinality(), None);
+    /// ```
+    pub fn cardinality(&self) -> Option<u64> {
+        let infinite_below = self.lo == NEG_INF && self.members.first().is_some_and(|first| matches!(first, Domain::Domain(Value::Infinite, _)));
+        let infinite_above = self.hi == POS_INF && self.members.last().is_some_and(|last| matches!(last, Domain::Domain(_, Value::Infinite)));
+        if infinite_below || infinite_above {
+            return None;
+        }
+        Some(self.runs().map(|(first, last)| (last - first + 1) as u64).sum())
+    }
+
+    /// Iterates the values let through in ascending order, like
+    /// `Domain::iter`
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union(vec![Domain::between(0, 4), Domain::between(8, 9)]);
+    /// let view = domain.view_above(3);
+    /// assert_eq!(view.iter().collect::<Vec<i32>>(), vec![3, 4, 8, 9]);
+    /// ```
+    pub fn iter(&self) -> DomainIter {
+        DomainIter::new(self.runs().collect())
+    }
+
+    /// The values let through as an owned domain, borders clipped by the
+    /// view become included borders
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::new();
+    /// let view = domain.view_above(-5).view_below(5);
+    /// assert_eq!(view.to_domain().repr(), "[-5;4]");
+    /// ```
+    pub fn to_domain(&self) -> Domain<i32> {
+        let members = self
+            .members
+            .iter()
+            .filter_map(|member| match member {
+                Domain::Domain(l, r) => {
+                    let (first, last) = run(member);
+                    let (lo, hi) = (first.max(self.lo), last.min(self.hi));
+                    if lo > hi {
+                        return None;
+                    }
+                    // A border past the window is replaced by the edge of the window
+                    let l = if first >= self.lo { l.clone() } else { Value::Included(lo as i32) };
+                    let r = if last <= self.hi { r.clone() } else { Value::Included(hi as i32) };
+                    Some((l, r))
+                },
+                _ => None,
+            })
+            .collect();
+        util::from_members(members)
+    }
+}
+
+impl Domain<i32> {
+
+    /// The values below `pivot` as a borrowed view, without building new
+    /// intervals, the same values as the owned `lt(Secluded(pivot))` of
+    /// the normalized domain. Views of views narrow further
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::Union((0..1_000).map(|i| Domain::between(i * 10, i * 10 + 4)).collect());
+    /// let low = domain.view_below(5_000);
+    /// assert_eq!(low.cardinality(), Some(2_500));
+    /// assert_eq!(low.view_below(2_500).cardinality(), Some(1_250));
+    /// ```
+    pub fn view_below(&self, pivot: i32) -> DomainView<'_> {
+        DomainView::new(self, NEG_INF, pivot as i64 - 1)
+    }
+
+    /// The values from `pivot` up as a borrowed view, the other half of
+    /// `view_below` of the same pivot
+    /// ```
+    /// use dynamic_domain::Domain;
+    /// let domain = Domain::between(0, 9);
+    /// assert_eq!(domain.view_below(4).cardinality().unwrap() + domain.view_above(4).cardinality().unwrap(), 10);
+    /// assert_eq!(domain.view_above(4).iter().next(), Some(4));
+    /// ```
+    pub fn view_above(&self, pivot: i32) -> DomainView<'_> {
+        DomainView::new(self, pivot as i64, POS_INF)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::algebra::flat_domain;
+    use crate::{Domain, Value};
+
+    fn pivot() -> impl Strategy<Value = i32> {
+        prop_oneof![8 => -35..35, 1 => Just(i32::MIN), 1 => Just(i32::MAX)]
+    }
+
+    fn below(domain: &Domain<i32>, pivot: i32) -> Domain<i32> {
+        domain.intersect(&Domain::Domain(Value::Infinite, Value::Secluded(pivot))).into_inner()
+    }
+
+    fn above(domain: &Domain<i32>, pivot: i32) -> Domain<i32> {
+        domain.intersect(&Domain::at_least(pivot)).into_inner()
+    }
+
+    proptest! {
+        #[test]
+        fn test_views_match_owned_split(domain in flat_domain(), p in pivot(), q in pivot(), value in pivot()) {
+            let normalized = domain.normalize();
+            for (domain, tag) in [(&domain, "written"), (&*normalized, "normalized")].iter().copied() {
+                let views = [
+                    (domain.view_below(p), below(domain, p)),
+                    (domain.view_above(p), above(domain, p)),
+                ];
+                for (view, owned) in views.iter() {
+                    let nested = [
+                        (view.view_below(q), below(owned, q)),
+                        (view.view_above(q), above(owned, q)),
+                    ];
+                    let all = std::iter::once((view.clone(), owned.clone())).chain(nested.iter().cloned());
+                    for (view, owned) in all {
+                        prop_assert_eq!(view.contains(value), owned.contains(value), "{}", tag);
+                        prop_assert_eq!(view.cardinality(), owned.cardinality(), "{}", tag);
+                        prop_assert_eq!(view.is_empty(), owned.is_empty(), "{}", tag);
+                        prop_assert_eq!(view.to_domain(), owned.clone(), "{}", tag);
+                        prop_assert!(view.iter().take(40).eq(owned.iter().take(40)), "{}", tag);
+                        prop_assert!(view.iter().rev().take(40).eq(owned.iter().rev().take(40)), "{}", tag);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_view_limits() {
+        let full = Domain::new();
+        assert!(full.view_below(i32::MIN).is_empty());
+        assert_eq!(full.view_below(i32::MIN).cardinality(), Some(0));
+        assert_eq!(full.view_above(i32::MAX).cardinality(), None);
+        assert_eq!(full.view_above(i32::MAX).view_below(i32::MAX).cardinality(), Some(0));
+        assert_eq!(full.view_above(i32::MIN).view_below(i32::MIN + 2).iter().collect::<Vec<i32>>(), vec![i32::MIN, i32::MIN + 1]);
+        assert_eq!(full.view_above(i32::MAX).view_above(0).iter().collect::<Vec<i32>>(), vec![i32::MAX]);
+        assert!(full.view_above(10).view_below(20).view_above(30).is_empty());
+
+        let closed = Domain::between(i32::MIN, i32::MAX);
+        assert_eq!(closed.view_above(i32::MAX).cardinality(), Some(1));
+        assert_eq!(closed.view_below(0).cardinality(), Some(1 << 31));
+        assert_eq!(closed.view_above(0).to_domain().repr(), "[0;2147483647]");
+        assert_eq!(Domain::None.view_above(0).to_domain(), Domain::None);
+    }
+
+    #[test]
+    fn test_normalized_is_borrowed() {
+        use std::borrow::Cow;
+
+        let domain = Domain::Union((0..100).map(|i| Domain::between(i * 10, i * 10 + 4)).collect());
+        let view = domain.view_above(500).view_below(600);
+        assert!(matches!(view.members, Cow::Borrowed(members) if members.len() == 10));
+        let unsorted = Domain::Union(vec![Domain::between(10, 12), Domain::between(0, 2)]);
+        assert!(matches!(unsorted.view_above(0).members, Cow::Owned(_)));
+        assert_eq!(unsorted.view_above(1).view_below(11).to_domain().repr(), "[1;2]⋃[10;10]");
+    }
+}
@@ -0,0 +1,101 @@
+use std::iter;
+
+use crate::util;
+use crate::Value;
+
+/// Order in which `Domain::generate` visits the values of a single interval
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Walk {
+    /// Both borders are finite: ascending from the first value to the last
+    Bounded(i32, i32),
+    /// Only the starting border is finite: ascending up to `i32::MAX`
+    From(i32),
+    /// Only the ending border is finite: descending down to `i32::MIN`
+    To(i32),
+    /// Both borders are infinite: 0, 1, -1, 2, -2 and so on out to the limits
+    Everywhere,
+}
+
+impl Walk {
+    /// Walk over the interval between the borders, `None` when the
+    /// interval admits no `i32`
+    pub fn of(l: &Value<i32>, r: &Value<i32>) -> Option<Walk> {
+        // The clipped run lies within the `i32` range whenever it is not reversed
+        let (first, last) = util::clipped_run(l, r);
+        if first > last {
+            return None;
+        }
+        let (first, last) = (first as i32, last as i32);
+
+        Some(match (l.is_infinite(), r.is_infinite()) {
+            (false, false) => Walk::Bounded(first, last),
+            (false, true) => Walk::From(first),
+            (true, false) => Walk::To(last),
+            (true, true) => Walk::Everywhere,
+        })
+    }
+
+    /// Values in visiting order
+    pub fn values(self) -> Box<dyn Iterator<Item = i32>> {
+        match self {
+            Walk::Bounded(first, last) => Box::new(first..=last),
+            Walk::From(first) => Box::new(first..=i32::MAX),
+            Walk::To(last) => Box::new((i32::MIN..=last).rev()),
+            Walk::Everywhere => Box::new(
+                iter::once(0)
+                    .chain((1..=i32::MAX).flat_map(|n| [n, -n]))
+                    .chain(iter::once(i32::MIN))
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Walk;
+    use crate::Value;
+
+    #[test]
+    fn test_cases() {
+        let walk = |l, r| Walk::of(&l, &r);
+        assert_eq!(walk(Value::Secluded(1), Value::Included(5)), Some(Walk::Bounded(2, 5)));
+        assert_eq!(walk(Value::Included(1), Value::Infinite), Some(Walk::From(1)));
+        assert_eq!(walk(Value::Infinite, Value::Secluded(5)), Some(Walk::To(4)));
+        assert_eq!(walk(Value::Infinite, Value::Infinite), Some(Walk::Everywhere));
+        assert_eq!(walk(Value::Secluded(i32::MAX), Value::Infinite), None);
+        assert_eq!(walk(Value::Infinite, Value::Secluded(i32::MIN)), None);
+        assert_eq!(walk(Value::Included(10), Value::Included(5)), None);
+        assert_eq!(walk(Value::Secluded(5), Value::Secluded(6)), None);
+    }
+
+    #[test]
+    fn test_bounded() {
+        let values: Vec<_> = Walk::Bounded(-2, 2).values().collect();
+        assert_eq!(values, vec![-2, -1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_from() {
+        let values: Vec<_> = Walk::From(3).values().take(3).collect();
+        assert_eq!(values, vec![3, 4, 5]);
+        let values: Vec<_> = Walk::From(i32::MAX - 1).values().collect();
+        assert_eq!(values, vec![i32::MAX - 1, i32::MAX]);
+    }
+
+    #[test]
+    fn test_to() {
+        let values: Vec<_> = Walk::To(4).values().take(3).collect();
+        assert_eq!(values, vec![4, 3, 2]);
+        let values: Vec<_> = Walk::To(i32::MIN + 1).values().collect();
+        assert_eq!(values, vec![i32::MIN + 1, i32::MIN]);
+    }
+
+    #[test]
+    fn test_everywhere() {
+        let values: Vec<_> = Walk::Everywhere.values().take(5).collect();
+        assert_eq!(values, vec![0, 1, -1, 2, -2]);
+        let mut window: Vec<_> = Walk::Everywhere.values().take(2001).collect();
+        window.sort_unstable();
+        assert_eq!(window, (-1000..=1000).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,53 @@
+//! Browser bindings, enabled by the `wasm` feature.
+//! `JsDomain` keeps the Rust domain alive behind the JS handle,
+//! so calls never copy the intervals across the boundary
+
+use wasm_bindgen::prelude::*;
+
+use crate::Domain;
+
+#[wasm_bindgen]
+pub struct JsDomain {
+    inner: Domain<i32>,
+}
+
+#[wasm_bindgen]
+impl JsDomain {
+    /// Parses interval notation, e.g. `new JsDomain("[0;10)⋃[20;30]")`
+    #[wasm_bindgen(constructor)]
+    pub fn new(repr: &str) -> Result<JsDomain, JsError> {
+        let inner = repr.parse::<Domain<i32>>()?;
+        Ok(JsDomain { inner })
+    }
+
+    pub fn contains(&self, n: i32) -> bool {
+        self.inner.contains(n)
+    }
+
+    /// Admitted value closest to `n`, `undefined` for the empty domain
+    pub fn clamp(&self, n: i32) -> Option<i32> {
+        self.inner.clamp(n)
+    }
+
+    pub fn repr(&self) -> String {
+        self.inner.clone().repr()
+    }
+
+    pub fn intersect(&self, other: &JsDomain) -> JsDomain {
+        JsDomain { inner: self.inner.intersect(&other.inner).into_inner() }
+    }
+
+    /// Object in the `serde_object` format, picked up by `JSON.stringify`
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<JsValue, JsError> {
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        Ok(crate::serde_object::serialize(&self.inner, &serializer)?)
+    }
+
+    /// Inverse of `toJSON`
+    #[wasm_bindgen(js_name = fromJSON)]
+    pub fn from_json(value: JsValue) -> Result<JsDomain, JsError> {
+        let inner = crate::serde_object::deserialize(serde_wasm_bindgen::Deserializer::from(value))?;
+        Ok(JsDomain { inner })
+    }
+}
@@ -0,0 +1,59 @@
+//! Counts the allocations of the hot paths with a counting global
+//! allocator, the one test of this binary so nothing else allocates
+//! meanwhile
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use dynamic_domain::{Domain, Value};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Allocations made by `f`
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    f();
+    ALLOCATIONS.load(Ordering::SeqCst) - before
+}
+
+#[test]
+fn test_hot_paths_do_not_allocate() {
+    let interval = Domain::between(-1_000, 1_000);
+    let union = Domain::Union(vec![Domain::at_most(i32::MIN), Domain::between(0, 4), Domain::Domain(Value::Secluded(9), Value::Infinite)]);
+    let floats = Domain::Domain(Value::Included(0.1), Value::Secluded(0.1 + 0.2));
+
+    assert_eq!(allocations(|| assert!(interval.contains(500) && !interval.contains(1_001))), 0);
+    assert_eq!(allocations(|| assert!(union.contains(i32::MIN) && !union.contains(9))), 0);
+    assert_eq!(allocations(|| assert!(!floats.contains(f64::NAN))), 0);
+
+    let mut out = String::with_capacity(256);
+    assert_eq!(allocations(|| interval.repr_into(&mut out)), 0);
+    assert_eq!(out, "[-1000;1000]");
+    out.clear();
+    assert_eq!(allocations(|| union.repr_into(&mut out)), 0);
+    assert_eq!(out, "(-∞;-2147483648]⋃[0;4]⋃(9;∞)");
+    out.clear();
+    assert_eq!(allocations(|| floats.repr_into(&mut out)), 0);
+    assert_eq!(out, "[0.1;0.30000000000000004)");
+}
@@ -0,0 +1,65 @@
+//! Builds the static library and `tests/ffi/domain_test.c` against
+//! the generated header, then runs the C program
+#![cfg(feature = "ffi")]
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Builds `libdynamic_domain.a` with the `ffi` feature into its own target
+/// directory, the regular build only produces the rlib
+fn build_staticlib() -> PathBuf {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let status = Command::new(cargo)
+        .args(["rustc", "--lib", "--features", "ffi", "--crate-type", "staticlib", "--target-dir"])
+        .arg(&target_dir)
+        .current_dir(manifest_dir())
+        .status()
+        .expect("cargo is required for the ffi test");
+    assert!(status.success(), "building the static library failed");
+
+    target_dir.join("debug")
+}
+
+#[test]
+fn test_header_is_up_to_date() {
+    let generated = Path::new(env!("DYNAMIC_DOMAIN_HEADER_DIR")).join("dynamic_domain.h");
+    let committed = manifest_dir().join("include").join("dynamic_domain.h");
+    assert_eq!(
+        fs::read_to_string(committed).unwrap(),
+        fs::read_to_string(generated).unwrap(),
+        "include/dynamic_domain.h is stale, copy it from OUT_DIR"
+    );
+}
+
+#[test]
+fn test_c_program() {
+    let profile_dir = build_staticlib();
+    let library = profile_dir.join("libdynamic_domain.a");
+    assert!(library.exists(), "{} was not built", library.display());
+
+    let binary = profile_dir.join("domain_test_c");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+
+    let status = Command::new(compiler)
+        .arg(manifest_dir().join("tests").join("ffi").join("domain_test.c"))
+        .arg("-I")
+        .arg(env!("DYNAMIC_DOMAIN_HEADER_DIR"))
+        .arg(&library)
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&binary)
+        .status()
+        .expect("C compiler is required for the ffi test");
+    assert!(status.success());
+
+    let output = Command::new(&binary).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
@@ -0,0 +1,49 @@
+/* Exercises the C ABI of the `ffi` feature, run by tests/ffi.rs */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "dynamic_domain.h"
+
+#define CHECK(cond)                                                 \
+    do {                                                            \
+        if (!(cond)) {                                              \
+            fprintf(stderr, "%s:%d: check failed: %s\n",            \
+                    __FILE__, __LINE__, #cond);                     \
+            return 1;                                               \
+        }                                                           \
+    } while (0)
+
+int main(void) {
+    DdDomain *low = dd_domain_new();
+    DdDomain *high = dd_domain_new();
+    DdDomain *both = NULL;
+    bool contained = false;
+    char buf[32];
+    size_t len = 0;
+
+    CHECK(low != NULL && high != NULL);
+    CHECK(dd_domain_gt(low, 5, true) == DD_STATUS_OK);
+    CHECK(dd_domain_lt(high, 10, false) == DD_STATUS_OK);
+    CHECK(dd_domain_intersect(low, high, &both) == DD_STATUS_OK);
+
+    CHECK(dd_domain_contains(both, 5, &contained) == DD_STATUS_OK && contained);
+    CHECK(dd_domain_contains(both, 10, &contained) == DD_STATUS_OK && !contained);
+
+    CHECK(dd_domain_repr(both, NULL, 0, &len) == DD_STATUS_BUFFER_TOO_SMALL);
+    CHECK(len == strlen("[5;10)"));
+    CHECK(dd_domain_repr(both, buf, 4, &len) == DD_STATUS_BUFFER_TOO_SMALL);
+    CHECK(dd_domain_repr(both, buf, sizeof buf, &len) == DD_STATUS_OK);
+    CHECK(strcmp(buf, "[5;10)") == 0);
+
+    CHECK(dd_domain_gt(NULL, 1, false) == DD_STATUS_NULL_POINTER);
+    CHECK(dd_domain_contains(both, 1, NULL) == DD_STATUS_NULL_POINTER);
+
+    dd_domain_free(low);
+    dd_domain_free(high);
+    dd_domain_free(both);
+    dd_domain_free(NULL);
+
+    puts("ok");
+    return 0;
+}
@@ -0,0 +1,75 @@
+//! Inputs which once panicked, hung or gave wrong domains, run as
+//! `Session` scripts by the interpreter of the `api` fuzz target. A new
+//! input found by `cargo fuzz run api` is printed as a script by
+//! `cargo fuzz fmt api <artifact>`: paste it into `CASES` with the repr
+//! the domain must end with once fixed. `union ∅` at the end of a script
+//! normalizes the domain, so the repr compares values rather than the
+//! spelling of the members
+#![cfg(feature = "fuzzing")]
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use dynamic_domain::fuzzing::{run, Session};
+
+/// A script running longer than this is taken for a hang
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Name, script and the repr of the domain it ends with
+const CASES: &[(&str, &str, &str)] = &[
+    // Included borders moved one value too far
+    ("gt included", "[0;10]; gt I5", "[5;10]"),
+    ("lt included", "[0;10]; lt I5", "[0;5]"),
+    ("gt lt secluded", "[0;10]; gt S5; lt S9", "(5;9)"),
+    ("gt lt included at max", "new; gt I2147483647; lt I2147483647", "[2147483647;2147483647]"),
+    // Stepping past i32::MAX in generate and the iterators
+    ("generate up to max", "[2147483645;inf); generate 10; iterate ascending 10; iterate descending 10", "[2147483645;∞)"),
+    ("generate max only", "[2147483647;2147483647]; generate 3; iterate from:2147483647 3", "[2147483647;2147483647]"),
+    ("generate down to min", "[-2147483648;-2147483646]; generate 5; iterate descending 5", "[-2147483648;-2147483646]"),
+    ("generate past max", "(2147483647;inf); iterate ascending 3; generate 3; union ∅", "∅"),
+    // gt and lt on unions only looked at the first member
+    ("gt on union", "[0;3]U[10;20]; gt I12; union ∅", "[12;20]"),
+    ("lt on union", "[0;3]U[10;20]; lt S2; union ∅", "[0;2)"),
+    ("gt lt on union", "[0;3]U[10;20]U(30;inf); gt S2; lt I15; union ∅", "(2;3]⋃[10;15]"),
+    // Reversed and empty borders looped forever
+    ("reversed borders", "[5;1]; generate 10; iterate ascending 10; iter_in 0 9 10; repr", "[5;1]"),
+    ("reversed complement", "[5;1]; complement", "(-∞;∞)"),
+    ("empty open interval", "(5;5); generate 3; iterate midpoint 3; complement", "(-∞;∞)"),
+    // Borders at the i32 limits
+    ("shift saturate", "[-2147483648;2147483647]; shift 1 saturate; scale -1 error", "[-2147483647;2147483647]"),
+    ("request example", "new; gt S5; lt I3; repr; intersect [1;2]u[9;9]", "∅"),
+];
+
+/// Runs `script` on its own thread, `Err` with the panic or the hang
+fn outcome(script: &str) -> Result<String, String> {
+    let session: Session = script.parse().map_err(|error| format!("unreadable script: {}", error))?;
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(run(&session).repr());
+    });
+    match receiver.recv_timeout(TIMEOUT) {
+        Ok(repr) => Ok(repr),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(format!("no result after {:?}", TIMEOUT)),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err("panicked".to_string()),
+    }
+}
+
+#[test]
+fn test_regressions() {
+    let failures: Vec<String> = CASES
+        .iter()
+        .filter_map(|(name, script, expected)| match outcome(script) {
+            Ok(repr) if repr == *expected => None,
+            Ok(repr) => Some(format!("{}: ended with {}, expected {}\n  {}", name, repr, expected, script)),
+            Err(error) => Some(format!("{}: {}\n  {}", name, error, script)),
+        })
+        .collect();
+    assert!(failures.is_empty(), "{} of {} regressions failed:\n{}", failures.len(), CASES.len(), failures.join("\n"));
+}
+
+#[test]
+fn test_failures_are_reported() {
+    assert_eq!(outcome("[0;10]; gt I5"), Ok("[5;10]".to_string()));
+    assert!(outcome("[0;10]; gt 5").unwrap_err().starts_with("unreadable script"));
+}
@@ -0,0 +1,26 @@
+//! Headless browser test, run with `wasm-pack test --headless --firefox -- --features wasm`
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use dynamic_domain::wasm::JsDomain;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn test_js_domain() {
+    let domain = JsDomain::new("[0;10)⋃[20;30]").unwrap();
+    assert!(domain.contains(5));
+    assert!(!domain.contains(15));
+    assert_eq!(domain.clamp(16), Some(20));
+
+    let window = JsDomain::new("[5;25]").unwrap();
+    assert_eq!(domain.intersect(&window).repr(), "[5;10)⋃[20;25]");
+    assert!(JsDomain::new("[0;").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_json_roundtrip() {
+    let domain = JsDomain::new("(5;10]").unwrap();
+    let json = domain.to_json().unwrap();
+    assert_eq!(JsDomain::from_json(json).unwrap().repr(), "(5;10]");
+}